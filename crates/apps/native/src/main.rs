@@ -1,3 +1,31 @@
-fn main() {
-    println!("Atlas native placeholder");
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    // `--trace <path>` writes a Chrome Trace Event capture of the run (needs `--features trace`).
+    let mut args = std::env::args().skip(1);
+    let mut trace_path: Option<PathBuf> = None;
+    while let Some(arg) = args.next() {
+        if arg == "--trace" {
+            trace_path = args.next().map(PathBuf::from);
+        }
+    }
+    if trace_path.is_some() {
+        runtime::trace::start_capture();
+    }
+
+    {
+        runtime::trace_span!("startup", "app");
+        println!("Atlas native placeholder");
+    }
+
+    if let Some(path) = trace_path {
+        let json = runtime::trace::stop_capture().to_chrome_json();
+        if let Err(err) = std::fs::write(&path, json) {
+            eprintln!("failed to write trace to {}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+        println!("wrote trace to {}", path.display());
+    }
+    ExitCode::SUCCESS
 }
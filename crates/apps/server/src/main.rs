@@ -2,17 +2,21 @@ use std::env;
 use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Instant;
 
 use axum::body::{Body, Bytes};
 use axum::extract::ws::WebSocketUpgrade;
-use axum::extract::{Path as AxumPath, State};
+use axum::extract::{MatchedPath, Path as AxumPath, Request, State};
 use axum::http::{HeaderMap, HeaderValue, Method, StatusCode};
+use axum::middleware::{self, Next};
 use axum::response::{IntoResponse, Response};
 use axum::routing::{delete, get, post, put};
 use axum::{Json, Router};
+use parking_lot::Mutex;
+use runtime::metrics::{ExpositionFormat, Metrics};
 use serde::Serialize;
 use serde_json::json;
-use streaming::StreamingConfig;
+use streaming::{StreamingConfig, TileCoord, TileMatrixSet};
 use tempfile::TempDir;
 use tokio::process::Command;
 use tower_http::cors::{Any, CorsLayer};
@@ -42,6 +46,7 @@ struct AppState {
     webhooks: Arc<WebhookRegistry>,
     streaming_config: StreamingConfig,
     feeds: Arc<FeedsStore>,
+    metrics: Arc<Mutex<Metrics>>,
 }
 
 #[derive(Clone, Debug)]
@@ -65,6 +70,19 @@ struct TerrainConfig {
     max_cogs_per_tile: usize,
 }
 
+impl TerrainConfig {
+    /// Plate carrée quadtree over the configured extent, one root tile at zoom 0.
+    fn tile_matrix_set(&self) -> TileMatrixSet {
+        TileMatrixSet::geographic(
+            "terrain",
+            [self.min_lon, self.min_lat, self.max_lon, self.max_lat],
+            1,
+            1,
+        )
+        .with_tile_size(self.tile_size)
+    }
+}
+
 #[derive(Debug, Serialize)]
 struct TerrainTileset {
     version: u32,
@@ -186,6 +204,7 @@ async fn main() {
         webhooks: webhooks.clone(),
         streaming_config: streaming_config.clone(),
         feeds,
+        metrics: Arc::new(Mutex::new(Metrics::new())),
     };
 
     if let Err(err) = tokio::fs::create_dir_all(&state.terrain.cache_root).await {
@@ -208,6 +227,7 @@ async fn main() {
 
     let app = Router::new()
         .route("/healthz", get(healthz))
+        .route("/metrics", get(get_metrics))
         .route("/terrain/status", get(get_terrain_status))
         .route("/terrain/tileset.json", get(get_tileset))
         .route("/terrain/tiles/:z/:x/:y.bin", get(get_tile))
@@ -259,6 +279,10 @@ async fn main() {
         .route("/api/feeds/fetch", post(fetch_url))
         .route("/api/feeds/:feed_id", delete(delete_feed))
         .route("/api/feeds/:feed_id/fetch", get(fetch_feed))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            track_requests,
+        ))
         .layer(cors)
         .layer(TraceLayer::new_for_http())
         .with_state(state.clone());
@@ -885,6 +909,35 @@ async fn healthz() -> Response {
     (StatusCode::OK, "ok").into_response()
 }
 
+/// Prometheus text export of the server metrics.
+async fn get_metrics(State(state): State<AppState>) -> Response {
+    let body = state.metrics.lock().encode(ExpositionFormat::Prometheus);
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        http::header::CONTENT_TYPE,
+        HeaderValue::from_static("text/plain; version=0.0.4"),
+    );
+    (StatusCode::OK, headers, Body::from(body)).into_response()
+}
+
+/// Counts requests and records their latency per matched route and status.
+async fn track_requests(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_default();
+    let started = Instant::now();
+    let response = next.run(request).await;
+    let elapsed_us = i64::try_from(started.elapsed().as_micros()).unwrap_or(i64::MAX);
+    let status = response.status().as_u16().to_string();
+    let labels = [("route", route.as_str()), ("status", status.as_str())];
+    let mut metrics = state.metrics.lock();
+    metrics.inc_counter_with("http_requests", &labels, 1);
+    metrics.record_histogram_with("http_request_duration_us", &labels[..1], elapsed_us);
+    response
+}
+
 async fn get_tileset(State(state): State<AppState>) -> Response {
     let cfg = &state.terrain;
     let tileset = TerrainTileset {
@@ -958,6 +1011,15 @@ async fn get_tile(
     if z < cfg.zoom_min || z > cfg.zoom_max {
         return (StatusCode::NOT_FOUND, "zoom out of range").into_response();
     }
+    let Ok(tile_z) = u8::try_from(z) else {
+        return (StatusCode::NOT_FOUND, "zoom out of range").into_response();
+    };
+    if !cfg
+        .tile_matrix_set()
+        .contains(&TileCoord::new(tile_z, x, y))
+    {
+        return (StatusCode::NOT_FOUND, "tile index out of range").into_response();
+    }
 
     let cache_path = cfg
         .cache_root
@@ -1098,7 +1160,8 @@ async fn build_tile(
     cache_path: &Path,
 ) -> Result<(), String> {
     let cfg = &state.terrain;
-    let (lon_min, lon_max, lat_min, lat_max) = terrain_tile_bounds(cfg, z, x, y);
+    let tile = TileCoord::new(u8::try_from(z).map_err(|_| "zoom out of range")?, x, y);
+    let [lon_min, lat_min, lon_max, lat_max] = cfg.tile_matrix_set().tile_bounds(&tile);
 
     if let Some(parent) = cache_path.parent() {
         tokio::fs::create_dir_all(parent)
@@ -1189,18 +1252,6 @@ async fn build_tile(
     Ok(())
 }
 
-fn terrain_tile_bounds(cfg: &TerrainConfig, z: u32, x: u32, y: u32) -> (f64, f64, f64, f64) {
-    let n = 2u32.pow(z);
-    let lon_span = (cfg.max_lon - cfg.min_lon) / n as f64;
-    let lat_span = (cfg.max_lat - cfg.min_lat) / n as f64;
-
-    let lon_min = cfg.min_lon + (x as f64) * lon_span;
-    let lon_max = lon_min + lon_span;
-    let lat_max = cfg.max_lat - (y as f64) * lat_span;
-    let lat_min = lat_max - lat_span;
-    (lon_min, lon_max, lat_min, lat_max)
-}
-
 async fn fetch_cog_hrefs(
     state: &AppState,
     lon_min: f64,
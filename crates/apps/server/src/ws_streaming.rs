@@ -310,30 +310,31 @@ async fn handle_view_update(
 
             // Instead of iterating all tiles, compute the visible tile range
             let _view_radius = view_radius_deg(&view);
-            let (x_min, x_max, y_min, y_max) = visible_tile_range(&view, z);
-
-            for x in x_min..=x_max {
-                for y in y_min..=y_max {
-                    let x = x % tiles_per_side;
-                    let y = y.min(tiles_per_side - 1);
-
-                    let coord = TileCoord::new(z, x, y);
-                    if !view.tile_visible(&coord) {
-                        continue;
+            // Two ranges when the view crosses the antimeridian.
+            for (x_min, x_max, y_min, y_max) in view.visible_tile_ranges(z) {
+                for x in x_min..=x_max {
+                    for y in y_min..=y_max {
+                        let x = x % tiles_per_side;
+                        let y = y.min(tiles_per_side - 1);
+
+                        let coord = TileCoord::new(z, x, y);
+                        if !view.tile_visible(&coord) {
+                            continue;
+                        }
+
+                        // Skip if already inflight
+                        if session.inflight_tiles.contains(&(view_id, coord)) {
+                            continue;
+                        }
+
+                        let priority = view.tile_priority(&coord);
+                        tile_queue.push(PrioritizedTile {
+                            coord,
+                            layer: layer.clone(),
+                            priority,
+                            view_id,
+                        });
                     }
-
-                    // Skip if already inflight
-                    if session.inflight_tiles.contains(&(view_id, coord)) {
-                        continue;
-                    }
-
-                    let priority = view.tile_priority(&coord);
-                    tile_queue.push(PrioritizedTile {
-                        coord,
-                        layer: layer.clone(),
-                        priority,
-                        view_id,
-                    });
                 }
             }
         }
@@ -456,37 +457,6 @@ async fn handle_explicit_tile_request(
     Ok(())
 }
 
-/// Compute the visible tile range for a given view and zoom.
-fn visible_tile_range(view: &ViewState, z: u8) -> (u32, u32, u32, u32) {
-    let _tiles_per_side = 1u32 << z;
-    let radius = view_radius_deg(view);
-
-    let lon_min = view.lon - radius;
-    let lon_max = view.lon + radius;
-    let lat_min = (view.lat - radius).max(-85.0);
-    let lat_max = (view.lat + radius).min(85.0);
-
-    let x_min = lon_to_tile_x(lon_min, z);
-    let x_max = lon_to_tile_x(lon_max, z);
-    let y_min = lat_to_tile_y(lat_max, z); // Note: Y is flipped
-    let y_max = lat_to_tile_y(lat_min, z);
-
-    (x_min, x_max, y_min, y_max)
-}
-
-fn lon_to_tile_x(lon: f64, z: u8) -> u32 {
-    let n = 1u32 << z;
-    let x = ((lon + 180.0) / 360.0 * n as f64).floor() as i32;
-    x.clamp(0, n as i32 - 1) as u32
-}
-
-fn lat_to_tile_y(lat: f64, z: u8) -> u32 {
-    let n = 1u32 << z;
-    let lat_rad = lat.to_radians();
-    let y = ((1.0 - lat_rad.tan().asinh() / std::f64::consts::PI) / 2.0 * n as f64).floor() as i32;
-    y.clamp(0, n as i32 - 1) as u32
-}
-
 fn base64_encode(data: &[u8]) -> String {
     use std::io::Write;
     let mut buf = Vec::with_capacity(data.len() * 4 / 3 + 4);
@@ -27,7 +27,10 @@ async fn yield_now() {
 use catalog::{CatalogEntry, CatalogStore};
 use formats::SceneManifest;
 use foundation::handles::Handle;
-use foundation::math::{Geodetic, WGS84_A, WGS84_B, ecef_to_geodetic, geodetic_to_ecef};
+use foundation::math::{
+    CoordinateFormat, Geodetic, ParseMode, WEB_MERCATOR_MAX_LAT_DEG, WGS84_A, WGS84_B, WebMercator,
+    ecef_to_geodetic, geodetic_to_ecef,
+};
 use layers::labels::{
     LabelAnchor, LabelLayoutConfig, LabelProjector, LabelRule, LabelStyle as LayerLabelStyle,
     LabelsConfig, LabelsLayer, PlacedLabel2D as LayerPlacedLabel2D, layout_labels_2d,
@@ -577,6 +580,8 @@ struct ViewerState {
     perf_gpu_draw_vertices: u64,
     perf_gpu_draw_indices: u64,
     perf_gpu_frame_ms: f64,
+    /// CPU time of each rendered frame (microseconds), for percentiles.
+    perf_gpu_frame_us: runtime::metrics::Histogram,
 
     // Labels (overlay canvas). This is intentionally a scaffold: incremental layout and batching.
     labels_enabled: bool,
@@ -765,6 +770,9 @@ thread_local! {
         perf_gpu_draw_vertices: 0,
         perf_gpu_draw_indices: 0,
         perf_gpu_frame_ms: 0.0,
+        perf_gpu_frame_us: runtime::metrics::Histogram::with_buckets(
+            runtime::metrics::Histogram::exponential_buckets(250, 1.25, 32),
+        ),
 
         labels_enabled: true,
         labels_gen: 0,
@@ -1034,19 +1042,8 @@ fn wrap_lon_deg(mut lon: f64) -> f64 {
     lon
 }
 
-const MERCATOR_MAX_LAT_DEG: f64 = 85.05112878;
-
 fn is_mercator_lat_valid(lat_deg: f64) -> bool {
-    lat_deg.is_finite() && (-MERCATOR_MAX_LAT_DEG..=MERCATOR_MAX_LAT_DEG).contains(&lat_deg)
-}
-
-fn mercator_x_m(lon_deg: f64) -> f64 {
-    WGS84_A * lon_deg.to_radians()
-}
-
-fn mercator_y_m(lat_deg: f64) -> f64 {
-    let lat = clamp(lat_deg, -MERCATOR_MAX_LAT_DEG, MERCATOR_MAX_LAT_DEG).to_radians();
-    WGS84_A * (0.5 * (std::f64::consts::FRAC_PI_2 + lat)).tan().ln()
+    lat_deg.is_finite() && (-WEB_MERCATOR_MAX_LAT_DEG..=WEB_MERCATOR_MAX_LAT_DEG).contains(&lat_deg)
 }
 
 fn unwrap_mercator_x_m(anchor_x_m: f64, x_m: f64) -> f64 {
@@ -1454,13 +1451,13 @@ fn clip_polygon_lat_band(mut poly: Vec<(f64, f64)>) -> Vec<(f64, f64)> {
 
     poly = clip_against(
         poly,
-        |lat| lat <= MERCATOR_MAX_LAT_DEG,
-        MERCATOR_MAX_LAT_DEG,
+        |lat| lat <= WEB_MERCATOR_MAX_LAT_DEG,
+        WEB_MERCATOR_MAX_LAT_DEG,
     );
     poly = clip_against(
         poly,
-        |lat| lat >= -MERCATOR_MAX_LAT_DEG,
-        -MERCATOR_MAX_LAT_DEG,
+        |lat| lat >= -WEB_MERCATOR_MAX_LAT_DEG,
+        -WEB_MERCATOR_MAX_LAT_DEG,
     );
     poly
 }
@@ -1480,12 +1477,12 @@ fn world_tris_to_mercator_clipped(pos: &[[f32; 3]]) -> Vec<[f32; 2]> {
         // If fully valid, fast path.
         if is_mercator_lat_valid(lat0) && is_mercator_lat_valid(lat1) && is_mercator_lat_valid(lat2)
         {
-            let ax = mercator_x_m(lon0);
-            let ay = mercator_y_m(lat0);
-            let bx0 = mercator_x_m(lon1);
-            let by = mercator_y_m(lat1);
-            let cx0 = mercator_x_m(lon2);
-            let cy = mercator_y_m(lat2);
+            let ax = WebMercator::x_m(lon0);
+            let ay = WebMercator::y_m(lat0);
+            let bx0 = WebMercator::x_m(lon1);
+            let by = WebMercator::y_m(lat1);
+            let cx0 = WebMercator::x_m(lon2);
+            let cy = WebMercator::y_m(lat2);
 
             // Unwrap within triangle to keep it local.
             let bx = unwrap_mercator_x_m(ax, bx0);
@@ -1528,15 +1525,15 @@ fn world_tris_to_mercator_clipped(pos: &[[f32; 3]]) -> Vec<[f32; 2]> {
 
         // Triangulate by fan.
         let (fan0_lon, fan0_lat) = poly[0];
-        let fan0_x = mercator_x_m(fan0_lon);
-        let fan0_y = mercator_y_m(fan0_lat);
+        let fan0_x = WebMercator::x_m(fan0_lon);
+        let fan0_y = WebMercator::y_m(fan0_lat);
         for w in poly.windows(2).skip(1) {
             let (b_lon, b_lat) = w[0];
             let (c_lon, c_lat) = w[1];
-            let bx0 = mercator_x_m(b_lon);
-            let by = mercator_y_m(b_lat);
-            let cx0 = mercator_x_m(c_lon);
-            let cy = mercator_y_m(c_lat);
+            let bx0 = WebMercator::x_m(b_lon);
+            let by = WebMercator::y_m(b_lat);
+            let cx0 = WebMercator::x_m(c_lon);
+            let cy = WebMercator::y_m(c_lat);
             let bx = unwrap_mercator_x_m(fan0_x, bx0);
             let cx = unwrap_mercator_x_m(fan0_x, cx0);
 
@@ -1574,44 +1571,44 @@ fn clip_segment_lat_band(a: (f64, f64), b: (f64, f64)) -> Option<((f64, f64), (f
     let (mut lon1, mut lat1) = b;
 
     // Quick reject if both outside on same side.
-    if lat0 < -MERCATOR_MAX_LAT_DEG && lat1 < -MERCATOR_MAX_LAT_DEG {
+    if lat0 < -WEB_MERCATOR_MAX_LAT_DEG && lat1 < -WEB_MERCATOR_MAX_LAT_DEG {
         return None;
     }
-    if lat0 > MERCATOR_MAX_LAT_DEG && lat1 > MERCATOR_MAX_LAT_DEG {
+    if lat0 > WEB_MERCATOR_MAX_LAT_DEG && lat1 > WEB_MERCATOR_MAX_LAT_DEG {
         return None;
     }
 
     // Clip against +max.
-    if lat0 > MERCATOR_MAX_LAT_DEG || lat1 > MERCATOR_MAX_LAT_DEG {
+    if lat0 > WEB_MERCATOR_MAX_LAT_DEG || lat1 > WEB_MERCATOR_MAX_LAT_DEG {
         let denom = lat1 - lat0;
         if denom.abs() < 1e-12 {
             return None;
         }
-        let t = (MERCATOR_MAX_LAT_DEG - lat0) / denom;
+        let t = (WEB_MERCATOR_MAX_LAT_DEG - lat0) / denom;
         let lon = lon0 + (lon1 - lon0) * t;
-        if lat0 > MERCATOR_MAX_LAT_DEG {
+        if lat0 > WEB_MERCATOR_MAX_LAT_DEG {
             lon0 = lon;
-            lat0 = MERCATOR_MAX_LAT_DEG;
+            lat0 = WEB_MERCATOR_MAX_LAT_DEG;
         } else {
             lon1 = lon;
-            lat1 = MERCATOR_MAX_LAT_DEG;
+            lat1 = WEB_MERCATOR_MAX_LAT_DEG;
         }
     }
 
     // Clip against -max.
-    if lat0 < -MERCATOR_MAX_LAT_DEG || lat1 < -MERCATOR_MAX_LAT_DEG {
+    if lat0 < -WEB_MERCATOR_MAX_LAT_DEG || lat1 < -WEB_MERCATOR_MAX_LAT_DEG {
         let denom = lat1 - lat0;
         if denom.abs() < 1e-12 {
             return None;
         }
-        let t = (-MERCATOR_MAX_LAT_DEG - lat0) / denom;
+        let t = (-WEB_MERCATOR_MAX_LAT_DEG - lat0) / denom;
         let lon = lon0 + (lon1 - lon0) * t;
-        if lat0 < -MERCATOR_MAX_LAT_DEG {
+        if lat0 < -WEB_MERCATOR_MAX_LAT_DEG {
             lon0 = lon;
-            lat0 = -MERCATOR_MAX_LAT_DEG;
+            lat0 = -WEB_MERCATOR_MAX_LAT_DEG;
         } else {
             lon1 = lon;
-            lat1 = -MERCATOR_MAX_LAT_DEG;
+            lat1 = -WEB_MERCATOR_MAX_LAT_DEG;
         }
     }
 
@@ -1629,10 +1626,10 @@ fn world_segs_to_mercator_clipped(pos: &[[f32; 3]]) -> Vec<[f32; 2]> {
             continue;
         };
 
-        let ax = mercator_x_m(clon0);
-        let ay = mercator_y_m(clat0);
-        let bx0 = mercator_x_m(clon1);
-        let by = mercator_y_m(clat1);
+        let ax = WebMercator::x_m(clon0);
+        let ay = WebMercator::y_m(clat0);
+        let bx0 = WebMercator::x_m(clon1);
+        let by = WebMercator::y_m(clat1);
         let bx = unwrap_mercator_x_m(ax, bx0);
 
         out.push([ax as f32, ay as f32]);
@@ -1641,18 +1638,9 @@ fn world_segs_to_mercator_clipped(pos: &[[f32; 3]]) -> Vec<[f32; 2]> {
     out
 }
 
-fn inverse_mercator_lon_deg(x_m: f64) -> f64 {
-    (x_m / WGS84_A).to_degrees()
-}
-
-fn inverse_mercator_lat_deg(y_m: f64) -> f64 {
-    let lat = 2.0 * (y_m / WGS84_A).exp().atan() - std::f64::consts::FRAC_PI_2;
-    lat.to_degrees()
-}
-
 fn camera2d_scale_px_per_m(cam: Camera2DState, w: f64, h: f64) -> f64 {
     let world_width_m = 2.0 * std::f64::consts::PI * WGS84_A;
-    let max_y = mercator_y_m(MERCATOR_MAX_LAT_DEG);
+    let max_y = WebMercator::y_m(WEB_MERCATOR_MAX_LAT_DEG);
     let world_height_m = 2.0 * max_y;
     // Use max() so the world FILLS the viewport (no edges visible at zoom=1).
     // At minimum zoom, either width or height will exactly match, the other will overflow.
@@ -1662,7 +1650,7 @@ fn camera2d_scale_px_per_m(cam: Camera2DState, w: f64, h: f64) -> f64 {
 
 /// Clamp center_y so the visible extent doesn't exceed the Mercator bounds.
 fn clamp_center_y_for_extent(center_y: f64, half_h_m: f64) -> f64 {
-    let max_y = mercator_y_m(MERCATOR_MAX_LAT_DEG);
+    let max_y = WebMercator::y_m(WEB_MERCATOR_MAX_LAT_DEG);
     // The visible top edge is center_y + half_h_m, bottom is center_y - half_h_m.
     // We need: center_y + half_h_m <= max_y  AND  center_y - half_h_m >= -max_y
     // Rearranging: center_y <= max_y - half_h_m  AND  center_y >= -max_y + half_h_m
@@ -1685,8 +1673,8 @@ struct MercatorProjector {
 
 impl MercatorProjector {
     fn new(cam: Camera2DState, w: f64, h: f64) -> Self {
-        let center_x = mercator_x_m(cam.center_lon_deg);
-        let center_y = mercator_y_m(cam.center_lat_deg);
+        let center_x = WebMercator::x_m(cam.center_lon_deg);
+        let center_y = WebMercator::y_m(cam.center_lat_deg);
         let scale_px_per_m = camera2d_scale_px_per_m(cam, w, h);
         let world_width_m = 2.0 * std::f64::consts::PI * WGS84_A;
         Self {
@@ -1698,8 +1686,8 @@ impl MercatorProjector {
     }
 
     fn project_lon_lat(&self, lon_deg: f64, lat_deg: f64, w: f64, h: f64) -> (f64, f64) {
-        let x_m = mercator_x_m(lon_deg);
-        let y_m = mercator_y_m(lat_deg);
+        let x_m = WebMercator::x_m(lon_deg);
+        let y_m = WebMercator::y_m(lat_deg);
         let dx = (x_m - self.center_x + 0.5 * self.world_width_m).rem_euclid(self.world_width_m)
             - 0.5 * self.world_width_m;
         let dy = y_m - self.center_y;
@@ -1713,11 +1701,11 @@ impl MercatorProjector {
         let dy_m = (h * 0.5 - y_px) / self.scale_px_per_m;
         let x_m = self.center_x + dx_m;
         let y_m = self.center_y + dy_m;
-        let lon = wrap_lon_deg(inverse_mercator_lon_deg(x_m));
+        let lon = wrap_lon_deg(WebMercator::lon_deg(x_m));
         let lat = clamp(
-            inverse_mercator_lat_deg(y_m),
-            -MERCATOR_MAX_LAT_DEG,
-            MERCATOR_MAX_LAT_DEG,
+            WebMercator::lat_deg(y_m),
+            -WEB_MERCATOR_MAX_LAT_DEG,
+            WEB_MERCATOR_MAX_LAT_DEG,
         );
         (lon, lat)
     }
@@ -1753,8 +1741,8 @@ fn pan_camera_2d(
     let clamped_center_y = clamp_center_y_for_extent(center_y, half_h_m);
 
     Camera2DState {
-        center_lon_deg: wrap_lon_deg(inverse_mercator_lon_deg(center_x)),
-        center_lat_deg: inverse_mercator_lat_deg(clamped_center_y),
+        center_lon_deg: wrap_lon_deg(WebMercator::lon_deg(center_x)),
+        center_lat_deg: WebMercator::lat_deg(clamped_center_y),
         ..cam
     }
 }
@@ -1806,8 +1794,8 @@ pub fn camera_zoom_at(x_px: f64, y_px: f64, wheel_delta_y: f64) -> Result<(), Js
         let clamped_center_y = clamp_center_y_for_extent(next_center_y, next_half_h_m);
 
         s.camera_2d = Camera2DState {
-            center_lon_deg: wrap_lon_deg(inverse_mercator_lon_deg(next_center_x)),
-            center_lat_deg: inverse_mercator_lat_deg(clamped_center_y),
+            center_lon_deg: wrap_lon_deg(WebMercator::lon_deg(next_center_x)),
+            center_lat_deg: WebMercator::lat_deg(clamped_center_y),
             zoom: next_zoom,
         };
     });
@@ -2050,68 +2038,18 @@ fn globe_controller_view_proj(
 }
 
 fn current_sun_direction_world() -> Option<[f32; 3]> {
-    // Low-cost solar position approximation.
-    fn wrap_360(mut d: f64) -> f64 {
-        d %= 360.0;
-        if d < 0.0 {
-            d += 360.0;
-        }
-        d
-    }
-    fn wrap_180(mut d: f64) -> f64 {
-        d = wrap_360(d);
-        if d > 180.0 {
-            d -= 360.0;
-        }
-        d
-    }
-
     let ms = js_sys::Date::new_0().get_time();
     if !ms.is_finite() {
         return None;
     }
-
-    // Julian Day from Unix epoch (1970-01-01T00:00:00Z) == 2440587.5
-    let jd = 2440587.5 + (ms / 86_400_000.0);
-    let n = jd - 2451545.0; // days since J2000
-
-    // Mean longitude and anomaly (degrees)
-    let l = wrap_360(280.46 + 0.9856474 * n);
-    let g = wrap_360(357.528 + 0.9856003 * n);
-
-    // Ecliptic longitude (degrees)
-    let lambda = wrap_360(l + 1.915 * g.to_radians().sin() + 0.020 * (2.0 * g).to_radians().sin());
-    // Obliquity of the ecliptic (degrees)
-    let epsilon = 23.439 - 0.0000004 * n;
-
-    // Right ascension (alpha) and declination (delta)
-    let lambda_rad = lambda.to_radians();
-    let eps_rad = epsilon.to_radians();
-    let alpha = (eps_rad.cos() * lambda_rad.sin())
-        .atan2(lambda_rad.cos())
-        .to_degrees();
-    let delta = (eps_rad.sin() * lambda_rad.sin()).asin().to_degrees();
-
-    // Greenwich Mean Sidereal Time (degrees)
-    let t = (jd - 2451545.0) / 36525.0;
-    let gmst = wrap_360(
-        280.46061837 + 360.98564736629 * (jd - 2451545.0) + 0.000387933 * t * t
-            - (t * t * t) / 38710000.0,
-    );
-
-    // Subsolar point
-    let subsolar_lon = wrap_180(alpha - gmst);
-    let subsolar_lat = delta;
-
-    // Convert to a unit direction in ECEF, then map to viewer coords: (x, z, -y)
-    let lon = subsolar_lon.to_radians();
-    let lat = subsolar_lat.to_radians();
-    let cos_lat = lat.cos();
-    let ecef = [cos_lat * lon.cos(), cos_lat * lon.sin(), lat.sin()];
-    Some([ecef[0] as f32, ecef[2] as f32, (-ecef[1]) as f32])
+    let sun = foundation::astro::sun_position(foundation::time::Time(ms / 1000.0));
+    // ECEF unit direction mapped to viewer coords: (x, z, -y).
+    let d = sun.direction();
+    Some([d.x as f32, d.z as f32, (-d.y) as f32])
 }
 
 fn render_scene() -> Result<(), JsValue> {
+    runtime::trace_span!("render_scene", "render");
     let mode = match STATE.try_with(|state_ref| state_ref.borrow().view_mode) {
         Ok(mode) => mode,
         // During hot-reload / teardown, JS callbacks can still fire briefly.
@@ -2180,6 +2118,8 @@ fn render_scene() -> Result<(), JsValue> {
                     s.perf_gpu_draw_vertices = snap.draw_vertices;
                     s.perf_gpu_draw_indices = snap.draw_indices;
                     s.perf_gpu_frame_ms = (t1 - t0).max(0.0);
+                    let frame_us = (s.perf_gpu_frame_ms * 1000.0).round() as i64;
+                    s.perf_gpu_frame_us.record(frame_us);
                 }
             });
 
@@ -2810,12 +2750,12 @@ fn render_scene_2d() -> Result<(), JsValue> {
                     let (lon_b, lat_b) = world_to_lon_lat_fast_deg(b);
                     let (lon_c, lat_c) = world_to_lon_lat_fast_deg(c);
 
-                    let ax_m = mercator_x_m(lon_a);
-                    let ay_m = mercator_y_m(lat_a);
-                    let bx_m = mercator_x_m(lon_b);
-                    let by_m = mercator_y_m(lat_b);
-                    let cx_m = mercator_x_m(lon_c);
-                    let cy_m = mercator_y_m(lat_c);
+                    let ax_m = WebMercator::x_m(lon_a);
+                    let ay_m = WebMercator::y_m(lat_a);
+                    let bx_m = WebMercator::x_m(lon_b);
+                    let by_m = WebMercator::y_m(lat_b);
+                    let cx_m = WebMercator::x_m(lon_c);
+                    let cy_m = WebMercator::y_m(lat_c);
 
                     // Unwrap mercator X per-triangle to avoid seam-spanning triangles.
                     let ax_adj = {
@@ -2953,10 +2893,10 @@ fn render_scene_2d() -> Result<(), JsValue> {
                     let (lon_a, lat_a) = world_to_lon_lat_fast_deg(a);
                     let (lon_b, lat_b) = world_to_lon_lat_fast_deg(b);
 
-                    let ax_m = mercator_x_m(lon_a);
-                    let ay_m = mercator_y_m(lat_a);
-                    let bx_m = mercator_x_m(lon_b);
-                    let by_m = mercator_y_m(lat_b);
+                    let ax_m = WebMercator::x_m(lon_a);
+                    let ay_m = WebMercator::y_m(lat_a);
+                    let bx_m = WebMercator::x_m(lon_b);
+                    let by_m = WebMercator::y_m(lat_b);
 
                     // Unwrap mercator X per-segment.
                     let ax_adj = {
@@ -3185,8 +3125,8 @@ fn render_scene_2d_wgpu() -> Result<(), JsValue> {
         let w = state.canvas_width.max(1.0);
         let h = state.canvas_height.max(1.0);
 
-        let center_x = mercator_x_m(state.camera_2d.center_lon_deg) as f32;
-        let center_y = mercator_y_m(state.camera_2d.center_lat_deg) as f32;
+        let center_x = WebMercator::x_m(state.camera_2d.center_lon_deg) as f32;
+        let center_y = WebMercator::y_m(state.camera_2d.center_lat_deg) as f32;
         let scale_px_per_m = camera2d_scale_px_per_m(state.camera_2d, w, h) as f32;
         let world_width_m = (2.0 * std::f64::consts::PI * WGS84_A) as f32;
 
@@ -3414,6 +3354,8 @@ fn render_scene_2d_wgpu() -> Result<(), JsValue> {
             state.perf_gpu_draw_vertices = snap.draw_vertices;
             state.perf_gpu_draw_indices = snap.draw_indices;
             state.perf_gpu_frame_ms = (t1 - t0).max(0.0);
+            let frame_us = (state.perf_gpu_frame_ms * 1000.0).round() as i64;
+            state.perf_gpu_frame_us.record(frame_us);
         }
         res
     }) {
@@ -3475,10 +3417,32 @@ pub fn get_2d_perf_stats() -> Result<JsValue, JsValue> {
     Ok(out.into())
 }
 
+/// Starts recording runtime trace spans (see `runtime::trace`). Spans are only emitted
+/// when the app is built with the `trace` feature.
+#[wasm_bindgen]
+pub fn start_trace_capture() {
+    runtime::trace::set_clock(performance_now_us);
+    runtime::trace::start_capture();
+}
+
+/// Stops recording and returns the capture as Chrome Trace Event JSON for Perfetto.
+#[wasm_bindgen]
+pub fn stop_trace_capture() -> String {
+    runtime::trace::stop_capture().to_chrome_json()
+}
+
+/// Sub-millisecond page clock for trace spans; `Date::now` is too coarse.
+fn performance_now_us() -> f64 {
+    let ms = web_sys::window()
+        .and_then(|w| w.performance())
+        .map_or_else(js_sys::Date::now, |p| p.now());
+    ms * 1000.0
+}
+
 #[wasm_bindgen]
 pub fn get_gpu_perf_stats() -> Result<JsValue, JsValue> {
     let out = js_sys::Object::new();
-    let (upload_calls, upload_bytes, passes, draw_calls, inst, vtx, idx, frame_ms) =
+    let (upload_calls, upload_bytes, passes, draw_calls, inst, vtx, idx, frame_ms, frame_us) =
         with_state(|state| {
             let s = state.borrow();
             (
@@ -3490,6 +3454,7 @@ pub fn get_gpu_perf_stats() -> Result<JsValue, JsValue> {
                 s.perf_gpu_draw_vertices,
                 s.perf_gpu_draw_indices,
                 s.perf_gpu_frame_ms,
+                s.perf_gpu_frame_us.clone(),
             )
         });
 
@@ -3504,6 +3469,14 @@ pub fn get_gpu_perf_stats() -> Result<JsValue, JsValue> {
         &JsValue::from_str("frame_ms"),
         &JsValue::from_f64(frame_ms),
     )?;
+    for (key, q) in [
+        ("frame_ms_p50", 0.5),
+        ("frame_ms_p95", 0.95),
+        ("frame_ms_p99", 0.99),
+    ] {
+        let ms = frame_us.quantile(q).unwrap_or(0.0) / 1000.0;
+        js_sys::Reflect::set(&out, &JsValue::from_str(key), &JsValue::from_f64(ms))?;
+    }
     js_sys::Reflect::set(
         &out,
         &JsValue::from_str("upload_calls"),
@@ -3619,6 +3592,7 @@ fn world_from_vector_chunk(
     chunk: &formats::VectorChunk,
     expected_kind: Option<VectorGeometryKind>,
 ) -> scene::World {
+    runtime::trace_span!("ingest_vector_chunk", "decode");
     let mut world = scene::World::new();
     scene::prefabs::spawn_wgs84_globe(&mut world);
     formats::ingest_vector_chunk(&mut world, chunk, expected_kind);
@@ -3997,6 +3971,7 @@ fn rebuild_styles_and_upload_only() -> Result<(), JsValue> {
 }
 
 fn rebuild_overlays_and_upload() -> Result<(), JsValue> {
+    runtime::trace_span!("rebuild_overlays_and_upload", "gpu");
     // Budget guardrails: the web viewer will trap on extremely large GPU buffers.
     // Increased limits to support larger datasets; JS-side validation can still apply lower limits.
     const MAX_UPLOADED_POINTS: usize = 2_000_000;
@@ -4381,10 +4356,10 @@ fn rebuild_overlays_and_upload() -> Result<(), JsValue> {
                             else {
                                 continue;
                             };
-                            let ax = mercator_x_m(clon0);
-                            let ay = mercator_y_m(clat0);
-                            let bx0 = mercator_x_m(clon1);
-                            let by = mercator_y_m(clat1);
+                            let ax = WebMercator::x_m(clon0);
+                            let ay = WebMercator::y_m(clat0);
+                            let bx0 = WebMercator::x_m(clon1);
+                            let by = WebMercator::y_m(clat1);
                             let bx = unwrap_mercator_x_m(ax, bx0);
                             lines2d.push(Segment2DInstance {
                                 a_m: [ax as f32, ay as f32],
@@ -4539,10 +4514,10 @@ fn rebuild_overlays_and_upload() -> Result<(), JsValue> {
                             else {
                                 continue;
                             };
-                            let ax = mercator_x_m(clon0);
-                            let ay = mercator_y_m(clat0);
-                            let bx0 = mercator_x_m(clon1);
-                            let by = mercator_y_m(clat1);
+                            let ax = WebMercator::x_m(clon0);
+                            let ay = WebMercator::y_m(clat0);
+                            let bx0 = WebMercator::x_m(clon1);
+                            let by = WebMercator::y_m(clat1);
                             let bx = unwrap_mercator_x_m(ax, bx0);
                             lines2d.push(Segment2DInstance {
                                 a_m: [ax as f32, ay as f32],
@@ -4891,10 +4866,10 @@ fn rebuild_overlays_and_upload() -> Result<(), JsValue> {
 
         // 2D graticule (Mercator). Small enough to regenerate per rebuild.
         {
-            let y0 = mercator_y_m(-85.0) as f32;
-            let y1 = mercator_y_m(85.0) as f32;
+            let y0 = WebMercator::y_m(-85.0) as f32;
+            let y1 = WebMercator::y_m(85.0) as f32;
             for lon in (-180..=180).step_by(10) {
-                let x = mercator_x_m(lon as f64) as f32;
+                let x = WebMercator::x_m(lon as f64) as f32;
                 grid2d.push(Segment2DInstance {
                     a_m: [x, y0],
                     b_m: [x, y1],
@@ -4903,10 +4878,10 @@ fn rebuild_overlays_and_upload() -> Result<(), JsValue> {
                 });
             }
             for lat in (-80..=80).step_by(10) {
-                let y = mercator_y_m(lat as f64) as f32;
+                let y = WebMercator::y_m(lat as f64) as f32;
                 let mut prev = None;
                 for lon in (-180..=180).step_by(10) {
-                    let x = mercator_x_m(lon as f64) as f32;
+                    let x = WebMercator::x_m(lon as f64) as f32;
                     let cur = [x, y];
                     if let Some(p) = prev {
                         grid2d.push(Segment2DInstance {
@@ -5674,7 +5649,7 @@ pub fn add_debug_label_with_priority(
         return Err(JsValue::from_str("debug label text too long"));
     }
     let lon = wrap_lon_deg(lon_deg);
-    let lat = clamp(lat_deg, -MERCATOR_MAX_LAT_DEG, MERCATOR_MAX_LAT_DEG);
+    let lat = clamp(lat_deg, -WEB_MERCATOR_MAX_LAT_DEG, WEB_MERCATOR_MAX_LAT_DEG);
 
     let viewer_pos = lon_lat_deg_to_world(lon, lat);
 
@@ -5803,6 +5778,8 @@ pub fn set_layer_visible(id: &str, visible: bool) -> Result<(), JsValue> {
         if id == "terrain" {
             ensure_terrain_loaded();
         }
+    } else if let Some(load) = AssetLoad::builtin_layer(id) {
+        cancel_asset_load(load);
     }
     let _ = rebuild_overlays_and_upload();
     render_scene()
@@ -6094,6 +6071,7 @@ fn outline_segments_from_geo_rings(rings: &[Vec<formats::GeoPoint>]) -> Vec<([f3
 }
 
 fn triangulate_geo_polygon_rings_to_world_tris(rings: &[Vec<formats::GeoPoint>]) -> Vec<[f32; 3]> {
+    runtime::trace_span!("triangulate_polygon", "triangulate");
     const MAX_RING_VERTICES: usize = 100_000;
     const MAX_TOTAL_VERTICES: usize = 500_000;
 
@@ -6485,52 +6463,55 @@ fn ensure_base_world_loaded() {
         s.base_world_source = Some("world.json".to_string());
     });
 
-    spawn_local(async move {
-        let chunk = match fetch_geojson_chunk("assets/world.json").await {
-            Ok(c) => c,
-            Err(err) => {
-                web_sys::console::log_1(&JsValue::from_str(&format!(
-                    "Failed to fetch base world: {:?}",
-                    err
-                )));
-                with_state(|state| {
-                    let mut s = state.borrow_mut();
-                    s.base_world_loading = false;
-                    s.base_world_error = Some("base world load failed".to_string());
-                });
-                return;
-            }
-        };
+    start_asset_load(AssetLoad::BaseWorld);
+}
 
-        let chunk = unwrap_antimeridian_chunk(&chunk);
+fn finish_base_world_load(chunk: Result<formats::VectorChunk, JsValue>) {
+    let chunk = match chunk {
+        Ok(c) => c,
+        Err(err) => {
+            web_sys::console::log_1(&JsValue::from_str(&format!(
+                "Failed to fetch base world: {:?}",
+                err
+            )));
+            with_state(|state| {
+                let mut s = state.borrow_mut();
+                s.base_world_loading = false;
+                s.base_world_error = Some("base world load failed".to_string());
+            });
+            return;
+        }
+    };
 
-        let (_points, _lines, polys) = count_chunk_features(&chunk);
-        let world = world_from_vector_chunk(&chunk, Some(VectorGeometryKind::Area));
-        // TODO: when a DEM/surface model is available, sample heights for layer-0 terrain.
+    let chunk = chunk.unwrap_antimeridian();
 
-        with_state(|state| {
-            let mut s = state.borrow_mut();
-            // Avoid races: if another base-world source started loading after we began
-            // fetching world.json (e.g., PMTiles streaming), do not overwrite it.
-            if s.base_world_source.as_deref() != Some("world.json") {
-                return;
-            }
-            s.base_world = Some(world);
-            s.base_count_polys = polys;
-            s.base_world_loading = false;
-            s.surface_last_error = None;
+    let (_points, _lines, polys) = count_chunk_features(&chunk);
+    let world = world_from_vector_chunk(&chunk, Some(VectorGeometryKind::Area));
+    // TODO: when a DEM/surface model is available, sample heights for layer-0 terrain.
 
-            // World content changed; refresh cached label anchors/layout.
-            s.labels_gen = s.labels_gen.wrapping_add(1);
-        });
+    with_state(|state| {
+        let mut s = state.borrow_mut();
+        // Avoid races: if another base-world source started loading after we began
+        // fetching world.json (e.g., PMTiles streaming), do not overwrite it.
+        if s.base_world_source.as_deref() != Some("world.json") {
+            return;
+        }
+        s.base_world = Some(world);
+        s.base_count_polys = polys;
+        s.base_world_loading = false;
+        s.surface_last_error = None;
 
-        let _ = rebuild_overlays_and_upload();
-        let _ = render_scene();
+        // World content changed; refresh cached label anchors/layout.
+        s.labels_gen = s.labels_gen.wrapping_add(1);
     });
+
+    let _ = rebuild_overlays_and_upload();
+    let _ = render_scene();
 }
 
 #[wasm_bindgen]
 pub fn begin_base_world_stream() {
+    cancel_asset_load(AssetLoad::BaseWorld);
     with_state(|state| {
         let mut s = state.borrow_mut();
         let mut world = scene::World::new();
@@ -6558,7 +6539,7 @@ pub fn begin_base_world_stream() {
 pub fn append_base_world_geojson_chunk(geojson_text: String) -> Result<(), JsValue> {
     let chunk = formats::VectorChunk::from_geojson_str(&geojson_text)
         .map_err(|e| JsValue::from_str(&e.to_string()))?;
-    let chunk = unwrap_antimeridian_chunk(&chunk);
+    let chunk = chunk.unwrap_antimeridian();
 
     with_state(|state| {
         let mut s = state.borrow_mut();
@@ -6606,175 +6587,227 @@ pub fn finish_base_world_stream() {
     let _ = render_scene();
 }
 
-fn unwrap_antimeridian_chunk(chunk: &formats::VectorChunk) -> formats::VectorChunk {
-    use formats::{VectorChunk, VectorFeature, VectorGeometry};
+fn ensure_builtin_layer_loaded(id: &str) {
+    if id == "world_base" {
+        ensure_surface_loaded();
+        return;
+    }
+    let Some(load) = AssetLoad::builtin_layer(id) else {
+        return;
+    };
+    let needs_load = with_state(|state| {
+        let s = state.borrow();
+        match load {
+            AssetLoad::Cities => s.cities_world.is_none(),
+            AssetLoad::AirCorridors => s.corridors_world.is_none(),
+            AssetLoad::Regions => s.regions_world.is_none(),
+            AssetLoad::BaseWorld => false,
+        }
+    });
+    if needs_load {
+        start_asset_load(load);
+    }
+}
 
-    let features = chunk
-        .features
-        .iter()
-        .map(|feat| {
-            let geometry = match &feat.geometry {
-                VectorGeometry::Polygon(rings) => {
-                    VectorGeometry::Polygon(rings.iter().map(|ring| unwrap_ring(ring)).collect())
-                }
-                VectorGeometry::MultiPolygon(polys) => VectorGeometry::MultiPolygon(
-                    polys
-                        .iter()
-                        .map(|poly| poly.iter().map(|ring| unwrap_ring(ring)).collect())
-                        .collect(),
-                ),
-                VectorGeometry::LineString(points) => {
-                    VectorGeometry::LineString(unwrap_ring(points))
-                }
-                VectorGeometry::MultiLineString(lines) => VectorGeometry::MultiLineString(
-                    lines.iter().map(|line| unwrap_ring(line)).collect(),
-                ),
-                other => other.clone(),
-            };
+fn finish_builtin_layer_load(load: AssetLoad, chunk: Result<formats::VectorChunk, JsValue>) {
+    let chunk = match chunk {
+        Ok(c) => c,
+        Err(err) => {
+            web_sys::console::log_1(&JsValue::from_str(&format!(
+                "Failed to fetch {}: {:?}",
+                load.label(),
+                err
+            )));
+            return;
+        }
+    };
 
-            VectorFeature {
-                id: feat.id.clone(),
-                properties: feat.properties.clone(),
-                geometry,
+    let (points, lines, polys) = count_chunk_features(&chunk);
+    with_state(|state| {
+        let mut s = state.borrow_mut();
+        match load {
+            AssetLoad::Cities => {
+                s.cities_world = Some(world_from_vector_chunk(
+                    &chunk,
+                    Some(VectorGeometryKind::Point),
+                ));
+                s.cities_count_points = points;
             }
-        })
-        .collect();
+            AssetLoad::AirCorridors => {
+                s.corridors_world = Some(world_from_vector_chunk(
+                    &chunk,
+                    Some(VectorGeometryKind::Line),
+                ));
+                s.corridors_count_lines = lines;
+            }
+            AssetLoad::Regions => {
+                s.regions_world = Some(world_from_vector_chunk(
+                    &chunk,
+                    Some(VectorGeometryKind::Area),
+                ));
+                s.regions_count_polys = polys;
+            }
+            AssetLoad::BaseWorld => return,
+        }
 
-    VectorChunk { features }
+        // World content changed; refresh cached label anchors/layout.
+        s.labels_gen = s.labels_gen.wrapping_add(1);
+    });
+    let _ = rebuild_overlays_and_upload();
+    let _ = render_scene();
 }
 
-fn unwrap_ring(points: &[formats::GeoPoint]) -> Vec<formats::GeoPoint> {
-    if points.is_empty() {
-        return Vec::new();
-    }
+/// Bundled assets fetched on demand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum AssetLoad {
+    BaseWorld,
+    Cities,
+    AirCorridors,
+    Regions,
+}
 
-    let mut out: Vec<formats::GeoPoint> = Vec::with_capacity(points.len());
-    let mut prev_lon = points[0].lon_deg;
-    out.push(formats::GeoPoint::new(prev_lon, points[0].lat_deg));
+impl AssetLoad {
+    fn builtin_layer(id: &str) -> Option<Self> {
+        match id {
+            "cities" => Some(Self::Cities),
+            "air_corridors" => Some(Self::AirCorridors),
+            "regions" => Some(Self::Regions),
+            _ => None,
+        }
+    }
 
-    for p in points.iter().skip(1) {
-        let mut lon = p.lon_deg;
-        let mut delta = lon - prev_lon;
-        if delta > 180.0 {
-            lon -= 360.0;
-        } else if delta < -180.0 {
-            lon += 360.0;
+    fn url(self) -> &'static str {
+        match self {
+            Self::BaseWorld => "assets/world.json",
+            Self::Cities => "assets/chunks/cities.avc",
+            Self::AirCorridors => "assets/chunks/air_corridors.avc",
+            Self::Regions => "assets/chunks/regions.avc",
         }
-        delta = lon - prev_lon;
-        if delta > 180.0 {
-            lon -= 360.0;
-        } else if delta < -180.0 {
-            lon += 360.0;
+    }
+
+    /// Task name on the executor, also used in log messages.
+    fn label(self) -> &'static str {
+        match self {
+            Self::BaseWorld => "base world",
+            Self::Cities => "cities",
+            Self::AirCorridors => "air corridors",
+            Self::Regions => "regions",
         }
-        prev_lon = lon;
-        out.push(formats::GeoPoint::new(lon, p.lat_deg));
     }
+}
 
-    out
+/// Asset fetches run as tasks on a `runtime::Executor` that `advance_frame` polls, so
+/// they make progress and land inside the frame loop rather than in free-running
+/// `spawn_local` futures. Kept apart from `STATE` because finishing a load borrows it.
+#[derive(Default)]
+struct AssetLoads {
+    executor: runtime::Executor,
+    frames: u64,
+    in_flight: BTreeMap<AssetLoad, runtime::TaskHandle<Result<formats::VectorChunk, JsValue>>>,
 }
 
-fn ensure_builtin_layer_loaded(id: &str) {
-    match id {
-        "world_base" => {
-            ensure_surface_loaded();
+thread_local! {
+    static ASSET_LOADS: RefCell<AssetLoads> = RefCell::new(AssetLoads::default());
+}
+
+/// Starts fetching `load` unless it is already in flight.
+fn start_asset_load(load: AssetLoad) {
+    ASSET_LOADS.with(|loads| {
+        let mut loads = loads.borrow_mut();
+        if loads.in_flight.contains_key(&load) {
+            return;
         }
-        "cities" => {
-            let needs_load = with_state(|state| state.borrow().cities_world.is_none());
-            if !needs_load {
-                return;
+        let handle = loads.executor.spawn(load.label(), async move {
+            match load {
+                AssetLoad::BaseWorld => fetch_geojson_chunk(load.url()).await,
+                _ => fetch_vector_chunk(load.url()).await,
             }
-            spawn_local(async move {
-                let chunk = match fetch_vector_chunk("assets/chunks/cities.avc").await {
-                    Ok(c) => c,
-                    Err(err) => {
-                        web_sys::console::log_1(&JsValue::from_str(&format!(
-                            "Failed to fetch cities: {:?}",
-                            err
-                        )));
-                        return;
-                    }
-                };
-
-                let (points, _lines, _polys) = count_chunk_features(&chunk);
-                let world = world_from_vector_chunk(&chunk, Some(VectorGeometryKind::Point));
-
-                with_state(|state| {
-                    let mut s = state.borrow_mut();
-                    s.cities_world = Some(world);
-                    s.cities_count_points = points;
+        });
+        loads.in_flight.insert(load, handle);
+    });
+}
 
-                    // World content changed; refresh cached label anchors/layout.
-                    s.labels_gen = s.labels_gen.wrapping_add(1);
-                });
-                let _ = rebuild_overlays_and_upload();
-                let _ = render_scene();
-            });
+/// Drops an in-flight fetch; the executor reports it as `task.canceled` next frame.
+fn cancel_asset_load(load: AssetLoad) {
+    ASSET_LOADS.with(|loads| {
+        if let Some(handle) = loads.borrow_mut().in_flight.remove(&load) {
+            handle.cancel();
         }
-        "air_corridors" => {
-            let needs_load = with_state(|state| state.borrow().corridors_world.is_none());
-            if !needs_load {
-                return;
-            }
-            spawn_local(async move {
-                let chunk = match fetch_vector_chunk("assets/chunks/air_corridors.avc").await {
-                    Ok(c) => c,
-                    Err(err) => {
-                        web_sys::console::log_1(&JsValue::from_str(&format!(
-                            "Failed to fetch air corridors: {:?}",
-                            err
-                        )));
-                        return;
-                    }
-                };
+    });
+}
 
-                let (_points, lines, _polys) = count_chunk_features(&chunk);
-                let world = world_from_vector_chunk(&chunk, Some(VectorGeometryKind::Line));
+/// Queue name of the asset fetches in the frame pacing controller.
+const ASSET_LOADS_SHARE: &str = "asset_loads";
 
-                with_state(|state| {
-                    let mut s = state.borrow_mut();
-                    s.corridors_world = Some(world);
-                    s.corridors_count_lines = lines;
+/// Frame intervals longer than this are pauses (hidden tab, debugger) rather than slow
+/// frames and are not fed to the controller.
+const MAX_PACED_FRAME_MS: f64 = 250.0;
 
-                    // World content changed; refresh cached label anchors/layout.
-                    s.labels_gen = s.labels_gen.wrapping_add(1);
-                });
-                let _ = rebuild_overlays_and_upload();
-                let _ = render_scene();
-            });
-        }
-        "regions" => {
-            let needs_load = with_state(|state| state.borrow().regions_world.is_none());
-            if !needs_load {
-                return;
-            }
-            spawn_local(async move {
-                let chunk = match fetch_vector_chunk("assets/chunks/regions.avc").await {
-                    Ok(c) => c,
-                    Err(err) => {
-                        web_sys::console::log_1(&JsValue::from_str(&format!(
-                            "Failed to fetch regions: {:?}",
-                            err
-                        )));
-                        return;
-                    }
-                };
+/// Scales per-frame work with the measured time between `advance_frame` calls.
+struct FramePacing {
+    controller: runtime::BudgetController,
+    last_frame_ms: Option<f64>,
+}
 
-                let (_points, _lines, polys) = count_chunk_features(&chunk);
-                let world = world_from_vector_chunk(&chunk, Some(VectorGeometryKind::Area));
+thread_local! {
+    static FRAME_PACING: RefCell<FramePacing> = RefCell::new(FramePacing {
+        controller: {
+            let mut controller = runtime::BudgetController::default();
+            controller.set_share(ASSET_LOADS_SHARE, 1);
+            controller
+        },
+        last_frame_ms: None,
+    });
+}
 
-                with_state(|state| {
-                    let mut s = state.borrow_mut();
-                    s.regions_world = Some(world);
-                    s.regions_count_polys = polys;
+/// Records the interval since the previous frame and returns this frame's budget for the
+/// asset fetches.
+fn pace_frame(now_ms: f64) -> runtime::FrameBudget {
+    FRAME_PACING.with(|pacing| {
+        let mut pacing = pacing.borrow_mut();
+        if let Some(last) = pacing.last_frame_ms.replace(now_ms) {
+            let frame_ms = now_ms - last;
+            if frame_ms <= MAX_PACED_FRAME_MS {
+                pacing.controller.record_frame(frame_ms);
+            }
+        }
+        pacing.controller.budget_for(ASSET_LOADS_SHARE)
+    })
+}
 
-                    // World content changed; refresh cached label anchors/layout.
-                    s.labels_gen = s.labels_gen.wrapping_add(1);
-                });
-                let _ = rebuild_overlays_and_upload();
-                let _ = render_scene();
+/// Polls the asset fetches once and applies those the executor reported completed.
+fn run_asset_loads(budget: &mut runtime::FrameBudget) {
+    let finished = ASSET_LOADS.with(|loads| {
+        let mut loads = loads.borrow_mut();
+        let loads = &mut *loads;
+        if loads.executor.is_empty() {
+            return Vec::new();
+        }
+        let mut bus = runtime::EventBus::new();
+        let frame = runtime::Frame::new(loads.frames, 0.0);
+        loads.frames += 1;
+        loads.executor.run_frame(frame, &mut bus, budget);
+        if !bus.events().iter().any(|e| e.kind == "task.completed") {
+            return Vec::new();
+        }
+        let mut finished = Vec::new();
+        loads
+            .in_flight
+            .retain(|&load, handle| match handle.try_take() {
+                Some(chunk) => {
+                    finished.push((load, chunk));
+                    false
+                }
+                None => true,
             });
+        finished
+    });
+
+    for (load, chunk) in finished {
+        match load {
+            AssetLoad::BaseWorld => finish_base_world_load(chunk),
+            _ => finish_builtin_layer_load(load, chunk),
         }
-        _ => {}
     }
 }
 
@@ -6850,18 +6883,24 @@ fn terrain_tile_url(
 }
 
 fn terrain_tile_bounds(tileset: &TerrainTileset, z: u32, x: u32, y: u32) -> (f64, f64, f64, f64) {
-    let n = 2u32.pow(z);
-    let lon_span = (tileset.max_lon - tileset.min_lon) / n as f64;
-    let lat_span = (tileset.max_lat - tileset.min_lat) / n as f64;
-
-    let lon_min = tileset.min_lon + (x as f64) * lon_span;
-    let lon_max = lon_min + lon_span;
-    let lat_max = tileset.max_lat - (y as f64) * lat_span;
-    let lat_min = lat_max - lat_span;
+    let tms = streaming::TileMatrixSet::geographic(
+        "terrain",
+        [
+            tileset.min_lon,
+            tileset.min_lat,
+            tileset.max_lon,
+            tileset.max_lat,
+        ],
+        1,
+        1,
+    );
+    let z = u8::try_from(z).unwrap_or(u8::MAX);
+    let [lon_min, lat_min, lon_max, lat_max] = tms.tile_bounds(&streaming::TileCoord::new(z, x, y));
     (lon_min, lon_max, lat_min, lat_max)
 }
 
 fn decode_f32_le(bytes: &[u8]) -> Vec<f32> {
+    runtime::trace_span!("decode_f32_le", "decode");
     let mut out = Vec::with_capacity(bytes.len() / 4);
     for chunk in bytes.chunks_exact(4) {
         out.push(f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]));
@@ -6870,6 +6909,7 @@ fn decode_f32_le(bytes: &[u8]) -> Vec<f32> {
 }
 
 fn decode_f32_vec3(bytes: &[u8]) -> Vec<[f32; 3]> {
+    runtime::trace_span!("decode_f32_vec3", "decode");
     let mut out = Vec::with_capacity(bytes.len() / 12);
     for chunk in bytes.chunks_exact(12) {
         let x = f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
@@ -7887,8 +7927,8 @@ fn world_to_lon_lat_deg(p: [f64; 3]) -> (f64, f64) {
 
 fn viewer_to_mercator_m(p: &[f32; 3]) -> [f32; 2] {
     let (lon, lat) = world_to_lon_lat_fast_deg([p[0], p[1], p[2]]);
-    let x = mercator_x_m(lon);
-    let y = mercator_y_m(lat);
+    let x = WebMercator::x_m(lon);
+    let y = WebMercator::y_m(lat);
     [x as f32, y as f32]
 }
 
@@ -8019,6 +8059,39 @@ pub fn cursor_move(x_px: f64, y_px: f64) -> Result<JsValue, JsValue> {
     Ok(out.into())
 }
 
+/// Format a position for the cursor readout. `format` is a `CoordinateFormat` name
+/// (`decimal`, `ddm`, `dms`, `utm`, `mgrs`, `usng`, `geohash`); `None` if the name is
+/// unknown or the position has no representation in that notation.
+#[wasm_bindgen]
+pub fn format_coordinate(
+    lon_deg: f64,
+    lat_deg: f64,
+    format: &str,
+    precision: Option<u8>,
+) -> Option<String> {
+    CoordinateFormat::from_name(format, precision)?
+        .format(Geodetic::from_degrees(lat_deg, lon_deg, 0.0))
+}
+
+/// Parse pasted coordinates in any supported notation (lenient) to `{ lon, lat }`.
+#[wasm_bindgen]
+pub fn parse_coordinate(text: &str) -> Result<JsValue, JsValue> {
+    let geo = foundation::math::parse_coordinate(text, ParseMode::Lenient)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let out = js_sys::Object::new();
+    js_sys::Reflect::set(
+        &out,
+        &JsValue::from_str("lon"),
+        &JsValue::from_f64(geo.lon_deg()),
+    )?;
+    js_sys::Reflect::set(
+        &out,
+        &JsValue::from_str("lat"),
+        &JsValue::from_f64(geo.lat_deg()),
+    )?;
+    Ok(out.into())
+}
+
 #[wasm_bindgen]
 pub fn cursor_click(x_px: f64, y_px: f64) -> Result<JsValue, JsValue> {
     let out = js_sys::Object::new();
@@ -8954,6 +9027,7 @@ pub fn load_dataset(url: String) {
 fn world_from_vector_chunks(
     chunks: &[(formats::VectorChunk, Option<VectorGeometryKind>)],
 ) -> scene::World {
+    runtime::trace_span!("ingest_vector_chunks", "decode");
     let mut world = scene::World::new();
     scene::prefabs::spawn_wgs84_globe(&mut world);
     for (chunk, expected_kind) in chunks {
@@ -8988,6 +9062,9 @@ fn now_ms() -> f64 {
 /// This is intentionally not wall-clock driven so it can be replayed.
 #[wasm_bindgen]
 pub fn advance_frame() -> Result<f64, JsValue> {
+    let mut budget = pace_frame(performance_now_us() / 1000.0);
+    run_asset_loads(&mut budget);
+
     with_state(|state| {
         let mut s = state.borrow_mut();
         s.frame_index = s.frame_index.wrapping_add(1);
@@ -9763,8 +9840,8 @@ mod camera_contract_tests {
     #[test]
     fn mercator_roundtrip_longitude() {
         let lon = 45.0;
-        let x = mercator_x_m(lon);
-        let roundtrip = inverse_mercator_lon_deg(x);
+        let x = WebMercator::x_m(lon);
+        let roundtrip = WebMercator::lon_deg(x);
         assert!(
             (roundtrip - lon).abs() < 1e-8,
             "mercator lon roundtrip must be lossless"
@@ -9774,8 +9851,8 @@ mod camera_contract_tests {
     #[test]
     fn mercator_roundtrip_latitude() {
         let lat = 51.5; // London
-        let y = mercator_y_m(lat);
-        let roundtrip = inverse_mercator_lat_deg(y);
+        let y = WebMercator::y_m(lat);
+        let roundtrip = WebMercator::lat_deg(y);
         assert!(
             (roundtrip - lat).abs() < 1e-8,
             "mercator lat roundtrip must be lossless"
@@ -9791,7 +9868,7 @@ mod camera_contract_tests {
         };
         // Large upward drag should not exceed max Mercator latitude.
         let result = pan_camera_2d(cam, 0.0, -100000.0, 1280.0, 720.0);
-        assert!(result.center_lat_deg <= MERCATOR_MAX_LAT_DEG);
-        assert!(result.center_lat_deg >= -MERCATOR_MAX_LAT_DEG);
+        assert!(result.center_lat_deg <= WEB_MERCATOR_MAX_LAT_DEG);
+        assert!(result.center_lat_deg >= -WEB_MERCATOR_MAX_LAT_DEG);
     }
 }
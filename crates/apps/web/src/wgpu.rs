@@ -878,6 +878,7 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
     }
 
     pub fn set_styles(ctx: &mut WgpuContext, styles: &[Style]) {
+        runtime::trace_span!("set_styles", "gpu");
         let required_bytes = (styles.len() * std::mem::size_of::<Style>()) as u64;
         if required_bytes > ctx.styles_capacity_bytes {
             ensure_buffer_capacity(
@@ -2537,6 +2538,7 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
     }
 
     pub fn set_cities_points(ctx: &mut WgpuContext, points: &[CityVertex]) {
+        runtime::trace_span!("set_cities_points", "gpu");
         if points.is_empty() {
             ctx.cities_instance_count = 0;
             return;
@@ -2561,6 +2563,7 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
     }
 
     pub fn set_corridors_points(ctx: &mut WgpuContext, points: &[CorridorVertex]) {
+        runtime::trace_span!("set_corridors_points", "gpu");
         if points.is_empty() {
             ctx.corridors_instance_count = 0;
             return;
@@ -2588,6 +2591,7 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
     }
 
     pub fn set_regions_points(ctx: &mut WgpuContext, points: &[OverlayVertex]) {
+        runtime::trace_span!("set_regions_points", "gpu");
         if points.is_empty() {
             ctx.regions_vertex_count = 0;
             return;
@@ -2612,6 +2616,7 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
     }
 
     pub fn set_terrain_points(ctx: &mut WgpuContext, points: &[TerrainVertex]) {
+        runtime::trace_span!("set_terrain_points", "gpu");
         if points.is_empty() {
             ctx.terrain_vertex_count = 0;
             return;
@@ -2636,6 +2641,7 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
     }
 
     pub fn set_base_regions_points(ctx: &mut WgpuContext, points: &[OverlayVertex]) {
+        runtime::trace_span!("set_base_regions_points", "gpu");
         if points.is_empty() {
             ctx.base_regions_vertex_count = 0;
             return;
@@ -2663,6 +2669,7 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
     }
 
     pub fn set_base_regions2d_vertices(ctx: &mut WgpuContext, verts: &[Overlay2DVertex]) {
+        runtime::trace_span!("set_base_regions2d_vertices", "gpu");
         if verts.is_empty() {
             ctx.base_regions2d_vertex_count = 0;
             return;
@@ -2690,6 +2697,7 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
     }
 
     pub fn set_regions2d_vertices(ctx: &mut WgpuContext, verts: &[Overlay2DVertex]) {
+        runtime::trace_span!("set_regions2d_vertices", "gpu");
         if verts.is_empty() {
             ctx.regions2d_vertex_count = 0;
             return;
@@ -2714,6 +2722,7 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
     }
 
     pub fn set_points2d_instances(ctx: &mut WgpuContext, inst: &[Point2DInstance]) {
+        runtime::trace_span!("set_points2d_instances", "gpu");
         if inst.is_empty() {
             ctx.points2d_instance_count = 0;
             return;
@@ -2738,6 +2747,7 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
     }
 
     pub fn set_lines2d_instances(ctx: &mut WgpuContext, inst: &[Segment2DInstance]) {
+        runtime::trace_span!("set_lines2d_instances", "gpu");
         if inst.is_empty() {
             ctx.lines2d_instance_count = 0;
             return;
@@ -2762,6 +2772,7 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
     }
 
     pub fn set_grid2d_instances(ctx: &mut WgpuContext, inst: &[Segment2DInstance]) {
+        runtime::trace_span!("set_grid2d_instances", "gpu");
         if inst.is_empty() {
             ctx.grid2d_instance_count = 0;
             return;
@@ -2786,6 +2797,7 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
     }
 
     pub fn set_label_instances(ctx: &mut WgpuContext, inst: &[LabelInstance]) {
+        runtime::trace_span!("set_label_instances", "gpu");
         if inst.is_empty() {
             ctx.labels_instance_count = 0;
             return;
@@ -2810,6 +2822,7 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
     }
 
     pub fn set_label_atlas(ctx: &mut WgpuContext, width: u32, height: u32, pixels: &[u8]) {
+        runtime::trace_span!("set_label_atlas", "gpu");
         if width == 0 || height == 0 {
             return;
         }
@@ -0,0 +1,145 @@
+use foundation::math::{
+    Ecef, Geodesic, GeodesicInverse, Geodetic, Vec3, ecef_to_geodetic, geodetic_to_ecef,
+};
+use scene::components::VectorGeometry;
+
+/// Geodesic measurements on the WGS84 ellipsoid for ECEF scene geometry.
+///
+/// Heights are ignored: distances and areas are taken on the ellipsoid surface.
+pub struct GeodesicAnalysis;
+
+impl GeodesicAnalysis {
+    /// Distance, forward azimuth and arrival azimuth between two ECEF points.
+    pub fn inverse(a: Vec3, b: Vec3) -> GeodesicInverse {
+        Geodesic::wgs84().inverse(to_geodetic(a), to_geodetic(b))
+    }
+
+    pub fn distance_m(a: Vec3, b: Vec3) -> f64 {
+        Self::inverse(a, b).distance_m
+    }
+
+    /// Points along the geodesic from `a` to `b`, no more than `max_spacing_m` apart.
+    pub fn waypoints(a: Vec3, b: Vec3, max_spacing_m: f64) -> Vec<Vec3> {
+        Geodesic::wgs84()
+            .waypoints_by_spacing(to_geodetic(a), to_geodetic(b), max_spacing_m)
+            .into_iter()
+            .map(|geo| {
+                let ecef = geodetic_to_ecef(geo);
+                Vec3::new(ecef.x, ecef.y, ecef.z)
+            })
+            .collect()
+    }
+
+    /// Returns (index, geodesic distance in meters).
+    pub fn nearest_point(points: &[Vec3], target: Vec3) -> Option<(usize, f64)> {
+        let geod = Geodesic::wgs84();
+        let target = to_geodetic(target);
+        let mut best: Option<(usize, f64)> = None;
+        for (i, p) in points.iter().enumerate() {
+            let d = geod.inverse(to_geodetic(*p), target).distance_m;
+            if best.map(|(_, bd)| d < bd).unwrap_or(true) {
+                best = Some((i, d));
+            }
+        }
+        best
+    }
+
+    /// Line length, or ring perimeter for areas (holes included). Points are zero.
+    pub fn length_m(geometry: &VectorGeometry) -> f64 {
+        let geod = Geodesic::wgs84();
+        match geometry {
+            VectorGeometry::Point { .. } => 0.0,
+            VectorGeometry::Line { vertices } => geod.path_length(&to_geodetics(vertices)),
+            VectorGeometry::Area { rings } => rings
+                .iter()
+                .map(|ring| geod.polygon(&to_geodetics(ring)).perimeter_m)
+                .sum(),
+        }
+    }
+
+    /// Unsigned area of the outer ring minus its holes. Points and lines are zero.
+    pub fn area_m2(geometry: &VectorGeometry) -> f64 {
+        let VectorGeometry::Area { rings } = geometry else {
+            return 0.0;
+        };
+        let geod = Geodesic::wgs84();
+        let mut area = 0.0;
+        for (i, ring) in rings.iter().enumerate() {
+            let ring_area = geod.polygon(&to_geodetics(ring)).area_m2.abs();
+            if i == 0 {
+                area += ring_area;
+            } else {
+                area -= ring_area;
+            }
+        }
+        area.max(0.0)
+    }
+}
+
+fn to_geodetic(p: Vec3) -> Geodetic {
+    ecef_to_geodetic(Ecef::new(p.x, p.y, p.z))
+}
+
+fn to_geodetics(points: &[Vec3]) -> Vec<Geodetic> {
+    points.iter().map(|p| to_geodetic(*p)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GeodesicAnalysis;
+    use foundation::math::{Geodetic, Vec3, geodetic_to_ecef};
+    use scene::components::VectorGeometry;
+
+    fn ecef(lat_deg: f64, lon_deg: f64) -> Vec3 {
+        let e = geodetic_to_ecef(Geodetic::from_degrees(lat_deg, lon_deg, 0.0));
+        Vec3::new(e.x, e.y, e.z)
+    }
+
+    #[test]
+    fn line_length_sums_geodesic_segments() {
+        let line = VectorGeometry::Line {
+            vertices: vec![ecef(0.0, 0.0), ecef(0.0, 1.0), ecef(0.0, 2.0)],
+        };
+        let len = GeodesicAnalysis::length_m(&line);
+        assert!((len - 2.0 * 111_319.490_793).abs() < 1e-3);
+        assert_eq!(GeodesicAnalysis::area_m2(&line), 0.0);
+    }
+
+    #[test]
+    fn area_subtracts_holes_regardless_of_winding() {
+        let outer = vec![
+            ecef(0.0, 0.0),
+            ecef(0.0, 1.0),
+            ecef(1.0, 1.0),
+            ecef(1.0, 0.0),
+        ];
+        let hole = vec![
+            ecef(0.25, 0.25),
+            ecef(0.25, 0.75),
+            ecef(0.75, 0.75),
+            ecef(0.75, 0.25),
+        ];
+        let solid = GeodesicAnalysis::area_m2(&VectorGeometry::Area {
+            rings: vec![outer.clone()],
+        });
+        let holed = GeodesicAnalysis::area_m2(&VectorGeometry::Area {
+            rings: vec![outer, hole],
+        });
+        assert!(solid > 1.2e10 && solid < 1.3e10);
+        assert!((holed / solid - 0.75).abs() < 1e-3);
+    }
+
+    #[test]
+    fn nearest_point_and_waypoints_use_geodesic_distance() {
+        let pts = vec![ecef(10.0, 0.0), ecef(0.0, 10.5)];
+        let (i, d) = GeodesicAnalysis::nearest_point(&pts, ecef(0.0, 0.0)).unwrap();
+        assert_eq!(i, 0);
+        assert!((d - 1_105_854.8).abs() < 1.0);
+
+        let path = GeodesicAnalysis::waypoints(ecef(0.0, 0.0), ecef(0.0, 10.0), 200_000.0);
+        assert_eq!(path.len(), 7);
+        for pair in path.windows(2) {
+            assert!(GeodesicAnalysis::distance_m(pair[0], pair[1]) <= 200_000.0);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::BTreeMap;
+
+use foundation::math::{Ecef, HexCell, Vec3, ecef_to_geodetic};
+
+/// Summary of the values binned into one grid cell.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct HexBin {
+    pub count: usize,
+    pub sum: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl HexBin {
+    fn new(value: f64) -> Self {
+        Self {
+            count: 1,
+            sum: value,
+            min: value,
+            max: value,
+        }
+    }
+
+    fn merge(&mut self, other: &HexBin) {
+        self.count += other.count;
+        self.sum += other.sum;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.sum / self.count as f64
+    }
+}
+
+/// Aggregation of ECEF points into hexagonal grid cells.
+///
+/// Results are keyed by cell in a `BTreeMap`, so iteration order is deterministic.
+pub struct HexBinAnalysis;
+
+impl HexBinAnalysis {
+    pub fn cell_of(point: Vec3, resolution: u8) -> HexCell {
+        HexCell::from_geodetic(
+            ecef_to_geodetic(Ecef::new(point.x, point.y, point.z)),
+            resolution,
+        )
+    }
+
+    pub fn count_points(points: &[Vec3], resolution: u8) -> BTreeMap<HexCell, usize> {
+        let mut counts = BTreeMap::new();
+        for p in points {
+            *counts.entry(Self::cell_of(*p, resolution)).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Count, sum, min and max of `values[i]` binned by the cell of `points[i]`.
+    ///
+    /// Extra points or values beyond the shorter slice are ignored.
+    pub fn aggregate(points: &[Vec3], values: &[f64], resolution: u8) -> BTreeMap<HexCell, HexBin> {
+        let mut bins: BTreeMap<HexCell, HexBin> = BTreeMap::new();
+        for (p, &v) in points.iter().zip(values) {
+            let bin = HexBin::new(v);
+            bins.entry(Self::cell_of(*p, resolution))
+                .and_modify(|b| b.merge(&bin))
+                .or_insert(bin);
+        }
+        bins
+    }
+
+    /// Merge bins into their parent cells; resolution-0 bins are kept as they are.
+    pub fn rollup(bins: &BTreeMap<HexCell, HexBin>) -> BTreeMap<HexCell, HexBin> {
+        let mut out: BTreeMap<HexCell, HexBin> = BTreeMap::new();
+        for (cell, bin) in bins {
+            out.entry(cell.parent().unwrap_or(*cell))
+                .and_modify(|b| b.merge(bin))
+                .or_insert(*bin);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HexBinAnalysis;
+    use foundation::math::{Geodetic, Vec3, geodetic_to_ecef};
+
+    fn ecef(lat_deg: f64, lon_deg: f64) -> Vec3 {
+        let e = geodetic_to_ecef(Geodetic::from_degrees(lat_deg, lon_deg, 0.0));
+        Vec3::new(e.x, e.y, e.z)
+    }
+
+    #[test]
+    fn aggregates_points_per_cell_and_rolls_up() {
+        let points = vec![
+            ecef(48.8566, 2.3522),
+            ecef(48.8570, 2.3530),
+            ecef(-33.8688, 151.2093),
+        ];
+        let counts = HexBinAnalysis::count_points(&points, 6);
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts.values().sum::<usize>(), 3);
+
+        let bins = HexBinAnalysis::aggregate(&points, &[1.0, 3.0, 10.0], 6);
+        let paris = bins[&HexBinAnalysis::cell_of(points[0], 6)];
+        assert_eq!((paris.count, paris.min, paris.max), (2, 1.0, 3.0));
+        assert_eq!(paris.mean(), 2.0);
+
+        let coarse = HexBinAnalysis::rollup(&bins);
+        assert!(coarse.keys().all(|c| c.resolution() == 5));
+        assert_eq!(coarse.values().map(|b| b.count).sum::<usize>(), 3);
+        assert_eq!(coarse.values().map(|b| b.sum).sum::<f64>(), 14.0);
+    }
+}
@@ -1,5 +1,11 @@
+pub mod geodesic;
+pub mod hexbin;
+pub mod solar;
 pub mod spatial;
 pub mod statistics;
 pub mod temporal;
 
+pub use geodesic::*;
+pub use hexbin::*;
+pub use solar::*;
 pub use spatial::*;
@@ -0,0 +1,101 @@
+use foundation::astro::{
+    Horizontal, SUNRISE_ELEVATION_DEG, SunCrossing, sun_crossings, sun_position,
+};
+use foundation::math::{Ecef, Geodetic, Vec3, ecef_to_geodetic};
+use foundation::time::{Time, TimeSpan};
+
+/// Daylight questions about ECEF sites, answered from `foundation::astro`.
+///
+/// "Daylight" means the Sun's upper limb is above the horizon with standard refraction,
+/// i.e. between sunrise and sunset.
+pub struct SolarAnalysis;
+
+impl SolarAnalysis {
+    pub fn sun_horizontal(site: Vec3, t: Time) -> Horizontal {
+        sun_position(t).horizontal(geodetic(site))
+    }
+
+    pub fn is_daylight(site: Vec3, t: Time) -> bool {
+        Self::sun_horizontal(site, t).elevation_rad > SUNRISE_ELEVATION_DEG.to_radians()
+    }
+
+    /// Daylight flag for each site at one instant; the Sun is computed once.
+    pub fn sunlit(sites: &[Vec3], t: Time) -> Vec<bool> {
+        let sun = sun_position(t);
+        let threshold = SUNRISE_ELEVATION_DEG.to_radians();
+        sites
+            .iter()
+            .map(|&site| sun.horizontal(geodetic(site)).elevation_rad > threshold)
+            .collect()
+    }
+
+    /// The parts of `span` during which `site` is in daylight, in order.
+    pub fn daylight_spans(site: Vec3, span: TimeSpan) -> Vec<TimeSpan> {
+        let geo = geodetic(site);
+        let mut out = Vec::new();
+        let mut day_start = Self::is_daylight(site, span.start).then_some(span.start);
+        for SunCrossing { time, rising } in sun_crossings(geo, span, SUNRISE_ELEVATION_DEG) {
+            match (rising, day_start) {
+                (true, None) => day_start = Some(time),
+                (false, Some(start)) => {
+                    out.push(TimeSpan::new(start, time));
+                    day_start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(start) = day_start {
+            out.push(TimeSpan::new(start, span.end));
+        }
+        out
+    }
+
+    /// Seconds of daylight at `site` within `span`.
+    pub fn daylight_seconds(site: Vec3, span: TimeSpan) -> f64 {
+        Self::daylight_spans(site, span)
+            .iter()
+            .map(TimeSpan::duration)
+            .sum()
+    }
+}
+
+fn geodetic(site: Vec3) -> Geodetic {
+    ecef_to_geodetic(Ecef::new(site.x, site.y, site.z))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SolarAnalysis;
+    use foundation::math::{Geodetic, Vec3, geodetic_to_ecef};
+    use foundation::time::{Time, TimeSpan};
+
+    fn ecef(lat_deg: f64, lon_deg: f64) -> Vec3 {
+        let e = geodetic_to_ecef(Geodetic::from_degrees(lat_deg, lon_deg, 0.0));
+        Vec3::new(e.x, e.y, e.z)
+    }
+
+    #[test]
+    fn daylight_over_a_day() {
+        let day = Time::parse_iso8601("2024-06-21T00:00:00Z").unwrap();
+        let span = TimeSpan::new(day, Time(day.0 + 86_400.0));
+        let noon = Time(day.0 + 12.0 * 3600.0);
+
+        // London in midsummer: one span of about 16h38m around noon.
+        let london = ecef(51.5074, -0.1278);
+        let spans = SolarAnalysis::daylight_spans(london, span);
+        assert_eq!(spans.len(), 1);
+        assert!(spans[0].contains(noon));
+        let hours = SolarAnalysis::daylight_seconds(london, span) / 3600.0;
+        assert!((hours - 16.63).abs() < 0.1, "{hours}");
+
+        // Tromsø has midnight sun; Sydney at midnight UTC is mid-morning in winter.
+        let tromso = ecef(69.65, 18.96);
+        assert_eq!(SolarAnalysis::daylight_seconds(tromso, span), 86_400.0);
+        let sites = [london, tromso, ecef(-33.87, 151.21), ecef(0.0, 0.0)];
+        assert_eq!(
+            SolarAnalysis::sunlit(&sites, day),
+            vec![false, true, true, false]
+        );
+        assert!(SolarAnalysis::is_daylight(london, noon));
+    }
+}
@@ -4,11 +4,11 @@ pub struct TemporalAnalysis;
 
 impl TemporalAnalysis {
     pub fn contains(span: TimeSpan, t: Time) -> bool {
-        t.0 >= span.start.0 && t.0 <= span.end.0
+        span.contains(t)
     }
 
     pub fn intersects(a: TimeSpan, b: TimeSpan) -> bool {
-        !(a.end.0 < b.start.0 || b.end.0 < a.start.0)
+        a.overlaps(&b)
     }
 }
 
@@ -19,14 +19,10 @@ mod tests {
 
     #[test]
     fn intersects_overlaps() {
-        let a = TimeSpan {
-            start: Time(0.0),
-            end: Time(10.0),
-        };
-        let b = TimeSpan {
-            start: Time(9.0),
-            end: Time(11.0),
-        };
+        let a = TimeSpan::new(Time(0.0), Time(10.0));
+        let b = TimeSpan::new(Time(9.0), Time(11.0));
         assert!(TemporalAnalysis::intersects(a, b));
+        let c = TimeSpan::half_open(Time(0.0), Time(9.0));
+        assert!(!TemporalAnalysis::intersects(c, b));
     }
 }
@@ -1,6 +1,10 @@
 use runtime::budget::FrameBudget;
+use runtime::budget_controller::BudgetController;
 use runtime::work_queue::{WorkId, WorkQueue, WorkQueueFull};
 
+/// Queue name of compute work in a [`BudgetController`].
+pub const COMPUTE_SHARE: &str = "compute";
+
 /// Compute work queue with deterministic ordering and backpressure.
 ///
 /// For MVP, `T` is caller-defined; callers should pass a stable request payload.
@@ -37,16 +41,27 @@ impl<T> ComputeQueue<T> {
         self.inner.cancel(id)
     }
 
+    pub fn reprioritize(&mut self, id: WorkId, priority: i32) -> bool {
+        self.inner.reprioritize(id, priority)
+    }
+
     pub fn pop_next_with_budget(&mut self, budget: &mut FrameBudget) -> Option<(WorkId, T)> {
         let (id, _priority, payload) = self.inner.pop_next_with_budget(budget)?;
         Some((id, payload))
     }
+
+    /// Pops work in order while it fits the controller's [`COMPUTE_SHARE`] of this frame.
+    pub fn drain_share(&mut self, controller: &BudgetController) -> Vec<(WorkId, T)> {
+        let mut budget = controller.budget_for(COMPUTE_SHARE);
+        std::iter::from_fn(|| self.pop_next_with_budget(&mut budget)).collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ComputeQueue;
+    use super::{COMPUTE_SHARE, ComputeQueue};
     use runtime::budget::FrameBudget;
+    use runtime::budget_controller::{BudgetController, BudgetControllerConfig};
 
     #[test]
     fn compute_queue_backpressure_and_budgeting() {
@@ -61,4 +76,35 @@ mod tests {
         let (_, v) = q.pop_next_with_budget(&mut budget).unwrap();
         assert_eq!(v, "job");
     }
+
+    #[test]
+    fn drains_the_compute_share_of_recorded_frames() {
+        let mut q = ComputeQueue::new(128);
+        for i in 0..100 {
+            q.try_submit(0, 1, i).unwrap();
+        }
+        let mut controller = BudgetController::new(BudgetControllerConfig {
+            initial_units: 16,
+            ..Default::default()
+        });
+        controller.set_share("streaming", 3);
+        controller.set_share(COMPUTE_SHARE, 1);
+
+        let first: Vec<i32> = q
+            .drain_share(&controller)
+            .into_iter()
+            .map(|(_, v)| v)
+            .collect();
+        assert_eq!(first, [0, 1, 2, 3]);
+
+        // Recorded slow frames shrink the share, fast ones grow it again.
+        for frame_ms in [40.0, 38.0, 42.0] {
+            controller.record_frame(frame_ms);
+        }
+        assert!(q.drain_share(&controller).len() < 4);
+        for _ in 0..30 {
+            controller.record_frame(5.0);
+        }
+        assert!(q.drain_share(&controller).len() > 4);
+    }
 }
@@ -1,3 +1,5 @@
+use crate::vector_chunk_avc::{dequantize_deg, quantize_deg};
+use foundation::bounds::GeoBounds;
 use serde::{Deserialize, Serialize};
 
 pub const MANIFEST_VERSION: &str = "1.0";
@@ -82,7 +84,8 @@ pub struct ChunkEntry {
     pub source_blob_hash: Option<String>,
 
     // Optional baked metadata for fast chunk pruning / indexing.
-    // Quantization matches AVc (1e-6 degrees): [min_lon_q, min_lat_q, max_lon_q, max_lat_q]
+    // Quantization matches AVc (1e-6 degrees): [west_q, south_q, east_q, north_q],
+    // with west > east when the chunk crosses the antimeridian (see `geo_bounds`).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub lon_lat_bounds_q: Option<[i32; 4]>,
     // Microseconds: [min_start_us, max_end_us]
@@ -92,6 +95,23 @@ pub struct ChunkEntry {
     pub feature_count: Option<u32>,
 }
 
+impl ChunkEntry {
+    /// `lon_lat_bounds_q` as wrap-aware bounds.
+    pub fn geo_bounds(&self) -> Option<GeoBounds> {
+        let [west, south, east, north] = self.lon_lat_bounds_q?.map(dequantize_deg);
+        Some(GeoBounds::new(west, south, east, north))
+    }
+
+    pub fn set_geo_bounds(&mut self, bounds: Option<GeoBounds>) {
+        self.lon_lat_bounds_q = bounds.as_ref().map(lon_lat_bounds_q);
+    }
+}
+
+/// Quantize bounds to the `ChunkEntry::lon_lat_bounds_q` layout.
+pub fn lon_lat_bounds_q(bounds: &GeoBounds) -> [i32; 4] {
+    [bounds.west, bounds.south, bounds.east, bounds.north].map(quantize_deg)
+}
+
 impl SceneManifest {
     pub fn new(package_id: impl Into<String>) -> Self {
         Self {
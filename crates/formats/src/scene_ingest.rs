@@ -5,34 +5,50 @@ use scene::components::{
     ComponentBounds, ComponentProperties, ComponentTimeSpan, ComponentVectorGeometry, Transform,
     VectorGeometry, VectorGeometryKind,
 };
-use serde_json::Value;
+use serde_json::{Map, Value};
 
-use crate::vector_chunk::{VectorChunk, VectorFeature, VectorGeometry as ChunkGeometry};
+use crate::vector_chunk::{
+    VectorChunk, VectorFeature, VectorGeometry as ChunkGeometry, reproject_geometry,
+};
 
-fn infer_time_span(feature: &VectorFeature) -> TimeSpan {
-    // Very small-but-useful convention:
-    // - If properties contain numeric "time" or "timestamp": treat as seconds and create an instant span.
-    // - Else if contain numeric "start" and "end": treat as seconds and create a range.
-    // - Else: forever.
-    let get_num = |k: &str| -> Option<f64> {
-        feature.properties.get(k).and_then(|v| match v {
-            Value::Number(n) => n.as_f64(),
-            Value::String(s) => s.parse::<f64>().ok(),
+/// Infer a feature's time span from its properties.
+///
+/// Values may be numbers (Unix seconds) or ISO 8601 / RFC 3339 strings:
+/// - "time" or "timestamp": an instant, or an ISO 8601 interval such as `2020-01-01/P1M`
+/// - "start" and/or "end": a closed range; a missing side is unbounded
+/// - otherwise (or if nothing parses): forever
+pub fn infer_time_span(properties: &Map<String, Value>) -> TimeSpan {
+    let get_str = |k: &str| properties.get(k).and_then(Value::as_str).map(str::trim);
+    let get_time = |k: &str| -> Option<Time> {
+        match properties.get(k)? {
+            Value::Number(n) => n.as_f64().map(Time),
+            Value::String(s) => s
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .map(Time)
+                .or_else(|| Time::parse_iso8601(s).ok()),
             _ => None,
-        })
+        }
     };
 
-    if let Some(t) = get_num("time").or_else(|| get_num("timestamp")) {
-        return TimeSpan::instant(Time(t));
+    for key in ["time", "timestamp"] {
+        if let Some(interval) = get_str(key).filter(|s| s.contains('/'))
+            && let Ok(span) = TimeSpan::parse_iso8601(interval)
+        {
+            return span;
+        }
+        if let Some(t) = get_time(key) {
+            return TimeSpan::instant(t);
+        }
     }
-    if let (Some(s), Some(e)) = (get_num("start"), get_num("end")) {
-        return TimeSpan {
-            start: Time(s),
-            end: Time(e),
-        };
+    match (get_time("start"), get_time("end")) {
+        (None, None) => TimeSpan::forever(),
+        (start, end) => TimeSpan::new(
+            start.unwrap_or(Time(f64::NEG_INFINITY)),
+            end.unwrap_or(Time(f64::INFINITY)),
+        ),
     }
-
-    TimeSpan::forever()
 }
 
 fn properties_to_pairs(feature: &VectorFeature) -> Vec<(String, String)> {
@@ -54,16 +70,33 @@ fn properties_to_pairs(feature: &VectorFeature) -> Vec<(String, String)> {
     out
 }
 
+/// Spawn one entity per feature geometry part.
+///
+/// Chunks that declare a non-WGS84 CRS are transformed to WGS 84 on the fly;
+/// features with coordinates outside that CRS's domain are skipped.
 pub fn ingest_vector_chunk(
     world: &mut World,
     chunk: &VectorChunk,
     expected: Option<VectorGeometryKind>,
 ) {
+    let transform = chunk.crs_transform();
     for feature in &chunk.features {
-        let span = infer_time_span(feature);
+        let span = infer_time_span(&feature.properties);
         let props = ComponentProperties::new(properties_to_pairs(feature));
 
-        match &feature.geometry {
+        let reprojected;
+        let geometry = match &transform {
+            Some(t) => match reproject_geometry(&feature.geometry, t) {
+                Some(g) => {
+                    reprojected = g;
+                    &reprojected
+                }
+                None => continue,
+            },
+            None => &feature.geometry,
+        };
+
+        match geometry {
             ChunkGeometry::Point(p) => {
                 ingest_point(world, p.lon_deg, p.lat_deg, expected, span, &props);
             }
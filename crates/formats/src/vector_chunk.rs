@@ -1,3 +1,5 @@
+use foundation::bounds::{GeoBounds, unwrap_lon_deg};
+use foundation::math::{CrsRegistry, CrsTransform, EPSG_WGS84, Vec3, epsg_from_name};
 use serde_json::{Map, Value};
 
 fn canonicalize_json_value(value: &Value) -> Value {
@@ -56,6 +58,87 @@ pub enum VectorGeometry {
     MultiPolygon(Vec<Vec<Vec<GeoPoint>>>),
 }
 
+impl VectorGeometry {
+    /// Vertex lists: one per point set, line or ring.
+    fn point_lists(&self) -> Vec<&[GeoPoint]> {
+        match self {
+            VectorGeometry::Point(p) => vec![std::slice::from_ref(p)],
+            VectorGeometry::MultiPoint(ps) | VectorGeometry::LineString(ps) => vec![ps],
+            VectorGeometry::MultiLineString(lists) | VectorGeometry::Polygon(lists) => {
+                lists.iter().map(Vec::as_slice).collect()
+            }
+            VectorGeometry::MultiPolygon(polys) => {
+                polys.iter().flatten().map(Vec::as_slice).collect()
+            }
+        }
+    }
+
+    /// Wrap-aware lon/lat bounds (see `GeoBounds::from_points`/`from_ring`).
+    pub fn geo_bounds(&self) -> Option<GeoBounds> {
+        geo_bounds_of(std::iter::once(self))
+    }
+
+    /// A copy with each line and ring unwrapped to continuous longitudes.
+    ///
+    /// Rings crossing ±180° then extend past the antimeridian instead of jumping across
+    /// the globe, which local-plane triangulation relies on.
+    pub fn unwrap_antimeridian(&self) -> Self {
+        let rings = |lists: &[Vec<GeoPoint>]| lists.iter().map(|l| unwrap_points(l)).collect();
+        match self {
+            VectorGeometry::LineString(ps) => VectorGeometry::LineString(unwrap_points(ps)),
+            VectorGeometry::MultiLineString(lines) => VectorGeometry::MultiLineString(rings(lines)),
+            VectorGeometry::Polygon(poly) => VectorGeometry::Polygon(rings(poly)),
+            VectorGeometry::MultiPolygon(polys) => {
+                VectorGeometry::MultiPolygon(polys.iter().map(|p| rings(p)).collect())
+            }
+            other => other.clone(),
+        }
+    }
+}
+
+fn unwrap_points(points: &[GeoPoint]) -> Vec<GeoPoint> {
+    let mut out: Vec<GeoPoint> = Vec::with_capacity(points.len());
+    for p in points {
+        let lon = match out.last() {
+            Some(prev) => unwrap_lon_deg(prev.lon_deg, p.lon_deg),
+            None => p.lon_deg,
+        };
+        out.push(GeoPoint::new(lon, p.lat_deg));
+    }
+    out
+}
+
+/// Tightest bounds over all vertices, widened for polygon rings that enclose a pole.
+fn geo_bounds_of<'a>(
+    geometries: impl Iterator<Item = &'a VectorGeometry> + Clone,
+) -> Option<GeoBounds> {
+    let coords = |points: &[GeoPoint]| -> Vec<[f64; 2]> {
+        points.iter().map(|p| [p.lon_deg, p.lat_deg]).collect()
+    };
+    let mut bounds = GeoBounds::from_points(
+        geometries
+            .clone()
+            .flat_map(|g| g.point_lists())
+            .flat_map(coords),
+    )?;
+    for geometry in geometries {
+        if !matches!(
+            geometry,
+            VectorGeometry::Polygon(_) | VectorGeometry::MultiPolygon(_)
+        ) {
+            continue;
+        }
+        for ring in geometry.point_lists() {
+            if let Some(ring_bounds) = GeoBounds::from_ring(&coords(ring))
+                && ring_bounds.is_full_lon()
+            {
+                bounds = bounds.union(&ring_bounds);
+            }
+        }
+    }
+    Some(bounds)
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct VectorFeature {
     pub id: Option<String>,
@@ -66,12 +149,19 @@ pub struct VectorFeature {
 #[derive(Debug, Clone, PartialEq)]
 pub struct VectorChunk {
     pub features: Vec<VectorFeature>,
+    /// EPSG code declared by the source GeoJSON `crs` member.
+    ///
+    /// `None` means WGS 84 lon/lat (the GeoJSON default). Coordinates of a
+    /// chunk with a declared CRS are in that CRS's native axis order
+    /// (easting/northing for projected CRSs) until `to_wgs84` is applied.
+    pub crs_epsg: Option<u32>,
 }
 
 #[derive(Debug)]
 pub enum VectorChunkError {
     NotAFeatureCollection,
     InvalidFeature { index: usize, reason: String },
+    UnsupportedCrs { name: String },
 }
 
 impl std::fmt::Display for VectorChunkError {
@@ -83,6 +173,9 @@ impl std::fmt::Display for VectorChunkError {
             VectorChunkError::InvalidFeature { index, reason } => {
                 write!(f, "invalid feature at index {index}: {reason}")
             }
+            VectorChunkError::UnsupportedCrs { name } => {
+                write!(f, "unsupported crs: {name}")
+            }
         }
     }
 }
@@ -111,6 +204,11 @@ impl VectorChunk {
             return Err(VectorChunkError::NotAFeatureCollection);
         }
 
+        let crs_epsg = match obj.get("crs") {
+            Some(crs) => parse_crs_member(crs),
+            None => None,
+        };
+
         let features_val = obj
             .get("features")
             .and_then(|v| v.as_array())
@@ -168,7 +266,77 @@ impl VectorChunk {
             });
         }
 
-        Ok(Self { features })
+        Ok(Self { features, crs_epsg })
+    }
+
+    /// Transform from the declared CRS to WGS 84 lon/lat.
+    ///
+    /// Returns `None` when no CRS is declared or it is already WGS 84 based.
+    pub fn crs_transform(&self) -> Option<CrsTransform> {
+        let crs = CrsRegistry::builtin().get(self.crs_epsg?)?;
+        let transform = CrsTransform::to_wgs84(crs);
+        (!transform.is_identity()).then_some(transform)
+    }
+
+    /// A copy of this chunk with all coordinates in WGS 84 lon/lat.
+    pub fn to_wgs84(&self) -> Result<Self, VectorChunkError> {
+        let Some(code) = self.crs_epsg else {
+            return Ok(self.clone());
+        };
+        if CrsRegistry::builtin().get(code).is_none() {
+            return Err(VectorChunkError::UnsupportedCrs {
+                name: format!("EPSG:{code}"),
+            });
+        }
+        let Some(transform) = self.crs_transform() else {
+            return Ok(Self {
+                features: self.features.clone(),
+                crs_epsg: None,
+            });
+        };
+
+        let mut features = Vec::with_capacity(self.features.len());
+        for (index, feat) in self.features.iter().enumerate() {
+            let geometry = reproject_geometry(&feat.geometry, &transform).ok_or_else(|| {
+                VectorChunkError::InvalidFeature {
+                    index,
+                    reason: format!("coordinate outside the domain of EPSG:{code}"),
+                }
+            })?;
+            features.push(VectorFeature {
+                id: feat.id.clone(),
+                properties: feat.properties.clone(),
+                geometry,
+            });
+        }
+        Ok(Self {
+            features,
+            crs_epsg: None,
+        })
+    }
+
+    /// Wrap-aware lon/lat bounds of all features, or `None` for an empty chunk.
+    ///
+    /// Coordinates are taken as lon/lat degrees; call `to_wgs84` first for projected data.
+    pub fn geo_bounds(&self) -> Option<GeoBounds> {
+        geo_bounds_of(self.features.iter().map(|f| &f.geometry))
+    }
+
+    /// A copy with every line and ring unwrapped across the antimeridian
+    /// (see `VectorGeometry::unwrap_antimeridian`).
+    pub fn unwrap_antimeridian(&self) -> Self {
+        Self {
+            features: self
+                .features
+                .iter()
+                .map(|feat| VectorFeature {
+                    id: feat.id.clone(),
+                    properties: feat.properties.clone(),
+                    geometry: feat.geometry.unwrap_antimeridian(),
+                })
+                .collect(),
+            crs_epsg: self.crs_epsg,
+        }
     }
 
     /// Semantic round-trip exporter: emits a GeoJSON FeatureCollection.
@@ -201,6 +369,9 @@ impl VectorChunk {
         }
 
         root.insert("features".to_string(), Value::Array(features));
+        if let Some(code) = self.crs_epsg {
+            root.insert("crs".to_string(), crs_member(code));
+        }
         Value::Object(root)
     }
 
@@ -298,6 +469,98 @@ fn geometry_to_geojson_value(geom: &VectorGeometry) -> Value {
     Value::Object(obj)
 }
 
+/// Parse a GeoJSON (2008) `crs` member into an EPSG code.
+///
+/// WGS 84 lon/lat (EPSG:4326, CRS84) normalizes to `None`. An unrecognised
+/// CRS also yields `None` (with a warning) and the data is read as lon/lat.
+fn parse_crs_member(crs: &Value) -> Option<u32> {
+    if crs.is_null() {
+        return None;
+    }
+    let props = crs.get("properties");
+    let name = match crs.get("type").and_then(|v| v.as_str()) {
+        Some("name") => props
+            .and_then(|p| p.get("name"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        Some("EPSG") => props
+            .and_then(|p| p.get("code"))
+            .and_then(|v| v.as_u64())
+            .map(|code| format!("EPSG:{code}")),
+        _ => None,
+    };
+    let code = name
+        .as_deref()
+        .and_then(epsg_from_name)
+        .filter(|&code| CrsRegistry::builtin().get(code).is_some());
+    let Some(code) = code else {
+        // Unknown CRS members used to be ignored; keep loading those files
+        // as WGS 84 instead of rejecting them.
+        eprintln!("warning: unsupported GeoJSON crs {crs}, assuming WGS 84");
+        return None;
+    };
+    (code != EPSG_WGS84).then_some(code)
+}
+
+fn crs_member(code: u32) -> Value {
+    let mut props = Map::new();
+    props.insert(
+        "name".to_string(),
+        Value::String(format!("urn:ogc:def:crs:EPSG::{code}")),
+    );
+    let mut obj = Map::new();
+    obj.insert("type".to_string(), Value::String("name".to_string()));
+    obj.insert("properties".to_string(), Value::Object(props));
+    Value::Object(obj)
+}
+
+fn reproject_point(p: &GeoPoint, transform: &CrsTransform) -> Option<GeoPoint> {
+    let out = transform.transform(Vec3::new(p.lon_deg, p.lat_deg, 0.0))?;
+    Some(GeoPoint::new(out.x, out.y))
+}
+
+fn reproject_points(ps: &[GeoPoint], transform: &CrsTransform) -> Option<Vec<GeoPoint>> {
+    ps.iter().map(|p| reproject_point(p, transform)).collect()
+}
+
+fn reproject_rings(
+    rings: &[Vec<GeoPoint>],
+    transform: &CrsTransform,
+) -> Option<Vec<Vec<GeoPoint>>> {
+    rings
+        .iter()
+        .map(|ring| reproject_points(ring, transform))
+        .collect()
+}
+
+/// Apply `transform` to every coordinate; `None` if any point falls outside its domain.
+pub(crate) fn reproject_geometry(
+    geom: &VectorGeometry,
+    transform: &CrsTransform,
+) -> Option<VectorGeometry> {
+    Some(match geom {
+        VectorGeometry::Point(p) => VectorGeometry::Point(reproject_point(p, transform)?),
+        VectorGeometry::MultiPoint(ps) => {
+            VectorGeometry::MultiPoint(reproject_points(ps, transform)?)
+        }
+        VectorGeometry::LineString(ps) => {
+            VectorGeometry::LineString(reproject_points(ps, transform)?)
+        }
+        VectorGeometry::MultiLineString(lines) => {
+            VectorGeometry::MultiLineString(reproject_rings(lines, transform)?)
+        }
+        VectorGeometry::Polygon(rings) => {
+            VectorGeometry::Polygon(reproject_rings(rings, transform)?)
+        }
+        VectorGeometry::MultiPolygon(polys) => VectorGeometry::MultiPolygon(
+            polys
+                .iter()
+                .map(|poly| reproject_rings(poly, transform))
+                .collect::<Option<_>>()?,
+        ),
+    })
+}
+
 fn point_coords(p: &GeoPoint) -> Value {
     Value::Array(vec![Value::from(p.lon_deg), Value::from(p.lat_deg)])
 }
@@ -388,7 +651,7 @@ fn parse_multi_polygon(coords: &Value) -> Result<Vec<Vec<Vec<GeoPoint>>>, String
 
 #[cfg(test)]
 mod tests {
-    use super::{VectorChunk, VectorGeometry};
+    use super::{GeoPoint, VectorChunk, VectorGeometry};
 
     #[test]
     fn parses_demo_cities_points() {
@@ -400,4 +663,75 @@ mod tests {
             VectorGeometry::Point(_)
         ));
     }
+
+    #[test]
+    fn declared_crs_reprojects_to_wgs84() {
+        let payload = r#"{
+            "type": "FeatureCollection",
+            "crs": { "type": "name", "properties": { "name": "urn:ogc:def:crs:EPSG::3857" } },
+            "features": [
+                { "type": "Feature", "properties": {},
+                  "geometry": { "type": "Point", "coordinates": [20037508.342789244, 0.0] } }
+            ]
+        }"#;
+        let chunk = VectorChunk::from_geojson_str(payload).expect("parse");
+        assert_eq!(chunk.crs_epsg, Some(3857));
+
+        let wgs = chunk.to_wgs84().expect("reproject");
+        assert_eq!(wgs.crs_epsg, None);
+        let VectorGeometry::Point(p) = &wgs.features[0].geometry else {
+            panic!("expected point");
+        };
+        assert!((p.lon_deg - 180.0).abs() < 1e-9);
+        assert!(p.lat_deg.abs() < 1e-9);
+    }
+
+    #[test]
+    fn wgs84_crs_normalizes_and_unknown_crs_falls_back() {
+        let crs84 = r#"{ "type": "FeatureCollection",
+            "crs": { "type": "name", "properties": { "name": "urn:ogc:def:crs:OGC:1.3:CRS84" } },
+            "features": [] }"#;
+        assert_eq!(VectorChunk::from_geojson_str(crs84).unwrap().crs_epsg, None);
+
+        let unknown = r#"{ "type": "FeatureCollection",
+            "crs": { "type": "name", "properties": { "name": "EPSG:999999" } },
+            "features": [] }"#;
+        assert_eq!(
+            VectorChunk::from_geojson_str(unknown).unwrap().crs_epsg,
+            None
+        );
+    }
+
+    #[test]
+    fn antimeridian_polygon_bounds_and_unwrap() {
+        let ring = vec![
+            GeoPoint::new(178.0, -17.0),
+            GeoPoint::new(-179.0, -17.0),
+            GeoPoint::new(-179.0, -16.0),
+            GeoPoint::new(178.0, -16.0),
+            GeoPoint::new(178.0, -17.0),
+        ];
+        let poly = VectorGeometry::Polygon(vec![ring]);
+        let b = poly.geo_bounds().unwrap();
+        assert_eq!(
+            (b.west, b.east, b.south, b.north),
+            (178.0, -179.0, -17.0, -16.0)
+        );
+
+        let VectorGeometry::Polygon(rings) = poly.unwrap_antimeridian() else {
+            unreachable!()
+        };
+        let lons: Vec<f64> = rings[0].iter().map(|p| p.lon_deg).collect();
+        assert_eq!(lons, [178.0, 181.0, 181.0, 178.0, 178.0]);
+
+        // A ring around the south pole extends the bounds to it.
+        let cap = VectorGeometry::Polygon(vec![
+            [-180.0, -90.0, 0.0, 90.0, 180.0]
+                .iter()
+                .map(|&lon| GeoPoint::new(lon, -80.0))
+                .collect(),
+        ]);
+        let b = cap.geo_bounds().unwrap();
+        assert!(b.is_full_lon() && b.south == -90.0 && b.north == -80.0);
+    }
 }
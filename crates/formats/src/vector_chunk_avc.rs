@@ -1,4 +1,7 @@
+use crate::scene_ingest::infer_time_span;
 use crate::vector_chunk::{GeoPoint, VectorChunk, VectorFeature, VectorGeometry};
+use foundation::bounds::GeoBounds;
+use foundation::time::TimeSpan;
 use serde_json::{Map, Value};
 use std::io::{Read, Write};
 
@@ -59,6 +62,13 @@ pub fn encode_avc(chunk: &VectorChunk) -> Result<Vec<u8>, AvcError> {
 }
 
 pub fn encode_avc_to_writer<W: Write>(chunk: &VectorChunk, w: &mut W) -> Result<(), AvcError> {
+    // AVC stores WGS 84 lon/lat only.
+    if chunk.crs_transform().is_some() {
+        return Err(AvcError::InvalidGeometry {
+            reason: "chunk is not in WGS 84; call VectorChunk::to_wgs84 first".to_string(),
+        });
+    }
+
     w.write_all(&MAGIC).map_err(|e| AvcError::Io {
         source: e.to_string(),
     })?;
@@ -70,45 +80,38 @@ pub fn encode_avc_to_writer<W: Write>(chunk: &VectorChunk, w: &mut W) -> Result<
     write_u32_le(w, chunk.features.len() as u32)?;
 
     // v2 baked metadata (for fast pruning / indexing)
-    // lon/lat quantized bounds: [min_lon_q, max_lon_q, min_lat_q, max_lat_q]
+    // lon/lat quantized bounds: [west_q, east_q, south_q, north_q]; west > east when the
+    // chunk crosses the antimeridian (see `GeoBounds`).
     // time bounds in microseconds: [min_start_us, max_end_us]
-    let mut min_lon_q = i32::MAX;
-    let mut max_lon_q = i32::MIN;
-    let mut min_lat_q = i32::MAX;
-    let mut max_lat_q = i32::MIN;
     let mut min_start_us = i64::MAX;
     let mut max_end_us = i64::MIN;
-
     for feat in &chunk.features {
         let (start_us, end_us) = infer_time_span_micros(&feat.properties);
         min_start_us = min_start_us.min(start_us);
         max_end_us = max_end_us.max(end_us);
-
-        update_bounds_for_geometry(
-            &feat.geometry,
-            &mut min_lon_q,
-            &mut max_lon_q,
-            &mut min_lat_q,
-            &mut max_lat_q,
-        );
-    }
-
-    if min_lon_q == i32::MAX {
-        // empty chunk (should be rare, but keep encoding stable)
-        min_lon_q = 0;
-        max_lon_q = 0;
-        min_lat_q = 0;
-        max_lat_q = 0;
     }
     if min_start_us == i64::MAX {
         min_start_us = i64::MIN;
         max_end_us = i64::MAX;
     }
 
-    write_i32_le(w, min_lon_q)?;
-    write_i32_le(w, max_lon_q)?;
-    write_i32_le(w, min_lat_q)?;
-    write_i32_le(w, max_lat_q)?;
+    // Empty chunks keep encoding stable with zero bounds.
+    let [west_q, east_q, south_q, north_q] = chunk
+        .geo_bounds()
+        .map(|b| {
+            [
+                quantize_deg(b.west),
+                quantize_deg(b.east),
+                quantize_deg(b.south),
+                quantize_deg(b.north),
+            ]
+        })
+        .unwrap_or([0; 4]);
+
+    write_i32_le(w, west_q)?;
+    write_i32_le(w, east_q)?;
+    write_i32_le(w, south_q)?;
+    write_i32_le(w, north_q)?;
     write_i64_le(w, min_start_us)?;
     write_i64_le(w, max_end_us)?;
 
@@ -157,7 +160,23 @@ pub fn decode_avc(bytes: &[u8]) -> Result<VectorChunk, AvcError> {
     decode_avc_from_reader(&mut cursor)
 }
 
-pub fn decode_avc_from_reader<R: Read>(r: &mut R) -> Result<VectorChunk, AvcError> {
+/// Baked chunk metadata from the start of an AVC stream.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AvcHeader {
+    pub version: u16,
+    pub feature_count: u32,
+    /// Chunk lon/lat bounds (v2 only; `None` for v1 or empty chunks).
+    pub bounds: Option<GeoBounds>,
+    /// `[min_start_us, max_end_us]` (v2 only).
+    pub time_bounds_us: Option<[i64; 2]>,
+}
+
+/// Read only the header, e.g. to prune chunks without decoding features.
+pub fn decode_avc_header(bytes: &[u8]) -> Result<AvcHeader, AvcError> {
+    read_avc_header(&mut std::io::Cursor::new(bytes))
+}
+
+fn read_avc_header<R: Read>(r: &mut R) -> Result<AvcHeader, AvcError> {
     let magic = read_exact_io::<4>(r)?;
     if magic.as_slice() != MAGIC.as_slice() {
         return Err(AvcError::InvalidMagic);
@@ -169,17 +188,36 @@ pub fn decode_avc_from_reader<R: Read>(r: &mut R) -> Result<VectorChunk, AvcErro
     }
 
     let _flags = read_u16_le_io(r)?;
-    let feature_count = read_u32_le_io(r)? as usize;
-
+    let feature_count = read_u32_le_io(r)?;
+
+    let mut header = AvcHeader {
+        version,
+        feature_count,
+        bounds: None,
+        time_bounds_us: None,
+    };
     if version == VERSION_V2 {
-        // baked metadata (currently ignored by the loader)
-        let _min_lon_q = read_i32_le_io(r)?;
-        let _max_lon_q = read_i32_le_io(r)?;
-        let _min_lat_q = read_i32_le_io(r)?;
-        let _max_lat_q = read_i32_le_io(r)?;
-        let _min_start_us = read_i64_le_io(r)?;
-        let _max_end_us = read_i64_le_io(r)?;
+        let west_q = read_i32_le_io(r)?;
+        let east_q = read_i32_le_io(r)?;
+        let south_q = read_i32_le_io(r)?;
+        let north_q = read_i32_le_io(r)?;
+        let min_start_us = read_i64_le_io(r)?;
+        let max_end_us = read_i64_le_io(r)?;
+        if feature_count > 0 {
+            header.bounds = Some(GeoBounds::new(
+                dequantize_deg(west_q),
+                dequantize_deg(south_q),
+                dequantize_deg(east_q),
+                dequantize_deg(north_q),
+            ));
+        }
+        header.time_bounds_us = Some([min_start_us, max_end_us]);
     }
+    Ok(header)
+}
+
+pub fn decode_avc_from_reader<R: Read>(r: &mut R) -> Result<VectorChunk, AvcError> {
+    let feature_count = read_avc_header(r)?.feature_count as usize;
 
     let mut features: Vec<VectorFeature> = Vec::with_capacity(feature_count);
     for _ in 0..feature_count {
@@ -216,7 +254,10 @@ pub fn decode_avc_from_reader<R: Read>(r: &mut R) -> Result<VectorChunk, AvcErro
         });
     }
 
-    Ok(VectorChunk { features })
+    Ok(VectorChunk {
+        features,
+        crs_epsg: None,
+    })
 }
 
 fn canonicalize_json_value(value: &Value) -> Value {
@@ -346,61 +387,6 @@ fn write_var_u64_to_writer(w: &mut impl Write, mut v: u64) -> Result<(), AvcErro
     })
 }
 
-fn update_bounds_for_geometry(
-    geometry: &VectorGeometry,
-    min_lon_q: &mut i32,
-    max_lon_q: &mut i32,
-    min_lat_q: &mut i32,
-    max_lat_q: &mut i32,
-) {
-    fn visit_point(
-        p: &GeoPoint,
-        min_lon_q: &mut i32,
-        max_lon_q: &mut i32,
-        min_lat_q: &mut i32,
-        max_lat_q: &mut i32,
-    ) {
-        let lon_q = quantize_deg(p.lon_deg);
-        let lat_q = quantize_deg(p.lat_deg);
-        *min_lon_q = (*min_lon_q).min(lon_q);
-        *max_lon_q = (*max_lon_q).max(lon_q);
-        *min_lat_q = (*min_lat_q).min(lat_q);
-        *max_lat_q = (*max_lat_q).max(lat_q);
-    }
-
-    match geometry {
-        VectorGeometry::Point(p) => visit_point(p, min_lon_q, max_lon_q, min_lat_q, max_lat_q),
-        VectorGeometry::MultiPoint(ps) | VectorGeometry::LineString(ps) => {
-            for p in ps {
-                visit_point(p, min_lon_q, max_lon_q, min_lat_q, max_lat_q);
-            }
-        }
-        VectorGeometry::MultiLineString(lines) => {
-            for line in lines {
-                for p in line {
-                    visit_point(p, min_lon_q, max_lon_q, min_lat_q, max_lat_q);
-                }
-            }
-        }
-        VectorGeometry::Polygon(rings) => {
-            for ring in rings {
-                for p in ring {
-                    visit_point(p, min_lon_q, max_lon_q, min_lat_q, max_lat_q);
-                }
-            }
-        }
-        VectorGeometry::MultiPolygon(polys) => {
-            for poly in polys {
-                for ring in poly {
-                    for p in ring {
-                        visit_point(p, min_lon_q, max_lon_q, min_lat_q, max_lat_q);
-                    }
-                }
-            }
-        }
-    }
-}
-
 fn encode_geometry(geom: &VectorGeometry) -> Result<(GeomTag, Vec<u8>), AvcError> {
     let mut out: Vec<u8> = Vec::new();
     match geom {
@@ -549,41 +535,28 @@ fn decode_geometry(tag: u8, bytes: &[u8]) -> Result<VectorGeometry, AvcError> {
 }
 
 fn infer_time_span_micros(props: &Map<String, Value>) -> (i64, i64) {
-    // Same convention as ingest:
-    // - numeric/string "time" or "timestamp" => seconds => instant
-    // - numeric/string "start" and "end" => seconds => range
-    // - else => forever
-    fn get_num(props: &Map<String, Value>, k: &str) -> Option<f64> {
-        props.get(k).and_then(|v| match v {
-            Value::Number(n) => n.as_f64(),
-            Value::String(s) => s.parse::<f64>().ok(),
-            _ => None,
-        })
-    }
-
-    if let Some(t) = get_num(props, "time").or_else(|| get_num(props, "timestamp")) {
-        let us = (t * 1_000_000.0).round();
-        let us = us.clamp(i64::MIN as f64, i64::MAX as f64) as i64;
-        return (us, us);
-    }
-
-    if let (Some(s), Some(e)) = (get_num(props, "start"), get_num(props, "end")) {
-        let s_us = (s * 1_000_000.0).round();
-        let e_us = (e * 1_000_000.0).round();
-        let s_us = s_us.clamp(i64::MIN as f64, i64::MAX as f64) as i64;
-        let e_us = e_us.clamp(i64::MIN as f64, i64::MAX as f64) as i64;
-        return (s_us, e_us);
-    }
+    // Same convention as ingest (see `infer_time_span`).
+    time_span_micros(infer_time_span(props))
+}
 
-    (i64::MIN, i64::MAX)
+/// Span endpoints as microseconds, saturating infinities to `i64::MIN`/`i64::MAX`.
+///
+/// AVC stores inclusive endpoints; open bounds are not preserved.
+pub fn time_span_micros(span: TimeSpan) -> (i64, i64) {
+    let to_us = |t: f64| {
+        (t * 1_000_000.0)
+            .round()
+            .clamp(i64::MIN as f64, i64::MAX as f64) as i64
+    };
+    (to_us(span.start.0), to_us(span.end.0))
 }
 
-fn quantize_deg(v: f64) -> i32 {
+pub(crate) fn quantize_deg(v: f64) -> i32 {
     let q = (v * DEG_Q).round();
     q.clamp(i32::MIN as f64, i32::MAX as f64) as i32
 }
 
-fn dequantize_deg(q: i32) -> f64 {
+pub(crate) fn dequantize_deg(q: i32) -> f64 {
     (q as f64) / DEG_Q
 }
 
@@ -649,7 +622,10 @@ impl<'a> Reader<'a> {
 
 #[cfg(test)]
 mod tests {
-    use super::{decode_avc, decode_avc_from_reader, encode_avc, encode_avc_to_writer};
+    use super::{
+        decode_avc, decode_avc_from_reader, decode_avc_header, encode_avc, encode_avc_to_writer,
+        infer_time_span_micros,
+    };
     use crate::{GeoPoint, VectorChunk, VectorFeature, VectorGeometry};
     use serde_json::{Map, Value};
     use std::io::Cursor;
@@ -659,6 +635,37 @@ mod tests {
         assert!(d <= eps, "expected {a} ~= {b} (diff {d})");
     }
 
+    #[test]
+    fn time_span_micros_accepts_iso8601_strings() {
+        let props = |v: Value| -> Map<String, Value> { serde_json::from_value(v).expect("object") };
+        let day_us = 86_400 * 1_000_000;
+        let jan1_us = 1_577_836_800 * 1_000_000;
+        assert_eq!(
+            infer_time_span_micros(&props(
+                serde_json::json!({ "time": "2020-01-01T00:00:00Z" })
+            )),
+            (jan1_us, jan1_us)
+        );
+        assert_eq!(
+            infer_time_span_micros(&props(serde_json::json!({ "time": "2020-01-01/P1D" }))),
+            (jan1_us, jan1_us + day_us)
+        );
+        assert_eq!(
+            infer_time_span_micros(&props(
+                serde_json::json!({ "start": "2020-01-01", "end": 1_577_923_200 })
+            )),
+            (jan1_us, jan1_us + day_us)
+        );
+        assert_eq!(
+            infer_time_span_micros(&props(serde_json::json!({ "start": "2020-01-01" }))),
+            (jan1_us, i64::MAX)
+        );
+        assert_eq!(
+            infer_time_span_micros(&props(serde_json::json!({ "time": "soon" }))),
+            (i64::MIN, i64::MAX)
+        );
+    }
+
     #[test]
     fn avc_round_trip_demo_points_quantized() {
         let payload = include_str!("../../apps/web/assets/chunks/cities.json");
@@ -719,13 +726,41 @@ mod tests {
 
         let bytes1 = encode_avc(&VectorChunk {
             features: vec![feat1],
+            crs_epsg: None,
         })
         .expect("encode 1");
         let bytes2 = encode_avc(&VectorChunk {
             features: vec![feat2],
+            crs_epsg: None,
         })
         .expect("encode 2");
 
         assert_eq!(bytes1, bytes2);
     }
+
+    #[test]
+    fn avc_header_bounds_wrap_the_antimeridian() {
+        let point = |lon: f64, lat: f64| VectorFeature {
+            id: None,
+            properties: Map::new(),
+            geometry: VectorGeometry::Point(GeoPoint::new(lon, lat)),
+        };
+        let chunk = VectorChunk {
+            features: vec![point(179.5, -18.0), point(-179.25, -16.5)],
+            crs_epsg: None,
+        };
+        let header = decode_avc_header(&encode_avc(&chunk).expect("encode")).expect("header");
+        assert_eq!(header.feature_count, 2);
+        let b = header.bounds.expect("bounds");
+        assert_eq!((b.west, b.east), (179.5, -179.25));
+        assert_eq!((b.south, b.north), (-18.0, -16.5));
+        assert_eq!(header.time_bounds_us, Some([i64::MIN, i64::MAX]));
+
+        let empty = VectorChunk {
+            features: Vec::new(),
+            crs_epsg: None,
+        };
+        let header = decode_avc_header(&encode_avc(&empty).expect("encode")).expect("header");
+        assert_eq!(header.bounds, None);
+    }
 }
@@ -0,0 +1,165 @@
+//! Low-precision solar and lunar ephemerides in the Earth-fixed frame.
+//!
+//! Positions are apparent geocentric, of date, rotated into ECEF by Greenwich mean sidereal
+//! time. The Sun is good to about 0.01° (Meeus, *Astronomical Algorithms*, ch. 25) and the
+//! Moon to about 0.3° (*Astronomical Almanac* low-precision formulae): ample for lighting,
+//! terminator overlays and daylight analysis, not for occultations. Everything is a pure
+//! function of `Time`, so every consumer sees the same sky.
+//!
+//! `Time` is UTC; UT1 − UTC (under a second) is ignored, and terrestrial time for the
+//! orbital arguments is derived from the leap-second table.
+//!
+//! Artificial satellites are propagated from two-line element sets with SGP4/SDP4 ([`Tle`],
+//! [`Sgp4`]) and rotated into the same Earth-fixed frame.
+
+mod moon;
+mod sgp4;
+mod sgp4_deep;
+mod sun;
+mod tle;
+
+pub use moon::*;
+pub use sgp4::*;
+pub use sun::*;
+pub use tle::*;
+
+use std::f64::consts::TAU;
+
+use crate::math::{Ecef, Geodetic, Vec3, ecef_to_enu};
+use crate::time::{Time, tai_minus_utc};
+
+/// Astronomical unit (meters).
+pub const ASTRONOMICAL_UNIT_M: f64 = 149_597_870_700.0;
+
+/// Julian date of the Unix epoch.
+const UNIX_EPOCH_JD: f64 = 2_440_587.5;
+/// Julian date of J2000.0.
+const J2000_JD: f64 = 2_451_545.0;
+/// TT − TAI (seconds).
+const TT_MINUS_TAI_S: f64 = 32.184;
+
+/// Julian date of `t` on the UTC scale.
+pub fn julian_date(t: Time) -> f64 {
+    UNIX_EPOCH_JD + t.0 / 86_400.0
+}
+
+/// Julian centuries of terrestrial time since J2000.0, the argument of the orbital series.
+pub fn julian_centuries_tt(t: Time) -> f64 {
+    let tt = t.0 + tai_minus_utc(t) as f64 + TT_MINUS_TAI_S;
+    (UNIX_EPOCH_JD + tt / 86_400.0 - J2000_JD) / 36_525.0
+}
+
+/// Greenwich mean sidereal time as an angle in `[0, 2π)` (IAU 1982).
+pub fn greenwich_sidereal_angle_rad(t: Time) -> f64 {
+    let d = julian_date(t) - J2000_JD;
+    let c = d / 36_525.0;
+    let deg =
+        280.460_618_37 + 360.985_647_366_29 * d + 0.000_387_933 * c * c - c * c * c / 38_710_000.0;
+    deg.to_radians().rem_euclid(TAU)
+}
+
+/// Mean obliquity of the ecliptic (degrees) for Julian centuries `c` (Meeus eq. 22.2).
+pub(crate) fn mean_obliquity_deg(c: f64) -> f64 {
+    23.0 + (26.0 + (21.448 - c * (46.815_0 + c * (0.000_59 - c * 0.001_813))) / 60.0) / 60.0
+}
+
+/// Azimuth (clockwise from north) and elevation above the ellipsoid's tangent plane.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Horizontal {
+    pub azimuth_rad: f64,
+    pub elevation_rad: f64,
+}
+
+/// Apparent geocentric position of a body at one instant.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CelestialPosition {
+    pub right_ascension_rad: f64,
+    pub declination_rad: f64,
+    pub distance_m: f64,
+    /// Greenwich sidereal angle used to rotate into ECEF.
+    pub sidereal_rad: f64,
+}
+
+impl CelestialPosition {
+    /// Builds a position from ecliptic-of-date longitude/latitude and obliquity.
+    pub(crate) fn from_ecliptic(
+        lon_rad: f64,
+        lat_rad: f64,
+        obliquity_rad: f64,
+        distance_m: f64,
+        t: Time,
+    ) -> Self {
+        let (sin_e, cos_e) = obliquity_rad.sin_cos();
+        let (sin_l, cos_l) = lon_rad.sin_cos();
+        let (sin_b, cos_b) = lat_rad.sin_cos();
+        let right_ascension_rad = (sin_l * cos_e - lat_rad.tan() * sin_e)
+            .atan2(cos_l)
+            .rem_euclid(TAU);
+        let declination_rad = (sin_b * cos_e + cos_b * sin_e * sin_l).asin();
+        Self {
+            right_ascension_rad,
+            declination_rad,
+            distance_m,
+            sidereal_rad: greenwich_sidereal_angle_rad(t),
+        }
+    }
+
+    /// Unit vector from the Earth's centre towards the body, in ECEF.
+    pub fn direction(&self) -> Vec3 {
+        let hour_angle = self.right_ascension_rad - self.sidereal_rad;
+        let (sin_d, cos_d) = self.declination_rad.sin_cos();
+        Vec3::new(cos_d * hour_angle.cos(), cos_d * hour_angle.sin(), sin_d)
+    }
+
+    /// Position of the body in ECEF meters.
+    pub fn ecef(&self) -> Ecef {
+        let p = self.direction() * self.distance_m;
+        Ecef::new(p.x, p.y, p.z)
+    }
+
+    /// Point on the ellipsoid whose surface normal points along [`Self::direction`]: where
+    /// the body is at the zenith, ignoring parallax.
+    pub fn sub_point(&self) -> Geodetic {
+        let lon = (self.right_ascension_rad - self.sidereal_rad + std::f64::consts::PI)
+            .rem_euclid(TAU)
+            - std::f64::consts::PI;
+        Geodetic::new(self.declination_rad, lon, 0.0)
+    }
+
+    /// Topocentric azimuth and geometric elevation (no refraction) seen from `observer`.
+    pub fn horizontal(&self, observer: Geodetic) -> Horizontal {
+        let enu = ecef_to_enu(self.ecef(), observer);
+        Horizontal {
+            azimuth_rad: enu.east.atan2(enu.north).rem_euclid(TAU),
+            elevation_rad: enu.up.atan2(enu.east.hypot(enu.north)),
+        }
+    }
+
+    /// Angle between this body and `other` as seen from the Earth's centre.
+    pub fn separation_rad(&self, other: &CelestialPosition) -> f64 {
+        self.direction()
+            .dot(other.direction())
+            .clamp(-1.0, 1.0)
+            .acos()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{greenwich_sidereal_angle_rad, julian_date};
+    use crate::time::Time;
+
+    fn assert_close(a: f64, b: f64, eps: f64) {
+        assert!((a - b).abs() <= eps, "{a} vs {b} (eps {eps})");
+    }
+
+    #[test]
+    fn julian_date_and_sidereal_time() {
+        let j2000 = Time::parse_iso8601("2000-01-01T12:00:00Z").unwrap();
+        assert_close(julian_date(j2000), 2_451_545.0, 1e-9);
+        // Meeus example 12.a: 1987-04-10 0h UT, GMST 13h10m46.3668s.
+        let t = Time::parse_iso8601("1987-04-10T00:00:00Z").unwrap();
+        let gmst_h = greenwich_sidereal_angle_rad(t).to_degrees() / 15.0;
+        assert_close(gmst_h, 13.0 + 10.0 / 60.0 + 46.3668 / 3600.0, 1e-6);
+    }
+}
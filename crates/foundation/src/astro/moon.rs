@@ -0,0 +1,76 @@
+//! Lunar position and phase.
+
+use super::{CelestialPosition, julian_centuries_tt, mean_obliquity_deg, sun_position};
+use crate::math::{Geodetic, WGS84_A};
+use crate::time::Time;
+
+/// Geocentric position of the Moon (*Astronomical Almanac* low-precision formulae: about
+/// 0.3° in longitude, 0.2° in latitude and 0.3% in distance).
+pub fn moon_position(t: Time) -> CelestialPosition {
+    let c = julian_centuries_tt(t);
+    let term =
+        |amplitude: f64, phase: f64, rate: f64| amplitude * (phase + rate * c).to_radians().sin();
+    let lon = 218.32
+        + 481_267.881 * c
+        + term(6.29, 135.0, 477_198.87)
+        + term(-1.27, 259.3, -413_335.36)
+        + term(0.66, 235.7, 890_534.22)
+        + term(0.21, 269.9, 954_397.74)
+        + term(-0.19, 357.5, 35_999.05)
+        + term(-0.11, 186.5, 966_404.03);
+    let lat = term(5.13, 93.3, 483_202.02)
+        + term(0.28, 228.2, 960_400.89)
+        + term(-0.28, 318.3, 6_003.15)
+        + term(-0.17, 217.6, -407_332.21);
+    let cos_term =
+        |amplitude: f64, phase: f64, rate: f64| amplitude * (phase + rate * c).to_radians().cos();
+    let parallax = 0.9508
+        + cos_term(0.0518, 135.0, 477_198.87)
+        + cos_term(0.0095, 259.3, -413_335.38)
+        + cos_term(0.0078, 235.7, 890_534.23)
+        + cos_term(0.0028, 269.9, 954_397.70);
+    CelestialPosition::from_ecliptic(
+        lon.to_radians(),
+        lat.to_radians(),
+        mean_obliquity_deg(c).to_radians(),
+        WGS84_A / parallax.to_radians().sin(),
+        t,
+    )
+}
+
+/// Point where the Moon is at the zenith, ignoring parallax.
+pub fn sublunar_point(t: Time) -> Geodetic {
+    moon_position(t).sub_point()
+}
+
+/// Illuminated fraction of the lunar disc, from 0 (new) to 1 (full).
+pub fn moon_illuminated_fraction(t: Time) -> f64 {
+    let moon = moon_position(t);
+    let sun = sun_position(t);
+    let elongation = moon.separation_rad(&sun);
+    let phase_angle = (sun.distance_m * elongation.sin())
+        .atan2(moon.distance_m - sun.distance_m * elongation.cos());
+    (1.0 + phase_angle.cos()) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{moon_illuminated_fraction, moon_position};
+    use crate::time::Time;
+
+    fn assert_close(a: f64, b: f64, eps: f64) {
+        assert!((a - b).abs() <= eps, "{a} vs {b} (eps {eps})");
+    }
+
+    #[test]
+    fn matches_meeus_examples() {
+        // Meeus examples 47.a and 48.a, 1992-04-12 0h TD: α = 134.688470°,
+        // δ = 13.768368°, Δ = 368409.7 km, illuminated fraction 0.6786.
+        let t = Time::parse_iso8601("1992-04-11T23:59:01Z").unwrap();
+        let moon = moon_position(t);
+        assert_close(moon.right_ascension_rad.to_degrees(), 134.688_47, 0.4);
+        assert_close(moon.declination_rad.to_degrees(), 13.768_368, 0.3);
+        assert_close(moon.distance_m / 1000.0, 368_409.7, 1_500.0);
+        assert_close(moon_illuminated_fraction(t), 0.6786, 0.01);
+    }
+}
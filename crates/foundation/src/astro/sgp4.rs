@@ -0,0 +1,680 @@
+//! SGP4/SDP4 orbit propagation for two-line element sets.
+//!
+//! A port of the reference implementation published with Vallado et al., *Revisiting
+//! Spacetrack Report #3* (AIAA 2006-6753), in its "improved" operation mode with WGS-72
+//! constants, which is what element sets are fitted against. Orbits with periods of 225
+//! minutes or more get the deep-space (SDP4) lunar-solar and resonance terms.
+
+use std::f64::consts::{PI, TAU};
+use std::fmt;
+
+use super::sgp4_deep::{DeepSpace, MeanState};
+use super::tle::Tle;
+use super::{greenwich_sidereal_angle_rad, julian_date};
+use crate::math::{Ecef, Geodetic, Vec3, ecef_to_geodetic};
+use crate::time::{Time, TimeSpan};
+
+/// WGS-72 Earth radius (km).
+pub(super) const EARTH_RADIUS_KM: f64 = 6378.135;
+/// WGS-72 gravitational parameter (km³/s²).
+const MU: f64 = 398_600.8;
+const J2: f64 = 0.001_082_616;
+const J3: f64 = -0.000_002_538_81;
+const J4: f64 = -0.000_001_655_97;
+const J3_OVER_J2: f64 = J3 / J2;
+const TWO_THIRDS: f64 = 2.0 / 3.0;
+/// Floor for `1 + cos i` in the long-period coefficient of retrograde equatorial orbits.
+const TEMP4: f64 = 1.5e-12;
+
+/// Square root of the gravitational parameter in Earth radii³/min².
+pub(super) fn xke() -> f64 {
+    60.0 / (EARTH_RADIUS_KM.powi(3) / MU).sqrt()
+}
+
+/// Why a propagation produced no usable state.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Sgp4Error {
+    /// Mean eccentricity left `[0, 1)`, usually from drag on an old element set.
+    MeanEccentricity(f64),
+    /// Mean motion dropped to zero or below.
+    MeanMotion(f64),
+    /// Perturbed eccentricity left `[0, 1]` (deep-space lunar-solar terms).
+    PerturbedEccentricity(f64),
+    /// Semi-latus rectum became negative.
+    SemiLatusRectum(f64),
+    /// The orbit radius fell below the Earth's surface.
+    Decayed,
+}
+
+impl fmt::Display for Sgp4Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Sgp4Error::MeanEccentricity(e) => write!(f, "mean eccentricity {e} out of range"),
+            Sgp4Error::MeanMotion(n) => write!(f, "mean motion {n} is not positive"),
+            Sgp4Error::PerturbedEccentricity(e) => {
+                write!(f, "perturbed eccentricity {e} out of range")
+            }
+            Sgp4Error::SemiLatusRectum(p) => write!(f, "semi-latus rectum {p} is negative"),
+            Sgp4Error::Decayed => write!(f, "satellite has decayed"),
+        }
+    }
+}
+
+impl std::error::Error for Sgp4Error {}
+
+/// Position and velocity in the True Equator Mean Equinox frame SGP4 works in.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TemeState {
+    pub position_km: Vec3,
+    pub velocity_km_s: Vec3,
+}
+
+/// An initialised SGP4/SDP4 propagator for one element set.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Sgp4 {
+    epoch: Time,
+    // Mean elements at epoch (radians, radians/minute).
+    bstar: f64,
+    ecco: f64,
+    argpo: f64,
+    inclo: f64,
+    mo: f64,
+    no_unkozai: f64,
+    nodeo: f64,
+    // Near-earth secular and drag coefficients.
+    isimp: bool,
+    aycof: f64,
+    con41: f64,
+    cc1: f64,
+    cc4: f64,
+    cc5: f64,
+    d2: f64,
+    d3: f64,
+    d4: f64,
+    delmo: f64,
+    eta: f64,
+    argpdot: f64,
+    omgcof: f64,
+    sinmao: f64,
+    t2cof: f64,
+    t3cof: f64,
+    t4cof: f64,
+    t5cof: f64,
+    x1mth2: f64,
+    x7thm1: f64,
+    mdot: f64,
+    nodedot: f64,
+    xlcof: f64,
+    xmcof: f64,
+    nodecf: f64,
+    deep: Option<DeepSpace>,
+}
+
+impl Sgp4 {
+    /// Initialises the propagator and checks it by propagating to the epoch.
+    pub fn new(tle: &Tle) -> Result<Self, Sgp4Error> {
+        let xke = xke();
+        let ecco = tle.eccentricity;
+        let inclo = tle.inclination_deg.to_radians();
+        let argpo = tle.arg_perigee_deg.to_radians();
+        let mo = tle.mean_anomaly_deg.to_radians();
+        let nodeo = tle.raan_deg.to_radians();
+        let no_kozai = tle.mean_motion_rad_per_min();
+        let bstar = tle.bstar;
+        if no_kozai <= 0.0 {
+            return Err(Sgp4Error::MeanMotion(no_kozai));
+        }
+        if !(0.0..1.0).contains(&ecco) {
+            return Err(Sgp4Error::MeanEccentricity(ecco));
+        }
+
+        // Recover the original (Brouwer) mean motion and semi-major axis from the Kozai
+        // mean motion on the card.
+        let eccsq = ecco * ecco;
+        let omeosq = 1.0 - eccsq;
+        let rteosq = omeosq.sqrt();
+        let (sinio, cosio) = inclo.sin_cos();
+        let cosio2 = cosio * cosio;
+        let ak = (xke / no_kozai).powf(TWO_THIRDS);
+        let d1 = 0.75 * J2 * (3.0 * cosio2 - 1.0) / (rteosq * omeosq);
+        let del = d1 / (ak * ak);
+        let adel = ak * (1.0 - del * del - del * (1.0 / 3.0 + 134.0 * del * del / 81.0));
+        let del = d1 / (adel * adel);
+        let no_unkozai = no_kozai / (1.0 + del);
+        let ao = (xke / no_unkozai).powf(TWO_THIRDS);
+        let po = ao * omeosq;
+        let con42 = 1.0 - 5.0 * cosio2;
+        let con41 = -con42 - cosio2 - cosio2;
+        let posq = po * po;
+        let rp = ao * (1.0 - ecco);
+
+        // Atmospheric density parameters, lowered for perigees under 156 km.
+        let ss = 78.0 / EARTH_RADIUS_KM + 1.0;
+        let qzms2t = ((120.0 - 78.0) / EARTH_RADIUS_KM).powi(4);
+        let mut isimp = rp < 220.0 / EARTH_RADIUS_KM + 1.0;
+        let mut sfour = ss;
+        let mut qzms24 = qzms2t;
+        let perigee_km = (rp - 1.0) * EARTH_RADIUS_KM;
+        if perigee_km < 156.0 {
+            sfour = if perigee_km < 98.0 {
+                20.0
+            } else {
+                perigee_km - 78.0
+            };
+            qzms24 = ((120.0 - sfour) / EARTH_RADIUS_KM).powi(4);
+            sfour = sfour / EARTH_RADIUS_KM + 1.0;
+        }
+
+        let pinvsq = 1.0 / posq;
+        let tsi = 1.0 / (ao - sfour);
+        let eta = ao * ecco * tsi;
+        let etasq = eta * eta;
+        let eeta = ecco * eta;
+        let psisq = (1.0 - etasq).abs();
+        let coef = qzms24 * tsi.powi(4);
+        let coef1 = coef / psisq.powf(3.5);
+        let cc2 = coef1
+            * no_unkozai
+            * (ao * (1.0 + 1.5 * etasq + eeta * (4.0 + etasq))
+                + 0.375 * J2 * tsi / psisq * con41 * (8.0 + 3.0 * etasq * (8.0 + etasq)));
+        let cc1 = bstar * cc2;
+        let cc3 = if ecco > 1.0e-4 {
+            -2.0 * coef * tsi * J3_OVER_J2 * no_unkozai * sinio / ecco
+        } else {
+            0.0
+        };
+        let x1mth2 = 1.0 - cosio2;
+        let cc4 = 2.0
+            * no_unkozai
+            * coef1
+            * ao
+            * omeosq
+            * (eta * (2.0 + 0.5 * etasq) + ecco * (0.5 + 2.0 * etasq)
+                - J2 * tsi / (ao * psisq)
+                    * (-3.0 * con41 * (1.0 - 2.0 * eeta + etasq * (1.5 - 0.5 * eeta))
+                        + 0.75
+                            * x1mth2
+                            * (2.0 * etasq - eeta * (1.0 + etasq))
+                            * (2.0 * argpo).cos()));
+        let cc5 = 2.0 * coef1 * ao * omeosq * (1.0 + 2.75 * (etasq + eeta) + eeta * etasq);
+        let cosio4 = cosio2 * cosio2;
+        let temp1 = 1.5 * J2 * pinvsq * no_unkozai;
+        let temp2 = 0.5 * temp1 * J2 * pinvsq;
+        let temp3 = -0.46875 * J4 * pinvsq * pinvsq * no_unkozai;
+        let mdot = no_unkozai
+            + 0.5 * temp1 * rteosq * con41
+            + 0.0625 * temp2 * rteosq * (13.0 - 78.0 * cosio2 + 137.0 * cosio4);
+        let argpdot = -0.5 * temp1 * con42
+            + 0.0625 * temp2 * (7.0 - 114.0 * cosio2 + 395.0 * cosio4)
+            + temp3 * (3.0 - 36.0 * cosio2 + 49.0 * cosio4);
+        let xhdot1 = -temp1 * cosio;
+        let nodedot = xhdot1
+            + (0.5 * temp2 * (4.0 - 19.0 * cosio2) + 2.0 * temp3 * (3.0 - 7.0 * cosio2)) * cosio;
+        let xpidot = argpdot + nodedot;
+        let omgcof = bstar * cc3 * argpo.cos();
+        let xmcof = if ecco > 1.0e-4 {
+            -TWO_THIRDS * coef * bstar / eeta
+        } else {
+            0.0
+        };
+        let nodecf = 3.5 * omeosq * xhdot1 * cc1;
+        let t2cof = 1.5 * cc1;
+        let xlcof = long_period_coefficient(sinio, cosio);
+        let aycof = -0.5 * J3_OVER_J2 * sinio;
+        let delmo = (1.0 + eta * mo.cos()).powi(3);
+        let sinmao = mo.sin();
+        let x7thm1 = 7.0 * cosio2 - 1.0;
+
+        let epoch_days = julian_date(tle.epoch) - 2_433_281.5;
+        let deep = if TAU / no_unkozai >= 225.0 {
+            isimp = true;
+            Some(DeepSpace::new(
+                epoch_days,
+                gstime(julian_date(tle.epoch)),
+                ecco,
+                argpo,
+                inclo,
+                mo,
+                nodeo,
+                no_unkozai,
+                mdot,
+                nodedot,
+                xpidot,
+            ))
+        } else {
+            None
+        };
+
+        let (mut d2, mut d3, mut d4) = (0.0, 0.0, 0.0);
+        let (mut t3cof, mut t4cof, mut t5cof) = (0.0, 0.0, 0.0);
+        if !isimp {
+            let cc1sq = cc1 * cc1;
+            d2 = 4.0 * ao * tsi * cc1sq;
+            let temp = d2 * tsi * cc1 / 3.0;
+            d3 = (17.0 * ao + sfour) * temp;
+            d4 = 0.5 * temp * ao * tsi * (221.0 * ao + 31.0 * sfour) * cc1;
+            t3cof = d2 + 2.0 * cc1sq;
+            t4cof = 0.25 * (3.0 * d3 + cc1 * (12.0 * d2 + 10.0 * cc1sq));
+            t5cof = 0.2
+                * (3.0 * d4 + 12.0 * cc1 * d3 + 6.0 * d2 * d2 + 15.0 * cc1sq * (2.0 * d2 + cc1sq));
+        }
+
+        let sgp4 = Self {
+            epoch: tle.epoch,
+            bstar,
+            ecco,
+            argpo,
+            inclo,
+            mo,
+            no_unkozai,
+            nodeo,
+            isimp,
+            aycof,
+            con41,
+            cc1,
+            cc4,
+            cc5,
+            d2,
+            d3,
+            d4,
+            delmo,
+            eta,
+            argpdot,
+            omgcof,
+            sinmao,
+            t2cof,
+            t3cof,
+            t4cof,
+            t5cof,
+            x1mth2,
+            x7thm1,
+            mdot,
+            nodedot,
+            xlcof,
+            xmcof,
+            nodecf,
+            deep,
+        };
+        sgp4.propagate_minutes(0.0)?;
+        Ok(sgp4)
+    }
+
+    pub fn epoch(&self) -> Time {
+        self.epoch
+    }
+
+    /// Whether the deep-space (SDP4) terms are in use.
+    pub fn is_deep_space(&self) -> bool {
+        self.deep.is_some()
+    }
+
+    /// Anomalistic period from the recovered mean motion (seconds).
+    pub fn period_s(&self) -> f64 {
+        TAU / self.no_unkozai * 60.0
+    }
+
+    /// State `t` in the TEME frame.
+    pub fn propagate(&self, t: Time) -> Result<TemeState, Sgp4Error> {
+        self.propagate_minutes((t.0 - self.epoch.0) / 60.0)
+    }
+
+    /// Position in ECEF meters at `t`: TEME rotated by Greenwich mean sidereal time, with
+    /// polar motion ignored.
+    pub fn position_ecef(&self, t: Time) -> Result<Ecef, Sgp4Error> {
+        let p = self.propagate(t)?.position_km;
+        Ok(teme_to_ecef(p * 1000.0, greenwich_sidereal_angle_rad(t)))
+    }
+
+    /// Sub-satellite point at `t`, with the satellite's height in `alt_m`.
+    pub fn geodetic(&self, t: Time) -> Result<Geodetic, Sgp4Error> {
+        Ok(ecef_to_geodetic(self.position_ecef(t)?))
+    }
+
+    /// Sub-satellite points (height zero) every `step_s` seconds across `span`, ending
+    /// exactly at its end. Instants where propagation fails are skipped.
+    pub fn ground_track(&self, span: TimeSpan, step_s: f64) -> Vec<Geodetic> {
+        sample_times(span, step_s)
+            .filter_map(|t| self.geodetic(t).ok())
+            .map(|geo| Geodetic::new(geo.lat_rad, geo.lon_rad, 0.0))
+            .collect()
+    }
+
+    /// One revolution starting at `t`, as a closed ECEF ring of `segments` points frozen
+    /// in the Earth's orientation at `t`: the orbit ellipse as drawn around a globe.
+    pub fn orbit_line(&self, t: Time, segments: usize) -> Vec<Ecef> {
+        let segments = segments.max(3);
+        let gmst = greenwich_sidereal_angle_rad(t);
+        let step = self.period_s() / segments as f64;
+        (0..segments)
+            .filter_map(|i| self.propagate(Time(t.0 + i as f64 * step)).ok())
+            .map(|state| teme_to_ecef(state.position_km * 1000.0, gmst))
+            .collect()
+    }
+
+    /// State `tsince` minutes after epoch, in km and km/s.
+    pub fn propagate_minutes(&self, tsince: f64) -> Result<TemeState, Sgp4Error> {
+        let xke = xke();
+        let t = tsince;
+
+        // Secular gravity and atmospheric drag.
+        let xmdf = self.mo + self.mdot * t;
+        let argpdf = self.argpo + self.argpdot * t;
+        let nodedf = self.nodeo + self.nodedot * t;
+        let mut argpm = argpdf;
+        let mut mm = xmdf;
+        let t2 = t * t;
+        let mut nodem = nodedf + self.nodecf * t2;
+        let mut tempa = 1.0 - self.cc1 * t;
+        let mut tempe = self.bstar * self.cc4 * t;
+        let mut templ = self.t2cof * t2;
+        if !self.isimp {
+            let delomg = self.omgcof * t;
+            let delm = self.xmcof * ((1.0 + self.eta * xmdf.cos()).powi(3) - self.delmo);
+            let temp = delomg + delm;
+            mm = xmdf + temp;
+            argpm = argpdf - temp;
+            let t3 = t2 * t;
+            let t4 = t3 * t;
+            tempa = tempa - self.d2 * t2 - self.d3 * t3 - self.d4 * t4;
+            tempe += self.bstar * self.cc5 * (mm.sin() - self.sinmao);
+            templ += self.t3cof * t3 + t4 * (self.t4cof + t * self.t5cof);
+        }
+
+        let mut nm = self.no_unkozai;
+        let mut em = self.ecco;
+        let mut inclm = self.inclo;
+        if let Some(deep) = &self.deep {
+            let mut state = MeanState {
+                em,
+                argpm,
+                inclm,
+                mm,
+                nodem,
+                nm,
+            };
+            deep.apply_secular(t, self.argpo, self.argpdot, self.no_unkozai, &mut state);
+            MeanState {
+                em,
+                argpm,
+                inclm,
+                mm,
+                nodem,
+                nm,
+            } = state;
+        }
+        if nm <= 0.0 {
+            return Err(Sgp4Error::MeanMotion(nm));
+        }
+        let am = (xke / nm).powf(TWO_THIRDS) * tempa * tempa;
+        nm = xke / am.powf(1.5);
+        em -= tempe;
+        if !(-0.001..1.0).contains(&em) {
+            return Err(Sgp4Error::MeanEccentricity(em));
+        }
+        em = em.max(1.0e-6);
+        mm += self.no_unkozai * templ;
+        let xlm = mm + argpm + nodem;
+        nodem %= TAU;
+        argpm %= TAU;
+        let xlm = xlm % TAU;
+        mm = (xlm - argpm - nodem) % TAU;
+
+        // Lunar-solar periodics.
+        let mut ep = em;
+        let mut xincp = inclm;
+        let mut argpp = argpm;
+        let mut nodep = nodem;
+        let mut mp = mm;
+        let mut aycof = self.aycof;
+        let mut xlcof = self.xlcof;
+        if let Some(deep) = &self.deep {
+            deep.apply_periodics(t, &mut ep, &mut xincp, &mut nodep, &mut argpp, &mut mp);
+            if xincp < 0.0 {
+                xincp = -xincp;
+                nodep += PI;
+                argpp -= PI;
+            }
+            if !(0.0..=1.0).contains(&ep) {
+                return Err(Sgp4Error::PerturbedEccentricity(ep));
+            }
+            let (sinip, cosip) = xincp.sin_cos();
+            aycof = -0.5 * J3_OVER_J2 * sinip;
+            xlcof = long_period_coefficient(sinip, cosip);
+        }
+        let (sinip, cosip) = xincp.sin_cos();
+
+        // Long-period periodics.
+        let axnl = ep * argpp.cos();
+        let temp = 1.0 / (am * (1.0 - ep * ep));
+        let aynl = ep * argpp.sin() + temp * aycof;
+        let xl = mp + argpp + nodep + temp * xlcof * axnl;
+
+        // Kepler's equation for the eccentric longitude.
+        let u = (xl - nodep) % TAU;
+        let mut eo1 = u;
+        let (mut sineo1, mut coseo1) = (0.0, 0.0);
+        let mut tem5: f64 = 9999.9;
+        let mut iterations = 1;
+        while tem5.abs() >= 1.0e-12 && iterations <= 10 {
+            (sineo1, coseo1) = eo1.sin_cos();
+            tem5 = 1.0 - coseo1 * axnl - sineo1 * aynl;
+            tem5 = (u - aynl * coseo1 + axnl * sineo1 - eo1) / tem5;
+            tem5 = tem5.clamp(-0.95, 0.95);
+            eo1 += tem5;
+            iterations += 1;
+        }
+
+        // Short-period periodics.
+        let ecose = axnl * coseo1 + aynl * sineo1;
+        let esine = axnl * sineo1 - aynl * coseo1;
+        let el2 = axnl * axnl + aynl * aynl;
+        let pl = am * (1.0 - el2);
+        if pl < 0.0 {
+            return Err(Sgp4Error::SemiLatusRectum(pl));
+        }
+        let rl = am * (1.0 - ecose);
+        let rdotl = am.sqrt() * esine / rl;
+        let rvdotl = pl.sqrt() / rl;
+        let betal = (1.0 - el2).sqrt();
+        let temp = esine / (1.0 + betal);
+        let sinu = am / rl * (sineo1 - aynl - axnl * temp);
+        let cosu = am / rl * (coseo1 - axnl + aynl * temp);
+        let mut su = sinu.atan2(cosu);
+        let sin2u = (cosu + cosu) * sinu;
+        let cos2u = 1.0 - 2.0 * sinu * sinu;
+        let temp = 1.0 / pl;
+        let temp1 = 0.5 * J2 * temp;
+        let temp2 = temp1 * temp;
+
+        let (con41, x1mth2, x7thm1) = if self.deep.is_some() {
+            let cosisq = cosip * cosip;
+            (3.0 * cosisq - 1.0, 1.0 - cosisq, 7.0 * cosisq - 1.0)
+        } else {
+            (self.con41, self.x1mth2, self.x7thm1)
+        };
+        let mrt = rl * (1.0 - 1.5 * temp2 * betal * con41) + 0.5 * temp1 * x1mth2 * cos2u;
+        su -= 0.25 * temp2 * x7thm1 * sin2u;
+        let xnode = nodep + 1.5 * temp2 * cosip * sin2u;
+        let xinc = xincp + 1.5 * temp2 * cosip * sinip * cos2u;
+        let mvt = rdotl - nm * temp1 * x1mth2 * sin2u / xke;
+        let rvdot = rvdotl + nm * temp1 * (x1mth2 * cos2u + 1.5 * con41) / xke;
+
+        // Orientation vectors.
+        let (sinsu, cossu) = su.sin_cos();
+        let (snod, cnod) = xnode.sin_cos();
+        let (sini, cosi) = xinc.sin_cos();
+        let xmx = -snod * cosi;
+        let xmy = cnod * cosi;
+        let u = Vec3::new(
+            xmx * sinsu + cnod * cossu,
+            xmy * sinsu + snod * cossu,
+            sini * sinsu,
+        );
+        let v = Vec3::new(
+            xmx * cossu - cnod * sinsu,
+            xmy * cossu - snod * sinsu,
+            sini * cossu,
+        );
+
+        if mrt < 1.0 {
+            return Err(Sgp4Error::Decayed);
+        }
+        let km_per_s = EARTH_RADIUS_KM * xke / 60.0;
+        Ok(TemeState {
+            position_km: u * (mrt * EARTH_RADIUS_KM),
+            velocity_km_s: (u * mvt + v * rvdot) * km_per_s,
+        })
+    }
+}
+
+/// Long-period coefficient of the mean longitude, guarded against `cos i = -1`.
+fn long_period_coefficient(sin_i: f64, cos_i: f64) -> f64 {
+    let denominator = if (cos_i + 1.0).abs() > TEMP4 {
+        1.0 + cos_i
+    } else {
+        TEMP4
+    };
+    -0.25 * J3_OVER_J2 * sin_i * (3.0 + 5.0 * cos_i) / denominator
+}
+
+/// Greenwich mean sidereal angle in the form the reference implementation uses to seed the
+/// deep-space resonance terms, from a UT1 Julian date.
+fn gstime(jd_ut1: f64) -> f64 {
+    let tut1 = (jd_ut1 - 2_451_545.0) / 36_525.0;
+    let seconds = -6.2e-6 * tut1 * tut1 * tut1
+        + 0.093_104 * tut1 * tut1
+        + (876_600.0 * 3600.0 + 8_640_184.812_866) * tut1
+        + 67_310.548_41;
+    (seconds.to_radians() / 240.0).rem_euclid(TAU)
+}
+
+fn teme_to_ecef(p: Vec3, gmst: f64) -> Ecef {
+    let (s, c) = gmst.sin_cos();
+    Ecef::new(c * p.x + s * p.y, -s * p.x + c * p.y, p.z)
+}
+
+fn sample_times(span: TimeSpan, step_s: f64) -> impl Iterator<Item = Time> {
+    let duration = span.end.0 - span.start.0;
+    let steps = if step_s > 0.0 && duration > 0.0 {
+        (duration / step_s).ceil() as usize
+    } else {
+        0
+    };
+    (0..=steps).map(move |i| Time((span.start.0 + i as f64 * step_s).min(span.end.0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Sgp4, Sgp4Error};
+    use crate::astro::Tle;
+    use crate::math::{Vec3, WGS84_A};
+    use crate::time::{Time, TimeSpan};
+
+    fn assert_close(a: Vec3, b: Vec3, eps: f64) {
+        assert!((a - b).length() <= eps, "{a:?} vs {b:?} (eps {eps})");
+    }
+
+    #[test]
+    fn matches_near_earth_verification_case() {
+        // Satellite 00005 from the SGP4 verification set (Vallado et al. 2006).
+        let tle = Tle::parse(
+            "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753",
+            "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667",
+        )
+        .unwrap();
+        let sgp4 = Sgp4::new(&tle).unwrap();
+        assert!(!sgp4.is_deep_space());
+
+        let s0 = sgp4.propagate_minutes(0.0).unwrap();
+        assert_close(
+            s0.position_km,
+            Vec3::new(7022.46529266, -1400.08296755, 0.03995155),
+            1e-6,
+        );
+        assert_close(
+            s0.velocity_km_s,
+            Vec3::new(1.893841015, 6.405893759, 4.534807250),
+            1e-8,
+        );
+        let s360 = sgp4.propagate_minutes(360.0).unwrap();
+        assert_close(
+            s360.position_km,
+            Vec3::new(-7154.03120202, -3783.17682504, -3536.19412294),
+            1e-6,
+        );
+        assert_close(
+            s360.velocity_km_s,
+            Vec3::new(4.741887409, -4.151817765, -2.093935425),
+            1e-8,
+        );
+    }
+
+    #[test]
+    fn matches_deep_space_verification_case() {
+        // Satellite 11801 (Spacetrack Report #3 SDP4 case), checksums recomputed.
+        let tle = Tle::parse(
+            "1 11801U          80230.29629788  .01431103  00000-0  14311-1 0    13",
+            "2 11801  46.7916 230.4354 7318036  47.4722  10.4117  2.28537848    13",
+        )
+        .unwrap();
+        let sgp4 = Sgp4::new(&tle).unwrap();
+        assert!(sgp4.is_deep_space());
+        let s0 = sgp4.propagate_minutes(0.0).unwrap();
+        assert_close(
+            s0.position_km,
+            Vec3::new(7473.37102491, 428.94748312, 5828.74846783),
+            1e-6,
+        );
+        assert_close(
+            s0.velocity_km_s,
+            Vec3::new(5.107155391, 6.444680305, -0.186133297),
+            1e-8,
+        );
+    }
+
+    #[test]
+    fn geostationary_satellite_holds_station() {
+        // A geostationary element set: the deep-space 24 h resonance path.
+        let tle = Tle::parse(
+            "1 28884U 05041A   24001.50000000  .00000000  00000-0  00000-0 0  9992",
+            "2 28884   0.0200  90.0000 0001000   0.0000 180.0000  1.00271000    04",
+        );
+        let tle = tle.unwrap();
+        let sgp4 = Sgp4::new(&tle).unwrap();
+        assert!(sgp4.is_deep_space());
+        let day = TimeSpan::new(tle.epoch, Time(tle.epoch.0 + 86_400.0));
+        let track = sgp4.ground_track(day, 3600.0);
+        assert_eq!(track.len(), 25);
+        for geo in &track {
+            assert!(geo.lat_deg().abs() < 0.1);
+            assert!((geo.lon_deg() - track[0].lon_deg()).abs() < 0.5, "{geo:?}");
+        }
+        let radius = sgp4.position_ecef(tle.epoch).unwrap();
+        let r = (radius.x * radius.x + radius.y * radius.y + radius.z * radius.z).sqrt();
+        assert!((r - 42_164_000.0).abs() < 20_000.0, "{r}");
+        assert_eq!(sgp4.orbit_line(tle.epoch, 90).len(), 90);
+    }
+
+    #[test]
+    fn reports_decay() {
+        let tle = Tle::parse(
+            "1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927",
+            "2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537",
+        )
+        .unwrap();
+        let sgp4 = Sgp4::new(&tle).unwrap();
+        let geo = sgp4.geodetic(tle.epoch).unwrap();
+        assert!((geo.alt_m - 350_000.0).abs() < 30_000.0, "{}", geo.alt_m);
+        assert!(geo.lat_deg().abs() <= 51.7);
+        assert!(
+            sgp4.position_ecef(tle.epoch)
+                .is_ok_and(|p| p.x.hypot(p.y) > WGS84_A * 0.5)
+        );
+        // Negative drag run backwards for decades drives the orbit out of range.
+        let far = Time(tle.epoch.0 - 40.0 * 365.0 * 86_400.0);
+        assert!(matches!(
+            sgp4.propagate(far),
+            Err(Sgp4Error::MeanEccentricity(_) | Sgp4Error::Decayed | Sgp4Error::MeanMotion(_))
+        ));
+    }
+}
@@ -0,0 +1,571 @@
+//! Deep-space (SDP4) terms: lunar-solar perturbations and the 12 h / 24 h geopotential
+//! resonances, following the reference `dscom`, `dsinit`, `dspace` and `dpper` routines.
+
+use std::f64::consts::{PI, TAU};
+
+use super::sgp4::xke;
+
+const ZES: f64 = 0.01675;
+const ZEL: f64 = 0.05490;
+const ZNS: f64 = 1.19459e-5;
+const ZNL: f64 = 1.5835218e-4;
+/// Earth rotation rate (rad/min).
+const RPTIM: f64 = 4.375_269_088_011_3e-3;
+/// Resonance integrator step (minutes).
+const STEP: f64 = 720.0;
+
+/// Mean elements carried through the secular update.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(super) struct MeanState {
+    pub em: f64,
+    pub argpm: f64,
+    pub inclm: f64,
+    pub mm: f64,
+    pub nodem: f64,
+    pub nm: f64,
+}
+
+/// Resonance class of a deep-space orbit.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Resonance {
+    None,
+    /// One revolution per sidereal day (geosynchronous).
+    Synchronous,
+    /// Two revolutions per day with high eccentricity (Molniya).
+    HalfDay,
+}
+
+/// Deep-space coefficients fixed at initialisation.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(super) struct DeepSpace {
+    gsto: f64,
+    // Lunar-solar periodic coefficients.
+    e3: f64,
+    ee2: f64,
+    se2: f64,
+    se3: f64,
+    sgh2: f64,
+    sgh3: f64,
+    sgh4: f64,
+    sh2: f64,
+    sh3: f64,
+    si2: f64,
+    si3: f64,
+    sl2: f64,
+    sl3: f64,
+    sl4: f64,
+    xgh2: f64,
+    xgh3: f64,
+    xgh4: f64,
+    xh2: f64,
+    xh3: f64,
+    xi2: f64,
+    xi3: f64,
+    xl2: f64,
+    xl3: f64,
+    xl4: f64,
+    zmol: f64,
+    zmos: f64,
+    // Lunar-solar secular rates.
+    dedt: f64,
+    didt: f64,
+    dmdt: f64,
+    dnodt: f64,
+    domdt: f64,
+    // Resonance terms.
+    resonance: Resonance,
+    d2201: f64,
+    d2211: f64,
+    d3210: f64,
+    d3222: f64,
+    d4410: f64,
+    d4422: f64,
+    d5220: f64,
+    d5232: f64,
+    d5421: f64,
+    d5433: f64,
+    del1: f64,
+    del2: f64,
+    del3: f64,
+    xfact: f64,
+    xlamo: f64,
+}
+
+/// Solar or lunar geometry terms from `dscom`.
+#[derive(Copy, Clone)]
+struct BodyTerms {
+    s1: f64,
+    s2: f64,
+    s3: f64,
+    s4: f64,
+    s5: f64,
+    s6: f64,
+    s7: f64,
+    z1: f64,
+    z2: f64,
+    z3: f64,
+    z11: f64,
+    z12: f64,
+    z13: f64,
+    z21: f64,
+    z22: f64,
+    z23: f64,
+    z31: f64,
+    z32: f64,
+    z33: f64,
+}
+
+impl DeepSpace {
+    /// `epoch_days` counts days from 1949-12-31 0h UT; `gsto` is the sidereal angle at
+    /// epoch. The remaining arguments are the recovered mean elements and secular rates.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn new(
+        epoch_days: f64,
+        gsto: f64,
+        ecco: f64,
+        argpo: f64,
+        inclo: f64,
+        mo: f64,
+        nodeo: f64,
+        no: f64,
+        mdot: f64,
+        nodedot: f64,
+        xpidot: f64,
+    ) -> Self {
+        // dscom: solar and lunar geometry at epoch.
+        let (snodm, cnodm) = nodeo.sin_cos();
+        let (sinomm, cosomm) = argpo.sin_cos();
+        let (sinim, cosim) = inclo.sin_cos();
+        let emsq = ecco * ecco;
+        let betasq = 1.0 - emsq;
+        let rtemsq = betasq.sqrt();
+
+        let day = epoch_days + 18_261.5;
+        let xnodce = (4.523_602_0 - 9.242_202_9e-4 * day) % TAU;
+        let (stem, ctem) = xnodce.sin_cos();
+        let zcosil = 0.913_751_64 - 0.035_680_96 * ctem;
+        let zsinil = (1.0 - zcosil * zcosil).sqrt();
+        let zsinhl = 0.089_683_511 * stem / zsinil;
+        let zcoshl = (1.0 - zsinhl * zsinhl).sqrt();
+        let gam = 5.835_151_4 + 0.001_944_368_0 * day;
+        let zx = (0.397_854_16 * stem / zsinil).atan2(zcoshl * ctem + 0.917_448_67 * zsinhl * stem);
+        let zx = gam + zx - xnodce;
+        let (zsingl, zcosgl) = zx.sin_cos();
+
+        let body =
+            |zcosg: f64, zsing: f64, zcosi: f64, zsini: f64, zcosh: f64, zsinh: f64, cc: f64| {
+                let a1 = zcosg * zcosh + zsing * zcosi * zsinh;
+                let a3 = -zsing * zcosh + zcosg * zcosi * zsinh;
+                let a7 = -zcosg * zsinh + zsing * zcosi * zcosh;
+                let a8 = zsing * zsini;
+                let a9 = zsing * zsinh + zcosg * zcosi * zcosh;
+                let a10 = zcosg * zsini;
+                let a2 = cosim * a7 + sinim * a8;
+                let a4 = cosim * a9 + sinim * a10;
+                let a5 = -sinim * a7 + cosim * a8;
+                let a6 = -sinim * a9 + cosim * a10;
+
+                let x1 = a1 * cosomm + a2 * sinomm;
+                let x2 = a3 * cosomm + a4 * sinomm;
+                let x3 = -a1 * sinomm + a2 * cosomm;
+                let x4 = -a3 * sinomm + a4 * cosomm;
+                let x5 = a5 * sinomm;
+                let x6 = a6 * sinomm;
+                let x7 = a5 * cosomm;
+                let x8 = a6 * cosomm;
+
+                let z31 = 12.0 * x1 * x1 - 3.0 * x3 * x3;
+                let z32 = 24.0 * x1 * x2 - 6.0 * x3 * x4;
+                let z33 = 12.0 * x2 * x2 - 3.0 * x4 * x4;
+                let z1 = 3.0 * (a1 * a1 + a2 * a2) + z31 * emsq;
+                let z2 = 6.0 * (a1 * a3 + a2 * a4) + z32 * emsq;
+                let z3 = 3.0 * (a3 * a3 + a4 * a4) + z33 * emsq;
+                let s3 = cc / no;
+                let s4 = s3 * rtemsq;
+                BodyTerms {
+                    z11: -6.0 * a1 * a5 + emsq * (-24.0 * x1 * x7 - 6.0 * x3 * x5),
+                    z12: -6.0 * (a1 * a6 + a3 * a5)
+                        + emsq * (-24.0 * (x2 * x7 + x1 * x8) - 6.0 * (x3 * x6 + x4 * x5)),
+                    z13: -6.0 * a3 * a6 + emsq * (-24.0 * x2 * x8 - 6.0 * x4 * x6),
+                    z21: 6.0 * a2 * a5 + emsq * (24.0 * x1 * x5 - 6.0 * x3 * x7),
+                    z22: 6.0 * (a4 * a5 + a2 * a6)
+                        + emsq * (24.0 * (x2 * x5 + x1 * x6) - 6.0 * (x4 * x7 + x3 * x8)),
+                    z23: 6.0 * a4 * a6 + emsq * (24.0 * x2 * x6 - 6.0 * x4 * x8),
+                    z1: z1 + z1 + betasq * z31,
+                    z2: z2 + z2 + betasq * z32,
+                    z3: z3 + z3 + betasq * z33,
+                    z31,
+                    z32,
+                    z33,
+                    s1: -15.0 * ecco * s4,
+                    s2: -0.5 * s3 / rtemsq,
+                    s3,
+                    s4,
+                    s5: x1 * x3 + x2 * x4,
+                    s6: x2 * x3 + x1 * x4,
+                    s7: x2 * x4 - x1 * x3,
+                }
+            };
+        let sun = body(
+            0.194_590_5,
+            -0.980_884_58,
+            0.917_448_67,
+            0.397_854_16,
+            cnodm,
+            snodm,
+            2.986_479_7e-6,
+        );
+        let moon = body(
+            zcosgl,
+            zsingl,
+            zcosil,
+            zsinil,
+            zcoshl * cnodm + zsinhl * snodm,
+            snodm * zcoshl - cnodm * zsinhl,
+            4.796_806_5e-7,
+        );
+
+        // dsinit: secular rates from both bodies.
+        let near_equatorial = !(5.235_987_7e-2..=PI - 5.235_987_7e-2).contains(&inclo);
+        let ses = sun.s1 * ZNS * sun.s5;
+        let sis = sun.s2 * ZNS * (sun.z11 + sun.z13);
+        let sls = -ZNS * sun.s3 * (sun.z1 + sun.z3 - 14.0 - 6.0 * emsq);
+        let sghs = sun.s4 * ZNS * (sun.z31 + sun.z33 - 6.0);
+        let mut shs = if near_equatorial {
+            0.0
+        } else {
+            -ZNS * sun.s2 * (sun.z21 + sun.z23)
+        };
+        if sinim != 0.0 {
+            shs /= sinim;
+        }
+        let sgs = sghs - cosim * shs;
+
+        let dedt = ses + moon.s1 * ZNL * moon.s5;
+        let didt = sis + moon.s2 * ZNL * (moon.z11 + moon.z13);
+        let dmdt = sls - ZNL * moon.s3 * (moon.z1 + moon.z3 - 14.0 - 6.0 * emsq);
+        let sghl = moon.s4 * ZNL * (moon.z31 + moon.z33 - 6.0);
+        let shll = if near_equatorial {
+            0.0
+        } else {
+            -ZNL * moon.s2 * (moon.z21 + moon.z23)
+        };
+        let mut domdt = sgs + sghl;
+        let mut dnodt = shs;
+        if sinim != 0.0 {
+            domdt -= cosim / sinim * shll;
+            dnodt += shll / sinim;
+        }
+
+        let mut deep = Self {
+            gsto,
+            e3: 2.0 * moon.s1 * moon.s7,
+            ee2: 2.0 * moon.s1 * moon.s6,
+            se2: 2.0 * sun.s1 * sun.s6,
+            se3: 2.0 * sun.s1 * sun.s7,
+            sgh2: 2.0 * sun.s4 * sun.z32,
+            sgh3: 2.0 * sun.s4 * (sun.z33 - sun.z31),
+            sgh4: -18.0 * sun.s4 * ZES,
+            sh2: -2.0 * sun.s2 * sun.z22,
+            sh3: -2.0 * sun.s2 * (sun.z23 - sun.z21),
+            si2: 2.0 * sun.s2 * sun.z12,
+            si3: 2.0 * sun.s2 * (sun.z13 - sun.z11),
+            sl2: -2.0 * sun.s3 * sun.z2,
+            sl3: -2.0 * sun.s3 * (sun.z3 - sun.z1),
+            sl4: -2.0 * sun.s3 * (-21.0 - 9.0 * emsq) * ZES,
+            xgh2: 2.0 * moon.s4 * moon.z32,
+            xgh3: 2.0 * moon.s4 * (moon.z33 - moon.z31),
+            xgh4: -18.0 * moon.s4 * ZEL,
+            xh2: -2.0 * moon.s2 * moon.z22,
+            xh3: -2.0 * moon.s2 * (moon.z23 - moon.z21),
+            xi2: 2.0 * moon.s2 * moon.z12,
+            xi3: 2.0 * moon.s2 * (moon.z13 - moon.z11),
+            xl2: -2.0 * moon.s3 * moon.z2,
+            xl3: -2.0 * moon.s3 * (moon.z3 - moon.z1),
+            xl4: -2.0 * moon.s3 * (-21.0 - 9.0 * emsq) * ZEL,
+            zmol: (4.719_967_2 + 0.229_971_50 * day - gam) % TAU,
+            zmos: (6.256_583_7 + 0.017_201_977 * day) % TAU,
+            dedt,
+            didt,
+            dmdt,
+            dnodt,
+            domdt,
+            resonance: Resonance::None,
+            d2201: 0.0,
+            d2211: 0.0,
+            d3210: 0.0,
+            d3222: 0.0,
+            d4410: 0.0,
+            d4422: 0.0,
+            d5220: 0.0,
+            d5232: 0.0,
+            d5421: 0.0,
+            d5433: 0.0,
+            del1: 0.0,
+            del2: 0.0,
+            del3: 0.0,
+            xfact: 0.0,
+            xlamo: 0.0,
+        };
+
+        // Geopotential resonance.
+        deep.resonance = if no > 0.003_490_658_5 && no < 0.005_235_987_7 {
+            Resonance::Synchronous
+        } else if (8.26e-3..=9.24e-3).contains(&no) && ecco >= 0.5 {
+            Resonance::HalfDay
+        } else {
+            return deep;
+        };
+        let theta = gsto % TAU;
+        let aonv = (no / xke()).powf(2.0 / 3.0);
+        if deep.resonance == Resonance::HalfDay {
+            let cosisq = cosim * cosim;
+            let (em, emsq) = (ecco, emsq);
+            let eoc = em * emsq;
+            let g201 = -0.306 - (em - 0.64) * 0.440;
+            let (g211, g310, g322, g410, g422, g520);
+            if em <= 0.65 {
+                g211 = 3.616 - 13.2470 * em + 16.2900 * emsq;
+                g310 = -19.302 + 117.3900 * em - 228.4190 * emsq + 156.5910 * eoc;
+                g322 = -18.9068 + 109.7927 * em - 214.6334 * emsq + 146.5816 * eoc;
+                g410 = -41.122 + 242.6940 * em - 471.0940 * emsq + 313.9530 * eoc;
+                g422 = -146.407 + 841.8800 * em - 1629.014 * emsq + 1083.4350 * eoc;
+                g520 = -532.114 + 3017.977 * em - 5740.032 * emsq + 3708.2760 * eoc;
+            } else {
+                g211 = -72.099 + 331.819 * em - 508.738 * emsq + 266.724 * eoc;
+                g310 = -346.844 + 1582.851 * em - 2415.925 * emsq + 1246.113 * eoc;
+                g322 = -342.585 + 1554.908 * em - 2366.899 * emsq + 1215.972 * eoc;
+                g410 = -1052.797 + 4758.686 * em - 7193.992 * emsq + 3651.957 * eoc;
+                g422 = -3581.690 + 16178.110 * em - 24462.770 * emsq + 12422.520 * eoc;
+                g520 = if em > 0.715 {
+                    -5149.66 + 29936.92 * em - 54087.36 * emsq + 31324.56 * eoc
+                } else {
+                    1464.74 - 4664.75 * em + 3763.64 * emsq
+                };
+            }
+            let (g533, g521, g532);
+            if em < 0.7 {
+                g533 = -919.22770 + 4988.6100 * em - 9064.7700 * emsq + 5542.21 * eoc;
+                g521 = -822.71072 + 4568.6173 * em - 8491.4146 * emsq + 5337.524 * eoc;
+                g532 = -853.66600 + 4690.2500 * em - 8624.7700 * emsq + 5341.4 * eoc;
+            } else {
+                g533 = -37995.780 + 161616.52 * em - 229838.20 * emsq + 109377.94 * eoc;
+                g521 = -51752.104 + 218913.95 * em - 309468.16 * emsq + 146349.42 * eoc;
+                g532 = -40023.880 + 170470.89 * em - 242699.48 * emsq + 115605.82 * eoc;
+            }
+
+            let sini2 = sinim * sinim;
+            let f220 = 0.75 * (1.0 + 2.0 * cosim + cosisq);
+            let f221 = 1.5 * sini2;
+            let f321 = 1.875 * sinim * (1.0 - 2.0 * cosim - 3.0 * cosisq);
+            let f322 = -1.875 * sinim * (1.0 + 2.0 * cosim - 3.0 * cosisq);
+            let f441 = 35.0 * sini2 * f220;
+            let f442 = 39.3750 * sini2 * sini2;
+            let f522 = 9.84375
+                * sinim
+                * (sini2 * (1.0 - 2.0 * cosim - 5.0 * cosisq)
+                    + 0.333_333_33 * (-2.0 + 4.0 * cosim + 6.0 * cosisq));
+            let f523 = sinim
+                * (4.921_875_12 * sini2 * (-2.0 - 4.0 * cosim + 10.0 * cosisq)
+                    + 6.562_500_12 * (1.0 + 2.0 * cosim - 3.0 * cosisq));
+            let f542 = 29.53125
+                * sinim
+                * (2.0 - 8.0 * cosim + cosisq * (-12.0 + 8.0 * cosim + 10.0 * cosisq));
+            let f543 = 29.53125
+                * sinim
+                * (-2.0 - 8.0 * cosim + cosisq * (12.0 + 8.0 * cosim - 10.0 * cosisq));
+
+            let mut temp1 = 3.0 * no * no * aonv * aonv;
+            let mut temp = temp1 * 1.789_167_9e-6;
+            deep.d2201 = temp * f220 * g201;
+            deep.d2211 = temp * f221 * g211;
+            temp1 *= aonv;
+            temp = temp1 * 3.739_379_2e-7;
+            deep.d3210 = temp * f321 * g310;
+            deep.d3222 = temp * f322 * g322;
+            temp1 *= aonv;
+            temp = 2.0 * temp1 * 7.363_695_3e-9;
+            deep.d4410 = temp * f441 * g410;
+            deep.d4422 = temp * f442 * g422;
+            temp1 *= aonv;
+            temp = temp1 * 1.142_863_9e-7;
+            deep.d5220 = temp * f522 * g520;
+            deep.d5232 = temp * f523 * g532;
+            temp = 2.0 * temp1 * 2.176_580_3e-9;
+            deep.d5421 = temp * f542 * g521;
+            deep.d5433 = temp * f543 * g533;
+            deep.xlamo = (mo + nodeo + nodeo - theta - theta) % TAU;
+            deep.xfact = mdot + dmdt + 2.0 * (nodedot + dnodt - RPTIM) - no;
+        } else {
+            let g200 = 1.0 + emsq * (-2.5 + 0.8125 * emsq);
+            let g310 = 1.0 + 2.0 * emsq;
+            let g300 = 1.0 + emsq * (-6.0 + 6.60937 * emsq);
+            let f220 = 0.75 * (1.0 + cosim) * (1.0 + cosim);
+            let f311 = 0.9375 * sinim * sinim * (1.0 + 3.0 * cosim) - 0.75 * (1.0 + cosim);
+            let f330 = 1.875 * (1.0 + cosim).powi(3);
+            let del1 = 3.0 * no * no * aonv * aonv;
+            deep.del2 = 2.0 * del1 * f220 * g200 * 1.789_167_9e-6;
+            deep.del3 = 3.0 * del1 * f330 * g300 * 2.212_301_5e-7 * aonv;
+            deep.del1 = del1 * f311 * g310 * 2.146_074_8e-6 * aonv;
+            deep.xlamo = (mo + nodeo + argpo - theta) % TAU;
+            deep.xfact = mdot + xpidot - RPTIM + dmdt + domdt + dnodt - no;
+        }
+        deep
+    }
+
+    /// Lunar-solar secular drift and resonance integration (`dspace`) to `t` minutes.
+    ///
+    /// The resonance integrator always restarts from epoch, so results do not depend on
+    /// earlier calls; the reference caches its state but takes the same steps.
+    pub(super) fn apply_secular(
+        &self,
+        t: f64,
+        argpo: f64,
+        argpdot: f64,
+        no: f64,
+        state: &mut MeanState,
+    ) {
+        let theta = (self.gsto + t * RPTIM) % TAU;
+        state.em += self.dedt * t;
+        state.inclm += self.didt * t;
+        state.argpm += self.domdt * t;
+        state.nodem += self.dnodt * t;
+        state.mm += self.dmdt * t;
+        if self.resonance == Resonance::None {
+            return;
+        }
+
+        let mut atime = 0.0;
+        let mut xni = no;
+        let mut xli = self.xlamo;
+        let delt = if t > 0.0 { STEP } else { -STEP };
+        let (ft, xndt, xldot, xnddt) = loop {
+            let (xndt, xldot, xnddt) = self.resonance_rates(xli, xni, argpo + argpdot * atime);
+            if (t - atime).abs() < STEP {
+                break (t - atime, xndt, xldot, xnddt);
+            }
+            xli += xldot * delt + xndt * STEP * STEP / 2.0;
+            xni += xndt * delt + xnddt * STEP * STEP / 2.0;
+            atime += delt;
+        };
+
+        let nm = xni + xndt * ft + xnddt * ft * ft * 0.5;
+        let xl = xli + xldot * ft + xndt * ft * ft * 0.5;
+        state.mm = match self.resonance {
+            Resonance::Synchronous => xl - state.nodem - state.argpm + theta,
+            _ => xl - 2.0 * state.nodem + 2.0 * theta,
+        };
+        state.nm = nm;
+    }
+
+    /// Mean motion rate, longitude rate and mean motion acceleration of the resonance.
+    fn resonance_rates(&self, xli: f64, xni: f64, xomi: f64) -> (f64, f64, f64) {
+        const FASX2: f64 = 0.131_309_08;
+        const FASX4: f64 = 2.884_319_8;
+        const FASX6: f64 = 0.374_480_87;
+        const G22: f64 = 5.768_639_6;
+        const G32: f64 = 0.952_408_98;
+        const G44: f64 = 1.801_499_8;
+        const G52: f64 = 1.050_833_0;
+        const G54: f64 = 4.410_889_8;
+
+        let xldot = xni + self.xfact;
+        if self.resonance == Resonance::Synchronous {
+            let xndt = self.del1 * (xli - FASX2).sin()
+                + self.del2 * (2.0 * (xli - FASX4)).sin()
+                + self.del3 * (3.0 * (xli - FASX6)).sin();
+            let xnddt = self.del1 * (xli - FASX2).cos()
+                + 2.0 * self.del2 * (2.0 * (xli - FASX4)).cos()
+                + 3.0 * self.del3 * (3.0 * (xli - FASX6)).cos();
+            return (xndt, xldot, xnddt * xldot);
+        }
+        let x2omi = xomi + xomi;
+        let x2li = xli + xli;
+        let xndt = self.d2201 * (x2omi + xli - G22).sin()
+            + self.d2211 * (xli - G22).sin()
+            + self.d3210 * (xomi + xli - G32).sin()
+            + self.d3222 * (-xomi + xli - G32).sin()
+            + self.d4410 * (x2omi + x2li - G44).sin()
+            + self.d4422 * (x2li - G44).sin()
+            + self.d5220 * (xomi + xli - G52).sin()
+            + self.d5232 * (-xomi + xli - G52).sin()
+            + self.d5421 * (xomi + x2li - G54).sin()
+            + self.d5433 * (-xomi + x2li - G54).sin();
+        let xnddt = self.d2201 * (x2omi + xli - G22).cos()
+            + self.d2211 * (xli - G22).cos()
+            + self.d3210 * (xomi + xli - G32).cos()
+            + self.d3222 * (-xomi + xli - G32).cos()
+            + self.d5220 * (xomi + xli - G52).cos()
+            + self.d5232 * (-xomi + xli - G52).cos()
+            + 2.0
+                * (self.d4410 * (x2omi + x2li - G44).cos()
+                    + self.d4422 * (x2li - G44).cos()
+                    + self.d5421 * (xomi + x2li - G54).cos()
+                    + self.d5433 * (-xomi + x2li - G54).cos());
+        (xndt, xldot, xnddt * xldot)
+    }
+
+    /// Lunar-solar periodics (`dpper`) applied to the osculating elements at `t` minutes.
+    pub(super) fn apply_periodics(
+        &self,
+        t: f64,
+        ep: &mut f64,
+        inclp: &mut f64,
+        nodep: &mut f64,
+        argpp: &mut f64,
+        mp: &mut f64,
+    ) {
+        let terms = |zm: f64, ze: f64| {
+            let zf = zm + 2.0 * ze * zm.sin();
+            let sinzf = zf.sin();
+            (0.5 * sinzf * sinzf - 0.25, -0.5 * sinzf * zf.cos(), sinzf)
+        };
+        let (f2, f3, sinzf) = terms(self.zmos + ZNS * t, ZES);
+        let ses = self.se2 * f2 + self.se3 * f3;
+        let sis = self.si2 * f2 + self.si3 * f3;
+        let sls = self.sl2 * f2 + self.sl3 * f3 + self.sl4 * sinzf;
+        let sghs = self.sgh2 * f2 + self.sgh3 * f3 + self.sgh4 * sinzf;
+        let shs = self.sh2 * f2 + self.sh3 * f3;
+        let (f2, f3, sinzf) = terms(self.zmol + ZNL * t, ZEL);
+        let sel = self.ee2 * f2 + self.e3 * f3;
+        let sil = self.xi2 * f2 + self.xi3 * f3;
+        let sll = self.xl2 * f2 + self.xl3 * f3 + self.xl4 * sinzf;
+        let sghl = self.xgh2 * f2 + self.xgh3 * f3 + self.xgh4 * sinzf;
+        let shll = self.xh2 * f2 + self.xh3 * f3;
+
+        let pe = ses + sel;
+        let pinc = sis + sil;
+        let pl = sls + sll;
+        let mut pgh = sghs + sghl;
+        let mut ph = shs + shll;
+
+        *inclp += pinc;
+        *ep += pe;
+        let (sinip, cosip) = inclp.sin_cos();
+        if *inclp >= 0.2 {
+            ph /= sinip;
+            pgh -= cosip * ph;
+            *argpp += pgh;
+            *nodep += ph;
+            *mp += pl;
+        } else {
+            // Lyddane's modification for low inclinations.
+            let (sinop, cosop) = nodep.sin_cos();
+            let alfdp = sinip * sinop + (ph * cosop + pinc * cosip * sinop);
+            let betdp = sinip * cosop + (-ph * sinop + pinc * cosip * cosop);
+            *nodep %= TAU;
+            let xls = *mp + *argpp + cosip * *nodep + pl + pgh - pinc * *nodep * sinip;
+            let xnoh = *nodep;
+            *nodep = alfdp.atan2(betdp);
+            if (xnoh - *nodep).abs() > PI {
+                if *nodep < xnoh {
+                    *nodep += TAU;
+                } else {
+                    *nodep -= TAU;
+                }
+            }
+            *mp += pl;
+            *argpp = xls - *mp - cosip * *nodep;
+        }
+    }
+}
@@ -0,0 +1,278 @@
+//! Solar position, sunrise/sunset and the day/night terminator.
+
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use super::{ASTRONOMICAL_UNIT_M, CelestialPosition, julian_centuries_tt, mean_obliquity_deg};
+use crate::math::Geodetic;
+use crate::time::{Time, TimeSpan};
+
+/// Geometric solar elevation at sunrise and sunset: refraction plus the solar semi-diameter.
+pub const SUNRISE_ELEVATION_DEG: f64 = -0.833;
+pub const CIVIL_TWILIGHT_DEG: f64 = -6.0;
+pub const NAUTICAL_TWILIGHT_DEG: f64 = -12.0;
+pub const ASTRONOMICAL_TWILIGHT_DEG: f64 = -18.0;
+
+/// Sampling step for horizon crossings; crossings closer together than this may be missed.
+const CROSSING_STEP_S: f64 = 600.0;
+
+/// Apparent geocentric position of the Sun (Meeus ch. 25, about 0.01°).
+pub fn sun_position(t: Time) -> CelestialPosition {
+    let c = julian_centuries_tt(t);
+    let mean_lon = 280.466_46 + c * (36_000.769_83 + 0.000_303_2 * c);
+    let anomaly = (357.529_11 + c * (35_999.050_29 - 0.000_153_7 * c)).to_radians();
+    let e = 0.016_708_634 - c * (0.000_042_037 + 0.000_000_126_7 * c);
+    let center = (1.914_602 - c * (0.004_817 + 0.000_014 * c)) * anomaly.sin()
+        + (0.019_993 - 0.000_101 * c) * (2.0 * anomaly).sin()
+        + 0.000_289 * (3.0 * anomaly).sin();
+    let true_anomaly = anomaly + center.to_radians();
+    let distance_au = 1.000_001_018 * (1.0 - e * e) / (1.0 + e * true_anomaly.cos());
+
+    // Nutation and aberration, to apparent longitude and true obliquity.
+    let omega = (125.04 - 1_934.136 * c).to_radians();
+    let apparent_lon = mean_lon + center - 0.005_69 - 0.004_78 * omega.sin();
+    let obliquity = mean_obliquity_deg(c) + 0.002_56 * omega.cos();
+    CelestialPosition::from_ecliptic(
+        apparent_lon.to_radians(),
+        0.0,
+        obliquity.to_radians(),
+        distance_au * ASTRONOMICAL_UNIT_M,
+        t,
+    )
+}
+
+/// Point where the Sun is at the zenith.
+pub fn subsolar_point(t: Time) -> Geodetic {
+    sun_position(t).sub_point()
+}
+
+/// Geometric solar elevation (no refraction) seen from `observer`.
+pub fn solar_elevation_rad(observer: Geodetic, t: Time) -> f64 {
+    sun_position(t).horizontal(observer).elevation_rad
+}
+
+/// The Sun crossing an elevation threshold at `observer`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SunCrossing {
+    pub time: Time,
+    /// `true` when the Sun rises through the threshold.
+    pub rising: bool,
+}
+
+/// Every time within `span` that the Sun crosses `elevation_deg` at `observer`, in order.
+///
+/// Pass [`SUNRISE_ELEVATION_DEG`] for sunrise/sunset or a twilight constant. Samples every
+/// ten minutes and bisects to well under a second.
+pub fn sun_crossings(observer: Geodetic, span: TimeSpan, elevation_deg: f64) -> Vec<SunCrossing> {
+    let threshold = elevation_deg.to_radians();
+    let above = |t: f64| solar_elevation_rad(observer, Time(t)) - threshold;
+    let (start, end) = (span.start.0, span.end.0);
+    let mut out = Vec::new();
+    let mut t0 = start;
+    let mut f0 = above(t0);
+    while t0 < end {
+        let t1 = (t0 + CROSSING_STEP_S).min(end);
+        let f1 = above(t1);
+        if (f0 < 0.0) != (f1 < 0.0) {
+            let (mut lo, mut hi) = (t0, t1);
+            while hi - lo > 0.01 {
+                let mid = 0.5 * (lo + hi);
+                if (above(mid) < 0.0) == (f0 < 0.0) {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            out.push(SunCrossing {
+                time: Time(0.5 * (lo + hi)),
+                rising: f1 >= 0.0,
+            });
+        }
+        t0 = t1;
+        f0 = f1;
+    }
+    out
+}
+
+/// Sunrise and sunset within one 24 hour window.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SunTimes {
+    /// The Sun crosses the horizon; either event may fall outside the window.
+    Crossings {
+        sunrise: Option<Time>,
+        sunset: Option<Time>,
+    },
+    /// The Sun stays up for the whole window.
+    PolarDay,
+    /// The Sun stays down for the whole window.
+    PolarNight,
+}
+
+/// First sunrise and sunset in the 24 hours from `start`; pass local midnight for a civil
+/// day.
+pub fn sunrise_sunset(observer: Geodetic, start: Time) -> SunTimes {
+    let span = TimeSpan::new(start, Time(start.0 + 86_400.0));
+    let crossings = sun_crossings(observer, span, SUNRISE_ELEVATION_DEG);
+    if crossings.is_empty() {
+        let up = solar_elevation_rad(observer, start) > SUNRISE_ELEVATION_DEG.to_radians();
+        return if up {
+            SunTimes::PolarDay
+        } else {
+            SunTimes::PolarNight
+        };
+    }
+    let first = |rising: bool| {
+        crossings
+            .iter()
+            .find(|c| c.rising == rising)
+            .map(|c| c.time)
+    };
+    SunTimes::Crossings {
+        sunrise: first(true),
+        sunset: first(false),
+    }
+}
+
+/// Boundary of the region where the Sun is below `elevation_deg` at time `t`, as a
+/// `[lon, lat]` degree ring with `segments` edges along the curve.
+///
+/// `0.0` gives the geometric day/night terminator; the twilight constants give the
+/// twilight bands. Computed on a sphere, which moves the line by well under 0.2°.
+///
+/// When the night side contains a pole (almost always, for thresholds near 0°) the ring
+/// runs along the curve from −180° to 180° and closes through that pole, ready for
+/// lon/lat rendering. Otherwise it is a small circle around the antisolar point, with
+/// longitudes kept continuous (they may leave `[-180, 180]` across the antimeridian).
+pub fn night_ring(t: Time, elevation_deg: f64, segments: usize) -> Vec<[f64; 2]> {
+    let segments = segments.max(3);
+    let sun = subsolar_point(t);
+    let (declination, sun_lon) = (sun.lat_rad, sun.lon_rad);
+    let h = elevation_deg.to_radians();
+    // The night cap is centred on the antisolar point with this angular radius.
+    let radius = FRAC_PI_2 + h;
+    let anti_lat = -declination;
+    let anti_lon = sun_lon + PI;
+
+    if FRAC_PI_2 - anti_lat.abs() <= radius {
+        // Each meridian crosses the curve once: solve
+        // sin φ sin δ + cos φ cos δ cos Δλ = sin h for the latitude φ.
+        let pole = FRAC_PI_2.copysign(anti_lat);
+        let mut ring = Vec::with_capacity(segments + 3);
+        for i in 0..=segments {
+            let lon = -PI + 2.0 * PI * i as f64 / segments as f64;
+            let a = declination.sin();
+            let b = declination.cos() * (lon - sun_lon).cos();
+            let r = a.hypot(b);
+            let closest = a.atan2(b);
+            let offset = (h.sin() / r).clamp(-1.0, 1.0).acos();
+            let lat = if pole < 0.0 {
+                closest - offset
+            } else {
+                closest + offset
+            };
+            ring.push([
+                lon.to_degrees(),
+                lat.clamp(-FRAC_PI_2, FRAC_PI_2).to_degrees(),
+            ]);
+        }
+        ring.push([180.0, pole.to_degrees()]);
+        ring.push([-180.0, pole.to_degrees()]);
+        return ring;
+    }
+
+    let (sin_a, cos_a) = anti_lat.sin_cos();
+    let (sin_r, cos_r) = radius.sin_cos();
+    (0..segments)
+        .map(|i| {
+            let bearing = 2.0 * PI * i as f64 / segments as f64;
+            let lat = (sin_a * cos_r + cos_a * sin_r * bearing.cos()).asin();
+            let dlon = (bearing.sin() * sin_r * cos_a).atan2(cos_r - sin_a * lat.sin());
+            [(anti_lon + dlon).to_degrees(), lat.to_degrees()]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        SUNRISE_ELEVATION_DEG, SunTimes, night_ring, solar_elevation_rad, subsolar_point,
+        sun_position, sunrise_sunset,
+    };
+    use crate::math::Geodetic;
+    use crate::time::Time;
+
+    fn assert_close(a: f64, b: f64, eps: f64) {
+        assert!((a - b).abs() <= eps, "{a} vs {b} (eps {eps})");
+    }
+
+    fn time(s: &str) -> Time {
+        Time::parse_iso8601(s).unwrap()
+    }
+
+    #[test]
+    fn matches_meeus_example() {
+        // Meeus example 25.a, 1992-10-13 0h TD: α = 13h13m31.4s, δ = −7°47'01".
+        let sun = sun_position(time("1992-10-12T23:59:01Z"));
+        assert_close(sun.right_ascension_rad.to_degrees(), 198.380_83, 0.01);
+        assert_close(sun.declination_rad.to_degrees(), -7.783_61, 0.01);
+        assert_close(sun.distance_m / super::ASTRONOMICAL_UNIT_M, 0.997_66, 1e-4);
+    }
+
+    #[test]
+    fn subsolar_point_tracks_noon_and_seasons() {
+        // Near the June solstice the Sun is over the Tropic of Cancer, and at 12:00 UTC
+        // close to the prime meridian (within the equation of time).
+        let june = subsolar_point(time("2024-06-20T12:00:00Z"));
+        assert_close(june.lat_deg(), 23.44, 0.05);
+        assert_close(june.lon_deg(), 0.0, 1.0);
+        let elevation = solar_elevation_rad(june, time("2024-06-20T12:00:00Z"));
+        assert_close(elevation.to_degrees(), 90.0, 0.01);
+    }
+
+    #[test]
+    fn sunrise_sunset_and_polar_days() {
+        // Greenwich on the March equinox: about 06:04 and 18:14 UTC.
+        let greenwich = Geodetic::from_degrees(51.4769, 0.0, 0.0);
+        let SunTimes::Crossings {
+            sunrise: Some(rise),
+            sunset: Some(set),
+        } = sunrise_sunset(greenwich, time("2024-03-20T00:00:00Z"))
+        else {
+            panic!("expected sunrise and sunset");
+        };
+        assert_close(rise.0, time("2024-03-20T06:03:00Z").0, 180.0);
+        assert_close(set.0, time("2024-03-20T18:14:00Z").0, 180.0);
+        assert_close(
+            solar_elevation_rad(greenwich, rise).to_degrees(),
+            SUNRISE_ELEVATION_DEG,
+            1e-4,
+        );
+
+        let svalbard = Geodetic::from_degrees(78.22, 15.65, 0.0);
+        let june = time("2024-06-21T00:00:00Z");
+        assert_eq!(sunrise_sunset(svalbard, june), SunTimes::PolarDay);
+        let december = time("2024-12-21T00:00:00Z");
+        assert_eq!(sunrise_sunset(svalbard, december), SunTimes::PolarNight);
+    }
+
+    #[test]
+    fn night_ring_follows_the_terminator() {
+        let t = time("2024-06-20T12:00:00Z");
+        let ring = night_ring(t, 0.0, 72);
+        // Northern summer: the night side holds the south pole.
+        assert_eq!(ring.len(), 75);
+        assert_eq!(ring[74], [-180.0, -90.0]);
+        for &[lon, lat] in &ring[..=72] {
+            let geo = Geodetic::from_degrees(lat, lon, 0.0);
+            assert_close(solar_elevation_rad(geo, t).to_degrees(), 0.0, 0.2);
+        }
+
+        // Deep twilight at the equinox: a small circle that misses both poles.
+        let equinox = time("2024-03-20T03:06:00Z");
+        let twilight = night_ring(equinox, -18.0, 36);
+        assert_eq!(twilight.len(), 36);
+        for &[lon, lat] in &twilight {
+            let geo = Geodetic::from_degrees(lat, lon, 0.0);
+            assert_close(solar_elevation_rad(geo, equinox).to_degrees(), -18.0, 0.2);
+        }
+    }
+}
@@ -0,0 +1,340 @@
+//! NORAD two-line element sets.
+
+use std::f64::consts::TAU;
+use std::fmt;
+
+use crate::time::{CivilDateTime, Time};
+
+/// One parsed two-line element set, in the units printed on the card.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tle {
+    /// Title line of a three-line set; empty when there is none.
+    pub name: String,
+    /// Catalog number; Alpha-5 numbers (`A0000`–`Z9999`) map to 100000–339999.
+    pub norad_id: u32,
+    pub classification: char,
+    pub international_designator: String,
+    pub epoch: Time,
+    /// First derivative of mean motion divided by two (rev/day²).
+    pub mean_motion_dot: f64,
+    /// Second derivative of mean motion divided by six (rev/day³).
+    pub mean_motion_ddot: f64,
+    /// Drag term (1/Earth radii).
+    pub bstar: f64,
+    pub element_set: u32,
+    pub inclination_deg: f64,
+    pub raan_deg: f64,
+    pub eccentricity: f64,
+    pub arg_perigee_deg: f64,
+    pub mean_anomaly_deg: f64,
+    /// Kozai mean motion (rev/day).
+    pub mean_motion_rev_per_day: f64,
+    pub revolution_number: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TleError {
+    /// A line is shorter than the 69 columns of the format.
+    LineLength {
+        line: u8,
+        len: usize,
+    },
+    /// Column 1 is not the expected line number.
+    LineNumber {
+        line: u8,
+    },
+    Checksum {
+        line: u8,
+        expected: u8,
+        found: u8,
+    },
+    /// The two lines carry different catalog numbers.
+    SatelliteMismatch,
+    InvalidField {
+        line: u8,
+        field: &'static str,
+    },
+    /// A multi-satellite text ended in the middle of a set.
+    Incomplete,
+}
+
+impl fmt::Display for TleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TleError::LineLength { line, len } => {
+                write!(f, "TLE line {line} has {len} columns, expected 69")
+            }
+            TleError::LineNumber { line } => {
+                write!(f, "TLE line {line} does not start with {line}")
+            }
+            TleError::Checksum {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "TLE line {line} checksum is {found}, expected {expected}"
+            ),
+            TleError::SatelliteMismatch => write!(f, "TLE lines have different catalog numbers"),
+            TleError::InvalidField { line, field } => {
+                write!(f, "invalid {field} on TLE line {line}")
+            }
+            TleError::Incomplete => write!(f, "incomplete TLE set"),
+        }
+    }
+}
+
+impl std::error::Error for TleError {}
+
+impl Tle {
+    /// Parses lines 1 and 2 of an element set, verifying both checksums.
+    pub fn parse(line1: &str, line2: &str) -> Result<Self, TleError> {
+        let l1 = card(line1, 1)?;
+        let l2 = card(line2, 2)?;
+
+        let norad_id = catalog_number(field(l1, 3, 7), 1)?;
+        if catalog_number(field(l2, 3, 7), 2)? != norad_id {
+            return Err(TleError::SatelliteMismatch);
+        }
+
+        let year: i64 = number(l1, 1, 19, 20, "epoch year")?;
+        let day: f64 = number(l1, 1, 21, 32, "epoch day")?;
+        // Two-digit years 57–99 are 1957–1999 (Sputnik 1 is the first catalogued object).
+        let year = if year < 57 { 2000 + year } else { 1900 + year };
+        let epoch = Time(CivilDateTime::from_date(year, 1, 1).to_time().0 + (day - 1.0) * 86_400.0);
+
+        Ok(Self {
+            name: String::new(),
+            norad_id,
+            classification: l1[7..8].chars().next().unwrap_or('U'),
+            international_designator: field(l1, 10, 17).to_string(),
+            epoch,
+            mean_motion_dot: number(l1, 1, 34, 43, "mean motion derivative")?,
+            mean_motion_ddot: implied_decimal(field(l1, 45, 52)).ok_or(TleError::InvalidField {
+                line: 1,
+                field: "mean motion second derivative",
+            })?,
+            bstar: implied_decimal(field(l1, 54, 61)).ok_or(TleError::InvalidField {
+                line: 1,
+                field: "BSTAR",
+            })?,
+            element_set: number_or_zero(l1, 1, 65, 68, "element set number")?,
+            inclination_deg: number(l2, 2, 9, 16, "inclination")?,
+            raan_deg: number(l2, 2, 18, 25, "right ascension of the ascending node")?,
+            eccentricity: format!("0.{}", field(l2, 27, 33)).parse().map_err(|_| {
+                TleError::InvalidField {
+                    line: 2,
+                    field: "eccentricity",
+                }
+            })?,
+            arg_perigee_deg: number(l2, 2, 35, 42, "argument of perigee")?,
+            mean_anomaly_deg: number(l2, 2, 44, 51, "mean anomaly")?,
+            mean_motion_rev_per_day: number(l2, 2, 53, 63, "mean motion")?,
+            revolution_number: number_or_zero(l2, 2, 64, 68, "revolution number")?,
+        })
+    }
+
+    /// Parses a three-line set: a title line followed by lines 1 and 2.
+    pub fn parse_named(name: &str, line1: &str, line2: &str) -> Result<Self, TleError> {
+        let mut tle = Self::parse(line1, line2)?;
+        let name = name.trim();
+        tle.name = name.strip_prefix("0 ").unwrap_or(name).trim().to_string();
+        Ok(tle)
+    }
+
+    /// Orbital period from the Kozai mean motion (seconds).
+    pub fn period_s(&self) -> f64 {
+        86_400.0 / self.mean_motion_rev_per_day
+    }
+
+    /// Mean motion in radians per minute, the unit SGP4 works in.
+    pub(crate) fn mean_motion_rad_per_min(&self) -> f64 {
+        self.mean_motion_rev_per_day * TAU / 1440.0
+    }
+}
+
+/// Parses a catalogue text of two- or three-line element sets (as published by CelesTrak
+/// or Space-Track), skipping blank lines.
+pub fn parse_tles(text: &str) -> Result<Vec<Tle>, TleError> {
+    let mut out = Vec::new();
+    let mut name: Option<&str> = None;
+    let mut lines = text
+        .lines()
+        .map(str::trim_end)
+        .filter(|l| !l.trim().is_empty());
+    while let Some(line) = lines.next() {
+        if line.starts_with("1 ") {
+            let line2 = lines.next().ok_or(TleError::Incomplete)?;
+            out.push(match name.take() {
+                Some(name) => Tle::parse_named(name, line, line2)?,
+                None => Tle::parse(line, line2)?,
+            });
+        } else if name.replace(line).is_some() {
+            return Err(TleError::Incomplete);
+        }
+    }
+    if name.is_some() {
+        return Err(TleError::Incomplete);
+    }
+    Ok(out)
+}
+
+/// Modulo-10 checksum over the first 68 columns: digits count their value, `-` counts one.
+pub fn tle_checksum(line: &str) -> u8 {
+    let sum: u32 = line
+        .bytes()
+        .take(68)
+        .map(|b| match b {
+            b'0'..=b'9' => (b - b'0') as u32,
+            b'-' => 1,
+            _ => 0,
+        })
+        .sum();
+    (sum % 10) as u8
+}
+
+fn card(line: &str, number: u8) -> Result<&str, TleError> {
+    let line = line.trim_end();
+    if !line.is_ascii() || line.len() < 69 {
+        return Err(TleError::LineLength {
+            line: number,
+            len: line.chars().count(),
+        });
+    }
+    if line.as_bytes()[0] != b'0' + number {
+        return Err(TleError::LineNumber { line: number });
+    }
+    let found = line.as_bytes()[68].wrapping_sub(b'0');
+    let expected = tle_checksum(line);
+    if found != expected {
+        return Err(TleError::Checksum {
+            line: number,
+            expected,
+            found,
+        });
+    }
+    Ok(line)
+}
+
+/// Columns `first..=last`, 1-based as in the format definition, trimmed.
+fn field(line: &str, first: usize, last: usize) -> &str {
+    line[first - 1..last].trim()
+}
+
+fn number<T: std::str::FromStr>(
+    line: &str,
+    number: u8,
+    first: usize,
+    last: usize,
+    name: &'static str,
+) -> Result<T, TleError> {
+    field(line, first, last)
+        .parse()
+        .map_err(|_| TleError::InvalidField {
+            line: number,
+            field: name,
+        })
+}
+
+/// Like [`number`], but an all-blank field (common for element set and revolution
+/// numbers) is zero.
+fn number_or_zero(
+    line: &str,
+    line_number: u8,
+    first: usize,
+    last: usize,
+    name: &'static str,
+) -> Result<u32, TleError> {
+    if field(line, first, last).is_empty() {
+        return Ok(0);
+    }
+    number(line, line_number, first, last, name)
+}
+
+/// Catalog number, including the Alpha-5 extension where a leading letter (skipping `I`
+/// and `O`) stands for 10–33 ten-thousands.
+fn catalog_number(text: &str, line: u8) -> Result<u32, TleError> {
+    let invalid = TleError::InvalidField {
+        line,
+        field: "catalog number",
+    };
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(c @ 'A'..='Z') if c != 'I' && c != 'O' && text.len() == 5 => {
+            let rest: u32 = chars.as_str().parse().map_err(|_| invalid)?;
+            let skipped = (c > 'I') as u32 + (c > 'O') as u32;
+            Ok((c as u32 - 'A' as u32 + 10 - skipped) * 10_000 + rest)
+        }
+        _ => text.parse().map_err(|_| invalid),
+    }
+}
+
+/// Decodes the exponent fields with an assumed leading decimal point: ` 12345-3` is
+/// `0.12345e-3`.
+fn implied_decimal(text: &str) -> Option<f64> {
+    if text.is_empty() {
+        return Some(0.0);
+    }
+    let (negative, rest) = match text.as_bytes()[0] {
+        b'-' => (true, &text[1..]),
+        b'+' => (false, &text[1..]),
+        _ => (false, text),
+    };
+    let split = rest.len().checked_sub(2)?;
+    let (mantissa, exponent) = rest.split_at(split);
+    let exponent: i32 = exponent.trim_start_matches('+').parse().ok()?;
+    if !mantissa.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let value = format!("0.{mantissa}").parse::<f64>().ok()? * 10f64.powi(exponent);
+    Some(if negative { -value } else { value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Tle, TleError, parse_tles, tle_checksum};
+    use crate::time::Time;
+
+    const ISS: &str = "ISS (ZARYA)
+1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927
+2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537
+";
+
+    #[test]
+    fn parses_iss_elements() {
+        let tles = parse_tles(ISS).unwrap();
+        assert_eq!(tles.len(), 1);
+        let iss = &tles[0];
+        assert_eq!(iss.name, "ISS (ZARYA)");
+        assert_eq!(iss.norad_id, 25544);
+        assert_eq!(iss.international_designator, "98067A");
+        let epoch = Time::parse_iso8601("2008-09-20T12:25:40.104192Z").unwrap();
+        assert!((iss.epoch.0 - epoch.0).abs() < 1e-3);
+        assert_eq!(iss.mean_motion_dot, -0.000_021_82);
+        assert!((iss.bstar - -0.11606e-4).abs() < 1e-15);
+        assert_eq!(iss.eccentricity, 0.000_670_3);
+        assert_eq!(iss.mean_motion_rev_per_day, 15.721_253_91);
+        assert_eq!(iss.revolution_number, 56353);
+        assert_eq!(iss.element_set, 292);
+    }
+
+    #[test]
+    fn rejects_damaged_lines() {
+        let lines: Vec<&str> = ISS.lines().collect();
+        let bad = lines[1].replace("2927", "2928");
+        assert_eq!(
+            Tle::parse(&bad, lines[2]),
+            Err(TleError::Checksum {
+                line: 1,
+                expected: 7,
+                found: 8
+            })
+        );
+        assert_eq!(tle_checksum(lines[2]), 7);
+        assert!(matches!(
+            Tle::parse(lines[2], lines[1]),
+            Err(TleError::LineNumber { line: 1 })
+        ));
+        assert_eq!(parse_tles(lines[1]), Err(TleError::Incomplete));
+    }
+}
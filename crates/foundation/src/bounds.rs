@@ -59,9 +59,260 @@ impl Aabb3 {
     }
 }
 
+/// Longitude wrapped into `[-180, 180)` degrees.
+pub fn wrap_lon_deg(lon: f64) -> f64 {
+    let wrapped = (lon + 180.0).rem_euclid(360.0) - 180.0;
+    // rem_euclid can round up to exactly 360 for tiny negative inputs.
+    if wrapped >= 180.0 { -180.0 } else { wrapped }
+}
+
+/// The longitude equivalent to `lon` that lies within 180° of `reference`.
+///
+/// Applied vertex by vertex this unwraps a path across the antimeridian into a
+/// continuous longitude range (which may leave `[-180, 180]`).
+pub fn unwrap_lon_deg(reference: f64, lon: f64) -> f64 {
+    reference + wrap_lon_deg(lon - reference)
+}
+
+/// Geographic bounds in degrees that may wrap across the antimeridian.
+///
+/// `west > east` means the box crosses ±180° (the GeoJSON bbox convention); a box
+/// covering every longitude is `west = -180, east = 180`. Longitude is meaningless at
+/// the poles, so a box reaching ±90° contains the pole whatever its longitude range.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GeoBounds {
+    pub west: f64,
+    pub south: f64,
+    pub east: f64,
+    pub north: f64,
+}
+
+impl GeoBounds {
+    pub const WORLD: GeoBounds = GeoBounds {
+        west: -180.0,
+        south: -90.0,
+        east: 180.0,
+        north: 90.0,
+    };
+
+    /// Bounds from edges; `west > east` selects the arc through the antimeridian.
+    pub fn new(west: f64, south: f64, east: f64, north: f64) -> Self {
+        let span = if east >= west {
+            east - west
+        } else {
+            (east - west).rem_euclid(360.0)
+        };
+        Self::from_arc(west, span, south, north)
+    }
+
+    pub fn from_point(lon: f64, lat: f64) -> Self {
+        let lat = lat.clamp(-90.0, 90.0);
+        let span = if lat.abs() >= 90.0 { 360.0 } else { 0.0 };
+        Self::from_arc(lon, span, lat, lat)
+    }
+
+    /// Tightest bounds of `[lon, lat]` points, or `None` when there are no finite points.
+    ///
+    /// The longitude range is the complement of the widest gap between the points, so
+    /// a cluster straddling ±180° gets a narrow crossing box instead of a world-wide one.
+    /// Points at a pole only extend the latitude range.
+    pub fn from_points<I: IntoIterator<Item = [f64; 2]>>(points: I) -> Option<Self> {
+        let mut lons = Vec::new();
+        let mut south = f64::INFINITY;
+        let mut north = f64::NEG_INFINITY;
+        for [lon, lat] in points {
+            if !lon.is_finite() || !lat.is_finite() {
+                continue;
+            }
+            let lat = lat.clamp(-90.0, 90.0);
+            south = south.min(lat);
+            north = north.max(lat);
+            if lat.abs() < 90.0 {
+                lons.push(wrap_lon_deg(lon));
+            }
+        }
+        if south > north {
+            return None;
+        }
+        if lons.is_empty() {
+            return Some(Self::from_arc(-180.0, 360.0, south, north));
+        }
+        lons.sort_by(f64::total_cmp);
+        let mut gap = lons[0] + 360.0 - lons[lons.len() - 1];
+        let mut west = lons[0];
+        for pair in lons.windows(2) {
+            if pair[1] - pair[0] > gap {
+                gap = pair[1] - pair[0];
+                west = pair[1];
+            }
+        }
+        Some(Self::from_arc(west, 360.0 - gap, south, north))
+    }
+
+    /// Bounds of a closed polygon ring (closing vertex optional).
+    ///
+    /// A ring that winds once around a pole (e.g. Antarctica) encloses it: the result
+    /// spans all longitudes and extends to that pole.
+    pub fn from_ring(ring: &[[f64; 2]]) -> Option<Self> {
+        let mut bounds = Self::from_points(ring.iter().copied())?;
+        let winding: f64 = ring
+            .iter()
+            .zip(ring.iter().cycle().skip(1))
+            .filter(|(a, b)| a[1].abs() < 90.0 && b[1].abs() < 90.0)
+            .map(|(a, b)| wrap_lon_deg(b[0] - a[0]))
+            .sum();
+        if winding.abs() > 180.0 {
+            bounds.west = -180.0;
+            bounds.east = 180.0;
+            if bounds.south + bounds.north < 0.0 {
+                bounds.south = -90.0;
+            } else {
+                bounds.north = 90.0;
+            }
+        }
+        Some(bounds)
+    }
+
+    fn from_arc(west: f64, span: f64, south: f64, north: f64) -> Self {
+        let south = south.clamp(-90.0, 90.0);
+        let north = north.clamp(-90.0, 90.0);
+        if span >= 360.0 {
+            return Self {
+                west: -180.0,
+                south,
+                east: 180.0,
+                north,
+            };
+        }
+        let west = wrap_lon_deg(west);
+        let mut east = west + span;
+        if east > 180.0 {
+            east -= 360.0;
+        }
+        Self {
+            west,
+            south,
+            east,
+            north,
+        }
+    }
+
+    /// Eastward longitude extent in degrees, in `[0, 360]`.
+    pub fn lon_span(&self) -> f64 {
+        if self.west <= self.east {
+            self.east - self.west
+        } else {
+            self.east - self.west + 360.0
+        }
+    }
+
+    pub fn crosses_antimeridian(&self) -> bool {
+        self.west > self.east
+    }
+
+    pub fn is_full_lon(&self) -> bool {
+        self.lon_span() >= 360.0
+    }
+
+    fn arc(&self) -> (f64, f64) {
+        (self.west, self.lon_span())
+    }
+
+    pub fn contains_point(&self, lon: f64, lat: f64) -> bool {
+        if lat < self.south || lat > self.north {
+            return false;
+        }
+        lat.abs() >= 90.0 || (lon - self.west).rem_euclid(360.0) <= self.lon_span()
+    }
+
+    pub fn contains(&self, other: &Self) -> bool {
+        if other.south < self.south || other.north > self.north {
+            return false;
+        }
+        if self.is_full_lon() || other.south >= 90.0 || other.north <= -90.0 {
+            return true;
+        }
+        (other.west - self.west).rem_euclid(360.0) + other.lon_span() <= self.lon_span()
+    }
+
+    /// Smallest bounds containing both.
+    pub fn union(&self, other: &Self) -> Self {
+        let (west, span) = union_arc(self.arc(), other.arc());
+        Self::from_arc(
+            west,
+            span,
+            self.south.min(other.south),
+            self.north.max(other.north),
+        )
+    }
+
+    pub fn expand_to_include(&mut self, lon: f64, lat: f64) {
+        *self = self.union(&Self::from_point(lon, lat));
+    }
+
+    /// Overlap of the two boxes (touching edges count), or `None` when disjoint.
+    ///
+    /// Two boxes that each wrap most of the way round can overlap in two separate
+    /// longitude pieces; the result then covers both pieces.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let south = self.south.max(other.south);
+        let north = self.north.min(other.north);
+        if south > north {
+            return None;
+        }
+        let (a, b) = (self.arc(), other.arc());
+        let (west, span) = if a.1 >= 360.0 {
+            b
+        } else if b.1 >= 360.0 {
+            a
+        } else {
+            let d_ab = (b.0 - a.0).rem_euclid(360.0);
+            let d_ba = (a.0 - b.0).rem_euclid(360.0);
+            let piece_b = (d_ab <= a.1).then(|| (b.0, b.1.min(a.1 - d_ab)));
+            let piece_a = (d_ba <= b.1).then(|| (a.0, a.1.min(b.1 - d_ba)));
+            match (piece_a, piece_b) {
+                (Some(p), Some(q)) => union_arc(p, q),
+                (Some(p), None) | (None, Some(p)) => p,
+                // Disjoint longitudes still meet at a shared pole.
+                (None, None) if north >= 90.0 => return Some(Self::from_point(0.0, 90.0)),
+                (None, None) if south <= -90.0 => return Some(Self::from_point(0.0, -90.0)),
+                (None, None) => return None,
+            }
+        };
+        Some(Self::from_arc(west, span, south, north))
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Split into one plain box, or two when crossing the antimeridian (`x` = lon, `y` = lat).
+    pub fn to_aabb2s(&self) -> Vec<Aabb2> {
+        if self.crosses_antimeridian() {
+            vec![
+                Aabb2::new([self.west, self.south], [180.0, self.north]),
+                Aabb2::new([-180.0, self.south], [self.east, self.north]),
+            ]
+        } else {
+            vec![Aabb2::new([self.west, self.south], [self.east, self.north])]
+        }
+    }
+}
+
+/// Shortest arc `(west, span)` covering both arcs; `span >= 360` means all longitudes.
+fn union_arc(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    let from_a = a.1.max((b.0 - a.0).rem_euclid(360.0) + b.1);
+    let from_b = b.1.max((a.0 - b.0).rem_euclid(360.0) + a.1);
+    if from_a <= from_b {
+        (a.0, from_a)
+    } else {
+        (b.0, from_b)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{Aabb2, Aabb3};
+    use super::{Aabb2, Aabb3, GeoBounds, unwrap_lon_deg, wrap_lon_deg};
 
     #[test]
     fn aabb3_contains_and_intersects() {
@@ -86,4 +337,75 @@ mod tests {
         assert!(a.intersects(&b)); // touching counts
         assert!(!a.intersects(&c));
     }
+
+    #[test]
+    fn geo_bounds_from_points_crosses_antimeridian() {
+        let b = GeoBounds::from_points([[179.0, 1.0], [-179.0, 2.0], [178.5, -1.0]]).unwrap();
+        assert_eq!((b.west, b.east), (178.5, -179.0));
+        assert!(b.crosses_antimeridian());
+        assert!((b.lon_span() - 2.5).abs() < 1e-12);
+        assert!(b.contains_point(180.0, 0.0) && b.contains_point(-179.5, 0.0));
+        assert!(!b.contains_point(0.0, 0.0));
+        let boxes = b.to_aabb2s();
+        assert_eq!(boxes.len(), 2);
+        assert_eq!(boxes[1], Aabb2::new([-180.0, -1.0], [-179.0, 2.0]));
+        assert!(GeoBounds::from_points([]).is_none());
+    }
+
+    #[test]
+    fn geo_bounds_union_intersection_and_containment_wrap() {
+        let east = GeoBounds::new(170.0, 0.0, 180.0, 10.0);
+        let west = GeoBounds::new(-180.0, 5.0, -170.0, 15.0);
+        let u = east.union(&west);
+        assert_eq!(u, GeoBounds::new(170.0, 0.0, -170.0, 15.0));
+        assert!(u.contains(&east) && u.contains(&west) && !east.contains(&u));
+
+        let i = u
+            .intersection(&GeoBounds::new(-175.0, -5.0, 20.0, 5.0))
+            .unwrap();
+        assert_eq!(i, GeoBounds::new(-175.0, 0.0, -170.0, 5.0));
+        assert!(!east.intersects(&GeoBounds::new(-10.0, 0.0, 10.0, 10.0)));
+        // Touching at the antimeridian counts as intersecting.
+        assert!(east.intersects(&west));
+
+        // Two arcs that together cover the globe union to all longitudes.
+        let big = GeoBounds::new(-100.0, 0.0, 120.0, 1.0);
+        let wrap = GeoBounds::new(100.0, 0.0, -90.0, 1.0);
+        assert!(big.union(&wrap).is_full_lon());
+        // ...and overlap in two pieces, both covered by the result.
+        let both = big.intersection(&wrap).unwrap();
+        assert!(both.contains_point(110.0, 0.5) && both.contains_point(-95.0, 0.5));
+    }
+
+    #[test]
+    fn geo_bounds_poles() {
+        let ring = [
+            [-180.0, -70.0],
+            [-60.0, -75.0],
+            [60.0, -72.0],
+            [180.0, -70.0],
+        ];
+        let b = GeoBounds::from_ring(&ring).unwrap();
+        assert!(b.is_full_lon());
+        assert_eq!((b.south, b.north), (-90.0, -70.0));
+
+        let cap_a = GeoBounds::new(0.0, 80.0, 10.0, 90.0);
+        let cap_b = GeoBounds::new(100.0, 85.0, 110.0, 90.0);
+        assert!(cap_a.intersects(&cap_b));
+        assert!(cap_a.contains_point(-120.0, 90.0));
+        assert!(GeoBounds::from_point(45.0, 90.0).is_full_lon());
+        assert_eq!(
+            GeoBounds::from_points([[10.0, 90.0], [20.0, 60.0]]).unwrap(),
+            GeoBounds::new(20.0, 60.0, 20.0, 90.0)
+        );
+    }
+
+    #[test]
+    fn lon_wrapping() {
+        assert_eq!(wrap_lon_deg(180.0), -180.0);
+        assert_eq!(wrap_lon_deg(-190.0), 170.0);
+        assert_eq!(wrap_lon_deg(-180.0 - 1e-14), -180.0);
+        assert_eq!(unwrap_lon_deg(179.0, -179.0), 181.0);
+        assert_eq!(unwrap_lon_deg(-179.0, 179.0), -181.0);
+    }
 }
@@ -33,6 +33,9 @@ impl Handle {
 #[derive(Debug, Default)]
 pub struct HandleAllocator {
     generations: Vec<u32>,
+    /// Whether each slot is currently allocated; a freed slot's bumped generation must
+    /// not validate before it is handed out again.
+    live: Vec<bool>,
     free: Vec<u32>,
 }
 
@@ -45,11 +48,13 @@ impl HandleAllocator {
     pub fn alloc(&mut self) -> Handle {
         if let Some(index) = self.free.pop() {
             let generation = self.generations[index as usize];
+            self.live[index as usize] = true;
             return Handle::new(index, generation);
         }
 
         let index = self.generations.len() as u32;
         self.generations.push(0);
+        self.live.push(true);
         Handle::new(index, 0)
     }
 
@@ -58,7 +63,7 @@ impl HandleAllocator {
         let Some(&generation) = self.generations.get(h.index() as usize) else {
             return false;
         };
-        generation == h.generation()
+        generation == h.generation() && self.live[h.index() as usize]
     }
 
     /// Free a handle. Returns `true` if it was valid and is now freed.
@@ -69,15 +74,22 @@ impl HandleAllocator {
         let Some(generation) = self.generations.get_mut(idx) else {
             return false;
         };
-        if *generation != h.generation() {
+        if *generation != h.generation() || !self.live[idx] {
             return false;
         }
 
         *generation = generation.wrapping_add(1);
+        self.live[idx] = false;
         self.free.push(h.index());
         true
     }
 
+    /// Current generation of slot `index`: the live handle's generation, or the one the
+    /// next allocation of a freed slot will get.
+    pub fn generation(&self, index: u32) -> Option<u32> {
+        self.generations.get(index as usize).copied()
+    }
+
     pub fn capacity(&self) -> usize {
         self.generations.len()
     }
@@ -114,11 +126,14 @@ mod tests {
         let h0 = a.alloc();
         assert!(a.free(h0));
         assert!(!a.is_valid(h0));
+        // The next generation is not valid until it is allocated.
+        assert!(!a.is_valid(Handle::new(h0.index(), h0.generation() + 1)));
 
         let h0b = a.alloc();
         assert_eq!(h0b.index(), h0.index());
         assert_ne!(h0b.generation(), h0.generation());
         assert!(a.is_valid(h0b));
+        assert_eq!(a.generation(h0.index()), Some(h0b.generation()));
     }
 
     #[test]
@@ -1,4 +1,5 @@
 pub mod arena;
+pub mod astro;
 pub mod bounds;
 pub mod handles;
 pub mod ids;
@@ -7,6 +8,7 @@ pub mod time;
 
 // Foundation crate: small, well-tested primitives only.
 pub use arena::*;
+pub use astro::*;
 pub use bounds::*;
 pub use handles::*;
 pub use ids::*;
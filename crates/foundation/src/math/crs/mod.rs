@@ -0,0 +1,176 @@
+//! Coordinate reference systems.
+//!
+//! A `Crs` couples a geodetic datum (ellipsoid + Helmert shift to WGS84) with
+//! a coordinate kind (geographic, geocentric or projected). `CrsRegistry`
+//! resolves EPSG codes and `CrsTransform` converts between any two CRSs via
+//! WGS84 ECEF.
+//!
+//! Coordinates are exchanged as `Vec3` in the CRS's native axis order:
+//! - geographic: `(lon_deg, lat_deg, height_m)` (GeoJSON order, regardless of
+//!   the EPSG-defined axis order)
+//! - geocentric: `(x, y, z)` meters
+//! - projected: `(easting, northing, height_m)`
+
+pub mod registry;
+pub mod transform;
+
+pub use registry::*;
+pub use transform::*;
+
+use super::projection::{
+    Equirectangular, LambertAzimuthalEqualArea, PolarStereographic, Projection, TransverseMercator,
+    WebMercator,
+};
+use super::{Ecef, Ellipsoid, Geodetic, Helmert7, Vec2, Vec3};
+
+/// EPSG code of WGS 84 geographic 2D coordinates.
+pub const EPSG_WGS84: u32 = 4326;
+
+/// Geodetic datum: reference ellipsoid plus the shift into WGS84.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Datum {
+    pub name: String,
+    pub ellipsoid: Ellipsoid,
+    /// Shift from this datum's ECEF frame to WGS84 ECEF.
+    pub to_wgs84: Helmert7,
+}
+
+impl Datum {
+    pub fn new(name: impl Into<String>, ellipsoid: Ellipsoid, to_wgs84: Helmert7) -> Self {
+        Self {
+            name: name.into(),
+            ellipsoid,
+            to_wgs84,
+        }
+    }
+
+    pub fn wgs84() -> Self {
+        Self::new("WGS 84", Ellipsoid::WGS84, Helmert7::IDENTITY)
+    }
+
+    /// True when both datums share the same ECEF frame and ellipsoid.
+    pub fn same_frame(&self, other: &Datum) -> bool {
+        self.ellipsoid == other.ellipsoid && self.to_wgs84 == other.to_wgs84
+    }
+}
+
+/// Projection attached to a projected CRS.
+///
+/// `TransverseMercator` carries its own ellipsoid; the other projections are
+/// defined on WGS84 and are only appropriate for WGS84-compatible datums
+/// (GRS80-based datums differ by well under a millimeter).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ProjectionDef {
+    WebMercator,
+    Equirectangular(Equirectangular),
+    TransverseMercator(TransverseMercator),
+    LambertAzimuthalEqualArea(LambertAzimuthalEqualArea),
+    PolarStereographic(PolarStereographic),
+}
+
+impl Projection for ProjectionDef {
+    fn forward(&self, geo: Geodetic) -> Option<Vec2> {
+        match self {
+            ProjectionDef::WebMercator => WebMercator.forward(geo),
+            ProjectionDef::Equirectangular(p) => p.forward(geo),
+            ProjectionDef::TransverseMercator(p) => p.forward(geo),
+            ProjectionDef::LambertAzimuthalEqualArea(p) => p.forward(geo),
+            ProjectionDef::PolarStereographic(p) => p.forward(geo),
+        }
+    }
+
+    fn inverse(&self, xy: Vec2) -> Option<Geodetic> {
+        match self {
+            ProjectionDef::WebMercator => WebMercator.inverse(xy),
+            ProjectionDef::Equirectangular(p) => p.inverse(xy),
+            ProjectionDef::TransverseMercator(p) => p.inverse(xy),
+            ProjectionDef::LambertAzimuthalEqualArea(p) => p.inverse(xy),
+            ProjectionDef::PolarStereographic(p) => p.inverse(xy),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CrsKind {
+    Geographic,
+    Geocentric,
+    Projected(ProjectionDef),
+}
+
+/// A coordinate reference system identified by EPSG code.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Crs {
+    pub epsg: u32,
+    pub name: String,
+    pub datum: Datum,
+    pub kind: CrsKind,
+}
+
+impl Crs {
+    pub fn new(epsg: u32, name: impl Into<String>, datum: Datum, kind: CrsKind) -> Self {
+        Self {
+            epsg,
+            name: name.into(),
+            datum,
+            kind,
+        }
+    }
+
+    /// WGS 84 geographic coordinates (EPSG:4326, lon/lat order).
+    pub fn wgs84() -> Self {
+        Self::new(EPSG_WGS84, "WGS 84", Datum::wgs84(), CrsKind::Geographic)
+    }
+
+    /// True for lon/lat on the WGS84 frame (no transform needed for ingestion).
+    pub fn is_wgs84_geographic(&self) -> bool {
+        self.kind == CrsKind::Geographic && self.datum.same_frame(&Datum::wgs84())
+    }
+
+    /// Native coordinates to geodetic on this CRS's own ellipsoid.
+    pub fn to_geodetic(&self, p: Vec3) -> Option<Geodetic> {
+        if !(p.x.is_finite() && p.y.is_finite() && p.z.is_finite()) {
+            return None;
+        }
+        match self.kind {
+            CrsKind::Geographic => Some(Geodetic::from_degrees(p.y, p.x, p.z)),
+            CrsKind::Geocentric => Some(
+                self.datum
+                    .ellipsoid
+                    .ecef_to_geodetic(Ecef::new(p.x, p.y, p.z)),
+            ),
+            CrsKind::Projected(proj) => {
+                let mut geo = proj.inverse(Vec2::new(p.x, p.y))?;
+                geo.alt_m = p.z;
+                Some(geo)
+            }
+        }
+    }
+
+    /// Geodetic on this CRS's own ellipsoid to native coordinates.
+    pub fn from_geodetic(&self, geo: Geodetic) -> Option<Vec3> {
+        match self.kind {
+            CrsKind::Geographic => Some(Vec3::new(geo.lon_deg(), geo.lat_deg(), geo.alt_m)),
+            CrsKind::Geocentric => {
+                let e = self.datum.ellipsoid.geodetic_to_ecef(geo);
+                Some(Vec3::new(e.x, e.y, e.z))
+            }
+            CrsKind::Projected(proj) => {
+                let xy = proj.forward(geo)?;
+                Some(Vec3::new(xy.x, xy.y, geo.alt_m))
+            }
+        }
+    }
+
+    /// Native coordinates to WGS84 ECEF.
+    pub fn to_wgs84_ecef(&self, p: Vec3) -> Option<Ecef> {
+        let geo = self.to_geodetic(p)?;
+        let local = self.datum.ellipsoid.geodetic_to_ecef(geo);
+        Some(self.datum.to_wgs84.apply(local))
+    }
+
+    /// WGS84 ECEF to native coordinates.
+    pub fn from_wgs84_ecef(&self, ecef: Ecef) -> Option<Vec3> {
+        let local = self.datum.to_wgs84.apply_inverse(ecef);
+        self.from_geodetic(self.datum.ellipsoid.ecef_to_geodetic(local))
+    }
+}
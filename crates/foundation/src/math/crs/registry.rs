@@ -0,0 +1,309 @@
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use super::{Crs, CrsKind, CrsTransform, Datum, EPSG_WGS84, ProjectionDef};
+use crate::math::projection::{
+    Equirectangular, Hemisphere, LambertAzimuthalEqualArea, PolarStereographic, Utm,
+};
+use crate::math::{Ellipsoid, Helmert7, TransverseMercator};
+
+/// EPSG-keyed CRS lookup.
+///
+/// `builtin()` covers WGS84 and a set of common national datums and grids.
+/// WGS 84 / UTM zones (EPSG:32601-32660, 32701-32760) are resolved on demand
+/// rather than stored. Iteration and lookup are deterministic (ordered map).
+#[derive(Debug, Clone, Default)]
+pub struct CrsRegistry {
+    entries: BTreeMap<u32, Crs>,
+}
+
+impl CrsRegistry {
+    /// An empty registry (UTM zones still resolve).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The shared built-in registry, built once on first use. Clone it to
+    /// register additional definitions.
+    pub fn builtin() -> &'static Self {
+        static BUILTIN: OnceLock<CrsRegistry> = OnceLock::new();
+        BUILTIN.get_or_init(|| {
+            let mut r = Self::new();
+            for crs in builtin_crs() {
+                r.register(crs);
+            }
+            r
+        })
+    }
+
+    /// Add or replace a CRS. Returns the previous definition for that code.
+    pub fn register(&mut self, crs: Crs) -> Option<Crs> {
+        self.entries.insert(crs.epsg, crs)
+    }
+
+    pub fn get(&self, epsg: u32) -> Option<Crs> {
+        if let Some(crs) = self.entries.get(&epsg) {
+            return Some(crs.clone());
+        }
+        wgs84_utm(epsg)
+    }
+
+    /// Resolve a CRS name as found in GeoJSON `crs` members or OGC APIs.
+    ///
+    /// Accepts `EPSG:27700`, `urn:ogc:def:crs:EPSG::27700`,
+    /// `http://www.opengis.net/def/crs/EPSG/0/27700` and the OGC CRS84 aliases.
+    pub fn resolve_name(&self, name: &str) -> Option<Crs> {
+        self.get(epsg_from_name(name)?)
+    }
+
+    /// Transform between two registered codes.
+    pub fn transform(&self, source: u32, target: u32) -> Option<CrsTransform> {
+        Some(CrsTransform::new(self.get(source)?, self.get(target)?))
+    }
+
+    /// Registered codes in ascending order (excludes on-demand UTM zones).
+    pub fn codes(&self) -> impl Iterator<Item = u32> + '_ {
+        self.entries.keys().copied()
+    }
+}
+
+/// Extract an EPSG code from a CRS name or URI.
+pub fn epsg_from_name(name: &str) -> Option<u32> {
+    let name = name.trim();
+    let upper = name.to_ascii_uppercase();
+    if upper.ends_with("CRS84") || upper.ends_with("CRS:84") {
+        return Some(EPSG_WGS84);
+    }
+    let code = if let Some(rest) = upper.strip_prefix("EPSG:") {
+        rest
+    } else if upper.starts_with("URN:OGC:DEF:CRS:EPSG:") {
+        upper.rsplit(':').next()?
+    } else if upper.contains("/DEF/CRS/EPSG/") {
+        upper.rsplit('/').next()?
+    } else {
+        return None;
+    };
+    code.parse().ok()
+}
+
+fn wgs84_utm(epsg: u32) -> Option<Crs> {
+    let (hemisphere, zone) = match epsg {
+        32601..=32660 => (Hemisphere::North, epsg - 32600),
+        32701..=32760 => (Hemisphere::South, epsg - 32700),
+        _ => return None,
+    };
+    let utm = Utm::new(zone as u8, hemisphere)?;
+    let suffix = match hemisphere {
+        Hemisphere::North => 'N',
+        Hemisphere::South => 'S',
+    };
+    Some(Crs::new(
+        epsg,
+        format!("WGS 84 / UTM zone {zone}{suffix}"),
+        Datum::wgs84(),
+        CrsKind::Projected(ProjectionDef::TransverseMercator(utm.transverse_mercator())),
+    ))
+}
+
+fn tm_utm(ellipsoid: Ellipsoid, zone: u8) -> ProjectionDef {
+    let lon0 = (-183.0 + 6.0 * zone as f64).to_radians();
+    ProjectionDef::TransverseMercator(
+        TransverseMercator::new(ellipsoid, 0.0, lon0, 0.9996).with_false_origin(500_000.0, 0.0),
+    )
+}
+
+fn builtin_crs() -> Vec<Crs> {
+    let wgs84 = Datum::wgs84();
+    let etrs89 = Datum::new("ETRS89", Ellipsoid::GRS80, Helmert7::IDENTITY);
+    let nad83 = Datum::new(
+        "North American Datum 1983",
+        Ellipsoid::GRS80,
+        Helmert7::IDENTITY,
+    );
+    let nad27 = Datum::new(
+        "North American Datum 1927",
+        Ellipsoid::CLARKE_1866,
+        Helmert7::translation(-8.0, 160.0, 176.0),
+    );
+    let osgb36 = Datum::new(
+        "OSGB 1936",
+        Ellipsoid::AIRY_1830,
+        Helmert7::position_vector(446.448, -125.157, 542.06, 0.15, 0.247, 0.842, -20.489),
+    );
+    let ed50 = Datum::new(
+        "European Datum 1950",
+        Ellipsoid::INTERNATIONAL_1924,
+        Helmert7::translation(-87.0, -98.0, -121.0),
+    );
+    let dhdn = Datum::new(
+        "Deutsches Hauptdreiecksnetz",
+        Ellipsoid::BESSEL_1841,
+        Helmert7::position_vector(598.1, 73.7, 418.2, 0.202, 0.045, -2.455, 6.7),
+    );
+    let gda94 = Datum::new(
+        "Geocentric Datum of Australia 1994",
+        Ellipsoid::GRS80,
+        Helmert7::IDENTITY,
+    );
+
+    vec![
+        Crs::wgs84(),
+        Crs::new(
+            4978,
+            "WGS 84 (geocentric)",
+            wgs84.clone(),
+            CrsKind::Geocentric,
+        ),
+        Crs::new(
+            3857,
+            "WGS 84 / Pseudo-Mercator",
+            wgs84.clone(),
+            CrsKind::Projected(ProjectionDef::WebMercator),
+        ),
+        Crs::new(
+            4087,
+            "WGS 84 / World Equidistant Cylindrical",
+            wgs84.clone(),
+            CrsKind::Projected(ProjectionDef::Equirectangular(Equirectangular::default())),
+        ),
+        Crs::new(
+            3413,
+            "WGS 84 / NSIDC Sea Ice Polar Stereographic North",
+            wgs84.clone(),
+            CrsKind::Projected(ProjectionDef::PolarStereographic(
+                PolarStereographic::with_true_scale_lat(
+                    Hemisphere::North,
+                    (-45f64).to_radians(),
+                    70f64.to_radians(),
+                ),
+            )),
+        ),
+        Crs::new(
+            3031,
+            "WGS 84 / Antarctic Polar Stereographic",
+            wgs84.clone(),
+            CrsKind::Projected(ProjectionDef::PolarStereographic(
+                PolarStereographic::with_true_scale_lat(
+                    Hemisphere::South,
+                    0.0,
+                    (-71f64).to_radians(),
+                ),
+            )),
+        ),
+        Crs::new(
+            32661,
+            "WGS 84 / UPS North (N,E)",
+            wgs84.clone(),
+            CrsKind::Projected(ProjectionDef::PolarStereographic(PolarStereographic::ups(
+                Hemisphere::North,
+            ))),
+        ),
+        Crs::new(
+            32761,
+            "WGS 84 / UPS South (N,E)",
+            wgs84,
+            CrsKind::Projected(ProjectionDef::PolarStereographic(PolarStereographic::ups(
+                Hemisphere::South,
+            ))),
+        ),
+        Crs::new(4258, "ETRS89", etrs89.clone(), CrsKind::Geographic),
+        Crs::new(
+            3035,
+            "ETRS89-extended / LAEA Europe",
+            etrs89.clone(),
+            CrsKind::Projected(ProjectionDef::LambertAzimuthalEqualArea(
+                LambertAzimuthalEqualArea::europe(),
+            )),
+        ),
+        Crs::new(
+            25832,
+            "ETRS89 / UTM zone 32N",
+            etrs89.clone(),
+            CrsKind::Projected(tm_utm(Ellipsoid::GRS80, 32)),
+        ),
+        Crs::new(
+            25833,
+            "ETRS89 / UTM zone 33N",
+            etrs89,
+            CrsKind::Projected(tm_utm(Ellipsoid::GRS80, 33)),
+        ),
+        Crs::new(4269, "NAD83", nad83, CrsKind::Geographic),
+        Crs::new(4267, "NAD27", nad27, CrsKind::Geographic),
+        Crs::new(4277, "OSGB36", osgb36.clone(), CrsKind::Geographic),
+        Crs::new(
+            27700,
+            "OSGB36 / British National Grid",
+            osgb36,
+            CrsKind::Projected(ProjectionDef::TransverseMercator(
+                TransverseMercator::new(
+                    Ellipsoid::AIRY_1830,
+                    49f64.to_radians(),
+                    (-2f64).to_radians(),
+                    0.999_601_271_7,
+                )
+                .with_false_origin(400_000.0, -100_000.0),
+            )),
+        ),
+        Crs::new(4230, "ED50", ed50.clone(), CrsKind::Geographic),
+        Crs::new(
+            23031,
+            "ED50 / UTM zone 31N",
+            ed50,
+            CrsKind::Projected(tm_utm(Ellipsoid::INTERNATIONAL_1924, 31)),
+        ),
+        Crs::new(4314, "DHDN", dhdn.clone(), CrsKind::Geographic),
+        Crs::new(
+            31467,
+            "DHDN / 3-degree Gauss-Kruger zone 3",
+            dhdn,
+            CrsKind::Projected(ProjectionDef::TransverseMercator(
+                TransverseMercator::new(Ellipsoid::BESSEL_1841, 0.0, 9f64.to_radians(), 1.0)
+                    .with_false_origin(3_500_000.0, 0.0),
+            )),
+        ),
+        Crs::new(4283, "GDA94", gda94, CrsKind::Geographic),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CrsRegistry, epsg_from_name};
+    use crate::math::crs::{CrsKind, EPSG_WGS84};
+
+    #[test]
+    fn parses_common_name_forms() {
+        assert_eq!(epsg_from_name("EPSG:27700"), Some(27700));
+        assert_eq!(epsg_from_name("urn:ogc:def:crs:EPSG::3857"), Some(3857));
+        assert_eq!(
+            epsg_from_name("http://www.opengis.net/def/crs/EPSG/0/4258"),
+            Some(4258)
+        );
+        assert_eq!(
+            epsg_from_name("urn:ogc:def:crs:OGC:1.3:CRS84"),
+            Some(EPSG_WGS84)
+        );
+        assert_eq!(epsg_from_name("not a crs"), None);
+    }
+
+    #[test]
+    fn resolves_builtin_and_utm_codes() {
+        let r = CrsRegistry::builtin();
+        assert!(r.get(EPSG_WGS84).unwrap().is_wgs84_geographic());
+        assert!(!r.get(27700).unwrap().is_wgs84_geographic());
+
+        let utm = r.get(32633).unwrap();
+        assert_eq!(utm.name, "WGS 84 / UTM zone 33N");
+        assert!(matches!(utm.kind, CrsKind::Projected(_)));
+        assert!(r.get(32700).is_none());
+        assert!(r.get(1).is_none());
+    }
+
+    #[test]
+    fn codes_are_sorted() {
+        let r = CrsRegistry::builtin();
+        let codes: Vec<u32> = r.codes().collect();
+        let mut sorted = codes.clone();
+        sorted.sort_unstable();
+        assert_eq!(codes, sorted);
+    }
+}
@@ -0,0 +1,128 @@
+use super::Crs;
+use crate::math::Vec3;
+
+/// Source CRS → WGS84 ECEF → target CRS pipeline.
+///
+/// When both CRSs share a datum frame the ECEF hop is skipped and heights are
+/// carried through unchanged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrsTransform {
+    source: Crs,
+    target: Crs,
+}
+
+impl CrsTransform {
+    pub fn new(source: Crs, target: Crs) -> Self {
+        Self { source, target }
+    }
+
+    /// Transform from `source` into WGS 84 geographic (lon, lat, height).
+    pub fn to_wgs84(source: Crs) -> Self {
+        Self::new(source, Crs::wgs84())
+    }
+
+    pub fn source(&self) -> &Crs {
+        &self.source
+    }
+
+    pub fn target(&self) -> &Crs {
+        &self.target
+    }
+
+    /// True when the transform leaves coordinates unchanged.
+    pub fn is_identity(&self) -> bool {
+        self.source.kind == self.target.kind && self.source.datum.same_frame(&self.target.datum)
+    }
+
+    /// Transform one coordinate (native axis order, see module docs).
+    ///
+    /// Returns `None` if the point is outside either projection's domain.
+    pub fn transform(&self, p: Vec3) -> Option<Vec3> {
+        if self.is_identity() {
+            return Some(p);
+        }
+        if self.source.datum.same_frame(&self.target.datum) {
+            let geo = self.source.to_geodetic(p)?;
+            return self.target.from_geodetic(geo);
+        }
+        let ecef = self.source.to_wgs84_ecef(p)?;
+        self.target.from_wgs84_ecef(ecef)
+    }
+
+    /// The reverse pipeline.
+    pub fn inverse(&self) -> Self {
+        Self::new(self.target.clone(), self.source.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CrsTransform;
+    use crate::math::crs::CrsRegistry;
+    use crate::math::{Ellipsoid, Geodetic, Vec3, geodetic_to_ecef};
+
+    fn assert_close(a: f64, b: f64, eps: f64) {
+        let diff = (a - b).abs();
+        assert!(diff <= eps, "expected {a} ~= {b} (diff {diff})");
+    }
+
+    #[test]
+    fn wgs84_to_web_mercator_and_back() {
+        let r = CrsRegistry::builtin();
+        let t = r.transform(4326, 3857).unwrap();
+        let p = t.transform(Vec3::new(180.0, 0.0, 0.0)).unwrap();
+        assert_close(p.x, std::f64::consts::PI * 6_378_137.0, 1e-6);
+        let back = t.inverse().transform(p).unwrap();
+        assert_close(back.x, 180.0, 1e-9);
+    }
+
+    #[test]
+    fn british_national_grid_round_trips_through_wgs84() {
+        let r = CrsRegistry::builtin();
+        let to_wgs = r.transform(27700, 4326).unwrap();
+        let from_wgs = to_wgs.inverse();
+
+        let bng = Vec3::new(651_409.903, 313_177.270, 0.0);
+        let wgs = to_wgs.transform(bng).unwrap();
+        let rt = from_wgs.transform(wgs).unwrap();
+        assert_close(rt.x, bng.x, 1e-3);
+        assert_close(rt.y, bng.y, 1e-3);
+        assert_close(rt.z, bng.z, 1e-3);
+    }
+
+    #[test]
+    fn osgb36_datum_shift_is_about_a_hundred_meters() {
+        let r = CrsRegistry::builtin();
+        let t = r.transform(4277, 4326).unwrap();
+        let osgb = Vec3::new(-0.1276, 51.5072, 0.0);
+        let wgs = t.transform(osgb).unwrap();
+
+        let a = geodetic_to_ecef(Geodetic::from_degrees(osgb.y, osgb.x, 0.0));
+        let b = geodetic_to_ecef(Geodetic::from_degrees(wgs.y, wgs.x, 0.0));
+        let horizontal = ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt();
+        assert!(
+            (50.0..150.0).contains(&horizontal),
+            "unexpected shift {horizontal} m"
+        );
+    }
+
+    #[test]
+    fn same_frame_skips_datum_shift() {
+        let r = CrsRegistry::builtin();
+        let t = r.transform(4258, 25832).unwrap();
+        let p = t.transform(Vec3::new(9.0, 0.0, 12.5)).unwrap();
+        assert_close(p.x, 500_000.0, 1e-6);
+        assert_close(p.y, 0.0, 1e-6);
+        assert_close(p.z, 12.5, 0.0);
+        assert!(CrsTransform::to_wgs84(r.get(4326).unwrap()).is_identity());
+    }
+
+    #[test]
+    fn geocentric_matches_ellipsoid_conversion() {
+        let r = CrsRegistry::builtin();
+        let t = r.transform(4326, 4978).unwrap();
+        let p = t.transform(Vec3::new(11.0, 48.0, 500.0)).unwrap();
+        let e = Ellipsoid::WGS84.geodetic_to_ecef(Geodetic::from_degrees(48.0, 11.0, 500.0));
+        assert_eq!(p, Vec3::new(e.x, e.y, e.z));
+    }
+}
@@ -0,0 +1,114 @@
+//! Icosahedron geometry and the triangular lattice on each face.
+//!
+//! Vertex 0 is the north pole and vertex 11 the south pole; vertices 1..=5 form the upper
+//! ring at latitude `atan(1/2)` (longitudes `72k`) and 6..=10 the lower ring at `-atan(1/2)`
+//! (longitudes `36 + 72k`). Faces are wound counter-clockwise seen from outside.
+
+use std::sync::OnceLock;
+
+use crate::math::{Mat3, Vec3};
+
+pub(super) const FACE_COUNT: usize = 20;
+
+/// One icosahedron face: vertex indices and the inverse of `[A B C]` for barycentrics.
+pub(super) struct Face {
+    pub vertices: [usize; 3],
+    pub center: Vec3,
+    inverse: Mat3,
+}
+
+pub(super) struct Icosahedron {
+    pub vertices: [Vec3; 12],
+    pub faces: Vec<Face>,
+}
+
+impl Icosahedron {
+    pub fn get() -> &'static Icosahedron {
+        static ICOSAHEDRON: OnceLock<Icosahedron> = OnceLock::new();
+        ICOSAHEDRON.get_or_init(Self::build)
+    }
+
+    fn build() -> Self {
+        let ring_lat = 0.5f64.atan();
+        let unit =
+            |lat: f64, lon: f64| Vec3::new(lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin());
+        let mut vertices = [Vec3::ZERO; 12];
+        vertices[0] = Vec3::Z;
+        vertices[11] = -Vec3::Z;
+        for k in 0..5 {
+            let lon = (72.0 * k as f64).to_radians();
+            vertices[1 + k] = unit(ring_lat, lon);
+            vertices[6 + k] = unit(-ring_lat, lon + 36f64.to_radians());
+        }
+
+        let mut faces = Vec::with_capacity(FACE_COUNT);
+        for k in 0..5 {
+            let (u0, u1) = (1 + k, 1 + (k + 1) % 5);
+            let (l0, l1) = (6 + k, 6 + (k + 1) % 5);
+            for tri in [[0, u0, u1], [u0, l0, u1], [u1, l0, l1], [l0, 11, l1]] {
+                faces.push(Self::face(&vertices, tri));
+            }
+        }
+        Self { vertices, faces }
+    }
+
+    fn face(vertices: &[Vec3; 12], mut tri: [usize; 3]) -> Face {
+        let [a, b, c] = tri.map(|v| vertices[v]);
+        if (b - a).cross(c - a).dot(a) < 0.0 {
+            tri.swap(1, 2);
+        }
+        let [a, b, c] = tri.map(|v| vertices[v]);
+        Face {
+            vertices: tri,
+            center: (a + b + c).normalize(),
+            inverse: Mat3::from_cols(a, b, c)
+                .inverse()
+                .expect("icosahedron face vertices are linearly independent"),
+        }
+    }
+
+    /// Face whose centre is closest to the unit vector `p`; that face contains `p`.
+    pub fn face_of(&self, p: Vec3) -> usize {
+        let mut best = 0;
+        let mut best_dot = f64::NEG_INFINITY;
+        for (f, face) in self.faces.iter().enumerate() {
+            let d = face.center.dot(p);
+            if d > best_dot {
+                best = f;
+                best_dot = d;
+            }
+        }
+        best
+    }
+
+    /// Barycentric coordinates of the gnomonic projection of `p` onto face `f`.
+    pub fn barycentric(&self, f: usize, p: Vec3) -> [f64; 3] {
+        let w = self.faces[f].inverse * p;
+        let sum = w.x + w.y + w.z;
+        [w.x / sum, w.y / sum, w.z / sum]
+    }
+
+    /// Point on face `f` with barycentric weights `w` (need not sum to one), on the unit sphere.
+    pub fn point(&self, f: usize, w: [f64; 3]) -> Vec3 {
+        let [a, b, c] = self.faces[f].vertices.map(|v| self.vertices[v]);
+        (a * w[0] + b * w[1] + c * w[2]).normalize()
+    }
+
+    /// Lowest-indexed face containing every vertex in `vertices`.
+    pub fn first_face_with(&self, vertices: &[usize]) -> usize {
+        self.faces
+            .iter()
+            .position(|face| vertices.iter().all(|v| face.vertices.contains(v)))
+            .expect("lattice point support lies on a single face")
+    }
+
+    /// Faces containing every vertex in `vertices`, in index order.
+    pub fn faces_with(&self, vertices: &[usize]) -> impl Iterator<Item = usize> + '_ {
+        let vertices = vertices.to_vec();
+        self.faces
+            .iter()
+            .enumerate()
+            .filter(move |(_, face)| vertices.iter().all(|v| face.vertices.contains(v)))
+            .map(|(f, _)| f)
+    }
+}
@@ -0,0 +1,514 @@
+//! Hierarchical hexagonal discrete global grid.
+//!
+//! Each face of an icosahedron (vertices at the poles) carries a triangular lattice with
+//! `2^resolution` steps per edge; cells are the hexagons around lattice points, plus one
+//! pentagon at each of the 12 icosahedron vertices. Points are assigned through a gnomonic
+//! projection onto the containing face. Each finer resolution has four times as many
+//! cells (`10 * 4^r + 2` in total).
+//!
+//! Like H3, children are not exactly contained in their parent: `parent` is the nearest
+//! coarser cell, with ties broken by a fixed rule, so the hierarchy is deterministic.
+//! Geodetic latitude is used directly as spherical latitude.
+
+mod icosahedron;
+mod polyfill;
+
+use std::collections::BTreeSet;
+
+use icosahedron::{FACE_COUNT, Icosahedron};
+
+use super::{Geodetic, Vec3};
+
+pub use polyfill::polyfill;
+
+/// Finest supported resolution (about 0.5 m between cell centres).
+pub const MAX_RESOLUTION: u8 = 24;
+
+/// Radius of the sphere with the WGS84 ellipsoid's surface area (meters).
+pub const AUTHALIC_RADIUS_M: f64 = 6_371_007.181;
+
+const COORD_BITS: u32 = 25;
+const COORD_MASK: u64 = (1 << COORD_BITS) - 1;
+const FACE_SHIFT: u32 = 2 * COORD_BITS;
+const RES_SHIFT: u32 = FACE_SHIFT + 5;
+
+/// Lattice steps in `(k, i, j)` face coordinates, in rotational order.
+const DIRECTIONS: [[i64; 3]; 6] = [
+    [1, -1, 0],
+    [1, 0, -1],
+    [0, 1, -1],
+    [-1, 1, 0],
+    [-1, 0, 1],
+    [0, -1, 1],
+];
+
+/// A cell of the hexagonal global grid.
+///
+/// The 64-bit id packs resolution, icosahedron face and lattice coordinates, so ordering
+/// by id groups cells by resolution and then by face.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HexCell(u64);
+
+/// A lattice point in the frame of one face: weights `(k, i, j)` on the face vertices,
+/// summing to `2^res`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct LatticePoint {
+    res: u8,
+    face: usize,
+    coords: [i64; 3],
+}
+
+impl LatticePoint {
+    fn n(res: u8) -> i64 {
+        1 << res
+    }
+
+    /// Icosahedron vertices with non-zero weight.
+    fn support(&self) -> Vec<usize> {
+        let vertices = Icosahedron::get().faces[self.face].vertices;
+        (0..3)
+            .filter(|&l| self.coords[l] > 0)
+            .map(|l| vertices[l])
+            .collect()
+    }
+
+    /// The same point expressed in face `face`, which must contain it.
+    fn in_face(&self, face: usize) -> Self {
+        let faces = &Icosahedron::get().faces;
+        let src = faces[self.face].vertices;
+        let coords = faces[face].vertices.map(|v| {
+            src.iter()
+                .position(|&s| s == v)
+                .map_or(0, |l| self.coords[l])
+        });
+        Self {
+            res: self.res,
+            face,
+            coords,
+        }
+    }
+
+    /// Points on an edge or vertex belong to several faces; the lowest face index wins.
+    fn canonical(&self) -> Self {
+        self.in_face(Icosahedron::get().first_face_with(&self.support()))
+    }
+
+    /// This point in every face that contains it.
+    fn representations(&self) -> Vec<Self> {
+        Icosahedron::get()
+            .faces_with(&self.support())
+            .map(|face| self.in_face(face))
+            .collect()
+    }
+
+    fn step(&self, d: [i64; 3]) -> Option<Self> {
+        let coords = [0, 1, 2].map(|l| self.coords[l] + d[l]);
+        coords
+            .iter()
+            .all(|&c| c >= 0)
+            .then_some(Self { coords, ..*self })
+    }
+
+    fn unit(&self) -> Vec3 {
+        Icosahedron::get().point(self.face, self.coords.map(|c| c as f64))
+    }
+
+    fn cell(&self) -> HexCell {
+        let p = self.canonical();
+        HexCell(
+            (p.res as u64) << RES_SHIFT
+                | (p.face as u64) << FACE_SHIFT
+                | (p.coords[1] as u64) << COORD_BITS
+                | p.coords[2] as u64,
+        )
+    }
+
+    /// Lattice triangles touching this point, as `[self, a, b]` in a shared face frame
+    /// with `a` and `b` consecutive neighbours.
+    fn triangles(&self) -> Vec<[Self; 3]> {
+        let mut out = Vec::new();
+        for rep in self.representations() {
+            for (da, db) in DIRECTIONS.iter().zip(DIRECTIONS.iter().cycle().skip(1)) {
+                if let (Some(a), Some(b)) = (rep.step(*da), rep.step(*db)) {
+                    out.push([rep, a, b]);
+                }
+            }
+        }
+        out
+    }
+}
+
+impl HexCell {
+    /// Cell containing a geodetic position; `resolution` is clamped to [`MAX_RESOLUTION`].
+    pub fn from_geodetic(geo: Geodetic, resolution: u8) -> Self {
+        Self::from_unit(unit_from_lat_lon(geo.lat_rad, geo.lon_rad), resolution)
+    }
+
+    pub fn from_lon_lat_deg(lon: f64, lat: f64, resolution: u8) -> Self {
+        Self::from_unit(
+            unit_from_lat_lon(lat.to_radians(), lon.to_radians()),
+            resolution,
+        )
+    }
+
+    fn from_unit(p: Vec3, resolution: u8) -> Self {
+        let res = resolution.min(MAX_RESOLUTION);
+        let n = LatticePoint::n(res);
+        let ico = Icosahedron::get();
+        let face = ico.face_of(p);
+        let scaled = ico.barycentric(face, p).map(|w| w * n as f64);
+        LatticePoint {
+            res,
+            face,
+            coords: round_to_lattice(scaled, n),
+        }
+        .cell()
+    }
+
+    /// The twelve resolution-0 cells (all pentagons).
+    pub fn base_cells() -> Vec<Self> {
+        let mut cells: Vec<Self> = (0..FACE_COUNT)
+            .flat_map(|face| {
+                [[1, 0, 0], [0, 1, 0], [0, 0, 1]].map(|coords| {
+                    LatticePoint {
+                        res: 0,
+                        face,
+                        coords,
+                    }
+                    .cell()
+                })
+            })
+            .collect();
+        cells.sort();
+        cells.dedup();
+        cells
+    }
+
+    /// Rebuild a cell from [`HexCell::raw`], or `None` if the id is not a valid cell.
+    pub fn from_raw(raw: u64) -> Option<Self> {
+        let res = (raw >> RES_SHIFT) as u8;
+        let face = ((raw >> FACE_SHIFT) & 0x1f) as usize;
+        if raw >> (RES_SHIFT + 5) != 0 || res > MAX_RESOLUTION || face >= FACE_COUNT {
+            return None;
+        }
+        let i = ((raw >> COORD_BITS) & COORD_MASK) as i64;
+        let j = (raw & COORD_MASK) as i64;
+        let k = LatticePoint::n(res) - i - j;
+        if k < 0 {
+            return None;
+        }
+        let cell = LatticePoint {
+            res,
+            face,
+            coords: [k, i, j],
+        }
+        .cell();
+        (cell.0 == raw).then_some(cell)
+    }
+
+    pub fn raw(self) -> u64 {
+        self.0
+    }
+
+    pub fn resolution(self) -> u8 {
+        (self.0 >> RES_SHIFT) as u8
+    }
+
+    fn lattice(self) -> LatticePoint {
+        let res = self.resolution();
+        let i = ((self.0 >> COORD_BITS) & COORD_MASK) as i64;
+        let j = (self.0 & COORD_MASK) as i64;
+        LatticePoint {
+            res,
+            face: ((self.0 >> FACE_SHIFT) & 0x1f) as usize,
+            coords: [LatticePoint::n(res) - i - j, i, j],
+        }
+    }
+
+    /// Pentagons sit on the 12 icosahedron vertices and have five neighbours.
+    pub fn is_pentagon(self) -> bool {
+        self.lattice().support().len() == 1
+    }
+
+    /// Cell centre on the surface (altitude 0).
+    pub fn center(self) -> Geodetic {
+        geodetic_from_unit(self.lattice().unit())
+    }
+
+    /// Boundary vertices counter-clockwise seen from above; five for pentagons, else six.
+    pub fn boundary(self) -> Vec<Geodetic> {
+        self.boundary_units()
+            .into_iter()
+            .map(geodetic_from_unit)
+            .collect()
+    }
+
+    fn boundary_units(self) -> Vec<Vec3> {
+        let p = self.lattice();
+        let center = p.unit();
+        // Tangent frame at the centre with `east × north` pointing outwards.
+        let east = Vec3::Z.cross(center).try_normalize().unwrap_or(Vec3::X);
+        let north = center.cross(east);
+        let mut corners: Vec<(f64, Vec3)> = p
+            .triangles()
+            .into_iter()
+            .map(|[p, a, b]| {
+                let w = [0, 1, 2].map(|l| (p.coords[l] + a.coords[l] + b.coords[l]) as f64);
+                let corner = Icosahedron::get().point(p.face, w);
+                (corner.dot(north).atan2(corner.dot(east)), corner)
+            })
+            .collect();
+        corners.sort_by(|a, b| a.0.total_cmp(&b.0));
+        corners.into_iter().map(|(_, corner)| corner).collect()
+    }
+
+    /// Adjacent cells, sorted; five for pentagons, else six.
+    pub fn neighbors(self) -> Vec<Self> {
+        let cells: BTreeSet<Self> = self
+            .lattice()
+            .triangles()
+            .into_iter()
+            .flat_map(|[_, a, b]| [a.cell(), b.cell()])
+            .collect();
+        cells.into_iter().collect()
+    }
+
+    /// Every cell within `k` steps, including this one, sorted.
+    pub fn k_ring(self, k: u32) -> Vec<Self> {
+        let mut seen = BTreeSet::from([self]);
+        let mut frontier = vec![self];
+        for _ in 0..k {
+            let mut next = Vec::new();
+            for cell in frontier {
+                for neighbor in cell.neighbors() {
+                    if seen.insert(neighbor) {
+                        next.push(neighbor);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        seen.into_iter().collect()
+    }
+
+    /// The coarser cell this one belongs to, or `None` at resolution 0.
+    pub fn parent(self) -> Option<Self> {
+        let p = self.lattice();
+        if p.res == 0 {
+            return None;
+        }
+        // Weights sum to an even number, so none or exactly two of them are odd. A cell
+        // between two parent lattice points goes to the one reached by rounding the first
+        // odd weight down and the second up.
+        let mut coords = p.coords;
+        let odd: Vec<usize> = (0..3).filter(|&l| coords[l] % 2 != 0).collect();
+        if let [down, up] = odd[..] {
+            coords[down] -= 1;
+            coords[up] += 1;
+        }
+        Some(
+            LatticePoint {
+                res: p.res - 1,
+                face: p.face,
+                coords: coords.map(|c| c / 2),
+            }
+            .cell(),
+        )
+    }
+
+    /// Cells one resolution finer whose parent is this cell, sorted; empty at
+    /// [`MAX_RESOLUTION`].
+    pub fn children(self) -> Vec<Self> {
+        let p = self.lattice();
+        if p.res >= MAX_RESOLUTION {
+            return Vec::new();
+        }
+        let mut cells = BTreeSet::new();
+        for rep in p.representations() {
+            let center = LatticePoint {
+                res: p.res + 1,
+                face: rep.face,
+                coords: rep.coords.map(|c| 2 * c),
+            };
+            cells.insert(center.cell());
+            for d in DIRECTIONS {
+                if let Some(child) = center.step(d).map(|c| c.cell())
+                    && child.parent() == Some(self)
+                {
+                    cells.insert(child);
+                }
+            }
+        }
+        cells.into_iter().collect()
+    }
+
+    /// Cell area on the authalic sphere, in square meters.
+    pub fn area_m2(self) -> f64 {
+        let center = self.lattice().unit();
+        let corners = self.boundary_units();
+        let mut excess = 0.0;
+        for (i, &a) in corners.iter().enumerate() {
+            let b = corners[(i + 1) % corners.len()];
+            excess += spherical_triangle_area(center, a, b);
+        }
+        excess * AUTHALIC_RADIUS_M * AUTHALIC_RADIUS_M
+    }
+}
+
+/// Solid angle of the spherical triangle with unit-vector corners (Van Oosterom–Strackee).
+fn spherical_triangle_area(a: Vec3, b: Vec3, c: Vec3) -> f64 {
+    let numerator = a.dot(b.cross(c)).abs();
+    let denominator = 1.0 + a.dot(b) + b.dot(c) + c.dot(a);
+    2.0 * numerator.atan2(denominator)
+}
+
+/// Nearest lattice point to barycentric coordinates scaled to sum to `n`.
+///
+/// Rounds each coordinate, then repairs the one with the largest rounding error so the
+/// sum is exact (hexagonal "cube rounding").
+fn round_to_lattice(scaled: [f64; 3], n: i64) -> [i64; 3] {
+    let mut r = scaled.map(|v| v.round().max(0.0) as i64);
+    let fix = (0..3)
+        .max_by(|&a, &b| {
+            let ea = (r[a] as f64 - scaled[a]).abs();
+            let eb = (r[b] as f64 - scaled[b]).abs();
+            ea.total_cmp(&eb)
+        })
+        .unwrap_or(0);
+    r[fix] = n - (r.iter().sum::<i64>() - r[fix]);
+    if r[fix] < 0 {
+        // Only reachable through round-off at a face corner.
+        r[fix] = 0;
+        let largest = (0..3).max_by_key(|&l| r[l]).unwrap_or(0);
+        r[largest] = n - (r.iter().sum::<i64>() - r[largest]);
+    }
+    r
+}
+
+fn unit_from_lat_lon(lat_rad: f64, lon_rad: f64) -> Vec3 {
+    let (sin_lat, cos_lat) = lat_rad.sin_cos();
+    let (sin_lon, cos_lon) = lon_rad.sin_cos();
+    Vec3::new(cos_lat * cos_lon, cos_lat * sin_lon, sin_lat)
+}
+
+fn geodetic_from_unit(p: Vec3) -> Geodetic {
+    Geodetic::new(p.z.clamp(-1.0, 1.0).asin(), p.y.atan2(p.x), 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AUTHALIC_RADIUS_M, HexCell, MAX_RESOLUTION, polyfill};
+    use crate::math::Geodetic;
+    use std::collections::BTreeSet;
+
+    fn all_cells(res: u8) -> Vec<HexCell> {
+        let mut cells = HexCell::base_cells();
+        for _ in 0..res {
+            cells = cells.iter().flat_map(|c| c.children()).collect();
+        }
+        cells
+    }
+
+    #[test]
+    fn points_round_trip_through_cell_centres() {
+        for res in [0, 1, 5, 12, MAX_RESOLUTION] {
+            for lat in (-90..=90).step_by(15) {
+                for lon in (-180..=180).step_by(25) {
+                    let (lon, lat) = (lon as f64 + 0.37, lat as f64);
+                    let cell = HexCell::from_lon_lat_deg(lon, lat, res);
+                    assert_eq!(cell.resolution(), res);
+                    assert_eq!(HexCell::from_geodetic(cell.center(), res), cell);
+                    assert_eq!(HexCell::from_raw(cell.raw()), Some(cell));
+                }
+            }
+        }
+        // Edge and vertex points have a single id whichever face they are reached from.
+        let pole = HexCell::from_geodetic(Geodetic::from_degrees(90.0, 0.0, 0.0), 3);
+        assert!(pole.is_pentagon());
+        assert_eq!(HexCell::from_lon_lat_deg(123.0, 89.999_999, 3), pole);
+        assert_eq!(HexCell::from_raw(u64::MAX), None);
+    }
+
+    #[test]
+    fn hierarchy_partitions_every_resolution() {
+        for res in 0..=3u8 {
+            let cells = all_cells(res);
+            let unique: BTreeSet<_> = cells.iter().copied().collect();
+            assert_eq!(cells.len(), 10 * 4usize.pow(res as u32) + 2);
+            assert_eq!(unique.len(), cells.len());
+            assert_eq!(cells.iter().filter(|c| c.is_pentagon()).count(), 12);
+            for cell in cells {
+                assert_eq!(cell.resolution(), res);
+                for child in cell.children() {
+                    assert_eq!(child.parent(), Some(cell));
+                }
+            }
+        }
+        assert_eq!(HexCell::base_cells()[0].parent(), None);
+        let deep = HexCell::from_lon_lat_deg(10.0, 20.0, MAX_RESOLUTION);
+        assert!(deep.children().is_empty());
+        // A point's cell at the next resolution has a parent close to the coarse cell.
+        let coarse = HexCell::from_lon_lat_deg(10.0, 20.0, 6);
+        let fine_parent = HexCell::from_lon_lat_deg(10.0, 20.0, 7).parent().unwrap();
+        assert!(coarse.k_ring(1).contains(&fine_parent));
+    }
+
+    #[test]
+    fn neighbours_are_symmetric_and_rings_grow() {
+        for cell in all_cells(2) {
+            let neighbors = cell.neighbors();
+            assert_eq!(neighbors.len(), if cell.is_pentagon() { 5 } else { 6 });
+            assert_eq!(cell.boundary().len(), neighbors.len());
+            for n in neighbors {
+                assert!(n.neighbors().contains(&cell), "{cell:?} <-> {n:?}");
+            }
+        }
+        let hex = HexCell::from_lon_lat_deg(12.0, 41.0, 8);
+        assert_eq!(hex.k_ring(0), vec![hex]);
+        assert_eq!(hex.k_ring(1).len(), 7);
+        assert_eq!(hex.k_ring(2).len(), 19);
+    }
+
+    #[test]
+    fn areas_cover_the_sphere() {
+        let total: f64 = all_cells(3).iter().map(|c| c.area_m2()).sum();
+        let sphere = 4.0 * std::f64::consts::PI * AUTHALIC_RADIUS_M * AUTHALIC_RADIUS_M;
+        assert!((total / sphere - 1.0).abs() < 1e-9, "{total} vs {sphere}");
+        // Boundaries wind counter-clockwise around the centre.
+        let cell = HexCell::from_lon_lat_deg(-70.0, -30.0, 4);
+        let b = cell.boundary();
+        let (c, p, q) = (cell.center(), b[0], b[1]);
+        let cross = (p.lon_rad - c.lon_rad) * (q.lat_rad - c.lat_rad)
+            - (p.lat_rad - c.lat_rad) * (q.lon_rad - c.lon_rad);
+        assert!(cross > 0.0);
+    }
+
+    #[test]
+    fn polyfill_covers_centres_inside_polygon() {
+        let in_square = |cell: &HexCell| {
+            let c = cell.center();
+            (1.0..11.0).contains(&c.lon_deg()) && (1.0..11.0).contains(&c.lat_deg())
+        };
+        let square = vec![[1.0, 1.0], [11.0, 1.0], [11.0, 11.0], [1.0, 11.0]];
+        let cells = polyfill(std::slice::from_ref(&square), 5);
+        let set: BTreeSet<_> = cells.iter().copied().collect();
+        assert!(!cells.is_empty());
+        assert!(cells.iter().all(in_square));
+        // Every cell around the middle whose centre is inside was found.
+        for cell in HexCell::from_lon_lat_deg(6.0, 6.0, 5).k_ring(8) {
+            assert_eq!(set.contains(&cell), in_square(&cell), "{cell:?}");
+        }
+
+        // A hole removes its cells; a ring across the antimeridian fills both sides.
+        let hole = vec![[4.0, 4.0], [8.0, 4.0], [8.0, 8.0], [4.0, 8.0]];
+        let holed = polyfill(&[square, hole], 5);
+        assert!(holed.len() < cells.len());
+        assert!(!holed.contains(&HexCell::from_lon_lat_deg(6.0, 6.0, 5)));
+        let dateline = vec![[175.0, -5.0], [-175.0, -5.0], [-175.0, 5.0], [175.0, 5.0]];
+        let wrapped = polyfill(&[dateline], 4);
+        assert!(wrapped.contains(&HexCell::from_lon_lat_deg(179.0, 0.5, 4)));
+        assert!(wrapped.contains(&HexCell::from_lon_lat_deg(-179.0, 0.5, 4)));
+        assert!(!wrapped.contains(&HexCell::from_lon_lat_deg(0.0, 0.0, 4)));
+        assert!(polyfill(&[], 4).is_empty());
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::{BTreeSet, VecDeque};
+
+use super::{HexCell, MAX_RESOLUTION};
+use crate::bounds::unwrap_lon_deg;
+use crate::math::orient2d;
+
+/// Angular length of an icosahedron edge, in degrees.
+const ICOSAHEDRON_EDGE_DEG: f64 = 63.434_948_822_922;
+
+/// Cells at `resolution` whose centres lie inside a polygon, sorted.
+///
+/// `rings[0]` is the outer ring and any further rings are holes, as `[lon, lat]` degrees
+/// with straight edges in lon/lat. Rings may cross the antimeridian but the polygon must
+/// not enclose a pole. A polygon that covers no cell centre yields no cells.
+pub fn polyfill(rings: &[Vec<[f64; 2]>], resolution: u8) -> Vec<HexCell> {
+    let Some(outer) = rings.first().filter(|ring| ring.len() >= 3) else {
+        return Vec::new();
+    };
+    let resolution = resolution.min(MAX_RESOLUTION);
+    let reference = outer[0][0];
+    let rings: Vec<Vec<[f64; 2]>> = rings
+        .iter()
+        .map(|ring| unwrap_ring(reference, ring))
+        .collect();
+    let (min_lon, max_lon) = rings[0]
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| {
+            (lo.min(p[0]), hi.max(p[0]))
+        });
+    let mid_lon = 0.5 * (min_lon + max_lon);
+    let inside = |cell: HexCell| {
+        let c = cell.center();
+        point_in_rings(&rings, unwrap_lon_deg(mid_lon, c.lon_deg()), c.lat_deg())
+    };
+
+    // Cells along the densely sampled boundary and their neighbours seed a flood fill
+    // over cells whose centres are inside.
+    let step_deg = ICOSAHEDRON_EDGE_DEG / (1u64 << resolution) as f64 / 4.0;
+    let mut seeds = BTreeSet::new();
+    for ring in &rings {
+        for (i, a) in ring.iter().enumerate() {
+            let b = ring[(i + 1) % ring.len()];
+            let len = (b[0] - a[0]).abs() + (b[1] - a[1]).abs();
+            let steps = (len / step_deg).ceil().max(1.0) as usize;
+            for s in 0..steps {
+                let t = s as f64 / steps as f64;
+                let lon = a[0] + (b[0] - a[0]) * t;
+                let lat = a[1] + (b[1] - a[1]) * t;
+                let cell = HexCell::from_lon_lat_deg(lon, lat, resolution);
+                seeds.insert(cell);
+                seeds.extend(cell.neighbors());
+            }
+        }
+    }
+
+    let mut visited = BTreeSet::new();
+    let mut covered = BTreeSet::new();
+    let mut queue = VecDeque::new();
+    for cell in seeds {
+        if visited.insert(cell) && inside(cell) {
+            covered.insert(cell);
+            queue.push_back(cell);
+        }
+    }
+    while let Some(cell) = queue.pop_front() {
+        for neighbor in cell.neighbors() {
+            if visited.insert(neighbor) && inside(neighbor) {
+                covered.insert(neighbor);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    covered.into_iter().collect()
+}
+
+/// Ring with each longitude moved within 180° of its predecessor.
+fn unwrap_ring(reference: f64, ring: &[[f64; 2]]) -> Vec<[f64; 2]> {
+    let mut prev = reference;
+    ring.iter()
+        .map(|&[lon, lat]| {
+            prev = unwrap_lon_deg(prev, lon);
+            [prev, lat]
+        })
+        .collect()
+}
+
+/// Even-odd test against every ring, so holes are excluded.
+fn point_in_rings(rings: &[Vec<[f64; 2]>], lon: f64, lat: f64) -> bool {
+    let mut inside = false;
+    for ring in rings {
+        let mut j = ring.len().saturating_sub(1);
+        for (i, a) in ring.iter().enumerate() {
+            let b = ring[j];
+            // The edge straddles the parallel; it crosses east of the point exactly when the
+            // point is left of the edge taken upwards.
+            if (a[1] > lat) != (b[1] > lat) {
+                let (lower, upper) = if a[1] < b[1] { (*a, b) } else { (b, *a) };
+                if orient2d(lower, upper, [lon, lat]) > 0.0 {
+                    inside = !inside;
+                }
+            }
+            j = i;
+        }
+    }
+    inside
+}
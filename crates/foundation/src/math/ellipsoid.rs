@@ -0,0 +1,125 @@
+use super::{Ecef, Geodetic};
+
+/// Reference ellipsoid defined by semi-major axis and flattening.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Ellipsoid {
+    /// Semi-major axis (meters).
+    pub a: f64,
+    /// Flattening.
+    pub f: f64,
+}
+
+impl Ellipsoid {
+    pub const WGS84: Ellipsoid = Ellipsoid::new(6_378_137.0, 1.0 / 298.257_223_563);
+    pub const GRS80: Ellipsoid = Ellipsoid::new(6_378_137.0, 1.0 / 298.257_222_101);
+    pub const AIRY_1830: Ellipsoid = Ellipsoid::new(6_377_563.396, 1.0 / 299.324_964_6);
+    pub const BESSEL_1841: Ellipsoid = Ellipsoid::new(6_377_397.155, 1.0 / 299.152_812_8);
+    pub const CLARKE_1866: Ellipsoid = Ellipsoid::new(6_378_206.4, 1.0 / 294.978_698_2);
+    pub const INTERNATIONAL_1924: Ellipsoid = Ellipsoid::new(6_378_388.0, 1.0 / 297.0);
+    pub const KRASSOWSKY_1940: Ellipsoid = Ellipsoid::new(6_378_245.0, 1.0 / 298.3);
+
+    pub const fn new(a: f64, f: f64) -> Self {
+        Self { a, f }
+    }
+
+    /// Semi-minor axis (meters).
+    #[inline]
+    pub fn b(self) -> f64 {
+        self.a * (1.0 - self.f)
+    }
+
+    /// First eccentricity squared.
+    #[inline]
+    pub fn e2(self) -> f64 {
+        self.f * (2.0 - self.f)
+    }
+
+    /// First eccentricity.
+    #[inline]
+    pub fn e(self) -> f64 {
+        self.e2().sqrt()
+    }
+
+    /// Second eccentricity squared.
+    #[inline]
+    pub fn ep2(self) -> f64 {
+        let b = self.b();
+        (self.a * self.a - b * b) / (b * b)
+    }
+
+    /// Third flattening `n = f / (2 - f)`.
+    #[inline]
+    pub fn n(self) -> f64 {
+        self.f / (2.0 - self.f)
+    }
+
+    pub fn geodetic_to_ecef(self, geo: Geodetic) -> Ecef {
+        let e2 = self.e2();
+        let sin_lat = geo.lat_rad.sin();
+        let cos_lat = geo.lat_rad.cos();
+        let sin_lon = geo.lon_rad.sin();
+        let cos_lon = geo.lon_rad.cos();
+
+        let n = self.a / (1.0 - e2 * sin_lat * sin_lat).sqrt();
+        let x = (n + geo.alt_m) * cos_lat * cos_lon;
+        let y = (n + geo.alt_m) * cos_lat * sin_lon;
+        let z = (n * (1.0 - e2) + geo.alt_m) * sin_lat;
+
+        Ecef::new(x, y, z)
+    }
+
+    /// Bowring's closed-form approximation (sub-millimeter for terrestrial heights).
+    pub fn ecef_to_geodetic(self, ecef: Ecef) -> Geodetic {
+        let a = self.a;
+        let b = self.b();
+        let e2 = self.e2();
+        let ep2 = self.ep2();
+
+        let p = (ecef.x * ecef.x + ecef.y * ecef.y).sqrt();
+        let lon = ecef.y.atan2(ecef.x);
+
+        let theta = (ecef.z * a).atan2(p * b);
+        let sin_theta = theta.sin();
+        let cos_theta = theta.cos();
+
+        let lat = (ecef.z + ep2 * b * sin_theta * sin_theta * sin_theta)
+            .atan2(p - e2 * a * cos_theta * cos_theta * cos_theta);
+
+        let sin_lat = lat.sin();
+        let n = a / (1.0 - e2 * sin_lat * sin_lat).sqrt();
+        let alt = p / lat.cos() - n;
+
+        Geodetic::new(lat, lon, alt)
+    }
+}
+
+impl Default for Ellipsoid {
+    fn default() -> Self {
+        Self::WGS84
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ellipsoid;
+    use crate::math::{Geodetic, WGS84_B, WGS84_E2, geodetic_to_ecef};
+
+    #[test]
+    fn wgs84_matches_constants() {
+        let e = Ellipsoid::WGS84;
+        assert_eq!(e.b(), WGS84_B);
+        assert_eq!(e.e2(), WGS84_E2);
+        let geo = Geodetic::from_degrees(48.0, 11.0, 500.0);
+        assert_eq!(e.geodetic_to_ecef(geo), geodetic_to_ecef(geo));
+    }
+
+    #[test]
+    fn round_trip_on_other_ellipsoid() {
+        let e = Ellipsoid::AIRY_1830;
+        let geo = Geodetic::from_degrees(52.658, 1.7179, 24.7);
+        let rt = e.ecef_to_geodetic(e.geodetic_to_ecef(geo));
+        assert!((rt.lat_rad - geo.lat_rad).abs() < 1e-11);
+        assert!((rt.lon_rad - geo.lon_rad).abs() < 1e-12);
+        assert!((rt.alt_m - geo.alt_m).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,539 @@
+//! Inverse problem: Newton iteration on the longitude equation (Karney 2013, §4).
+
+use super::Geodesic;
+use super::math::{ang_diff, ang_round, lat_fix, norm, sin_cos_series, sincosd, sq, tiny};
+use super::series::{self, ORDER};
+
+const MAXIT1: usize = 20;
+const MAXIT2: usize = MAXIT1 + f64::MANTISSA_DIGITS as usize + 10;
+
+fn tol0() -> f64 {
+    f64::EPSILON
+}
+
+fn tol1() -> f64 {
+    200.0 * tol0()
+}
+
+fn tol2() -> f64 {
+    tol0().sqrt()
+}
+
+fn tolb() -> f64 {
+    tol0() * tol2()
+}
+
+fn xthresh() -> f64 {
+    1000.0 * tol2()
+}
+
+/// Raw inverse solution: arc, distance, azimuth sines/cosines and area term.
+#[derive(Debug, Copy, Clone)]
+pub(super) struct InverseSolution {
+    pub a12: f64,
+    pub s12: f64,
+    pub salp1: f64,
+    pub calp1: f64,
+    pub salp2: f64,
+    pub calp2: f64,
+    pub area: f64,
+}
+
+struct Lambda12 {
+    lam12: f64,
+    salp2: f64,
+    calp2: f64,
+    sig12: f64,
+    ssig1: f64,
+    csig1: f64,
+    ssig2: f64,
+    csig2: f64,
+    eps: f64,
+    domg12: f64,
+    dlam12: f64,
+}
+
+struct Start {
+    sig12: f64,
+    salp1: f64,
+    calp1: f64,
+    salp2: f64,
+    calp2: f64,
+    dnm: f64,
+}
+
+impl Geodesic {
+    /// Distance (`s12b`) and reduced length (`m12b`) in units of `b`, plus `m0`.
+    fn lengths(
+        &self,
+        eps: f64,
+        sig12: f64,
+        (ssig1, csig1, dn1): (f64, f64, f64),
+        (ssig2, csig2, dn2): (f64, f64, f64),
+    ) -> (f64, f64, f64) {
+        let mut c1a = [0.0; ORDER + 1];
+        let mut c2a = [0.0; ORDER + 1];
+        let a1 = series::a1m1f(eps);
+        series::c1f(eps, &mut c1a);
+        let a2 = series::a2m1f(eps);
+        series::c2f(eps, &mut c2a);
+        let m0x = a1 - a2;
+        let a1 = 1.0 + a1;
+        let a2 = 1.0 + a2;
+        let b1 =
+            sin_cos_series(true, ssig2, csig2, &c1a) - sin_cos_series(true, ssig1, csig1, &c1a);
+        let s12b = a1 * (sig12 + b1);
+        let b2 =
+            sin_cos_series(true, ssig2, csig2, &c2a) - sin_cos_series(true, ssig1, csig1, &c2a);
+        let j12 = m0x * sig12 + (a1 * b1 - a2 * b2);
+        let m12b = dn2 * (csig1 * ssig2) - dn1 * (ssig1 * csig2) - csig1 * csig2 * j12;
+        (s12b, m12b, m0x)
+    }
+
+    fn inverse_start(
+        &self,
+        (sbet1, cbet1): (f64, f64),
+        (sbet2, cbet2): (f64, f64),
+        lam12: f64,
+        slam12: f64,
+        clam12: f64,
+    ) -> Start {
+        let mut out = Start {
+            sig12: -1.0,
+            salp1: 0.0,
+            calp1: 0.0,
+            salp2: f64::NAN,
+            calp2: f64::NAN,
+            dnm: f64::NAN,
+        };
+        let sbet12 = sbet2 * cbet1 - cbet2 * sbet1;
+        let cbet12 = cbet2 * cbet1 + sbet2 * sbet1;
+        let sbet12a = sbet2 * cbet1 + cbet2 * sbet1;
+        let shortline = cbet12 >= 0.0 && sbet12 < 0.5 && cbet2 * lam12 < 0.5;
+        let (mut somg12, mut comg12) = (slam12, clam12);
+        if shortline {
+            let mut sbetm2 = sq(sbet1 + sbet2);
+            sbetm2 /= sbetm2 + sq(cbet1 + cbet2);
+            out.dnm = (1.0 + self.ep2 * sbetm2).sqrt();
+            let omg12 = lam12 / (self.f1 * out.dnm);
+            somg12 = omg12.sin();
+            comg12 = omg12.cos();
+        }
+        out.salp1 = cbet2 * somg12;
+        out.calp1 = if comg12 >= 0.0 {
+            sbet12 + cbet2 * sbet1 * sq(somg12) / (1.0 + comg12)
+        } else {
+            sbet12a - cbet2 * sbet1 * sq(somg12) / (1.0 - comg12)
+        };
+        let ssig12 = out.salp1.hypot(out.calp1);
+        let csig12 = sbet1 * sbet2 + cbet1 * cbet2 * comg12;
+
+        if shortline && ssig12 < self.etol2 {
+            let salp2 = cbet1 * somg12;
+            let calp2 = sbet12
+                - cbet1
+                    * sbet2
+                    * if comg12 >= 0.0 {
+                        sq(somg12) / (1.0 + comg12)
+                    } else {
+                        1.0 - comg12
+                    };
+            (out.salp2, out.calp2) = norm(salp2, calp2);
+            out.sig12 = ssig12.atan2(csig12);
+        } else if self.n.abs() >= 0.1
+            || csig12 >= 0.0
+            || ssig12 >= 6.0 * self.n.abs() * std::f64::consts::PI * sq(cbet1)
+        {
+            // Zeroth-order spherical approximation is adequate.
+        } else {
+            // Nearly antipodal: solve the astroid problem.
+            let lam12x = (-slam12).atan2(-clam12);
+            let k2 = sq(sbet1) * self.ep2;
+            let eps = k2 / (2.0 * (1.0 + (1.0 + k2).sqrt()) + k2);
+            let lamscale = self.f * cbet1 * self.a3f(eps) * std::f64::consts::PI;
+            let betscale = lamscale * cbet1;
+            let x = lam12x / lamscale;
+            let y = sbet12a / betscale;
+            if y > -tol1() && x > -1.0 - xthresh() {
+                out.salp1 = (-x).min(1.0);
+                out.calp1 = -(1.0 - sq(out.salp1)).sqrt();
+            } else {
+                let k = astroid(x, y);
+                let omg12a = lamscale * (-x * k / (1.0 + k));
+                let somg12 = omg12a.sin();
+                let comg12 = -omg12a.cos();
+                out.salp1 = cbet2 * somg12;
+                out.calp1 = sbet12a - cbet2 * sbet1 * sq(somg12) / (1.0 - comg12);
+            }
+        }
+        if out.salp1 > 0.0 || out.salp1.is_nan() {
+            (out.salp1, out.calp1) = norm(out.salp1, out.calp1);
+        } else {
+            out.salp1 = 1.0;
+            out.calp1 = 0.0;
+        }
+        out
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn lambda12(
+        &self,
+        (sbet1, cbet1, dn1): (f64, f64, f64),
+        (sbet2, cbet2, dn2): (f64, f64, f64),
+        salp1: f64,
+        calp1: f64,
+        slam120: f64,
+        clam120: f64,
+        diffp: bool,
+    ) -> Lambda12 {
+        let calp1 = if sbet1 == 0.0 && calp1 == 0.0 {
+            -tiny()
+        } else {
+            calp1
+        };
+        let salp0 = salp1 * cbet1;
+        let calp0 = calp1.hypot(salp1 * sbet1);
+        let somg1 = salp0 * sbet1;
+        let comg1 = calp1 * cbet1;
+        let (ssig1, csig1) = norm(sbet1, comg1);
+
+        let salp2 = if cbet2 != cbet1 { salp0 / cbet2 } else { salp1 };
+        let calp2 = if cbet2 != cbet1 || sbet2.abs() != -sbet1 {
+            (sq(calp1 * cbet1)
+                + if cbet1 < -sbet1 {
+                    (cbet2 - cbet1) * (cbet1 + cbet2)
+                } else {
+                    (sbet1 - sbet2) * (sbet1 + sbet2)
+                })
+            .sqrt()
+                / cbet2
+        } else {
+            calp1.abs()
+        };
+        let somg2 = salp0 * sbet2;
+        let comg2 = calp2 * cbet2;
+        let (ssig2, csig2) = norm(sbet2, comg2);
+
+        let sig12 = (csig1 * ssig2 - ssig1 * csig2)
+            .max(0.0)
+            .atan2(csig1 * csig2 + ssig1 * ssig2);
+        let somg12 = (comg1 * somg2 - somg1 * comg2).max(0.0);
+        let comg12 = comg1 * comg2 + somg1 * somg2;
+        let eta = (somg12 * clam120 - comg12 * slam120).atan2(comg12 * clam120 + somg12 * slam120);
+
+        let k2 = sq(calp0) * self.ep2;
+        let eps = k2 / (2.0 * (1.0 + (1.0 + k2).sqrt()) + k2);
+        let mut c3a = [0.0; ORDER];
+        self.c3f(eps, &mut c3a);
+        let b312 =
+            sin_cos_series(true, ssig2, csig2, &c3a) - sin_cos_series(true, ssig1, csig1, &c3a);
+        let domg12 = -self.f * self.a3f(eps) * salp0 * (sig12 + b312);
+        let lam12 = eta + domg12;
+
+        let dlam12 = if !diffp {
+            f64::NAN
+        } else if calp2 == 0.0 {
+            -2.0 * self.f1 * dn1 / sbet1
+        } else {
+            let (_, m12b, _) = self.lengths(eps, sig12, (ssig1, csig1, dn1), (ssig2, csig2, dn2));
+            m12b * self.f1 / (calp2 * cbet2)
+        };
+
+        Lambda12 {
+            lam12,
+            salp2,
+            calp2,
+            sig12,
+            ssig1,
+            csig1,
+            ssig2,
+            csig2,
+            eps,
+            domg12,
+            dlam12,
+        }
+    }
+
+    /// Solve the inverse problem for latitudes/longitudes in degrees.
+    ///
+    /// The area term `S12` is only evaluated when `with_area` is set.
+    pub(super) fn gen_inverse(
+        &self,
+        lat1: f64,
+        lon1: f64,
+        lat2: f64,
+        lon2: f64,
+        with_area: bool,
+    ) -> InverseSolution {
+        let (lon12, lon12s) = ang_diff(lon1, lon2);
+        let mut lonsign: f64 = if lon12 >= 0.0 { 1.0 } else { -1.0 };
+        let lon12 = lonsign * ang_round(lon12);
+        let lon12s = ang_round((180.0 - lon12) - lonsign * lon12s);
+        let lam12 = lon12.to_radians();
+        let (slam12, clam12) = if lon12 > 90.0 {
+            let (s, c) = sincosd(lon12s);
+            (s, -c)
+        } else {
+            sincosd(lon12)
+        };
+
+        let mut lat1 = ang_round(lat_fix(lat1));
+        let mut lat2 = ang_round(lat_fix(lat2));
+        let swapp: f64 = if lat1.abs() < lat2.abs() { -1.0 } else { 1.0 };
+        if swapp < 0.0 {
+            lonsign = -lonsign;
+            std::mem::swap(&mut lat1, &mut lat2);
+        }
+        let latsign: f64 = if lat1 < 0.0 { 1.0 } else { -1.0 };
+        lat1 *= latsign;
+        lat2 *= latsign;
+
+        let (sbet1, cbet1) = sincosd(lat1);
+        let (sbet1, cbet1) = norm(self.f1 * sbet1, cbet1);
+        let cbet1 = cbet1.max(tiny());
+        let (sbet2, cbet2) = sincosd(lat2);
+        let (mut sbet2, cbet2) = norm(self.f1 * sbet2, cbet2);
+        let mut cbet2 = cbet2.max(tiny());
+        if cbet1 < -sbet1 {
+            if cbet2 == cbet1 {
+                sbet2 = sbet1.copysign(sbet2);
+            }
+        } else if sbet2.abs() == -sbet1 {
+            cbet2 = cbet1;
+        }
+        let dn1 = (1.0 + self.ep2 * sq(sbet1)).sqrt();
+        let dn2 = (1.0 + self.ep2 * sq(sbet2)).sqrt();
+
+        let mut a12 = f64::NAN;
+        let mut s12x = f64::NAN;
+        let mut sig12;
+        let (mut salp1, mut calp1, mut salp2, mut calp2);
+        let mut omg12 = 0.0;
+        let mut somg12 = 2.0;
+        let mut comg12 = 0.0;
+
+        let mut meridian = lat1 == -90.0 || slam12 == 0.0;
+        if meridian {
+            calp1 = clam12;
+            salp1 = slam12;
+            calp2 = 1.0;
+            salp2 = 0.0;
+            let ssig1 = sbet1;
+            let csig1 = calp1 * cbet1;
+            let ssig2 = sbet2;
+            let csig2 = calp2 * cbet2;
+            sig12 = (csig1 * ssig2 - ssig1 * csig2)
+                .max(0.0)
+                .atan2(csig1 * csig2 + ssig1 * ssig2);
+            let (mut s, m, _) =
+                self.lengths(self.n, sig12, (ssig1, csig1, dn1), (ssig2, csig2, dn2));
+            if sig12 < 1.0 || m >= 0.0 {
+                if sig12 < 3.0 * tiny() || (sig12 < tol0() && (s < 0.0 || m < 0.0)) {
+                    sig12 = 0.0;
+                    s = 0.0;
+                }
+                s12x = s * self.b;
+                a12 = sig12.to_degrees();
+            } else {
+                meridian = false;
+            }
+        } else {
+            salp1 = f64::NAN;
+            calp1 = f64::NAN;
+            salp2 = f64::NAN;
+            calp2 = f64::NAN;
+        }
+
+        if !meridian && sbet1 == 0.0 && (self.f <= 0.0 || lon12s >= self.f * 180.0) {
+            // Equatorial geodesic.
+            calp1 = 0.0;
+            calp2 = 0.0;
+            salp1 = 1.0;
+            salp2 = 1.0;
+            s12x = self.a * lam12;
+            sig12 = lam12 / self.f1;
+            omg12 = sig12;
+            a12 = lon12 / self.f1;
+        } else if !meridian {
+            let start = self.inverse_start((sbet1, cbet1), (sbet2, cbet2), lam12, slam12, clam12);
+            sig12 = start.sig12;
+            salp1 = start.salp1;
+            calp1 = start.calp1;
+            if sig12 >= 0.0 {
+                // Short line with a closed-form solution.
+                salp2 = start.salp2;
+                calp2 = start.calp2;
+                s12x = sig12 * self.b * start.dnm;
+                a12 = sig12.to_degrees();
+                omg12 = lam12 / (self.f1 * start.dnm);
+            } else {
+                let mut numit = 0;
+                let mut tripn = false;
+                let mut tripb = false;
+                let (mut salp1a, mut calp1a) = (tiny(), 1.0);
+                let (mut salp1b, mut calp1b) = (tiny(), -1.0);
+                let mut sol;
+                loop {
+                    sol = self.lambda12(
+                        (sbet1, cbet1, dn1),
+                        (sbet2, cbet2, dn2),
+                        salp1,
+                        calp1,
+                        slam12,
+                        clam12,
+                        numit < MAXIT1,
+                    );
+                    let v = sol.lam12;
+                    if numit >= MAXIT2
+                        || tripb
+                        || v.is_nan()
+                        || v.abs() < if tripn { 8.0 } else { 1.0 } * tol0()
+                    {
+                        break;
+                    }
+                    if v > 0.0 && (numit > MAXIT1 || calp1 / salp1 > calp1b / salp1b) {
+                        salp1b = salp1;
+                        calp1b = calp1;
+                    } else if v < 0.0 && (numit > MAXIT1 || calp1 / salp1 < calp1a / salp1a) {
+                        salp1a = salp1;
+                        calp1a = calp1;
+                    }
+                    numit += 1;
+                    if numit < MAXIT1 && sol.dlam12 > 0.0 {
+                        let dalp1 = -v / sol.dlam12;
+                        let (sdalp1, cdalp1) = dalp1.sin_cos();
+                        let nsalp1 = salp1 * cdalp1 + calp1 * sdalp1;
+                        if nsalp1 > 0.0 && dalp1.abs() < std::f64::consts::PI {
+                            calp1 = calp1 * cdalp1 - salp1 * sdalp1;
+                            salp1 = nsalp1;
+                            (salp1, calp1) = norm(salp1, calp1);
+                            tripn = v.abs() <= 16.0 * tol0();
+                            continue;
+                        }
+                    }
+                    // Fall back to bisection.
+                    salp1 = (salp1a + salp1b) / 2.0;
+                    calp1 = (calp1a + calp1b) / 2.0;
+                    (salp1, calp1) = norm(salp1, calp1);
+                    tripn = false;
+                    tripb = (salp1a - salp1).abs() + (calp1a - calp1) < tolb()
+                        || (salp1 - salp1b).abs() + (calp1 - calp1b) < tolb();
+                }
+                salp2 = sol.salp2;
+                calp2 = sol.calp2;
+                sig12 = sol.sig12;
+                let (s, _, _) = self.lengths(
+                    sol.eps,
+                    sig12,
+                    (sol.ssig1, sol.csig1, dn1),
+                    (sol.ssig2, sol.csig2, dn2),
+                );
+                s12x = s * self.b;
+                a12 = sig12.to_degrees();
+                let (sdomg12, cdomg12) = sol.domg12.sin_cos();
+                somg12 = slam12 * cdomg12 - clam12 * sdomg12;
+                comg12 = clam12 * cdomg12 + slam12 * sdomg12;
+            }
+        }
+
+        let mut area = f64::NAN;
+        if with_area {
+            area = self.area_term(
+                (salp1, calp1, salp2, calp2),
+                (sbet1, cbet1, sbet2, cbet2),
+                meridian,
+                (omg12, somg12, comg12),
+            ) * swapp
+                * lonsign
+                * latsign
+                + 0.0;
+        }
+
+        if swapp < 0.0 {
+            std::mem::swap(&mut salp1, &mut salp2);
+            std::mem::swap(&mut calp1, &mut calp2);
+        }
+        InverseSolution {
+            a12,
+            s12: s12x + 0.0,
+            salp1: salp1 * swapp * lonsign,
+            calp1: calp1 * swapp * latsign,
+            salp2: salp2 * swapp * lonsign,
+            calp2: calp2 * swapp * latsign,
+            area,
+        }
+    }
+
+    /// Area between the geodesic and the equator, before sign normalization.
+    fn area_term(
+        &self,
+        (salp1, calp1, salp2, calp2): (f64, f64, f64, f64),
+        (sbet1, cbet1, sbet2, cbet2): (f64, f64, f64, f64),
+        meridian: bool,
+        (omg12, mut somg12, mut comg12): (f64, f64, f64),
+    ) -> f64 {
+        let salp0 = salp1 * cbet1;
+        let calp0 = calp1.hypot(salp1 * sbet1);
+        let mut s12 = 0.0;
+        if calp0 != 0.0 && salp0 != 0.0 {
+            let (ssig1, csig1) = norm(sbet1, calp1 * cbet1);
+            let (ssig2, csig2) = norm(sbet2, calp2 * cbet2);
+            let k2 = sq(calp0) * self.ep2;
+            let eps = k2 / (2.0 * (1.0 + (1.0 + k2).sqrt()) + k2);
+            let a4 = sq(self.a) * calp0 * salp0 * self.e2;
+            let mut c4a = [0.0; ORDER];
+            self.c4f(eps, &mut c4a);
+            let b41 = sin_cos_series(false, ssig1, csig1, &c4a);
+            let b42 = sin_cos_series(false, ssig2, csig2, &c4a);
+            s12 = a4 * (b42 - b41);
+        }
+        if !meridian && somg12 > 1.0 {
+            (somg12, comg12) = omg12.sin_cos();
+        }
+        let alp12 =
+            if !meridian && comg12 > -std::f64::consts::FRAC_1_SQRT_2 && sbet2 - sbet1 < 1.75 {
+                let domg12 = 1.0 + comg12;
+                let dbet1 = 1.0 + cbet1;
+                let dbet2 = 1.0 + cbet2;
+                2.0 * (somg12 * (sbet1 * dbet2 + sbet2 * dbet1))
+                    .atan2(domg12 * (sbet1 * sbet2 + dbet1 * dbet2))
+            } else {
+                let mut salp12 = salp2 * calp1 - calp2 * salp1;
+                let mut calp12 = calp2 * calp1 + salp2 * salp1;
+                if salp12 == 0.0 && calp12 < 0.0 {
+                    salp12 = tiny() * calp1;
+                    calp12 = -1.0;
+                }
+                salp12.atan2(calp12)
+            };
+        s12 + self.c2 * alp12
+    }
+}
+
+/// Solve the astroid equation for `k` (Karney 2013, eq. 55).
+fn astroid(x: f64, y: f64) -> f64 {
+    let p = sq(x);
+    let q = sq(y);
+    let r = (p + q - 1.0) / 6.0;
+    if q == 0.0 && r <= 0.0 {
+        return 0.0;
+    }
+    let s = p * q / 4.0;
+    let r2 = sq(r);
+    let r3 = r * r2;
+    let disc = s * (s + 2.0 * r3);
+    let mut u = r;
+    if disc >= 0.0 {
+        let mut t3 = s + r3;
+        t3 += if t3 < 0.0 { -disc.sqrt() } else { disc.sqrt() };
+        let t = t3.cbrt();
+        u += t + if t != 0.0 { r2 / t } else { 0.0 };
+    } else {
+        let ang = (-disc).sqrt().atan2(-(s + r3));
+        u += 2.0 * r * (ang / 3.0).cos();
+    }
+    let v = (sq(u) + q).sqrt();
+    let uv = if u < 0.0 { q / (v - u) } else { u + v };
+    let w = (uv - q) / (2.0 * v);
+    uv / ((uv + sq(w)).sqrt() + w)
+}
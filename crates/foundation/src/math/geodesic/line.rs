@@ -0,0 +1,126 @@
+//! Geodesic lines: positions at a given distance from a fixed start point.
+
+use super::math::{
+    ang_normalize, ang_round, atan2d, lat_fix, norm, sin_cos_series, sincosd, sq, tiny,
+};
+use super::series::{self, ORDER};
+use super::{Geodesic, GeodesicDirect};
+use crate::math::Geodetic;
+
+/// A geodesic from a start point and azimuth, for repeated direct solutions.
+#[derive(Debug, Clone)]
+pub struct GeodesicLine {
+    lon1: f64,
+    alt_m: f64,
+    f1: f64,
+    b: f64,
+    salp0: f64,
+    calp0: f64,
+    ssig1: f64,
+    csig1: f64,
+    somg1: f64,
+    comg1: f64,
+    stau1: f64,
+    ctau1: f64,
+    a1m1: f64,
+    b11: f64,
+    a3c: f64,
+    b31: f64,
+    c1pa: [f64; ORDER + 1],
+    c3a: [f64; ORDER],
+}
+
+impl GeodesicLine {
+    pub(super) fn new(geod: &Geodesic, from: Geodetic, azimuth_deg: f64) -> Self {
+        let lat1 = lat_fix(from.lat_deg());
+        let (salp1, calp1) = sincosd(ang_round(azimuth_deg));
+        let (sbet1, cbet1) = sincosd(ang_round(lat1));
+        let (sbet1, cbet1) = norm(geod.f1 * sbet1, cbet1);
+        let cbet1 = cbet1.max(tiny());
+        let salp0 = salp1 * cbet1;
+        let calp0 = calp1.hypot(salp1 * sbet1);
+        let somg1 = salp0 * sbet1;
+        let comg1 = if sbet1 != 0.0 || calp1 != 0.0 {
+            cbet1 * calp1
+        } else {
+            1.0
+        };
+        let (ssig1, csig1) = norm(sbet1, comg1);
+        let k2 = sq(calp0) * geod.ep2;
+        let eps = k2 / (2.0 * (1.0 + (1.0 + k2).sqrt()) + k2);
+
+        let a1m1 = series::a1m1f(eps);
+        let mut c1a = [0.0; ORDER + 1];
+        series::c1f(eps, &mut c1a);
+        let b11 = sin_cos_series(true, ssig1, csig1, &c1a);
+        let (s, c) = b11.sin_cos();
+        let stau1 = ssig1 * c + csig1 * s;
+        let ctau1 = csig1 * c - ssig1 * s;
+        let mut c1pa = [0.0; ORDER + 1];
+        series::c1pf(eps, &mut c1pa);
+
+        let mut c3a = [0.0; ORDER];
+        geod.c3f(eps, &mut c3a);
+        let a3c = -geod.f * salp0 * geod.a3f(eps);
+        let b31 = sin_cos_series(true, ssig1, csig1, &c3a);
+
+        Self {
+            lon1: from.lon_deg(),
+            alt_m: from.alt_m,
+            f1: geod.f1,
+            b: geod.b,
+            salp0,
+            calp0,
+            ssig1,
+            csig1,
+            somg1,
+            comg1,
+            stau1,
+            ctau1,
+            a1m1,
+            b11,
+            a3c,
+            b31,
+            c1pa,
+            c3a,
+        }
+    }
+
+    /// Position and azimuth `distance_m` along the line (negative goes backwards).
+    pub fn position(&self, distance_m: f64) -> GeodesicDirect {
+        let tau12 = distance_m / (self.b * (1.0 + self.a1m1));
+        let (s, c) = tau12.sin_cos();
+        let b12 = -sin_cos_series(
+            true,
+            self.stau1 * c + self.ctau1 * s,
+            self.ctau1 * c - self.stau1 * s,
+            &self.c1pa,
+        );
+        let sig12 = tau12 - (b12 - self.b11);
+        let (ssig12, csig12) = sig12.sin_cos();
+        let ssig2 = self.ssig1 * csig12 + self.csig1 * ssig12;
+        let mut csig2 = self.csig1 * csig12 - self.ssig1 * ssig12;
+        let sbet2 = self.calp0 * ssig2;
+        let mut cbet2 = self.salp0.hypot(self.calp0 * csig2);
+        if cbet2 == 0.0 {
+            cbet2 = tiny();
+            csig2 = tiny();
+        }
+        let salp2 = self.salp0;
+        let calp2 = self.calp0 * csig2;
+
+        let somg2 = self.salp0 * ssig2;
+        let comg2 = csig2;
+        let omg12 = (somg2 * self.comg1 - comg2 * self.somg1)
+            .atan2(comg2 * self.comg1 + somg2 * self.somg1);
+        let lam12 =
+            omg12 + self.a3c * (sig12 + (sin_cos_series(true, ssig2, csig2, &self.c3a) - self.b31));
+        let lon2 = ang_normalize(ang_normalize(self.lon1) + ang_normalize(lam12.to_degrees()));
+        let lat2 = atan2d(sbet2, self.f1 * cbet2);
+
+        GeodesicDirect {
+            position: Geodetic::from_degrees(lat2, lon2, self.alt_m),
+            azimuth2_deg: atan2d(salp2, calp2),
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! Angle and series helpers shared by the geodesic solver.
+//!
+//! These follow GeographicLib's `Math` conventions: angles in degrees are
+//! reduced exactly (via `%`) before conversion to radians so that multiples
+//! of 90° produce exact sines and cosines.
+
+/// Smallest positive normal number scaled as in GeographicLib (`sqrt(MIN_POSITIVE)`).
+pub(super) fn tiny() -> f64 {
+    f64::MIN_POSITIVE.sqrt()
+}
+
+#[inline]
+pub(super) fn sq(x: f64) -> f64 {
+    x * x
+}
+
+/// Evaluate the polynomial `p[s..=s+n]` (highest order first) at `x`.
+pub(super) fn polyval(n: isize, p: &[f64], s: usize, x: f64) -> f64 {
+    if n < 0 {
+        return 0.0;
+    }
+    let mut y = p[s];
+    for i in 1..=n as usize {
+        y = y * x + p[s + i];
+    }
+    y
+}
+
+/// Error-free sum: returns `(u + v, error)`.
+pub(super) fn sum(u: f64, v: f64) -> (f64, f64) {
+    let s = u + v;
+    let up = s - v;
+    let vpp = s - up;
+    let t = -((up - u) + (vpp - v));
+    (s, t)
+}
+
+/// Round tiny angles to zero so that they behave symmetrically.
+pub(super) fn ang_round(x: f64) -> f64 {
+    let z = 1.0 / 16.0;
+    if x == 0.0 {
+        return 0.0;
+    }
+    let mut y = x.abs();
+    if y < z {
+        y = z - (z - y);
+    }
+    y.copysign(x)
+}
+
+/// Reduce an angle in degrees to `(-180, 180]`.
+pub(super) fn ang_normalize(x: f64) -> f64 {
+    let y = x % 360.0;
+    if y <= -180.0 {
+        y + 360.0
+    } else if y > 180.0 {
+        y - 360.0
+    } else {
+        y
+    }
+}
+
+/// Exact difference `y - x` in degrees, reduced to `(-180, 180]`, plus rounding error.
+pub(super) fn ang_diff(x: f64, y: f64) -> (f64, f64) {
+    let (d, t) = sum(ang_normalize(-x), ang_normalize(y));
+    let d = ang_normalize(d);
+    sum(if d == 180.0 && t > 0.0 { -180.0 } else { d }, t)
+}
+
+/// Latitudes outside `[-90, 90]` become NaN.
+pub(super) fn lat_fix(x: f64) -> f64 {
+    if x.abs() > 90.0 { f64::NAN } else { x }
+}
+
+/// Sine and cosine of an angle in degrees, exact for multiples of 90°.
+pub(super) fn sincosd(x: f64) -> (f64, f64) {
+    let mut r = x % 360.0;
+    let q = if r.is_nan() {
+        0
+    } else {
+        (r / 90.0).round() as i64
+    };
+    r -= 90.0 * q as f64;
+    let r = r.to_radians();
+    let (s, c) = (r.sin(), r.cos());
+    let (mut s, c) = match q.rem_euclid(4) {
+        0 => (s, c),
+        1 => (c, -s),
+        2 => (-s, -c),
+        _ => (-c, s),
+    };
+    if x == 0.0 {
+        s = x;
+    }
+    (s, c + 0.0)
+}
+
+/// `atan2(y, x)` in degrees with exact results at the quadrant boundaries.
+pub(super) fn atan2d(y: f64, x: f64) -> f64 {
+    let (mut y, mut x) = (y, x);
+    let mut q = 0;
+    if y.abs() > x.abs() {
+        std::mem::swap(&mut x, &mut y);
+        q = 2;
+    }
+    if x < 0.0 {
+        q += 1;
+        x = -x;
+    }
+    let ang = y.atan2(x).to_degrees();
+    match q {
+        1 => (if y >= 0.0 { 180.0 } else { -180.0 }) - ang,
+        2 => 90.0 - ang,
+        3 => -90.0 + ang,
+        _ => ang,
+    }
+}
+
+/// Normalize `(x, y)` to unit length.
+pub(super) fn norm(x: f64, y: f64) -> (f64, f64) {
+    let r = x.hypot(y);
+    (x / r, y / r)
+}
+
+/// Clenshaw summation of `sum(c[l] * sin(2 l x))` (`sinp`) or `sum(c[l] * cos((2 l + 1) x))`.
+///
+/// For the sine series `c[0]` is unused.
+pub(super) fn sin_cos_series(sinp: bool, sinx: f64, cosx: f64, c: &[f64]) -> f64 {
+    let mut k = c.len();
+    let mut n = k - usize::from(sinp);
+    let ar = 2.0 * (cosx - sinx) * (cosx + sinx);
+    let mut y1 = 0.0;
+    let mut y0 = if n & 1 == 1 {
+        k -= 1;
+        c[k]
+    } else {
+        0.0
+    };
+    n /= 2;
+    while n > 0 {
+        n -= 1;
+        k -= 1;
+        y1 = ar * y0 - y1 + c[k];
+        k -= 1;
+        y0 = ar * y1 - y0 + c[k];
+    }
+    if sinp {
+        2.0 * sinx * cosx * y0
+    } else {
+        cosx * (y0 - y1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ang_diff, ang_normalize, atan2d, sincosd};
+
+    #[test]
+    fn sincosd_is_exact_at_quadrants() {
+        assert_eq!(sincosd(90.0), (1.0, 0.0));
+        assert_eq!(sincosd(180.0), (0.0, -1.0));
+        assert_eq!(sincosd(-90.0), (-1.0, 0.0));
+        assert_eq!(atan2d(1.0, 0.0), 90.0);
+        assert_eq!(atan2d(0.0, -1.0), 180.0);
+    }
+
+    #[test]
+    fn angles_normalize() {
+        assert_eq!(ang_normalize(540.0), 180.0);
+        assert_eq!(ang_normalize(-180.0), 180.0);
+        assert_eq!(ang_diff(170.0, -170.0).0, 20.0);
+    }
+}
@@ -0,0 +1,276 @@
+//! Ellipsoidal geodesics (Karney 2013, "Algorithms for geodesics").
+//!
+//! Solves the direct and inverse problems to round-off accuracy on any oblate
+//! ellipsoid, and integrates the geodesic area term used for polygon areas.
+
+mod inverse;
+mod line;
+mod math;
+mod polygon;
+mod series;
+
+pub use line::*;
+pub use polygon::*;
+
+use super::{Ellipsoid, Geodetic};
+use series::{N_C3X, N_C4X, ORDER};
+
+/// Solution of the inverse geodesic problem.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GeodesicInverse {
+    /// Geodesic distance (meters).
+    pub distance_m: f64,
+    /// Forward azimuth at the start point (degrees clockwise from north).
+    pub azimuth1_deg: f64,
+    /// Forward azimuth at the end point (degrees clockwise from north).
+    pub azimuth2_deg: f64,
+    /// Arc length on the auxiliary sphere (degrees).
+    pub arc_deg: f64,
+}
+
+impl GeodesicInverse {
+    /// Back azimuth at the end point, pointing towards the start (degrees).
+    pub fn back_azimuth_deg(&self) -> f64 {
+        math::ang_normalize(self.azimuth2_deg + 180.0)
+    }
+}
+
+/// Solution of the direct geodesic problem.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GeodesicDirect {
+    /// Destination point (altitude is copied from the start point).
+    pub position: Geodetic,
+    /// Forward azimuth at the destination (degrees).
+    pub azimuth2_deg: f64,
+}
+
+/// Geodesic solver for a given ellipsoid.
+#[derive(Debug, Clone)]
+pub struct Geodesic {
+    a: f64,
+    f: f64,
+    f1: f64,
+    e2: f64,
+    ep2: f64,
+    n: f64,
+    b: f64,
+    c2: f64,
+    etol2: f64,
+    a3x: [f64; ORDER],
+    c3x: [f64; N_C3X],
+    c4x: [f64; N_C4X],
+}
+
+impl Default for Geodesic {
+    fn default() -> Self {
+        Self::wgs84()
+    }
+}
+
+impl Geodesic {
+    /// Build a solver for an oblate ellipsoid (`f >= 0`).
+    pub fn new(ellipsoid: Ellipsoid) -> Self {
+        let a = ellipsoid.a;
+        let f = ellipsoid.f.max(0.0);
+        let f1 = 1.0 - f;
+        let e2 = f * (2.0 - f);
+        let ep2 = e2 / (f1 * f1);
+        let n = f / (2.0 - f);
+        let b = a * f1;
+        let c2 = if e2 == 0.0 {
+            (a * a + b * b) / 2.0
+        } else {
+            (a * a + b * b * e2.sqrt().atanh() / e2.sqrt()) / 2.0
+        };
+        let tol2 = f64::EPSILON.sqrt();
+        let etol2 = 0.1 * tol2 / (f.max(0.001) * (1.0 - f / 2.0).min(1.0) / 2.0).sqrt();
+        Self {
+            a,
+            f,
+            f1,
+            e2,
+            ep2,
+            n,
+            b,
+            c2,
+            etol2,
+            a3x: series::a3_coeffs(n),
+            c3x: series::c3_coeffs(n),
+            c4x: series::c4_coeffs(n),
+        }
+    }
+
+    pub fn wgs84() -> Self {
+        Self::new(Ellipsoid::WGS84)
+    }
+
+    /// Total surface area of the ellipsoid (square meters).
+    pub fn ellipsoid_area_m2(&self) -> f64 {
+        4.0 * std::f64::consts::PI * self.c2
+    }
+
+    /// Shortest geodesic between two points.
+    pub fn inverse(&self, from: Geodetic, to: Geodetic) -> GeodesicInverse {
+        let r = self.gen_inverse(
+            from.lat_deg(),
+            from.lon_deg(),
+            to.lat_deg(),
+            to.lon_deg(),
+            false,
+        );
+        GeodesicInverse {
+            distance_m: r.s12,
+            azimuth1_deg: math::atan2d(r.salp1, r.calp1),
+            azimuth2_deg: math::atan2d(r.salp2, r.calp2),
+            arc_deg: r.a12,
+        }
+    }
+
+    /// Point reached by travelling `distance_m` from `from` along `azimuth_deg`.
+    pub fn direct(&self, from: Geodetic, azimuth_deg: f64, distance_m: f64) -> GeodesicDirect {
+        self.line(from, azimuth_deg).position(distance_m)
+    }
+
+    /// Geodesic line starting at `from` with initial azimuth `azimuth_deg`.
+    pub fn line(&self, from: Geodetic, azimuth_deg: f64) -> GeodesicLine {
+        GeodesicLine::new(self, from, azimuth_deg)
+    }
+
+    /// Points along the geodesic from `from` to `to`, inclusive of both ends.
+    ///
+    /// `segments` equal-length pieces produce `segments + 1` points; zero is treated as one.
+    pub fn waypoints(&self, from: Geodetic, to: Geodetic, segments: usize) -> Vec<Geodetic> {
+        let segments = segments.max(1);
+        let inv = self.inverse(from, to);
+        let line = self.line(from, inv.azimuth1_deg);
+        let mut out = Vec::with_capacity(segments + 1);
+        out.push(from);
+        for i in 1..segments {
+            let s = inv.distance_m * i as f64 / segments as f64;
+            let mut p = line.position(s).position;
+            p.alt_m = from.alt_m + (to.alt_m - from.alt_m) * i as f64 / segments as f64;
+            out.push(p);
+        }
+        out.push(to);
+        out
+    }
+
+    /// Waypoints spaced at most `max_spacing_m` apart along the geodesic.
+    pub fn waypoints_by_spacing(
+        &self,
+        from: Geodetic,
+        to: Geodetic,
+        max_spacing_m: f64,
+    ) -> Vec<Geodetic> {
+        let distance = self.inverse(from, to).distance_m;
+        let segments = if max_spacing_m > 0.0 && distance.is_finite() {
+            (distance / max_spacing_m).ceil() as usize
+        } else {
+            1
+        };
+        self.waypoints(from, to, segments)
+    }
+
+    fn a3f(&self, eps: f64) -> f64 {
+        math::polyval(ORDER as isize - 1, &self.a3x, 0, eps)
+    }
+
+    fn c3f(&self, eps: f64, c: &mut [f64; ORDER]) {
+        let mut mult = 1.0;
+        let mut o = 0;
+        for (l, slot) in c.iter_mut().enumerate().skip(1) {
+            let m = ORDER - l - 1;
+            mult *= eps;
+            *slot = mult * math::polyval(m as isize, &self.c3x, o, eps);
+            o += m + 1;
+        }
+    }
+
+    fn c4f(&self, eps: f64, c: &mut [f64; ORDER]) {
+        let mut mult = 1.0;
+        let mut o = 0;
+        for (l, slot) in c.iter_mut().enumerate() {
+            let m = ORDER - l - 1;
+            *slot = mult * math::polyval(m as isize, &self.c4x, o, eps);
+            o += m + 1;
+            mult *= eps;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Geodesic;
+    use crate::math::Geodetic;
+
+    fn assert_close(a: f64, b: f64, eps: f64) {
+        let diff = (a - b).abs();
+        assert!(diff <= eps, "expected {a} ~= {b} (diff {diff})");
+    }
+
+    #[test]
+    fn inverse_matches_reference_jfk_to_cdg() {
+        let g = Geodesic::wgs84();
+        let r = g.inverse(
+            Geodetic::from_degrees(40.6, -73.8, 0.0),
+            Geodetic::from_degrees(49.01666667, 2.55, 0.0),
+        );
+        assert_close(r.azimuth1_deg, 53.470_218_24, 1e-8);
+        assert_close(r.azimuth2_deg, 111.593_669_51, 1e-8);
+        assert_close(r.distance_m, 5_853_226.256, 1e-3);
+    }
+
+    #[test]
+    fn direct_matches_karney_example() {
+        let g = Geodesic::wgs84();
+        let r = g.direct(Geodetic::from_degrees(40.0, 0.0, 0.0), 30.0, 10_000_000.0);
+        assert_close(r.position.lat_deg(), 41.793_310_205_06, 1e-10);
+        assert_close(r.position.lon_deg(), 137.844_900_043_77, 1e-10);
+        assert_close(r.azimuth2_deg, 149.090_169_318_07, 1e-10);
+    }
+
+    #[test]
+    fn equator_and_meridian_lengths() {
+        let g = Geodesic::wgs84();
+        let eq = g.inverse(
+            Geodetic::from_degrees(0.0, 0.0, 0.0),
+            Geodetic::from_degrees(0.0, 1.0, 0.0),
+        );
+        assert_close(eq.distance_m, 111_319.490_793_273_6, 1e-6);
+        assert_close(eq.azimuth1_deg, 90.0, 1e-12);
+
+        let quarter = g.inverse(
+            Geodetic::from_degrees(0.0, 0.0, 0.0),
+            Geodetic::from_degrees(90.0, 0.0, 0.0),
+        );
+        assert_close(quarter.distance_m, 10_001_965.729, 1e-3);
+    }
+
+    #[test]
+    fn nearly_antipodal_points_converge() {
+        let g = Geodesic::wgs84();
+        let from = Geodetic::from_degrees(0.5, 0.0, 0.0);
+        let to = Geodetic::from_degrees(-0.5, 179.7, 0.0);
+        let r = g.inverse(from, to);
+        assert!(r.distance_m.is_finite() && r.distance_m > 19_900_000.0);
+        let back = g.direct(from, r.azimuth1_deg, r.distance_m);
+        assert_close(back.position.lat_deg(), -0.5, 1e-9);
+        assert_close(back.position.lon_deg(), 179.7, 1e-9);
+    }
+
+    #[test]
+    fn waypoints_lie_on_the_geodesic() {
+        let g = Geodesic::wgs84();
+        let from = Geodetic::from_degrees(-33.9, 151.2, 0.0);
+        let to = Geodetic::from_degrees(51.5, -0.1, 0.0);
+        let total = g.inverse(from, to).distance_m;
+        let pts = g.waypoints(from, to, 8);
+        assert_eq!(pts.len(), 9);
+        let mut sum = 0.0;
+        for pair in pts.windows(2) {
+            sum += g.inverse(pair[0], pair[1]).distance_m;
+        }
+        assert_close(sum, total, 1e-3);
+        assert_eq!(g.waypoints_by_spacing(from, to, 1_000_000.0).len(), 18);
+    }
+}
@@ -0,0 +1,135 @@
+//! Geodesic polygon area and perimeter (Karney 2013, §6).
+
+use super::Geodesic;
+use super::math::{ang_diff, ang_normalize};
+use crate::math::Geodetic;
+
+/// Area and perimeter of a closed geodesic polygon.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GeodesicPolygon {
+    /// Signed area (square meters); counter-clockwise rings are positive.
+    pub area_m2: f64,
+    /// Perimeter including the closing edge (meters).
+    pub perimeter_m: f64,
+}
+
+impl Geodesic {
+    /// Area and perimeter of the polygon whose edges are geodesics between `ring` vertices.
+    ///
+    /// The ring is closed implicitly; a repeated closing vertex is harmless. Rings with
+    /// fewer than three vertices have zero area.
+    pub fn polygon(&self, ring: &[Geodetic]) -> GeodesicPolygon {
+        let mut perimeter = 0.0;
+        let mut area = 0.0;
+        let mut crossings = 0i32;
+        for (i, a) in ring.iter().enumerate() {
+            let b = ring[(i + 1) % ring.len()];
+            let (lat1, lon1) = (a.lat_deg(), a.lon_deg());
+            let (lat2, lon2) = (b.lat_deg(), b.lon_deg());
+            let r = self.gen_inverse(lat1, lon1, lat2, lon2, true);
+            perimeter += r.s12;
+            area += r.area;
+            crossings += transit(lon1, lon2);
+        }
+        if ring.len() < 3 {
+            return GeodesicPolygon {
+                area_m2: 0.0,
+                perimeter_m: perimeter,
+            };
+        }
+
+        let total = self.ellipsoid_area_m2();
+        if crossings & 1 != 0 {
+            area += if area < 0.0 { total } else { -total } / 2.0;
+        }
+        // Edge terms accumulate clockwise-positive; report counter-clockwise-positive.
+        area = -area;
+        if area > total / 2.0 {
+            area -= total;
+        } else if area <= -total / 2.0 {
+            area += total;
+        }
+        GeodesicPolygon {
+            area_m2: area + 0.0,
+            perimeter_m: perimeter,
+        }
+    }
+
+    /// Length of a polyline whose segments are geodesics (meters).
+    pub fn path_length(&self, points: &[Geodetic]) -> f64 {
+        points
+            .windows(2)
+            .map(|pair| self.inverse(pair[0], pair[1]).distance_m)
+            .sum()
+    }
+}
+
+/// +1 / -1 when an edge crosses the prime meridian eastwards / westwards.
+fn transit(lon1: f64, lon2: f64) -> i32 {
+    let lon12 = ang_diff(lon1, lon2).0;
+    let lon1 = ang_normalize(lon1);
+    let lon2 = ang_normalize(lon2);
+    if lon12 > 0.0 && ((lon1 < 0.0 && lon2 >= 0.0) || (lon1 > 0.0 && lon2 == 0.0)) {
+        1
+    } else if lon12 < 0.0 && lon1 >= 0.0 && lon2 < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Geodesic;
+    use crate::math::Geodetic;
+
+    fn assert_close(a: f64, b: f64, eps: f64) {
+        let diff = (a - b).abs();
+        assert!(diff <= eps, "expected {a} ~= {b} (diff {diff})");
+    }
+
+    fn ring(points: &[(f64, f64)]) -> Vec<Geodetic> {
+        points
+            .iter()
+            .map(|&(lat, lon)| Geodetic::from_degrees(lat, lon, 0.0))
+            .collect()
+    }
+
+    #[test]
+    fn octant_is_one_eighth_of_the_ellipsoid() {
+        let g = Geodesic::wgs84();
+        let octant = ring(&[(0.0, 0.0), (0.0, 90.0), (90.0, 0.0)]);
+        let p = g.polygon(&octant);
+        assert_close(g.ellipsoid_area_m2(), 510_065_621_724_088.5, 1.0);
+        assert_close(p.area_m2, g.ellipsoid_area_m2() / 8.0, 1.0);
+        assert_close(p.perimeter_m, 30_022_685.630, 1e-3);
+
+        let mut reversed = octant.clone();
+        reversed.reverse();
+        assert_close(g.polygon(&reversed).area_m2, -p.area_m2, 1.0);
+    }
+
+    #[test]
+    fn small_square_crossing_the_antimeridian() {
+        let g = Geodesic::wgs84();
+        let east = g.polygon(&ring(&[
+            (0.0, 179.5),
+            (0.0, 180.5),
+            (1.0, 180.5),
+            (1.0, 179.5),
+        ]));
+        let west = g.polygon(&ring(&[(0.0, -0.5), (0.0, 0.5), (1.0, 0.5), (1.0, -0.5)]));
+        assert_close(east.area_m2, west.area_m2, 1e-3);
+        assert!(east.area_m2 > 1.2e10 && east.area_m2 < 1.3e10);
+    }
+
+    #[test]
+    fn degenerate_rings_have_no_area() {
+        let g = Geodesic::wgs84();
+        let line = ring(&[(0.0, 0.0), (0.0, 1.0)]);
+        let p = g.polygon(&line);
+        assert_eq!(p.area_m2, 0.0);
+        assert_close(p.perimeter_m, 2.0 * 111_319.490_793_273_6, 1e-6);
+        assert_eq!(g.polygon(&[]).perimeter_m, 0.0);
+    }
+}
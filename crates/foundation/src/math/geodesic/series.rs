@@ -0,0 +1,164 @@
+//! Series coefficients for the geodesic integrals (order 6, Karney 2013).
+
+use super::math::{polyval, sq};
+
+pub(super) const ORDER: usize = 6;
+pub(super) const N_C3X: usize = 15;
+pub(super) const N_C4X: usize = 21;
+
+/// `A1 - 1` for the distance integral.
+pub(super) fn a1m1f(eps: f64) -> f64 {
+    const COEFF: [f64; 5] = [1.0, 4.0, 64.0, 0.0, 256.0];
+    let t = polyval(3, &COEFF, 0, sq(eps)) / COEFF[4];
+    (t + eps) / (1.0 - eps)
+}
+
+/// `C1[l]` coefficients for the distance integral (`c[0]` unused).
+pub(super) fn c1f(eps: f64, c: &mut [f64; ORDER + 1]) {
+    const COEFF: [f64; 18] = [
+        -1.0, 6.0, -16.0, 32.0, //
+        -9.0, 64.0, -128.0, 2048.0, //
+        9.0, -16.0, 768.0, //
+        3.0, -5.0, 512.0, //
+        -7.0, 1280.0, //
+        -7.0, 2048.0,
+    ];
+    fill_series(eps, &COEFF, c);
+}
+
+/// `C1'[l]` coefficients for the inverse of the distance integral.
+pub(super) fn c1pf(eps: f64, c: &mut [f64; ORDER + 1]) {
+    const COEFF: [f64; 18] = [
+        205.0, -432.0, 768.0, 1536.0, //
+        4005.0, -4736.0, 3840.0, 12288.0, //
+        -225.0, 116.0, 384.0, //
+        -7173.0, 2695.0, 7680.0, //
+        3467.0, 7680.0, //
+        38081.0, 61440.0,
+    ];
+    fill_series(eps, &COEFF, c);
+}
+
+/// `A2 - 1` for the reduced-length integral.
+pub(super) fn a2m1f(eps: f64) -> f64 {
+    const COEFF: [f64; 5] = [-11.0, -28.0, -192.0, 0.0, 256.0];
+    let t = polyval(3, &COEFF, 0, sq(eps)) / COEFF[4];
+    (t - eps) / (1.0 + eps)
+}
+
+/// `C2[l]` coefficients for the reduced-length integral (`c[0]` unused).
+pub(super) fn c2f(eps: f64, c: &mut [f64; ORDER + 1]) {
+    const COEFF: [f64; 18] = [
+        1.0, 2.0, 16.0, 32.0, //
+        35.0, 64.0, 384.0, 2048.0, //
+        15.0, 80.0, 768.0, //
+        7.0, 35.0, 512.0, //
+        63.0, 1280.0, //
+        77.0, 2048.0,
+    ];
+    fill_series(eps, &COEFF, c);
+}
+
+fn fill_series(eps: f64, coeff: &[f64], c: &mut [f64; ORDER + 1]) {
+    let eps2 = sq(eps);
+    let mut d = eps;
+    let mut o = 0;
+    for (l, slot) in c.iter_mut().enumerate().skip(1) {
+        let m = (ORDER - l) / 2;
+        *slot = d * polyval(m as isize, coeff, o, eps2) / coeff[o + m + 1];
+        o += m + 2;
+        d *= eps;
+    }
+}
+
+/// Coefficients of `A3` as a polynomial in `eps`, for third flattening `n`.
+pub(super) fn a3_coeffs(n: f64) -> [f64; ORDER] {
+    const COEFF: [f64; 18] = [
+        -3.0, 128.0, //
+        -2.0, -3.0, 64.0, //
+        -1.0, -3.0, -1.0, 16.0, //
+        3.0, -1.0, -2.0, 8.0, //
+        1.0, -1.0, 2.0, //
+        1.0, 1.0,
+    ];
+    let mut out = [0.0; ORDER];
+    let mut o = 0;
+    for (k, j) in (0..ORDER).rev().enumerate() {
+        let m = (ORDER - j - 1).min(j);
+        out[k] = polyval(m as isize, &COEFF, o, n) / COEFF[o + m + 1];
+        o += m + 2;
+    }
+    out
+}
+
+/// Coefficients of `C3[l]` as polynomials in `eps`, for third flattening `n`.
+pub(super) fn c3_coeffs(n: f64) -> [f64; N_C3X] {
+    const COEFF: [f64; 45] = [
+        3.0, 128.0, //
+        2.0, 5.0, 128.0, //
+        -1.0, 3.0, 3.0, 64.0, //
+        -1.0, 0.0, 1.0, 8.0, //
+        -1.0, 1.0, 4.0, //
+        5.0, 256.0, //
+        1.0, 3.0, 128.0, //
+        -3.0, -2.0, 3.0, 64.0, //
+        1.0, -3.0, 2.0, 32.0, //
+        7.0, 512.0, //
+        -10.0, 9.0, 384.0, //
+        5.0, -9.0, 5.0, 192.0, //
+        7.0, 512.0, //
+        -14.0, 7.0, 512.0, //
+        21.0, 2560.0,
+    ];
+    let mut out = [0.0; N_C3X];
+    let mut o = 0;
+    let mut k = 0;
+    for l in 1..ORDER {
+        for j in (l..ORDER).rev() {
+            let m = (ORDER - j - 1).min(j);
+            out[k] = polyval(m as isize, &COEFF, o, n) / COEFF[o + m + 1];
+            k += 1;
+            o += m + 2;
+        }
+    }
+    out
+}
+
+/// Coefficients of `C4[l]` (area integral) as polynomials in `eps`.
+pub(super) fn c4_coeffs(n: f64) -> [f64; N_C4X] {
+    const COEFF: [f64; 77] = [
+        97.0, 15015.0, //
+        1088.0, 156.0, 45045.0, //
+        -224.0, -4784.0, 1573.0, 45045.0, //
+        -10656.0, 14144.0, -4576.0, -858.0, 45045.0, //
+        64.0, 624.0, -4576.0, 6864.0, -3003.0, 15015.0, //
+        100.0, 208.0, 572.0, 3432.0, -12012.0, 30030.0, 45045.0, //
+        1.0, 9009.0, //
+        -2944.0, 468.0, 135135.0, //
+        5792.0, 1040.0, -1287.0, 135135.0, //
+        5952.0, -11648.0, 9152.0, -2574.0, 135135.0, //
+        -64.0, -624.0, 4576.0, -6864.0, 3003.0, 135135.0, //
+        8.0, 10725.0, //
+        1856.0, -936.0, 225225.0, //
+        -8448.0, 4992.0, -1144.0, 225225.0, //
+        -1440.0, 4160.0, -4576.0, 1716.0, 225225.0, //
+        -136.0, 63063.0, //
+        1024.0, -208.0, 105105.0, //
+        3584.0, -3328.0, 1144.0, 315315.0, //
+        -128.0, 135135.0, //
+        -2560.0, 832.0, 405405.0, //
+        128.0, 99099.0,
+    ];
+    let mut out = [0.0; N_C4X];
+    let mut o = 0;
+    let mut k = 0;
+    for l in 0..ORDER {
+        for j in (l..ORDER).rev() {
+            let m = ORDER - j - 1;
+            out[k] = polyval(m as isize, &COEFF, o, n) / COEFF[o + m + 1];
+            k += 1;
+            o += m + 2;
+        }
+    }
+    out
+}
@@ -1,4 +1,4 @@
-use super::Ecef;
+use super::{Ecef, Ellipsoid};
 
 /// WGS84 semi-major axis (meters).
 pub const WGS84_A: f64 = 6_378_137.0;
@@ -27,38 +27,27 @@ impl Geodetic {
             alt_m,
         }
     }
-}
 
-pub fn geodetic_to_ecef(geo: Geodetic) -> Ecef {
-    let sin_lat = geo.lat_rad.sin();
-    let cos_lat = geo.lat_rad.cos();
-    let sin_lon = geo.lon_rad.sin();
-    let cos_lon = geo.lon_rad.cos();
+    /// Construct from latitude/longitude in degrees.
+    pub fn from_degrees(lat_deg: f64, lon_deg: f64, alt_m: f64) -> Self {
+        Self::new(lat_deg.to_radians(), lon_deg.to_radians(), alt_m)
+    }
 
-    let n = WGS84_A / (1.0 - WGS84_E2 * sin_lat * sin_lat).sqrt();
-    let x = (n + geo.alt_m) * cos_lat * cos_lon;
-    let y = (n + geo.alt_m) * cos_lat * sin_lon;
-    let z = (n * (1.0 - WGS84_E2) + geo.alt_m) * sin_lat;
+    pub fn lat_deg(self) -> f64 {
+        self.lat_rad.to_degrees()
+    }
 
-    Ecef::new(x, y, z)
+    pub fn lon_deg(self) -> f64 {
+        self.lon_rad.to_degrees()
+    }
 }
 
-pub fn ecef_to_geodetic(ecef: Ecef) -> Geodetic {
-    let p = (ecef.x * ecef.x + ecef.y * ecef.y).sqrt();
-    let lon = ecef.y.atan2(ecef.x);
-
-    let theta = (ecef.z * WGS84_A).atan2(p * WGS84_B);
-    let sin_theta = theta.sin();
-    let cos_theta = theta.cos();
-
-    let lat = (ecef.z + WGS84_EP2 * WGS84_B * sin_theta * sin_theta * sin_theta)
-        .atan2(p - WGS84_E2 * WGS84_A * cos_theta * cos_theta * cos_theta);
-
-    let sin_lat = lat.sin();
-    let n = WGS84_A / (1.0 - WGS84_E2 * sin_lat * sin_lat).sqrt();
-    let alt = p / lat.cos() - n;
+pub fn geodetic_to_ecef(geo: Geodetic) -> Ecef {
+    Ellipsoid::WGS84.geodetic_to_ecef(geo)
+}
 
-    Geodetic::new(lat, lon, alt)
+pub fn ecef_to_geodetic(ecef: Ecef) -> Geodetic {
+    Ellipsoid::WGS84.ecef_to_geodetic(ecef)
 }
 
 #[cfg(test)]
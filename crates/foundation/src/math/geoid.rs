@@ -0,0 +1,456 @@
+//! Geoid undulation grids for orthometric ↔ ellipsoidal heights.
+//!
+//! `h = H + N`: an ellipsoidal height `h` is the orthometric ("above mean sea level")
+//! height `H` plus the geoid undulation `N` at that position. DEMs are usually
+//! orthometric (EGM96/EGM2008) while `Geodetic::alt_m` is ellipsoidal.
+//!
+//! Supported grid files:
+//! - PROJ `.gtx`: 40-byte big-endian header (south-west lat/lon, lat/lon spacing in
+//!   degrees, rows, columns as i32) followed by big-endian `f32` rows from south to north
+//! - NGA `.grd` ASCII (e.g. `WW15MGH.GRD`): `south north west east dlat dlon` then
+//!   values row by row from north to south
+
+use std::fmt;
+use std::path::Path;
+
+use super::Geodetic;
+
+/// GTX marker for cells without data.
+const GTX_NO_DATA: f32 = -88.8888;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeoidError {
+    Io { path: String, message: String },
+    InvalidHeader(&'static str),
+    Truncated { expected: usize, found: usize },
+    InvalidValue(String),
+}
+
+impl fmt::Display for GeoidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeoidError::Io { path, message } => write!(f, "failed to read {path}: {message}"),
+            GeoidError::InvalidHeader(reason) => write!(f, "invalid geoid grid header: {reason}"),
+            GeoidError::Truncated { expected, found } => {
+                write!(
+                    f,
+                    "geoid grid truncated: expected {expected} values, found {found}"
+                )
+            }
+            GeoidError::InvalidValue(token) => write!(f, "invalid geoid grid value {token:?}"),
+        }
+    }
+}
+
+impl std::error::Error for GeoidError {}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum GeoidInterpolation {
+    #[default]
+    Bilinear,
+    /// Catmull-Rom over the surrounding 4×4 nodes; smoother, and exact at nodes.
+    Bicubic,
+}
+
+/// Reference surface for a height value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerticalDatum {
+    /// Height above the WGS84 ellipsoid (what `Geodetic::alt_m` holds).
+    Ellipsoidal,
+    Egm96,
+    Egm2008,
+    /// An orthometric datum without a built-in name.
+    Other(String),
+}
+
+impl VerticalDatum {
+    /// Parse names as used in tileset metadata, e.g. `msl-egm2008`, `EGM96`, `ellipsoid`.
+    pub fn from_name(name: &str) -> Self {
+        let lower = name.trim().to_ascii_lowercase();
+        if lower.contains("egm2008") || lower.contains("egm08") {
+            VerticalDatum::Egm2008
+        } else if lower.contains("egm96") {
+            VerticalDatum::Egm96
+        } else if matches!(
+            lower.as_str(),
+            "ellipsoid" | "ellipsoidal" | "wgs84" | "wgs 84" | "hae" | "epsg:4979"
+        ) {
+            VerticalDatum::Ellipsoidal
+        } else {
+            VerticalDatum::Other(name.trim().to_string())
+        }
+    }
+
+    /// True for heights measured from a geoid, which need a grid to become ellipsoidal.
+    pub fn is_orthometric(&self) -> bool {
+        *self != VerticalDatum::Ellipsoidal
+    }
+}
+
+/// Regular lat/lon grid of geoid undulations in meters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoidGrid {
+    south_deg: f64,
+    west_deg: f64,
+    dlat_deg: f64,
+    dlon_deg: f64,
+    rows: usize,
+    cols: usize,
+    /// Row-major from the south row, west to east; NaN marks missing data.
+    values: Vec<f32>,
+    interpolation: GeoidInterpolation,
+}
+
+impl GeoidGrid {
+    /// Grid with its south-west node at (`south_deg`, `west_deg`); `values` are row-major
+    /// from the south row, west to east. NaN values mark missing data.
+    pub fn new(
+        south_deg: f64,
+        west_deg: f64,
+        dlat_deg: f64,
+        dlon_deg: f64,
+        rows: usize,
+        cols: usize,
+        values: Vec<f32>,
+    ) -> Result<Self, GeoidError> {
+        if !(dlat_deg > 0.0 && dlon_deg > 0.0) {
+            return Err(GeoidError::InvalidHeader("spacing must be positive"));
+        }
+        if !(south_deg.is_finite() && west_deg.is_finite()) {
+            return Err(GeoidError::InvalidHeader("origin must be finite"));
+        }
+        if rows < 2 || cols < 2 {
+            return Err(GeoidError::InvalidHeader("grid needs at least 2x2 nodes"));
+        }
+        if values.len() < rows * cols {
+            return Err(GeoidError::Truncated {
+                expected: rows * cols,
+                found: values.len(),
+            });
+        }
+        Ok(Self {
+            south_deg,
+            west_deg,
+            dlat_deg,
+            dlon_deg,
+            rows,
+            cols,
+            values,
+            interpolation: GeoidInterpolation::default(),
+        })
+    }
+
+    pub fn with_interpolation(mut self, interpolation: GeoidInterpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Load a `.gtx` or `.grd` file, chosen by extension.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, GeoidError> {
+        let path = path.as_ref();
+        let io_err = |e: std::io::Error| GeoidError::Io {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        };
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+        match ext.as_deref() {
+            Some("gtx") => Self::from_gtx_bytes(&std::fs::read(path).map_err(io_err)?),
+            Some("grd") => Self::from_grd_str(&std::fs::read_to_string(path).map_err(io_err)?),
+            _ => Err(GeoidError::InvalidHeader("expected a .gtx or .grd file")),
+        }
+    }
+
+    pub fn from_gtx_bytes(bytes: &[u8]) -> Result<Self, GeoidError> {
+        if bytes.len() < 40 {
+            return Err(GeoidError::InvalidHeader("gtx header is 40 bytes"));
+        }
+        let f64_at = |i: usize| f64::from_be_bytes(bytes[i..i + 8].try_into().unwrap());
+        let i32_at = |i: usize| i32::from_be_bytes(bytes[i..i + 4].try_into().unwrap());
+        let (rows, cols) = (i32_at(32), i32_at(36));
+        if rows <= 0 || cols <= 0 {
+            return Err(GeoidError::InvalidHeader(
+                "gtx rows and columns must be positive",
+            ));
+        }
+        let (rows, cols) = (rows as usize, cols as usize);
+        let values: Vec<f32> = bytes[40..]
+            .chunks_exact(4)
+            .take(rows * cols)
+            .map(|c| {
+                let v = f32::from_be_bytes(c.try_into().unwrap());
+                if (v - GTX_NO_DATA).abs() < 1e-3 {
+                    f32::NAN
+                } else {
+                    v
+                }
+            })
+            .collect();
+        Self::new(
+            f64_at(0),
+            f64_at(8),
+            f64_at(16),
+            f64_at(24),
+            rows,
+            cols,
+            values,
+        )
+    }
+
+    pub fn from_grd_str(text: &str) -> Result<Self, GeoidError> {
+        let mut tokens = text.split_whitespace();
+        let mut header = [0.0f64; 6];
+        for h in &mut header {
+            let token = tokens
+                .next()
+                .ok_or(GeoidError::InvalidHeader("grd header needs 6 numbers"))?;
+            *h = token
+                .parse()
+                .map_err(|_| GeoidError::InvalidValue(token.to_string()))?;
+        }
+        let [south, north, west, east, dlat, dlon] = header;
+        if !(dlat > 0.0 && dlon > 0.0 && north > south && east > west) {
+            return Err(GeoidError::InvalidHeader(
+                "grd extent or spacing is invalid",
+            ));
+        }
+        let rows = ((north - south) / dlat).round() as usize + 1;
+        let cols = ((east - west) / dlon).round() as usize + 1;
+        let mut north_first = Vec::with_capacity(rows * cols);
+        for token in tokens.take(rows * cols) {
+            let v: f32 = token
+                .parse()
+                .map_err(|_| GeoidError::InvalidValue(token.to_string()))?;
+            north_first.push(v);
+        }
+        if north_first.len() < rows * cols {
+            return Err(GeoidError::Truncated {
+                expected: rows * cols,
+                found: north_first.len(),
+            });
+        }
+        let values = north_first
+            .chunks_exact(cols)
+            .rev()
+            .flatten()
+            .copied()
+            .collect();
+        Self::new(south, west, dlat, dlon, rows, cols, values)
+    }
+
+    /// True when the columns span every longitude, so lookups wrap around.
+    pub fn is_global(&self) -> bool {
+        self.cols as f64 * self.dlon_deg >= 360.0 - 1e-9
+    }
+
+    /// Geoid undulation `N` in meters, or `None` outside the grid or over missing data.
+    pub fn undulation_m(&self, lat_deg: f64, lon_deg: f64) -> Option<f64> {
+        let r = (lat_deg - self.south_deg) / self.dlat_deg;
+        let c = (lon_deg - self.west_deg).rem_euclid(360.0) / self.dlon_deg;
+        let max_r = (self.rows - 1) as f64;
+        if !(r > -1e-9 && r < max_r + 1e-9) {
+            return None;
+        }
+        if !self.is_global() && c > (self.cols - 1) as f64 + 1e-9 {
+            return None;
+        }
+        let r = r.clamp(0.0, max_r);
+        let r0 = (r.floor() as usize).min(self.rows - 2);
+        let c0 = c.floor() as i64;
+        let (fr, fc) = (r - r0 as f64, c - c0 as f64);
+        let n = match self.interpolation {
+            GeoidInterpolation::Bilinear => {
+                let v00 = self.node(r0 as i64, c0)?;
+                let v01 = self.node(r0 as i64, c0 + 1)?;
+                let v10 = self.node(r0 as i64 + 1, c0)?;
+                let v11 = self.node(r0 as i64 + 1, c0 + 1)?;
+                let south = v00 + (v01 - v00) * fc;
+                let north = v10 + (v11 - v10) * fc;
+                south + (north - south) * fr
+            }
+            GeoidInterpolation::Bicubic => {
+                let mut rows = [0.0; 4];
+                for (k, row) in rows.iter_mut().enumerate() {
+                    let rr = r0 as i64 - 1 + k as i64;
+                    let mut p = [0.0; 4];
+                    for (m, v) in p.iter_mut().enumerate() {
+                        *v = self.node(rr, c0 - 1 + m as i64)?;
+                    }
+                    *row = catmull_rom(p, fc);
+                }
+                catmull_rom(rows, fr)
+            }
+        };
+        Some(n)
+    }
+
+    /// Ellipsoidal height from an orthometric height at the same position.
+    pub fn orthometric_to_ellipsoidal(&self, lat_deg: f64, lon_deg: f64, h_m: f64) -> Option<f64> {
+        Some(h_m + self.undulation_m(lat_deg, lon_deg)?)
+    }
+
+    /// Orthometric height from an ellipsoidal height at the same position.
+    pub fn ellipsoidal_to_orthometric(&self, lat_deg: f64, lon_deg: f64, h_m: f64) -> Option<f64> {
+        Some(h_m - self.undulation_m(lat_deg, lon_deg)?)
+    }
+
+    /// Treat `geo.alt_m` as orthometric and return the position with an ellipsoidal height.
+    pub fn to_ellipsoidal(&self, geo: Geodetic) -> Option<Geodetic> {
+        let alt_m = self.orthometric_to_ellipsoidal(geo.lat_deg(), geo.lon_deg(), geo.alt_m)?;
+        Some(Geodetic { alt_m, ..geo })
+    }
+
+    /// Treat `geo.alt_m` as ellipsoidal and return the position with an orthometric height.
+    pub fn to_orthometric(&self, geo: Geodetic) -> Option<Geodetic> {
+        let alt_m = self.ellipsoidal_to_orthometric(geo.lat_deg(), geo.lon_deg(), geo.alt_m)?;
+        Some(Geodetic { alt_m, ..geo })
+    }
+
+    /// Node value; rows clamp at the edges, columns wrap on global grids and clamp otherwise.
+    fn node(&self, row: i64, col: i64) -> Option<f64> {
+        let row = row.clamp(0, self.rows as i64 - 1) as usize;
+        let col = if self.is_global() {
+            let period = (360.0 / self.dlon_deg).round() as i64;
+            (col.rem_euclid(period) as usize).min(self.cols - 1)
+        } else {
+            col.clamp(0, self.cols as i64 - 1) as usize
+        };
+        let v = self.values[row * self.cols + col];
+        (!v.is_nan()).then_some(v as f64)
+    }
+}
+
+fn catmull_rom(p: [f64; 4], t: f64) -> f64 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    0.5 * (2.0 * p[1]
+        + (p[2] - p[0]) * t
+        + (2.0 * p[0] - 5.0 * p[1] + 4.0 * p[2] - p[3]) * t2
+        + (3.0 * p[1] - p[0] - 3.0 * p[2] + p[3]) * t3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GeoidError, GeoidGrid, GeoidInterpolation, VerticalDatum};
+    use crate::math::Geodetic;
+
+    fn assert_close(a: f64, b: f64, eps: f64) {
+        let diff = (a - b).abs();
+        assert!(diff <= eps, "expected {a} ~= {b} (diff {diff})");
+    }
+
+    /// Global 90°-spaced grid whose value is `lat + lon / 10` (lon in 0..360).
+    fn global_grid() -> GeoidGrid {
+        let mut values = Vec::new();
+        for r in 0..3 {
+            for c in 0..4 {
+                values.push((-90.0 + 90.0 * r as f64 + 9.0 * c as f64) as f32);
+            }
+        }
+        GeoidGrid::new(-90.0, 0.0, 90.0, 90.0, 3, 4, values).unwrap()
+    }
+
+    #[test]
+    fn bilinear_lookup_wraps_and_converts_heights() {
+        let grid = global_grid();
+        assert!(grid.is_global());
+        assert_close(grid.undulation_m(0.0, 90.0).unwrap(), 9.0, 1e-9);
+        assert_close(grid.undulation_m(45.0, 45.0).unwrap(), 45.0 + 4.5, 1e-9);
+        // Between the last column (270°) and the wrapped first column (360° = 0°).
+        assert_close(
+            grid.undulation_m(0.0, -45.0).unwrap(),
+            0.5 * (27.0 + 0.0),
+            1e-9,
+        );
+        assert_close(grid.undulation_m(90.0, 10.0).unwrap(), 91.0, 1e-6);
+
+        let h = grid.orthometric_to_ellipsoidal(45.0, 45.0, 100.0).unwrap();
+        assert_close(h, 149.5, 1e-9);
+        assert_close(
+            grid.ellipsoidal_to_orthometric(45.0, 45.0, h).unwrap(),
+            100.0,
+            1e-9,
+        );
+        let geo = grid
+            .to_ellipsoidal(Geodetic::from_degrees(45.0, 45.0, 100.0))
+            .unwrap();
+        assert_close(geo.alt_m, 149.5, 1e-9);
+        assert_close(grid.to_orthometric(geo).unwrap().alt_m, 100.0, 1e-9);
+    }
+
+    #[test]
+    fn regional_grids_reject_outside_points_and_missing_data() {
+        let values = vec![1.0, 2.0, f32::NAN, 3.0, 4.0, 5.0];
+        let grid = GeoidGrid::new(40.0, 10.0, 1.0, 1.0, 2, 3, values).unwrap();
+        assert!(!grid.is_global());
+        assert_close(grid.undulation_m(40.5, 10.5).unwrap(), 2.5, 1e-9);
+        assert_eq!(grid.undulation_m(40.5, 11.5), None);
+        assert_eq!(grid.undulation_m(39.0, 10.5), None);
+        assert_eq!(grid.undulation_m(40.5, 12.5), None);
+        assert!(matches!(
+            GeoidGrid::new(0.0, 0.0, 1.0, 1.0, 2, 2, vec![0.0; 3]),
+            Err(GeoidError::Truncated {
+                expected: 4,
+                found: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn bicubic_is_exact_at_nodes_and_reproduces_linear_fields() {
+        let grid = global_grid().with_interpolation(GeoidInterpolation::Bicubic);
+        assert_close(grid.undulation_m(0.0, 180.0).unwrap(), 18.0, 1e-9);
+        let values: Vec<f32> = (0..25)
+            .map(|i| (2 * (i / 5) + 3 * (i % 5)) as f32)
+            .collect();
+        let grid = GeoidGrid::new(0.0, 0.0, 1.0, 1.0, 5, 5, values)
+            .unwrap()
+            .with_interpolation(GeoidInterpolation::Bicubic);
+        assert_close(grid.undulation_m(2.25, 1.5).unwrap(), 4.5 + 4.5, 1e-9);
+    }
+
+    #[test]
+    fn parses_gtx_and_grd_files() {
+        let mut gtx = Vec::new();
+        for v in [40.0f64, 10.0, 1.0, 1.0] {
+            gtx.extend(v.to_be_bytes());
+        }
+        gtx.extend(2i32.to_be_bytes());
+        gtx.extend(3i32.to_be_bytes());
+        for v in [1.0f32, 2.0, -88.8888, 3.0, 4.0, 5.0] {
+            gtx.extend(v.to_be_bytes());
+        }
+        let grid = GeoidGrid::from_gtx_bytes(&gtx).unwrap();
+        assert_close(grid.undulation_m(40.5, 10.5).unwrap(), 2.5, 1e-6);
+        assert_eq!(grid.undulation_m(40.5, 11.5), None);
+        assert!(GeoidGrid::from_gtx_bytes(&gtx[..50]).is_err());
+
+        // Rows run north to south in .grd files.
+        let grd = "40 41 10 12 1 1\n 7 8 9\n 1 2 3\n";
+        let grid = GeoidGrid::from_grd_str(grd).unwrap();
+        assert_close(grid.undulation_m(40.0, 10.0).unwrap(), 1.0, 1e-9);
+        assert_close(grid.undulation_m(41.0, 12.0).unwrap(), 9.0, 1e-9);
+        assert!(matches!(
+            GeoidGrid::from_grd_str("40 41 10 12 1 1\n 7 8"),
+            Err(GeoidError::Truncated { .. })
+        ));
+        assert!(GeoidGrid::load("missing.gtx").is_err());
+    }
+
+    #[test]
+    fn vertical_datum_names() {
+        assert_eq!(
+            VerticalDatum::from_name("msl-egm2008"),
+            VerticalDatum::Egm2008
+        );
+        assert_eq!(VerticalDatum::from_name("EGM96"), VerticalDatum::Egm96);
+        assert_eq!(
+            VerticalDatum::from_name("ellipsoid"),
+            VerticalDatum::Ellipsoidal
+        );
+        assert!(!VerticalDatum::Ellipsoidal.is_orthometric());
+        assert!(VerticalDatum::from_name("NAVD88").is_orthometric());
+    }
+}
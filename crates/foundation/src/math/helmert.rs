@@ -0,0 +1,155 @@
+use super::Ecef;
+
+const ARCSEC_TO_RAD: f64 = std::f64::consts::PI / (180.0 * 3600.0);
+
+/// Seven-parameter Helmert datum shift between geocentric frames.
+///
+/// Parameters are stored in the position-vector convention (EPSG method 9606,
+/// also used by PROJ `+towgs84`). Use `coordinate_frame` for parameters
+/// published in the coordinate-frame convention (EPSG method 9607).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Helmert7 {
+    /// Translations (meters).
+    pub tx: f64,
+    pub ty: f64,
+    pub tz: f64,
+    /// Rotations (arc-seconds).
+    pub rx: f64,
+    pub ry: f64,
+    pub rz: f64,
+    /// Scale difference (parts per million).
+    pub s_ppm: f64,
+}
+
+impl Helmert7 {
+    pub const IDENTITY: Helmert7 = Helmert7::position_vector(0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+
+    pub const fn position_vector(
+        tx: f64,
+        ty: f64,
+        tz: f64,
+        rx: f64,
+        ry: f64,
+        rz: f64,
+        s_ppm: f64,
+    ) -> Self {
+        Self {
+            tx,
+            ty,
+            tz,
+            rx,
+            ry,
+            rz,
+            s_ppm,
+        }
+    }
+
+    /// Parameters in the coordinate-frame convention (rotation signs flipped).
+    pub const fn coordinate_frame(
+        tx: f64,
+        ty: f64,
+        tz: f64,
+        rx: f64,
+        ry: f64,
+        rz: f64,
+        s_ppm: f64,
+    ) -> Self {
+        Self::position_vector(tx, ty, tz, -rx, -ry, -rz, s_ppm)
+    }
+
+    /// Three-parameter (geocentric translation) shift.
+    pub const fn translation(tx: f64, ty: f64, tz: f64) -> Self {
+        Self::position_vector(tx, ty, tz, 0.0, 0.0, 0.0, 0.0)
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+
+    fn rotation_rad(&self) -> [f64; 3] {
+        [
+            self.rx * ARCSEC_TO_RAD,
+            self.ry * ARCSEC_TO_RAD,
+            self.rz * ARCSEC_TO_RAD,
+        ]
+    }
+
+    /// Apply the shift: `p' = T + (1 + s) * R * p` with the small-angle rotation `R`.
+    pub fn apply(&self, p: Ecef) -> Ecef {
+        if self.is_identity() {
+            return p;
+        }
+        let [wx, wy, wz] = self.rotation_rad();
+        let m = 1.0 + self.s_ppm * 1e-6;
+        Ecef::new(
+            self.tx + m * (p.x - wz * p.y + wy * p.z),
+            self.ty + m * (wz * p.x + p.y - wx * p.z),
+            self.tz + m * (-wy * p.x + wx * p.y + p.z),
+        )
+    }
+
+    /// Exact inverse of `apply` (not the sign-flipped approximation).
+    pub fn apply_inverse(&self, p: Ecef) -> Ecef {
+        if self.is_identity() {
+            return p;
+        }
+        let [wx, wy, wz] = self.rotation_rad();
+        let m = 1.0 + self.s_ppm * 1e-6;
+        let qx = (p.x - self.tx) / m;
+        let qy = (p.y - self.ty) / m;
+        let qz = (p.z - self.tz) / m;
+
+        // (I + [w]x)^-1 q = (q - w x q + w (w . q)) / (1 + |w|^2)
+        let cx = wy * qz - wz * qy;
+        let cy = wz * qx - wx * qz;
+        let cz = wx * qy - wy * qx;
+        let wq = wx * qx + wy * qy + wz * qz;
+        let k = 1.0 / (1.0 + wx * wx + wy * wy + wz * wz);
+        Ecef::new(
+            (qx - cx + wx * wq) * k,
+            (qy - cy + wy * wq) * k,
+            (qz - cz + wz * wq) * k,
+        )
+    }
+}
+
+impl Default for Helmert7 {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Helmert7;
+    use crate::math::Ecef;
+
+    fn assert_close(a: f64, b: f64, eps: f64) {
+        let diff = (a - b).abs();
+        assert!(diff <= eps, "expected {a} ~= {b} (diff {diff})");
+    }
+
+    #[test]
+    fn translation_only() {
+        let h = Helmert7::translation(1.0, -2.0, 3.0);
+        let p = h.apply(Ecef::new(10.0, 20.0, 30.0));
+        assert_eq!(p, Ecef::new(11.0, 18.0, 33.0));
+    }
+
+    #[test]
+    fn inverse_is_exact() {
+        let h = Helmert7::position_vector(446.448, -125.157, 542.06, 0.15, 0.247, 0.842, -20.489);
+        let p = Ecef::new(3_874_938.85, 116_218.62, 5_047_168.21);
+        let rt = h.apply_inverse(h.apply(p));
+        assert_close(rt.x, p.x, 1e-6);
+        assert_close(rt.y, p.y, 1e-6);
+        assert_close(rt.z, p.z, 1e-6);
+    }
+
+    #[test]
+    fn coordinate_frame_flips_rotations() {
+        let cf = Helmert7::coordinate_frame(0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 0.0);
+        assert_eq!((cf.rx, cf.ry, cf.rz), (-1.0, -2.0, -3.0));
+        assert!(Helmert7::default().is_identity());
+    }
+}
@@ -0,0 +1,133 @@
+use super::{Quat, Vec3};
+
+/// Column-major 3x3 matrix: `cols[c][r]` is row `r` of column `c`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Mat3 {
+    pub cols: [[f64; 3]; 3],
+}
+
+impl Default for Mat3 {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mat3 {
+    pub const IDENTITY: Mat3 = Mat3 {
+        cols: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+    };
+
+    pub fn from_cols(x: Vec3, y: Vec3, z: Vec3) -> Self {
+        Self {
+            cols: [x.to_array(), y.to_array(), z.to_array()],
+        }
+    }
+
+    pub fn from_quat(q: Quat) -> Self {
+        let (x2, y2, z2) = (q.x + q.x, q.y + q.y, q.z + q.z);
+        let (xx, yy, zz) = (q.x * x2, q.y * y2, q.z * z2);
+        let (xy, xz, yz) = (q.x * y2, q.x * z2, q.y * z2);
+        let (wx, wy, wz) = (q.w * x2, q.w * y2, q.w * z2);
+        Self {
+            cols: [
+                [1.0 - (yy + zz), xy + wz, xz - wy],
+                [xy - wz, 1.0 - (xx + zz), yz + wx],
+                [xz + wy, yz - wx, 1.0 - (xx + yy)],
+            ],
+        }
+    }
+
+    pub fn col(&self, c: usize) -> Vec3 {
+        Vec3::from_array(self.cols[c])
+    }
+
+    pub fn transpose(&self) -> Self {
+        let m = &self.cols;
+        Self {
+            cols: [
+                [m[0][0], m[1][0], m[2][0]],
+                [m[0][1], m[1][1], m[2][1]],
+                [m[0][2], m[1][2], m[2][2]],
+            ],
+        }
+    }
+
+    pub fn determinant(&self) -> f64 {
+        self.col(0).dot(self.col(1).cross(self.col(2)))
+    }
+
+    /// Inverse, or `None` when the matrix is singular.
+    pub fn inverse(&self) -> Option<Self> {
+        let (a, b, c) = (self.col(0), self.col(1), self.col(2));
+        let r0 = b.cross(c);
+        let r1 = c.cross(a);
+        let r2 = a.cross(b);
+        let det = a.dot(r0);
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        // Rows of the inverse are the cross products divided by the determinant.
+        Some(Self::from_cols(r0 / det, r1 / det, r2 / det).transpose())
+    }
+
+    pub fn mul_vec3(&self, v: Vec3) -> Vec3 {
+        self.col(0) * v.x + self.col(1) * v.y + self.col(2) * v.z
+    }
+}
+
+impl std::ops::Mul for Mat3 {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        Self::from_cols(
+            self.mul_vec3(other.col(0)),
+            self.mul_vec3(other.col(1)),
+            self.mul_vec3(other.col(2)),
+        )
+    }
+}
+
+impl std::ops::Mul<Vec3> for Mat3 {
+    type Output = Vec3;
+
+    fn mul(self, v: Vec3) -> Self::Output {
+        self.mul_vec3(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Mat3;
+    use crate::math::{Quat, Vec3};
+
+    fn assert_mat_close(a: &Mat3, b: &Mat3, eps: f64) {
+        for c in 0..3 {
+            for r in 0..3 {
+                let diff = (a.cols[c][r] - b.cols[c][r]).abs();
+                assert!(diff <= eps, "[{c}][{r}]: {a:?} vs {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn from_quat_matches_quaternion_rotation() {
+        let q = Quat::from_axis_angle(Vec3::new(1.0, -2.0, 0.5), 1.1);
+        let m = Mat3::from_quat(q);
+        let v = Vec3::new(0.3, 4.0, -2.0);
+        assert!((m * v - q * v).length() < 1e-12);
+        assert!((m.determinant() - 1.0).abs() < 1e-12);
+        assert_mat_close(&m.transpose(), &Mat3::from_quat(q.conjugate()), 1e-12);
+    }
+
+    #[test]
+    fn inverse_round_trips_and_rejects_singular() {
+        let m = Mat3 {
+            cols: [[2.0, 0.5, 1.0], [0.0, 3.0, -1.0], [1.0, 0.0, 4.0]],
+        };
+        let inv = m.inverse().unwrap();
+        assert_mat_close(&(m * inv), &Mat3::IDENTITY, 1e-12);
+        assert_mat_close(&(inv * m), &Mat3::IDENTITY, 1e-12);
+        let singular = Mat3::from_cols(Vec3::X, Vec3::Y, Vec3::X + Vec3::Y);
+        assert!(singular.inverse().is_none());
+    }
+}
@@ -0,0 +1,290 @@
+use super::{Mat3, Quat, Vec3};
+
+/// Column-major 4x4 matrix: `cols[c][r]` is row `r` of column `c`, the layout WGSL expects.
+///
+/// Camera helpers are right-handed (view looks down -Z) and map depth to `[0, 1]`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Mat4 {
+    pub cols: [[f64; 4]; 4],
+}
+
+impl Default for Mat4 {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mat4 {
+    pub const IDENTITY: Mat4 = Mat4 {
+        cols: [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ],
+    };
+
+    pub fn from_translation(t: Vec3) -> Self {
+        let mut m = Self::IDENTITY;
+        m.cols[3] = [t.x, t.y, t.z, 1.0];
+        m
+    }
+
+    pub fn from_scale(s: Vec3) -> Self {
+        let mut m = Self::IDENTITY;
+        m.cols[0][0] = s.x;
+        m.cols[1][1] = s.y;
+        m.cols[2][2] = s.z;
+        m
+    }
+
+    /// Rotation/linear part in the upper-left 3x3, no translation.
+    pub fn from_mat3(m: Mat3) -> Self {
+        let c = &m.cols;
+        Self {
+            cols: [
+                [c[0][0], c[0][1], c[0][2], 0.0],
+                [c[1][0], c[1][1], c[1][2], 0.0],
+                [c[2][0], c[2][1], c[2][2], 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    pub fn from_quat(q: Quat) -> Self {
+        Self::from_mat3(Mat3::from_quat(q))
+    }
+
+    /// Rotate by `rotation`, then translate by `translation`.
+    pub fn from_rotation_translation(rotation: Quat, translation: Vec3) -> Self {
+        let mut m = Self::from_quat(rotation);
+        m.cols[3] = [translation.x, translation.y, translation.z, 1.0];
+        m
+    }
+
+    /// View matrix for a camera at `eye` looking at `target`.
+    ///
+    /// When `up` is parallel to the view direction another world axis is used instead.
+    pub fn look_at_rh(eye: Vec3, target: Vec3, up: Vec3) -> Self {
+        let f = (target - eye).normalize();
+        let mut s = f.cross(up);
+        if s.length_squared() < 1e-24 {
+            let alt = if f.x.abs() < 0.9 { Vec3::X } else { Vec3::Y };
+            s = f.cross(alt);
+        }
+        let s = s.normalize();
+        let u = s.cross(f);
+        Self {
+            cols: [
+                [s.x, u.x, -f.x, 0.0],
+                [s.y, u.y, -f.y, 0.0],
+                [s.z, u.z, -f.z, 0.0],
+                [-s.dot(eye), -u.dot(eye), f.dot(eye), 1.0],
+            ],
+        }
+    }
+
+    fn perspective_with_depth(fov_y_rad: f64, aspect: f64, m22: f64, m32: f64) -> Self {
+        let f = 1.0 / (0.5 * fov_y_rad).tan();
+        Self {
+            cols: [
+                [f / aspect, 0.0, 0.0, 0.0],
+                [0.0, f, 0.0, 0.0],
+                [0.0, 0.0, m22, -1.0],
+                [0.0, 0.0, m32, 0.0],
+            ],
+        }
+    }
+
+    /// Perspective projection mapping `near` to depth 0 and `far` to depth 1.
+    pub fn perspective_rh(fov_y_rad: f64, aspect: f64, near: f64, far: f64) -> Self {
+        let r = 1.0 / (near - far);
+        Self::perspective_with_depth(fov_y_rad, aspect, far * r, near * far * r)
+    }
+
+    /// Reversed-Z perspective: `near` maps to depth 1 and `far` to 0.
+    ///
+    /// Pair with a `Greater` depth test and a depth clear of 0; float depth precision then
+    /// spreads evenly over distance, which matters at planetary scale.
+    pub fn perspective_rh_reversed_z(fov_y_rad: f64, aspect: f64, near: f64, far: f64) -> Self {
+        let r = 1.0 / (far - near);
+        Self::perspective_with_depth(fov_y_rad, aspect, near * r, near * far * r)
+    }
+
+    /// Perspective with the far plane at infinity (`near` → 0, infinity → 1).
+    pub fn perspective_rh_infinite(fov_y_rad: f64, aspect: f64, near: f64) -> Self {
+        Self::perspective_with_depth(fov_y_rad, aspect, -1.0, -near)
+    }
+
+    /// Reversed-Z perspective with the far plane at infinity (`near` → 1, infinity → 0).
+    pub fn perspective_rh_infinite_reversed_z(fov_y_rad: f64, aspect: f64, near: f64) -> Self {
+        Self::perspective_with_depth(fov_y_rad, aspect, 0.0, near)
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut out = [[0.0; 4]; 4];
+        for (c, col) in out.iter_mut().enumerate() {
+            for (r, v) in col.iter_mut().enumerate() {
+                *v = self.cols[r][c];
+            }
+        }
+        Self { cols: out }
+    }
+
+    /// Inverse by Gauss-Jordan elimination with partial pivoting, or `None` when singular.
+    pub fn inverse(&self) -> Option<Self> {
+        // Work on rows of [A | I]; A is stored transposed so rows are contiguous.
+        let mut a = self.transpose().cols;
+        let mut inv = Self::IDENTITY.cols;
+        for col in 0..4 {
+            let pivot = (col..4)
+                .max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))
+                .unwrap_or(col);
+            let p = a[pivot][col];
+            if p == 0.0 || !p.is_finite() {
+                return None;
+            }
+            a.swap(col, pivot);
+            inv.swap(col, pivot);
+            for k in 0..4 {
+                a[col][k] /= p;
+                inv[col][k] /= p;
+            }
+            for row in 0..4 {
+                if row == col {
+                    continue;
+                }
+                let factor = a[row][col];
+                if factor != 0.0 {
+                    for k in 0..4 {
+                        a[row][k] -= factor * a[col][k];
+                        inv[row][k] -= factor * inv[col][k];
+                    }
+                }
+            }
+        }
+        Some(Self { cols: inv }.transpose())
+    }
+
+    pub fn mul_vec4(&self, v: [f64; 4]) -> [f64; 4] {
+        let mut out = [0.0; 4];
+        for (c, col) in self.cols.iter().enumerate() {
+            for (r, o) in out.iter_mut().enumerate() {
+                *o += col[r] * v[c];
+            }
+        }
+        out
+    }
+
+    /// Transform a point (w = 1), dividing by the resulting w.
+    pub fn transform_point3(&self, p: Vec3) -> Vec3 {
+        let [x, y, z, w] = self.mul_vec4([p.x, p.y, p.z, 1.0]);
+        Vec3::new(x / w, y / w, z / w)
+    }
+
+    /// Transform a direction (w = 0); translation is ignored.
+    pub fn transform_vector3(&self, v: Vec3) -> Vec3 {
+        let [x, y, z, _] = self.mul_vec4([v.x, v.y, v.z, 0.0]);
+        Vec3::new(x, y, z)
+    }
+
+    /// Narrow to `f32` for GPU uniform upload.
+    pub fn to_cols_f32(&self) -> [[f32; 4]; 4] {
+        self.cols.map(|col| col.map(|v| v as f32))
+    }
+}
+
+impl std::ops::Mul for Mat4 {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        Self {
+            cols: other.cols.map(|col| self.mul_vec4(col)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Mat4;
+    use crate::math::{Quat, Vec3};
+    use std::f64::consts::FRAC_PI_3;
+
+    fn assert_close(a: f64, b: f64, eps: f64) {
+        let diff = (a - b).abs();
+        assert!(diff <= eps, "expected {a} ~= {b} (diff {diff})");
+    }
+
+    fn depth(m: &Mat4, view_z: f64) -> f64 {
+        let [_, _, z, w] = m.mul_vec4([0.0, 0.0, view_z, 1.0]);
+        z / w
+    }
+
+    #[test]
+    fn look_at_puts_target_on_negative_z() {
+        let eye = Vec3::new(10.0, 5.0, -3.0);
+        let target = Vec3::new(-2.0, 1.0, 4.0);
+        let view = Mat4::look_at_rh(eye, target, Vec3::Y);
+        let t = view.transform_point3(target);
+        assert_close(t.x, 0.0, 1e-12);
+        assert_close(t.y, 0.0, 1e-12);
+        assert_close(t.z, -eye.distance(target), 1e-12);
+        assert!(view.transform_point3(eye).length() < 1e-12);
+        // Looking straight down the up axis still yields a valid rotation.
+        let down = Mat4::look_at_rh(Vec3::Y * 5.0, Vec3::ZERO, Vec3::Y);
+        assert_close(down.transform_point3(Vec3::ZERO).z, -5.0, 1e-12);
+    }
+
+    #[test]
+    fn perspective_depth_ranges() {
+        let (n, f) = (0.5, 1000.0);
+        let std = Mat4::perspective_rh(FRAC_PI_3, 1.5, n, f);
+        assert_close(depth(&std, -n), 0.0, 1e-12);
+        assert_close(depth(&std, -f), 1.0, 1e-12);
+        let rev = Mat4::perspective_rh_reversed_z(FRAC_PI_3, 1.5, n, f);
+        assert_close(depth(&rev, -n), 1.0, 1e-12);
+        assert_close(depth(&rev, -f), 0.0, 1e-12);
+        let inf = Mat4::perspective_rh_infinite(FRAC_PI_3, 1.5, n);
+        assert_close(depth(&inf, -n), 0.0, 1e-12);
+        assert_close(depth(&inf, -1e12), 1.0, 1e-9);
+        let inf_rev = Mat4::perspective_rh_infinite_reversed_z(FRAC_PI_3, 1.5, n);
+        assert_close(depth(&inf_rev, -n), 1.0, 1e-12);
+        assert_close(depth(&inf_rev, -1e12), 0.0, 1e-9);
+        // A point on the top frustum edge lands on NDC y = 1.
+        let y = (FRAC_PI_3 * 0.5).tan() * 10.0;
+        let [_, cy, _, cw] = std.mul_vec4([0.0, y, -10.0, 1.0]);
+        assert_close(cy / cw, 1.0, 1e-12);
+    }
+
+    #[test]
+    fn inverse_and_composition() {
+        let m = Mat4::from_rotation_translation(
+            Quat::from_axis_angle(Vec3::new(0.2, 1.0, -0.4), 0.7),
+            Vec3::new(3.0, -1.0, 8.0),
+        ) * Mat4::from_scale(Vec3::new(2.0, 0.5, 1.5));
+        let inv = m.inverse().unwrap();
+        let id = m * inv;
+        for c in 0..4 {
+            for r in 0..4 {
+                assert_close(id.cols[c][r], Mat4::IDENTITY.cols[c][r], 1e-12);
+            }
+        }
+        let p = Vec3::new(1.0, 2.0, 3.0);
+        assert!((inv.transform_point3(m.transform_point3(p)) - p).length() < 1e-12);
+        let proj = Mat4::perspective_rh(FRAC_PI_3, 1.0, 1.0, 100.0);
+        let q = proj
+            .inverse()
+            .unwrap()
+            .transform_point3(proj.transform_point3(p - Vec3::Z * 10.0));
+        assert!((q - (p - Vec3::Z * 10.0)).length() < 1e-9);
+        assert!(
+            Mat4::from_scale(Vec3::new(1.0, 0.0, 1.0))
+                .inverse()
+                .is_none()
+        );
+        assert_eq!(
+            Mat4::from_translation(Vec3::X).transform_vector3(Vec3::Y),
+            Vec3::Y
+        );
+    }
+}
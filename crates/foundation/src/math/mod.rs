@@ -1,13 +1,35 @@
+pub mod crs;
+pub mod dggs;
 pub mod ecef;
+pub mod ellipsoid;
+pub mod geodesic;
 pub mod geodesy;
+pub mod geoid;
+pub mod helmert;
 pub mod local;
+pub mod mat3;
+pub mod mat4;
+pub mod notation;
 pub mod precision;
+pub mod predicates;
 pub mod projection;
+pub mod quat;
 pub mod vec;
 
+pub use crs::*;
+pub use dggs::*;
 pub use ecef::*;
+pub use ellipsoid::*;
+pub use geodesic::*;
 pub use geodesy::*;
+pub use geoid::*;
+pub use helmert::*;
 pub use local::*;
+pub use mat3::*;
+pub use mat4::*;
+pub use notation::*;
 pub use precision::*;
+pub use predicates::*;
 pub use projection::*;
+pub use quat::*;
 pub use vec::*;
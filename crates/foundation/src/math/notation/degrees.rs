@@ -0,0 +1,520 @@
+//! Decimal degrees, degrees-decimal-minutes and degrees-minutes-seconds.
+
+use super::{CoordinateParseError, ParseMode, parse_unsigned_decimal};
+use crate::math::Geodetic;
+use crate::math::projection::wrap_lon_rad;
+
+/// Fractional digits beyond this are ignored; keeps the fixed-point rounding in `u64`.
+const MAX_DECIMALS: u8 = 9;
+
+/// Signed `lat, lon`, e.g. `40.446195, -79.982222`.
+pub fn format_decimal_degrees(geo: Geodetic, decimals: u8) -> String {
+    let d = decimals.min(MAX_DECIMALS) as usize;
+    let (lat, lon) = lat_lon_deg(geo);
+    format!("{lat:.d$}, {lon:.d$}")
+}
+
+/// Degrees and decimal minutes with hemisphere letters, e.g. `40°26.772'N 79°58.933'W`.
+pub fn format_ddm(geo: Geodetic, decimals: u8) -> String {
+    let (lat, lon) = lat_lon_deg(geo);
+    format!(
+        "{} {}",
+        format_angle(lat, ['N', 'S'], 1, decimals),
+        format_angle(lon, ['E', 'W'], 1, decimals)
+    )
+}
+
+/// Degrees, minutes and decimal seconds with hemisphere letters,
+/// e.g. `40°26'46.30"N 79°58'56.00"W`.
+pub fn format_dms(geo: Geodetic, decimals: u8) -> String {
+    let (lat, lon) = lat_lon_deg(geo);
+    format!(
+        "{} {}",
+        format_angle(lat, ['N', 'S'], 2, decimals),
+        format_angle(lon, ['E', 'W'], 2, decimals)
+    )
+}
+
+/// Parse decimal degrees, DDM or DMS (see the module docs for what each mode accepts).
+///
+/// Strict mode takes the output of [`format_decimal_degrees`], [`format_ddm`] and
+/// [`format_dms`] (any number of fractional digits, `,` or whitespace between the two
+/// angles); latitude comes first unless hemisphere letters say otherwise.
+pub fn parse_degrees(input: &str, mode: ParseMode) -> Result<Geodetic, CoordinateParseError> {
+    let invalid = |reason| CoordinateParseError::InvalidDegrees {
+        input: input.to_string(),
+        reason,
+    };
+    if input.trim().is_empty() {
+        return Err(CoordinateParseError::Empty);
+    }
+    let tokens = tokenize(input, mode).map_err(invalid)?;
+    let (first, second, separated) = split_pair(&tokens).map_err(invalid)?;
+    if mode == ParseMode::Strict {
+        check_strict(first, second, separated).map_err(invalid)?;
+    }
+    let (a, a_axis) = parse_angle(first).map_err(invalid)?;
+    let (b, b_axis) = parse_angle(second).map_err(invalid)?;
+    let (lat, lon) = match (a_axis, b_axis) {
+        (Some(Axis::Lat), Some(Axis::Lat)) => return Err(invalid("two latitudes given")),
+        (Some(Axis::Lon), Some(Axis::Lon)) => return Err(invalid("two longitudes given")),
+        (Some(Axis::Lon), _) | (_, Some(Axis::Lat)) => (b, a),
+        _ => (a, b),
+    };
+    if lat.abs() > 90.0 {
+        return Err(invalid("latitude must be within ±90°"));
+    }
+    let lon_limit = match mode {
+        ParseMode::Strict => 180.0,
+        ParseMode::Lenient => 360.0,
+    };
+    if lon.abs() > lon_limit {
+        return Err(invalid("longitude is out of range"));
+    }
+    let lon = if lon > 180.0 {
+        lon - 360.0
+    } else if lon < -180.0 {
+        lon + 360.0
+    } else {
+        lon
+    };
+    Ok(Geodetic::from_degrees(lat, lon, 0.0))
+}
+
+fn lat_lon_deg(geo: Geodetic) -> (f64, f64) {
+    let lon = if (-180.0..=180.0).contains(&geo.lon_deg()) {
+        geo.lon_deg()
+    } else {
+        wrap_lon_rad(geo.lon_rad).to_degrees()
+    };
+    (geo.lat_deg(), lon)
+}
+
+/// `deg°[min'[sec"]]H` with `parts` sub-degree components (0..=2) and the last one carrying
+/// `decimals` fractional digits. Rounds once in fixed point so `59.9999"` carries into the
+/// minutes instead of printing `60.00"`.
+fn format_angle(value: f64, hemispheres: [char; 2], parts: u32, decimals: u8) -> String {
+    let d = decimals.min(MAX_DECIMALS) as u32;
+    let scale = 10u64.pow(d);
+    let per_degree = 60u64.pow(parts) * scale;
+    let total = (value.abs() * per_degree as f64).round() as u64;
+    let hemisphere = if value < 0.0 && total > 0 {
+        hemispheres[1]
+    } else {
+        hemispheres[0]
+    };
+
+    let mut out = format!("{}°", total / per_degree);
+    for part in 1..=parts {
+        let symbol = if part == 1 { '\'' } else { '"' };
+        if part < parts {
+            let unit = 60u64.pow(parts - part) * scale;
+            out.push_str(&format!("{:02}{symbol}", (total / unit) % 60));
+        } else {
+            let last = total % (60 * scale);
+            if d == 0 {
+                out.push_str(&format!("{last:02}{symbol}"));
+            } else {
+                let d = d as usize;
+                out.push_str(&format!("{:02}.{:0d$}{symbol}", last / scale, last % scale));
+            }
+        }
+    }
+    out.push(hemisphere);
+    out
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Axis {
+    Lat,
+    Lon,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Unit {
+    Degrees,
+    Minutes,
+    Seconds,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Token {
+    Number { value: f64, fraction: bool },
+    Sign { negative: bool },
+    Unit(Unit),
+    Colon,
+    Hemisphere(char),
+    Separator,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Spanned {
+    token: Token,
+    /// Whitespace came right before this token.
+    spaced: bool,
+}
+
+fn tokenize(input: &str, mode: ParseMode) -> Result<Vec<Spanned>, &'static str> {
+    let chars: Vec<char> = input.trim().chars().collect();
+    let lenient = mode == ParseMode::Lenient;
+    let mut tokens = Vec::new();
+    let mut spaced = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        i += 1;
+        if c.is_whitespace() {
+            spaced = true;
+            continue;
+        }
+        let token = match c {
+            '0'..='9' | '.' => {
+                let start = i - 1;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let value = parse_unsigned_decimal(&text).ok_or("malformed number")?;
+                Token::Number {
+                    value,
+                    fraction: text.contains('.'),
+                }
+            }
+            '-' | '+' => Token::Sign { negative: c == '-' },
+            '°' => Token::Unit(Unit::Degrees),
+            '\'' if chars.get(i) == Some(&'\'') => {
+                i += 1;
+                Token::Unit(Unit::Seconds)
+            }
+            '\'' => Token::Unit(Unit::Minutes),
+            '"' => Token::Unit(Unit::Seconds),
+            ',' => Token::Separator,
+            'N' | 'S' | 'E' | 'W' => Token::Hemisphere(c),
+            _ if !lenient => {
+                return Err(if matches!(c, 'n' | 's' | 'e' | 'w') {
+                    "hemisphere letters must be upper case"
+                } else {
+                    "unexpected character"
+                });
+            }
+            '−' => Token::Sign { negative: true },
+            'º' | '˚' => Token::Unit(Unit::Degrees),
+            'd' | 'D' => {
+                if chars[i..].starts_with(&['e', 'g']) {
+                    i += 2;
+                }
+                Token::Unit(Unit::Degrees)
+            }
+            '′' | '’' | '‘' | '´' | '`' => Token::Unit(Unit::Minutes),
+            '″' | '“' | '”' => Token::Unit(Unit::Seconds),
+            ':' => Token::Colon,
+            ';' => Token::Separator,
+            'n' | 's' | 'e' | 'w' => Token::Hemisphere(c.to_ascii_uppercase()),
+            _ => return Err("unexpected character"),
+        };
+        tokens.push(Spanned { token, spaced });
+        spaced = false;
+    }
+    Ok(tokens)
+}
+
+/// Split the token stream into the two angles; the flag records an explicit separator.
+fn split_pair(tokens: &[Spanned]) -> Result<(&[Spanned], &[Spanned], bool), &'static str> {
+    let separators: Vec<usize> = tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| t.token == Token::Separator)
+        .map(|(i, _)| i)
+        .collect();
+    match separators[..] {
+        [] => {}
+        [i] => return Ok((&tokens[..i], &tokens[i + 1..], true)),
+        _ => return Err("expected a single separator between latitude and longitude"),
+    }
+
+    let prefix_hemisphere =
+        matches!(tokens.first(), Some(t) if matches!(t.token, Token::Hemisphere(_)));
+    let mut has_number = false;
+    let mut uses_markers = false;
+    let mut last_unit = None;
+    for (i, t) in tokens.iter().enumerate() {
+        let previous = i.checked_sub(1).map(|p| tokens[p].token);
+        let next = tokens.get(i + 1).map(|t| t.token);
+        let starts_second = has_number
+            && match t.token {
+                Token::Hemisphere(_) => prefix_hemisphere,
+                Token::Sign { .. } => true,
+                Token::Number { .. } => match (previous, next) {
+                    (_, Some(Token::Unit(unit))) if last_unit.is_some_and(|l| unit <= l) => true,
+                    (Some(Token::Unit(Unit::Seconds)), _) => true,
+                    (Some(Token::Number { .. }), _) => uses_markers,
+                    _ => false,
+                },
+                _ => false,
+            }
+            || !prefix_hemisphere && matches!(previous, Some(Token::Hemisphere(_)));
+        if starts_second {
+            return Ok((&tokens[..i], &tokens[i..], false));
+        }
+        match t.token {
+            Token::Number { .. } => has_number = true,
+            Token::Unit(unit) => {
+                uses_markers = true;
+                last_unit = Some(unit);
+            }
+            Token::Colon => uses_markers = true,
+            _ => {}
+        }
+    }
+
+    // Bare whitespace-separated (signed) numbers, `lat lon`, `d m d m` or `d m s d m s`,
+    // with at most a leading and a trailing hemisphere letter.
+    let is_hemisphere = |t: &Spanned| matches!(t.token, Token::Hemisphere(_));
+    let lead = usize::from(tokens.first().is_some_and(is_hemisphere));
+    let trail = usize::from(tokens.len() > lead && tokens.last().is_some_and(is_hemisphere));
+    let core = &tokens[lead..tokens.len() - trail];
+    let numbers: Vec<usize> = (0..core.len())
+        .filter(|&i| matches!(core[i].token, Token::Number { .. }))
+        .collect();
+    let bare = core.iter().enumerate().all(|(i, t)| match t.token {
+        Token::Number { .. } => true,
+        Token::Sign { .. } => {
+            matches!(core.get(i + 1), Some(n) if matches!(n.token, Token::Number { .. }))
+        }
+        _ => false,
+    });
+    if bare && matches!(numbers.len(), 2 | 4 | 6) {
+        let mut split = numbers[numbers.len() / 2];
+        if matches!(core[split - 1].token, Token::Sign { .. }) {
+            split -= 1;
+        }
+        return Ok((&tokens[..lead + split], &tokens[lead + split..], false));
+    }
+    Err("expected a latitude and a longitude")
+}
+
+fn check_strict(
+    first: &[Spanned],
+    second: &[Spanned],
+    separated: bool,
+) -> Result<(), &'static str> {
+    let decimal = |group: &[Spanned]| {
+        let (sign, number) = match group {
+            [number] => (None, number),
+            [sign, number] => (Some(sign), number),
+            _ => return false,
+        };
+        matches!(number.token, Token::Number { .. })
+            && sign.is_none_or(|s| matches!(s.token, Token::Sign { .. }) && !number.spaced)
+    };
+    if decimal(first) && decimal(second) {
+        return if separated {
+            Ok(())
+        } else {
+            Err("decimal degrees must be separated by a comma")
+        };
+    }
+
+    let symbolic = |group: &[Spanned], hemispheres: [char; 2]| {
+        let Some((last, body)) = group.split_last() else {
+            return false;
+        };
+        let hemisphere = matches!(last.token, Token::Hemisphere(h) if hemispheres.contains(&h));
+        let components = body.len() / 2;
+        hemisphere
+            && !last.spaced
+            && body.len() % 2 == 0
+            && (1..=3).contains(&components)
+            && body.chunks(2).enumerate().all(|(k, pair)| {
+                matches!(pair[0].token, Token::Number { fraction, .. } if !fraction || k + 1 == components)
+                    && (k == 0 || !pair[0].spaced)
+                    && !pair[1].spaced
+                    && pair[1].token == Token::Unit(UNITS[k])
+            })
+    };
+    if symbolic(first, ['N', 'S']) && symbolic(second, ['E', 'W']) {
+        Ok(())
+    } else {
+        Err("strict mode expects D°M'S\"H pairs (latitude first) or signed \"lat, lon\"")
+    }
+}
+
+const UNITS: [Unit; 3] = [Unit::Degrees, Unit::Minutes, Unit::Seconds];
+
+/// One angle in degrees, and its axis if a hemisphere letter gave it away.
+fn parse_angle(group: &[Spanned]) -> Result<(f64, Option<Axis>), &'static str> {
+    let mut tokens: Vec<Token> = group.iter().map(|t| t.token).collect();
+    let mut hemisphere = None;
+    if let Some(&Token::Hemisphere(h)) = tokens.first() {
+        hemisphere = Some(h);
+        tokens.remove(0);
+    }
+    if let Some(&Token::Hemisphere(h)) = tokens.last() {
+        if hemisphere.is_some() {
+            return Err("hemisphere letter given twice");
+        }
+        hemisphere = Some(h);
+        tokens.pop();
+    }
+    let mut negative = false;
+    let mut signed = false;
+    if let Some(&Token::Sign { negative: n }) = tokens.first() {
+        negative = n;
+        signed = true;
+        tokens.remove(0);
+    }
+
+    let mut components: Vec<(f64, bool)> = Vec::with_capacity(3);
+    let mut rest = tokens.as_slice();
+    while let Some((&head, tail)) = rest.split_first() {
+        let Token::Number { value, fraction } = head else {
+            return Err(match head {
+                Token::Hemisphere(_) => "hemisphere letter in the middle of an angle",
+                Token::Sign { .. } => "sign in the middle of an angle",
+                _ => "expected a number",
+            });
+        };
+        if components.len() == 3 {
+            return Err("too many components; expected degrees, minutes and seconds");
+        }
+        rest = tail;
+        match rest.first() {
+            Some(&Token::Unit(unit)) => {
+                if unit != UNITS[components.len()] {
+                    return Err("components must be in degrees, minutes, seconds order");
+                }
+                rest = &rest[1..];
+                if rest.first() == Some(&Token::Colon) {
+                    return Err("unexpected ':' after a unit symbol");
+                }
+            }
+            Some(Token::Colon) => rest = &rest[1..],
+            _ => {}
+        }
+        components.push((value, fraction));
+    }
+
+    if components.is_empty() {
+        return Err("missing degrees");
+    }
+    if components[..components.len() - 1].iter().any(|&(_, f)| f) {
+        return Err("only the last component may have a fractional part");
+    }
+    if components[1..].iter().any(|&(v, _)| v >= 60.0) {
+        return Err("minutes and seconds must be less than 60");
+    }
+    let magnitude = components
+        .iter()
+        .zip([1.0, 60.0, 3600.0])
+        .map(|(&(v, _), d)| v / d)
+        .sum::<f64>();
+
+    let axis = match hemisphere {
+        Some('N' | 'S') => Some(Axis::Lat),
+        Some(_) => Some(Axis::Lon),
+        None => None,
+    };
+    if signed && hemisphere.is_some() {
+        return Err("both a sign and a hemisphere letter given");
+    }
+    let negative = negative || matches!(hemisphere, Some('S' | 'W'));
+    Ok((if negative { -magnitude } else { magnitude }, axis))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_ddm, format_decimal_degrees, format_dms, parse_degrees};
+    use crate::math::Geodetic;
+    use crate::math::notation::{CoordinateParseError, ParseMode};
+
+    fn assert_close(a: f64, b: f64, eps: f64) {
+        assert!((a - b).abs() <= eps, "{a} vs {b} (eps {eps})");
+    }
+
+    fn lat_lon(input: &str, mode: ParseMode) -> (f64, f64) {
+        let geo = parse_degrees(input, mode).unwrap_or_else(|e| panic!("{e}"));
+        (geo.lat_deg(), geo.lon_deg())
+    }
+
+    #[test]
+    fn formats_with_precision_and_carry() {
+        let geo = Geodetic::from_degrees(40.446195, -79.982222, 0.0);
+        assert_eq!(format_decimal_degrees(geo, 4), "40.4462, -79.9822");
+        assert_eq!(format_ddm(geo, 3), "40°26.772'N 79°58.933'W");
+        assert_eq!(format_dms(geo, 2), "40°26'46.30\"N 79°58'56.00\"W");
+        assert_eq!(format_dms(geo, 0), "40°26'46\"N 79°58'56\"W");
+
+        // 59.9996" rounds up into the next minute and degree.
+        let edge = Geodetic::from_degrees(-(10.0 - 0.0004 / 3600.0), 0.0, 0.0);
+        assert_eq!(format_dms(edge, 3), "10°00'00.000\"S 0°00'00.000\"E");
+        let wrapped = Geodetic::from_degrees(0.0, 190.0, 0.0);
+        assert_eq!(format_decimal_degrees(wrapped, 1), "0.0, -170.0");
+    }
+
+    #[test]
+    fn strict_accepts_formatter_output_only() {
+        let strict = ParseMode::Strict;
+        let (lat, lon) = lat_lon("40°26'46.30\"N 79°58'56.00\"W", strict);
+        assert_close(lat, 40.446194, 1e-6);
+        assert_close(lon, -79.982222, 1e-6);
+        let (lat, lon) = lat_lon("40°26.772'N, 79°58.933'W", strict);
+        assert_close(lat, 40.4462, 1e-6);
+        assert_close(lon, -79.982217, 1e-6);
+        assert_eq!(lat_lon("-33.5, 151.25", strict), (-33.5, 151.25));
+
+        for bad in [
+            "-33.5 151.25",
+            "40 26 46 N 79 58 56 W",
+            "N40°26'46\" W79°58'56\"",
+            "79°58'56\"W 40°26'46\"N",
+            "40°26'46\"n 79°58'56\"w",
+        ] {
+            assert!(parse_degrees(bad, strict).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn lenient_accepts_pasted_variants() {
+        let lenient = ParseMode::Lenient;
+        let expected = (40.446194, -79.982222);
+        for input in [
+            "40°26'46.3\"N 79°58'56\"W",
+            "40° 26′ 46.3″ N, 79° 58′ 56″ W",
+            "N 40 26 46.3 W 79 58 56",
+            "79d58'56\"w 40d26'46.3\"n",
+            "40:26:46.3 -79:58:56",
+            "40 26 46.3 -79 58 56",
+            "40 26 46.3 79 58 56 W",
+        ] {
+            let (lat, lon) = lat_lon(input, lenient);
+            assert_close(lat, expected.0, 1e-6);
+            assert_close(lon, expected.1, 1e-6);
+        }
+        assert_eq!(lat_lon("51.5 359.5", lenient), (51.5, -0.5));
+        assert_eq!(lat_lon("-33.5 151.25", lenient), (-33.5, 151.25));
+    }
+
+    #[test]
+    fn reports_what_is_wrong() {
+        let reason = |input: &str| match parse_degrees(input, ParseMode::Lenient) {
+            Err(CoordinateParseError::InvalidDegrees { reason, .. }) => reason,
+            other => panic!("{input}: {other:?}"),
+        };
+        assert_eq!(
+            reason("40°61'N 79°W"),
+            "minutes and seconds must be less than 60"
+        );
+        assert_eq!(reason("40N 50S"), "two latitudes given");
+        assert_eq!(reason("95, 10"), "latitude must be within ±90°");
+        assert_eq!(
+            reason("-40S 10E"),
+            "both a sign and a hemisphere letter given"
+        );
+        assert_eq!(
+            reason("40.5°30'N 10E"),
+            "only the last component may have a fractional part"
+        );
+        assert_eq!(reason("40 x 10"), "unexpected character");
+        assert_eq!(reason("40"), "expected a latitude and a longitude");
+    }
+}
@@ -0,0 +1,136 @@
+//! Geohash (base-32 interleaved lon/lat bisection).
+
+use super::{CoordinateParseError, ParseMode};
+use crate::math::Geodetic;
+use crate::math::projection::wrap_lon_rad;
+
+const ALPHABET: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+/// Twelve characters resolve to a few centimetres; more adds nothing an `f64` can hold.
+const MAX_LENGTH: usize = 12;
+
+pub(super) fn is_geohash_char(c: char) -> bool {
+    c.is_ascii() && ALPHABET.contains(&(c as u8))
+}
+
+/// Geohash of `length` characters, clamped to `1..=12`.
+pub fn format_geohash(geo: Geodetic, length: u8) -> String {
+    let length = (length as usize).clamp(1, MAX_LENGTH);
+    let lat = geo.lat_deg().clamp(-90.0, 90.0);
+    let lon = wrap_lon_rad(geo.lon_rad).to_degrees();
+    let mut lat_range = (-90.0, 90.0);
+    let mut lon_range = (-180.0, 180.0);
+    let mut out = String::with_capacity(length);
+    let mut even = true;
+    for _ in 0..length {
+        let mut index = 0;
+        for _ in 0..5 {
+            let (range, value) = if even {
+                (&mut lon_range, lon)
+            } else {
+                (&mut lat_range, lat)
+            };
+            let mid = (range.0 + range.1) / 2.0;
+            index <<= 1;
+            if value >= mid {
+                index |= 1;
+                range.0 = mid;
+            } else {
+                range.1 = mid;
+            }
+            even = !even;
+        }
+        out.push(ALPHABET[index] as char);
+    }
+    out
+}
+
+/// Parse a geohash to the centre of its cell.
+///
+/// Strict mode takes 1..=12 lower-case characters; lenient mode also accepts upper case,
+/// surrounding whitespace and longer hashes.
+pub fn parse_geohash(input: &str, mode: ParseMode) -> Result<Geodetic, CoordinateParseError> {
+    let invalid = |reason| CoordinateParseError::InvalidGeohash {
+        input: input.to_string(),
+        reason,
+    };
+    let hash = match mode {
+        ParseMode::Strict => input,
+        ParseMode::Lenient => input.trim(),
+    };
+    if hash.is_empty() {
+        return Err(CoordinateParseError::Empty);
+    }
+    if mode == ParseMode::Strict && hash.len() > MAX_LENGTH {
+        return Err(invalid("longer than 12 characters"));
+    }
+
+    let mut lat_range = (-90.0f64, 90.0f64);
+    let mut lon_range = (-180.0f64, 180.0f64);
+    let mut even = true;
+    for c in hash.chars() {
+        let c = match mode {
+            ParseMode::Strict if c.is_ascii_uppercase() => {
+                return Err(invalid("strict mode expects lower case"));
+            }
+            ParseMode::Strict => c,
+            ParseMode::Lenient => c.to_ascii_lowercase(),
+        };
+        let Some(index) = ALPHABET.iter().position(|&a| a as char == c) else {
+            return Err(invalid("characters must be 0-9 or b-z without i, l and o"));
+        };
+        for bit in (0..5).rev() {
+            let range = if even { &mut lon_range } else { &mut lat_range };
+            let mid = (range.0 + range.1) / 2.0;
+            if index >> bit & 1 == 1 {
+                range.0 = mid;
+            } else {
+                range.1 = mid;
+            }
+            even = !even;
+        }
+    }
+    Ok(Geodetic::from_degrees(
+        (lat_range.0 + lat_range.1) / 2.0,
+        (lon_range.0 + lon_range.1) / 2.0,
+        0.0,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_geohash, parse_geohash};
+    use crate::math::Geodetic;
+    use crate::math::notation::{CoordinateParseError, ParseMode};
+
+    fn assert_close(a: f64, b: f64, eps: f64) {
+        assert!((a - b).abs() <= eps, "{a} vs {b} (eps {eps})");
+    }
+
+    #[test]
+    fn encodes_and_decodes_reference_hashes() {
+        let geo = Geodetic::from_degrees(57.64911, 10.40744, 0.0);
+        assert_eq!(format_geohash(geo, 11), "u4pruydqqvj");
+        assert_eq!(format_geohash(geo, 0), "u");
+        assert_eq!(format_geohash(geo, 40).len(), 12);
+
+        let back = parse_geohash("u4pruydqqvj", ParseMode::Strict).unwrap();
+        assert_close(back.lat_deg(), 57.64911, 1e-6);
+        assert_close(back.lon_deg(), 10.40744, 1e-6);
+        let coarse = parse_geohash(" U4P ", ParseMode::Lenient).unwrap();
+        assert_close(coarse.lat_deg(), 56.953125, 1e-9);
+        assert_close(coarse.lon_deg(), 10.546875, 1e-9);
+
+        let reason = |input: &str, mode| match parse_geohash(input, mode) {
+            Err(CoordinateParseError::InvalidGeohash { reason, .. }) => reason,
+            other => panic!("{input}: {other:?}"),
+        };
+        assert_eq!(
+            reason("U4P", ParseMode::Strict),
+            "strict mode expects lower case"
+        );
+        assert_eq!(
+            reason("u4a", ParseMode::Lenient),
+            "characters must be 0-9 or b-z without i, l and o"
+        );
+    }
+}
@@ -0,0 +1,373 @@
+//! MGRS and its space-separated USNG layout, including the polar (UPS) squares.
+
+use super::utm::{BAND_SLACK_DEG, band_hemisphere, band_letter, band_range};
+use super::{CoordinateParseError, ParseMode};
+use crate::math::projection::{Hemisphere, PolarStereographic, Projection, Utm};
+use crate::math::{Geodetic, Vec2};
+
+/// 100 km column letters for UTM zones `1, 2, 3` (mod 3).
+const UTM_COLUMNS: [&[u8; 8]; 3] = [b"ABCDEFGH", b"JKLMNPQR", b"STUVWXYZ"];
+/// 100 km row letters for UTM zones; even zones start five letters in.
+const UTM_ROWS: &[u8; 20] = b"ABCDEFGHJKLMNPQRSTUV";
+/// 100 km column letters in the polar regions (`A` at easting 2000 km, east of the pole).
+const UPS_COLUMNS: &[u8; 18] = b"ABCFGHJKLPQRSTUXYZ";
+/// 100 km row letters in the polar regions.
+const UPS_ROWS: &[u8; 24] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+const SQUARE_M: f64 = 100_000.0;
+
+/// Compact MGRS, e.g. `17TNE8931677700`, with `digits` (0..=5) digits per axis.
+///
+/// Digits are truncated, not rounded, so the reference names the square containing `geo`.
+pub fn format_mgrs(geo: Geodetic, digits: u8) -> Option<String> {
+    let r = Reference::of(geo, digits)?;
+    Some(format!(
+        "{}{}{}{}",
+        r.zone_band, r.square, r.easting, r.northing
+    ))
+}
+
+/// USNG layout of [`format_mgrs`], e.g. `17T NE 89316 77700`.
+pub fn format_usng(geo: Geodetic, digits: u8) -> Option<String> {
+    let r = Reference::of(geo, digits)?;
+    let mut out = format!("{} {}", r.zone_band, r.square);
+    if !r.easting.is_empty() {
+        out.push_str(&format!(" {} {}", r.easting, r.northing));
+    }
+    Some(out)
+}
+
+/// Parse an MGRS or USNG reference to the south-west corner of its square.
+///
+/// Strict mode takes the compact MGRS or the [`format_usng`] layout in upper case.
+/// Lenient mode ignores case and whitespace.
+pub fn parse_mgrs(input: &str, mode: ParseMode) -> Result<Geodetic, CoordinateParseError> {
+    let invalid = |reason| CoordinateParseError::InvalidMgrs {
+        input: input.to_string(),
+        reason,
+    };
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err(CoordinateParseError::Empty);
+    }
+    let compact: String = match mode {
+        ParseMode::Strict => {
+            if trimmed.chars().any(|c| c.is_ascii_lowercase()) {
+                return Err(invalid("strict mode expects upper-case letters"));
+            }
+            trimmed.chars().filter(|c| *c != ' ').collect()
+        }
+        ParseMode::Lenient => trimmed
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| c.to_ascii_uppercase())
+            .collect(),
+    };
+    if !compact.is_ascii() {
+        return Err(invalid("unexpected character"));
+    }
+
+    let zone_digits = compact.chars().take_while(char::is_ascii_digit).count();
+    if zone_digits > 2 {
+        return Err(invalid("zone must be one or two digits"));
+    }
+    let (zone, rest) = compact.split_at(zone_digits);
+    let letters = rest.as_bytes();
+    if letters.len() < 3 || !letters[..3].iter().all(u8::is_ascii_uppercase) {
+        return Err(invalid(
+            "expected a band letter and a two-letter 100 km square",
+        ));
+    }
+    let (band, column, row) = (letters[0] as char, letters[1], letters[2]);
+    let numbers = &rest[3..];
+    if !numbers.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid("expected only digits after the 100 km square"));
+    }
+    if numbers.len() % 2 != 0 {
+        return Err(invalid(
+            "easting and northing must have the same number of digits",
+        ));
+    }
+    if numbers.len() > 10 {
+        return Err(invalid("at most five digits per axis"));
+    }
+    if mode == ParseMode::Strict && !is_strict_layout(trimmed, zone_digits, numbers.len()) {
+        return Err(invalid(
+            "strict mode expects `17TNE8931677700` or `17T NE 89316 77700`",
+        ));
+    }
+    let (e, n) = numbers.split_at(numbers.len() / 2);
+    let scale = 10f64.powi(5 - e.len() as i32);
+    let within = Vec2::new(
+        e.parse().unwrap_or(0.0) * scale,
+        n.parse().unwrap_or(0.0) * scale,
+    );
+
+    if zone.is_empty() {
+        return parse_polar(band, column, row, within).map_err(invalid);
+    }
+    let zone: u8 = zone.parse().map_err(|_| invalid("zone must be a number"))?;
+    if !(1..=60).contains(&zone) {
+        return Err(invalid("zone must be within 1..=60"));
+    }
+    let Some((south, north)) = band_range(band) else {
+        return Err(invalid("band letter must be one of C..X without I and O"));
+    };
+    let hemisphere = band_hemisphere(band);
+    let utm = Utm::new(zone, hemisphere).ok_or_else(|| invalid("zone must be within 1..=60"))?;
+
+    let set = UTM_COLUMNS[(zone as usize - 1) % 3];
+    let Some(col) = set.iter().position(|&c| c == column) else {
+        return Err(invalid("column letter is not used in this zone"));
+    };
+    let Some(row) = UTM_ROWS.iter().position(|&c| c == row) else {
+        return Err(invalid("row letter must be one of A..V without I and O"));
+    };
+    let row = (row + if zone.is_multiple_of(2) { 15 } else { 0 }) % UTM_ROWS.len();
+
+    // Row letters repeat every 2000 km; pick the repeat that lands in the band.
+    let band_floor = utm
+        .forward(Geodetic::from_degrees(
+            south,
+            utm.central_meridian_rad().to_degrees(),
+            0.0,
+        ))
+        .map_or(0.0, |xy| xy.y);
+    let mut northing = row as f64 * SQUARE_M + within.y;
+    while northing < band_floor - SQUARE_M {
+        northing += 2_000_000.0;
+    }
+    let easting = (col + 1) as f64 * SQUARE_M + within.x;
+    let geo = utm
+        .inverse(Vec2::new(easting, northing))
+        .ok_or_else(|| invalid("position is out of range"))?;
+    if !(south - BAND_SLACK_DEG..=north + BAND_SLACK_DEG).contains(&geo.lat_deg()) {
+        return Err(invalid("100 km square does not lie in the latitude band"));
+    }
+    Ok(geo)
+}
+
+/// The parts of a reference, with digits already truncated to precision.
+struct Reference {
+    zone_band: String,
+    square: String,
+    easting: String,
+    northing: String,
+}
+
+impl Reference {
+    fn of(geo: Geodetic, digits: u8) -> Option<Self> {
+        let lat = geo.lat_deg();
+        if !lat.is_finite() || !geo.lon_rad.is_finite() {
+            return None;
+        }
+        let (zone_band, column, row, xy) = match band_letter(lat) {
+            Some(band) => {
+                let utm = Utm::for_geodetic(geo);
+                let xy = utm.forward(geo)?;
+                let col = (xy.x / SQUARE_M).floor() as usize;
+                let set = UTM_COLUMNS[(utm.zone() as usize - 1) % 3];
+                let column = *set.get(col.checked_sub(1)?)?;
+                let offset = if utm.zone().is_multiple_of(2) { 5 } else { 0 };
+                let row = ((xy.y / SQUARE_M).floor() as usize + offset) % UTM_ROWS.len();
+                (format!("{}{band}", utm.zone()), column, UTM_ROWS[row], xy)
+            }
+            None => {
+                let hemisphere = Hemisphere::of_lat(geo.lat_rad);
+                let xy = PolarStereographic::ups(hemisphere).forward(geo)?;
+                let band = ups_band(hemisphere, xy.x);
+                let col = (xy.x / SQUARE_M).floor() as usize;
+                let column = *UPS_COLUMNS.get(col.checked_sub(ups_column_origin(band))?)?;
+                let row = (xy.y / SQUARE_M).floor() as usize;
+                let row = *UPS_ROWS.get(row.checked_sub(ups_row_origin(hemisphere))?)?;
+                (band.to_string(), column, row, xy)
+            }
+        };
+
+        let digits = digits.min(5) as usize;
+        let truncate = |v: f64| {
+            // A hair of slack so round-tripped square corners do not drop into the
+            // square below.
+            let within = (v + 1e-6).rem_euclid(SQUARE_M);
+            let value = (within / 10f64.powi(5 - digits as i32)).floor() as u64;
+            if digits == 0 {
+                String::new()
+            } else {
+                format!("{value:0digits$}")
+            }
+        };
+        Some(Self {
+            zone_band,
+            square: format!("{}{}", column as char, row as char),
+            easting: truncate(xy.x),
+            northing: truncate(xy.y),
+        })
+    }
+}
+
+fn ups_band(hemisphere: Hemisphere, easting: f64) -> char {
+    match (hemisphere, easting < 2_000_000.0) {
+        (Hemisphere::North, true) => 'Y',
+        (Hemisphere::North, false) => 'Z',
+        (Hemisphere::South, true) => 'A',
+        (Hemisphere::South, false) => 'B',
+    }
+}
+
+/// Easting, in 100 km squares, of the first entry of `UPS_COLUMNS` for a polar band.
+///
+/// West of the pole the letters run `J..Z` from 800 km; east of it `A..` from 2000 km.
+fn ups_column_origin(band: char) -> usize {
+    match band {
+        'A' | 'Y' => 2,
+        _ => 20,
+    }
+}
+
+/// Northing, in 100 km squares, of the first entry of `UPS_ROWS`.
+fn ups_row_origin(hemisphere: Hemisphere) -> usize {
+    match hemisphere {
+        Hemisphere::North => 13,
+        Hemisphere::South => 8,
+    }
+}
+
+fn parse_polar(band: char, column: u8, row: u8, within: Vec2) -> Result<Geodetic, &'static str> {
+    let hemisphere = match band {
+        'A' | 'B' => Hemisphere::South,
+        'Y' | 'Z' => Hemisphere::North,
+        _ => return Err("polar references start with band A, B, Y or Z"),
+    };
+    // West of the pole the letters are `J..Z`, east of it `A..R`.
+    let col = UPS_COLUMNS
+        .iter()
+        .position(|&c| c == column)
+        .filter(|&c| {
+            if matches!(band, 'A' | 'Y') {
+                c >= 6
+            } else {
+                c <= 11
+            }
+        })
+        .map(|c| c + ups_column_origin(band))
+        .ok_or("column letter is not used in this polar band")?;
+    let row = UPS_ROWS
+        .iter()
+        .position(|&c| c == row)
+        .map(|r| r + ups_row_origin(hemisphere))
+        .filter(|&r| hemisphere == Hemisphere::South || r < 27)
+        .ok_or("row letter is not used in this polar band")?;
+    let xy = Vec2::new(
+        col as f64 * SQUARE_M + within.x,
+        row as f64 * SQUARE_M + within.y,
+    );
+    PolarStereographic::ups(hemisphere)
+        .inverse(xy)
+        .ok_or("position is out of range")
+}
+
+/// Compact (`17TNE8931677700`) or USNG (`17T NE 89316 77700`) with single spaces.
+fn is_strict_layout(input: &str, zone_digits: usize, digit_count: usize) -> bool {
+    if !input.contains(' ') {
+        return true;
+    }
+    let fields: Vec<&str> = input.split(' ').collect();
+    let expected = if digit_count == 0 { 2 } else { 4 };
+    fields.len() == expected
+        && fields[0].len() == zone_digits + 1
+        && fields[1].len() == 2
+        && fields[2..].iter().all(|f| f.len() == digit_count / 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_mgrs, format_usng, parse_mgrs};
+    use crate::math::Geodetic;
+    use crate::math::notation::{CoordinateParseError, ParseMode};
+
+    fn assert_close(a: f64, b: f64, eps: f64) {
+        assert!((a - b).abs() <= eps, "{a} vs {b} (eps {eps})");
+    }
+
+    #[test]
+    fn formats_known_references() {
+        // Washington Monument.
+        let geo = Geodetic::from_degrees(38.8895, -77.0352, 0.0);
+        assert_eq!(format_mgrs(geo, 5).unwrap(), "18SUJ2348606483");
+        assert_eq!(format_mgrs(geo, 2).unwrap(), "18SUJ2306");
+        assert_eq!(format_usng(geo, 3).unwrap(), "18S UJ 234 064");
+        assert_eq!(format_usng(geo, 0).unwrap(), "18S UJ");
+
+        // Southern hemisphere, even zone.
+        let sydney = Geodetic::from_degrees(-33.8568, 151.2153, 0.0);
+        assert_eq!(format_mgrs(sydney, 1).unwrap(), "56HLH35");
+    }
+
+    #[test]
+    fn round_trips_to_square_corners_everywhere() {
+        for (lat, lon) in [
+            (38.8895, -77.0352),
+            (-33.8568, 151.2153),
+            (0.5, 0.5),
+            (-0.5, -179.5),
+            (71.9, 25.0),
+            (83.9, 30.0),
+            (-79.9, 100.0),
+            (60.39, 5.32),
+            (86.0, -45.0),
+            (89.9, 120.0),
+            (-85.0, 10.0),
+            (-88.0, -170.0),
+        ] {
+            let geo = Geodetic::from_degrees(lat, lon, 0.0);
+            let text = format_mgrs(geo, 5).unwrap();
+            let back = parse_mgrs(&text, ParseMode::Strict).unwrap_or_else(|e| panic!("{e}"));
+            // The south-west corner of a 1 m square.
+            assert_close(back.lat_deg(), lat, 2e-5);
+            assert_eq!(format_mgrs(back, 5).unwrap(), text, "{lat}, {lon}");
+        }
+    }
+
+    #[test]
+    fn lenient_and_strict_layouts() {
+        let expected = parse_mgrs("18SUJ2348606483", ParseMode::Strict).unwrap();
+        for input in [
+            "18S UJ 23486 06483",
+            " 18suj 2348606483 ",
+            "18 S U J 23486 06483",
+        ] {
+            let geo = parse_mgrs(input, ParseMode::Lenient).unwrap_or_else(|e| panic!("{e}"));
+            assert_eq!(geo, expected);
+        }
+        assert!(parse_mgrs("18S UJ 23486 06483", ParseMode::Strict).is_ok());
+
+        let reason = |input: &str, mode| match parse_mgrs(input, mode) {
+            Err(CoordinateParseError::InvalidMgrs { reason, .. }) => reason,
+            other => panic!("{input}: {other:?}"),
+        };
+        let strict = ParseMode::Strict;
+        assert_eq!(
+            reason("18suj2348606483", strict),
+            "strict mode expects upper-case letters"
+        );
+        assert_eq!(
+            reason("18 S UJ 23486 06483", strict),
+            "strict mode expects `17TNE8931677700` or `17T NE 89316 77700`"
+        );
+        assert_eq!(
+            reason("18SUJ234860648", strict),
+            "easting and northing must have the same number of digits"
+        );
+        assert_eq!(
+            reason("18SJJ2348606483", strict),
+            "column letter is not used in this zone"
+        );
+        assert_eq!(
+            reason("18MUJ2348606483", strict),
+            "100 km square does not lie in the latitude band"
+        );
+        assert_eq!(
+            reason("YAB1234", ParseMode::Lenient),
+            "column letter is not used in this polar band"
+        );
+    }
+}
@@ -0,0 +1,329 @@
+//! Coordinate notations: decimal degrees, DMS/DDM, UTM, MGRS/USNG and geohash.
+//!
+//! Every notation formats a WGS84 [`Geodetic`] with explicit precision and parses back to
+//! one (altitude `0.0`). Parsing comes in two flavours:
+//! - [`ParseMode::Strict`] accepts exactly what the formatters here produce (plus the usual
+//!   published variants such as compact MGRS), and is meant for APIs and files.
+//! - [`ParseMode::Lenient`] accepts what people paste: any case, stray whitespace, unicode
+//!   primes, prefix or suffix hemisphere letters, signed values, `:`-separated components
+//!   and longitude-first input when hemisphere letters make the order unambiguous.
+//!
+//! MGRS references denote the south-west corner of their grid square; geohashes decode to
+//! the centre of their cell.
+
+mod degrees;
+mod geohash;
+mod mgrs;
+mod utm;
+
+pub use degrees::*;
+pub use geohash::*;
+pub use mgrs::*;
+pub use utm::*;
+
+use std::fmt;
+
+use super::Geodetic;
+use geohash::is_geohash_char;
+
+/// How forgiving coordinate parsers are about layout.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum ParseMode {
+    Strict,
+    #[default]
+    Lenient,
+}
+
+/// A coordinate notation family, as recognised by [`detect_notation`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CoordinateNotation {
+    /// Decimal degrees, degrees-decimal-minutes or degrees-minutes-seconds.
+    Degrees,
+    Utm,
+    /// MGRS, including the space-separated USNG layout.
+    Mgrs,
+    Geohash,
+}
+
+/// A notation together with its precision, for formatting.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CoordinateFormat {
+    /// Signed `lat, lon` with `decimals` fractional digits.
+    Decimal { decimals: u8 },
+    /// `40°26.772'N 79°58.933'W` with `decimals` fractional digits of minutes.
+    Ddm { decimals: u8 },
+    /// `40°26'46.30"N 79°58'56.00"W` with `decimals` fractional digits of seconds.
+    Dms { decimals: u8 },
+    /// `17T 589316 4477700` with `decimals` fractional digits of meters.
+    Utm { decimals: u8 },
+    /// `17TNE8931677700` with `digits` (0..=5) digits per axis.
+    Mgrs { digits: u8 },
+    /// `17T NE 89316 77700` with `digits` (0..=5) digits per axis.
+    Usng { digits: u8 },
+    /// Geohash of `length` (1..=12) characters.
+    Geohash { length: u8 },
+}
+
+impl CoordinateFormat {
+    /// Names accepted by [`CoordinateFormat::from_name`], in display order.
+    pub const NAMES: [&'static str; 7] =
+        ["decimal", "ddm", "dms", "utm", "mgrs", "usng", "geohash"];
+
+    /// Look a format up by name (case-insensitive). `precision` is the notation's own
+    /// precision parameter; `None` picks roughly meter-level precision.
+    pub fn from_name(name: &str, precision: Option<u8>) -> Option<Self> {
+        let format = match name.to_ascii_lowercase().as_str() {
+            "decimal" | "dd" => CoordinateFormat::Decimal {
+                decimals: precision.unwrap_or(6),
+            },
+            "ddm" => CoordinateFormat::Ddm {
+                decimals: precision.unwrap_or(4),
+            },
+            "dms" => CoordinateFormat::Dms {
+                decimals: precision.unwrap_or(2),
+            },
+            "utm" => CoordinateFormat::Utm {
+                decimals: precision.unwrap_or(0),
+            },
+            "mgrs" => CoordinateFormat::Mgrs {
+                digits: precision.unwrap_or(5),
+            },
+            "usng" => CoordinateFormat::Usng {
+                digits: precision.unwrap_or(5),
+            },
+            "geohash" => CoordinateFormat::Geohash {
+                length: precision.unwrap_or(9),
+            },
+            _ => return None,
+        };
+        Some(format)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            CoordinateFormat::Decimal { .. } => "decimal",
+            CoordinateFormat::Ddm { .. } => "ddm",
+            CoordinateFormat::Dms { .. } => "dms",
+            CoordinateFormat::Utm { .. } => "utm",
+            CoordinateFormat::Mgrs { .. } => "mgrs",
+            CoordinateFormat::Usng { .. } => "usng",
+            CoordinateFormat::Geohash { .. } => "geohash",
+        }
+    }
+
+    pub fn notation(self) -> CoordinateNotation {
+        match self {
+            CoordinateFormat::Decimal { .. }
+            | CoordinateFormat::Ddm { .. }
+            | CoordinateFormat::Dms { .. } => CoordinateNotation::Degrees,
+            CoordinateFormat::Utm { .. } => CoordinateNotation::Utm,
+            CoordinateFormat::Mgrs { .. } | CoordinateFormat::Usng { .. } => {
+                CoordinateNotation::Mgrs
+            }
+            CoordinateFormat::Geohash { .. } => CoordinateNotation::Geohash,
+        }
+    }
+
+    /// Format `geo`, or `None` if it is not finite or has no representation in this
+    /// notation (UTM outside 80°S..84°N).
+    pub fn format(self, geo: Geodetic) -> Option<String> {
+        if !geo.lat_rad.is_finite() || !geo.lon_rad.is_finite() {
+            return None;
+        }
+        match self {
+            CoordinateFormat::Decimal { decimals } => Some(format_decimal_degrees(geo, decimals)),
+            CoordinateFormat::Ddm { decimals } => Some(format_ddm(geo, decimals)),
+            CoordinateFormat::Dms { decimals } => Some(format_dms(geo, decimals)),
+            CoordinateFormat::Utm { decimals } => format_utm(geo, decimals),
+            CoordinateFormat::Mgrs { digits } => format_mgrs(geo, digits),
+            CoordinateFormat::Usng { digits } => format_usng(geo, digits),
+            CoordinateFormat::Geohash { length } => Some(format_geohash(geo, length)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoordinateParseError {
+    Empty,
+    InvalidDegrees { input: String, reason: &'static str },
+    InvalidUtm { input: String, reason: &'static str },
+    InvalidMgrs { input: String, reason: &'static str },
+    InvalidGeohash { input: String, reason: &'static str },
+}
+
+impl fmt::Display for CoordinateParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoordinateParseError::Empty => write!(f, "empty coordinate"),
+            CoordinateParseError::InvalidDegrees { input, reason } => {
+                write!(f, "invalid degree coordinate {input:?}: {reason}")
+            }
+            CoordinateParseError::InvalidUtm { input, reason } => {
+                write!(f, "invalid UTM coordinate {input:?}: {reason}")
+            }
+            CoordinateParseError::InvalidMgrs { input, reason } => {
+                write!(f, "invalid MGRS reference {input:?}: {reason}")
+            }
+            CoordinateParseError::InvalidGeohash { input, reason } => {
+                write!(f, "invalid geohash {input:?}: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CoordinateParseError {}
+
+/// Guess the notation of `input` from its shape alone.
+///
+/// Some single tokens fit both MGRS and geohash (`9qxy12`). Geohashes are conventionally
+/// lower case and MGRS upper case, so a lower-case token made of geohash characters is
+/// taken as a geohash and anything else as MGRS. Returns `None` for empty input.
+pub fn detect_notation(input: &str) -> Option<CoordinateNotation> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+    if input.chars().any(|c| c.is_ascii_lowercase()) && input.chars().all(is_geohash_char) {
+        return Some(CoordinateNotation::Geohash);
+    }
+    if looks_like_mgrs(input) {
+        return Some(CoordinateNotation::Mgrs);
+    }
+    if looks_like_utm(input) {
+        return Some(CoordinateNotation::Utm);
+    }
+    if input.chars().any(|c| c.is_ascii_alphabetic())
+        && input
+            .chars()
+            .all(|c| is_geohash_char(c.to_ascii_lowercase()))
+    {
+        return Some(CoordinateNotation::Geohash);
+    }
+    Some(CoordinateNotation::Degrees)
+}
+
+/// Parse a coordinate in any supported notation, detected with [`detect_notation`].
+pub fn parse_coordinate(input: &str, mode: ParseMode) -> Result<Geodetic, CoordinateParseError> {
+    match detect_notation(input) {
+        None => Err(CoordinateParseError::Empty),
+        Some(CoordinateNotation::Degrees) => parse_degrees(input, mode),
+        Some(CoordinateNotation::Utm) => parse_utm(input, mode),
+        Some(CoordinateNotation::Mgrs) => parse_mgrs(input, mode),
+        Some(CoordinateNotation::Geohash) => parse_geohash(input, mode),
+    }
+}
+
+/// `[0-9]+(\.[0-9]*)?` or `\.[0-9]+` as `f64`; rejects signs, exponents, `inf` and `nan`.
+pub(crate) fn parse_unsigned_decimal(s: &str) -> Option<f64> {
+    let mut digits = 0;
+    let mut dots = 0;
+    for c in s.chars() {
+        match c {
+            '0'..='9' => digits += 1,
+            '.' => dots += 1,
+            _ => return None,
+        }
+    }
+    if digits == 0 || dots > 1 {
+        return None;
+    }
+    s.parse().ok()
+}
+
+fn looks_like_mgrs(input: &str) -> bool {
+    let compact: Vec<char> = input.chars().filter(|c| !c.is_whitespace()).collect();
+    let digits = compact.iter().take_while(|c| c.is_ascii_digit()).count();
+    let letters = match digits {
+        1 | 2 => &compact[digits..],
+        // Polar (UPS) references start with the band letter; see `detect_notation`.
+        0 if compact.first().is_some_and(|c| "ABYZ".contains(*c)) => &compact[..],
+        _ => return false,
+    };
+    letters.len() >= 3
+        && letters[..3].iter().all(|c| c.is_ascii_alphabetic())
+        && letters[3..].iter().all(|c| c.is_ascii_digit())
+        && (letters.len() - 3) % 2 == 0
+}
+
+fn looks_like_utm(input: &str) -> bool {
+    let normalized = input.replace(',', " ");
+    let mut tokens = normalized.split_whitespace();
+    let Some(first) = tokens.next() else {
+        return false;
+    };
+    let digits = first.chars().take_while(|c| c.is_ascii_digit()).count();
+    if !(1..=2).contains(&digits) {
+        return false;
+    }
+    let rest = &first[digits..];
+    let zone_letter = match rest.len() {
+        0 => tokens.next().is_some_and(is_hemisphere_word),
+        1 => rest.chars().all(|c| c.is_ascii_alphabetic()),
+        _ => false,
+    };
+    // Eastings have six digits; this keeps `40 N 79 W` out.
+    zone_letter && tokens.any(|t| t.chars().take_while(char::is_ascii_digit).count() >= 5)
+}
+
+fn is_hemisphere_word(token: &str) -> bool {
+    token.len() == 1 && token.chars().all(|c| c.is_ascii_alphabetic())
+        || ["north", "south"].contains(&token.to_ascii_lowercase().as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CoordinateFormat, CoordinateNotation, CoordinateParseError, ParseMode, detect_notation,
+        parse_coordinate,
+    };
+    use crate::math::Geodetic;
+
+    fn assert_close(a: f64, b: f64, eps: f64) {
+        assert!((a - b).abs() <= eps, "{a} vs {b} (eps {eps})");
+    }
+
+    #[test]
+    fn detects_notation_from_shape() {
+        let d = |s: &str| detect_notation(s);
+        assert_eq!(d("40.4462, -79.9822"), Some(CoordinateNotation::Degrees));
+        assert_eq!(
+            d("40°26'46\"N 79°58'56\"W"),
+            Some(CoordinateNotation::Degrees)
+        );
+        assert_eq!(d("17T 589316 4477700"), Some(CoordinateNotation::Utm));
+        assert_eq!(d("17 N 589316 4477700"), Some(CoordinateNotation::Utm));
+        assert_eq!(d("17TNE8931677700"), Some(CoordinateNotation::Mgrs));
+        assert_eq!(d("17T NE 89316 77700"), Some(CoordinateNotation::Mgrs));
+        assert_eq!(d("ZGC 12345 67890"), Some(CoordinateNotation::Mgrs));
+        assert_eq!(d("dppn59uz"), Some(CoordinateNotation::Geohash));
+        assert_eq!(d("  "), None);
+    }
+
+    #[test]
+    fn every_format_round_trips_through_auto_detection() {
+        let pittsburgh = Geodetic::from_degrees(40.446195, -79.982222, 0.0);
+        for name in CoordinateFormat::NAMES {
+            let format = CoordinateFormat::from_name(name, None).unwrap();
+            assert_eq!(format.name(), name);
+            let text = format.format(pittsburgh).unwrap();
+            assert_eq!(detect_notation(&text), Some(format.notation()), "{text}");
+            for mode in [ParseMode::Strict, ParseMode::Lenient] {
+                let back = parse_coordinate(&text, mode).unwrap_or_else(|e| panic!("{e}"));
+                assert_close(back.lat_deg(), pittsburgh.lat_deg(), 2e-5);
+                assert_close(back.lon_deg(), pittsburgh.lon_deg(), 2e-5);
+            }
+        }
+
+        let nan = Geodetic::from_degrees(f64::NAN, 0.0, 0.0);
+        assert_eq!(CoordinateFormat::Dms { decimals: 0 }.format(nan), None);
+        assert_eq!(
+            parse_coordinate(" ", ParseMode::Lenient),
+            Err(CoordinateParseError::Empty)
+        );
+        let err = parse_coordinate("17T 589316", ParseMode::Lenient).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid UTM coordinate \"17T 589316\": expected zone, band, easting and northing"
+        );
+    }
+}
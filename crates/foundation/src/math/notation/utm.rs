@@ -0,0 +1,208 @@
+//! UTM coordinates with a latitude band letter, e.g. `17T 589316 4477700`.
+
+use super::{CoordinateParseError, ParseMode, parse_unsigned_decimal};
+use crate::math::projection::{Hemisphere, Projection, Utm};
+use crate::math::{Geodetic, Vec2};
+
+/// Latitude band letters from 80°S, 8° each (`X` spans 72°N..84°N).
+pub(super) const BANDS: &[u8; 20] = b"CDEFGHJKLMNPQRSTUVWX";
+
+/// How far (degrees) a decoded position may stray outside its band: grid squares and
+/// rounded coordinates straddle band edges.
+pub(super) const BAND_SLACK_DEG: f64 = 0.5;
+
+/// Band letter for a latitude inside the UTM region (80°S..84°N).
+pub(super) fn band_letter(lat_deg: f64) -> Option<char> {
+    if !(-80.0..84.0).contains(&lat_deg) {
+        return None;
+    }
+    let index = (((lat_deg + 80.0) / 8.0).floor() as usize).min(BANDS.len() - 1);
+    Some(BANDS[index] as char)
+}
+
+/// Latitude range `(south, north)` of a band letter.
+pub(super) fn band_range(letter: char) -> Option<(f64, f64)> {
+    let index = BANDS.iter().position(|&b| b as char == letter)?;
+    let south = -80.0 + 8.0 * index as f64;
+    let north = if letter == 'X' { 84.0 } else { south + 8.0 };
+    Some((south, north))
+}
+
+pub(super) fn band_hemisphere(letter: char) -> Hemisphere {
+    if letter >= 'N' {
+        Hemisphere::North
+    } else {
+        Hemisphere::South
+    }
+}
+
+/// `zone band easting northing` with `decimals` fractional digits of meters, or `None`
+/// outside the UTM region (80°S..84°N, where MGRS switches to UPS).
+pub fn format_utm(geo: Geodetic, decimals: u8) -> Option<String> {
+    let band = band_letter(geo.lat_deg())?;
+    let utm = Utm::for_geodetic(geo);
+    let xy = utm.forward(geo)?;
+    let d = decimals.min(6) as usize;
+    Some(format!("{}{band} {:.d$} {:.d$}", utm.zone(), xy.x, xy.y))
+}
+
+/// Parse `zone band easting northing`.
+///
+/// Strict mode takes exactly the [`format_utm`] layout. Lenient mode also accepts lower
+/// case, a space between zone and band, commas, `m`/`mE`/`mN` suffixes, and a hemisphere
+/// (`N`/`S`/`north`/`south`) in place of the band: an `N` or `S` that does not fit the
+/// northing as a band letter is read as a hemisphere.
+pub fn parse_utm(input: &str, mode: ParseMode) -> Result<Geodetic, CoordinateParseError> {
+    let invalid = |reason| CoordinateParseError::InvalidUtm {
+        input: input.to_string(),
+        reason,
+    };
+    if input.trim().is_empty() {
+        return Err(CoordinateParseError::Empty);
+    }
+    let lenient = mode == ParseMode::Lenient;
+    let normalized = if lenient {
+        input.replace(',', " ").to_ascii_uppercase()
+    } else {
+        input.to_string()
+    };
+    let mut tokens: Vec<&str> = normalized.split_whitespace().collect();
+    if !lenient && tokens.join(" ") != input {
+        return Err(invalid("strict mode expects single spaces between fields"));
+    }
+
+    // Split `17T` into zone and band; lenient input may already have them apart.
+    if tokens.len() == 3 {
+        let first = tokens[0];
+        let (zone, band) = first.split_at(first.chars().take_while(char::is_ascii_digit).count());
+        tokens.splice(0..1, [zone, band]);
+    } else if !lenient {
+        return Err(invalid("strict mode expects `zone+band easting northing`"));
+    }
+    let [zone, band, easting, northing] = tokens[..] else {
+        return Err(invalid("expected zone, band, easting and northing"));
+    };
+
+    let zone: u8 = match zone.len() {
+        1 | 2 => zone.parse().map_err(|_| invalid("zone must be a number"))?,
+        _ => return Err(invalid("zone must be one or two digits")),
+    };
+    if !(1..=60).contains(&zone) {
+        return Err(invalid("zone must be within 1..=60"));
+    }
+    let meters = |token: &str, suffix: &str| {
+        let token = if lenient {
+            token
+                .strip_suffix(suffix)
+                .or_else(|| token.strip_suffix('M'))
+                .unwrap_or(token)
+        } else {
+            token
+        };
+        parse_unsigned_decimal(token)
+    };
+    let easting = meters(easting, "ME").ok_or_else(|| invalid("malformed easting"))?;
+    let northing = meters(northing, "MN").ok_or_else(|| invalid("malformed northing"))?;
+    if !(100_000.0..=900_000.0).contains(&easting) {
+        return Err(invalid("easting must be within 100000..=900000 m"));
+    }
+    if northing > 10_000_000.0 {
+        return Err(invalid("northing must be within 0..=10000000 m"));
+    }
+
+    let xy = Vec2::new(easting, northing);
+    let hemisphere_word = match band {
+        "NORTH" if lenient => Some(Hemisphere::North),
+        "SOUTH" if lenient => Some(Hemisphere::South),
+        _ => None,
+    };
+    if let Some(hemisphere) = hemisphere_word {
+        return unproject(zone, hemisphere, xy).ok_or_else(|| invalid("position is out of range"));
+    }
+
+    let mut letters = band.chars();
+    let (Some(letter), None) = (letters.next(), letters.next()) else {
+        return Err(invalid("band must be a single letter"));
+    };
+    let Some((south, north)) = band_range(letter) else {
+        return Err(invalid("band letter must be one of C..X without I and O"));
+    };
+    let geo = unproject(zone, band_hemisphere(letter), xy)
+        .ok_or_else(|| invalid("position is out of range"))?;
+    let in_band = (south - BAND_SLACK_DEG..=north + BAND_SLACK_DEG).contains(&geo.lat_deg());
+    if in_band {
+        return Ok(geo);
+    }
+    match letter {
+        'N' if lenient => unproject(zone, Hemisphere::North, xy),
+        'S' if lenient => unproject(zone, Hemisphere::South, xy),
+        _ => None,
+    }
+    .ok_or_else(|| invalid("northing does not lie in the latitude band"))
+}
+
+fn unproject(zone: u8, hemisphere: Hemisphere, xy: Vec2) -> Option<Geodetic> {
+    Utm::new(zone, hemisphere)?.inverse(xy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_utm, parse_utm};
+    use crate::math::Geodetic;
+    use crate::math::notation::{CoordinateParseError, ParseMode};
+
+    fn assert_close(a: f64, b: f64, eps: f64) {
+        assert!((a - b).abs() <= eps, "{a} vs {b} (eps {eps})");
+    }
+
+    #[test]
+    fn formats_and_parses_both_hemispheres() {
+        let cape_town = Geodetic::from_degrees(-33.9249, 18.4241, 0.0);
+        let text = format_utm(cape_town, 0).unwrap();
+        assert_eq!(text, "34H 261882 6243182");
+        let back = parse_utm(&text, ParseMode::Strict).unwrap();
+        assert_close(back.lat_deg(), -33.9249, 1e-5);
+        assert_close(back.lon_deg(), 18.4241, 1e-5);
+
+        assert_eq!(format_utm(Geodetic::from_degrees(85.0, 0.0, 0.0), 0), None);
+        assert_eq!(format_utm(Geodetic::from_degrees(-80.5, 0.0, 0.0), 0), None);
+    }
+
+    #[test]
+    fn lenient_reads_hemisphere_letters_and_suffixes() {
+        let lenient = ParseMode::Lenient;
+        // `S` does not fit 6243182 m as band S (32°N..40°N), so it is the hemisphere.
+        for input in [
+            "34 S 261882mE 6243182mN",
+            "34s, 261882, 6243182",
+            "34 south 261882 6243182",
+        ] {
+            let geo = parse_utm(input, lenient).unwrap_or_else(|e| panic!("{e}"));
+            assert_close(geo.lat_deg(), -33.9249, 1e-5);
+        }
+        let north = parse_utm("17N 589316 4477700", lenient).unwrap();
+        assert_close(north.lat_deg(), 40.446, 1e-3);
+
+        let reason = |input: &str, mode| match parse_utm(input, mode) {
+            Err(CoordinateParseError::InvalidUtm { reason, .. }) => reason,
+            other => panic!("{input}: {other:?}"),
+        };
+        let strict = ParseMode::Strict;
+        assert_eq!(
+            reason("34S 261882 6243182", strict),
+            "northing does not lie in the latitude band"
+        );
+        assert_eq!(
+            reason("61H 261882 6243182", strict),
+            "zone must be within 1..=60"
+        );
+        assert_eq!(
+            reason("34I 261882 6243182", lenient),
+            "band letter must be one of C..X without I and O"
+        );
+        assert_eq!(
+            reason("34H  261882 6243182", strict),
+            "strict mode expects single spaces between fields"
+        );
+    }
+}
@@ -0,0 +1,171 @@
+//! Exact floating-point expansions (Shewchuk, *Adaptive Precision Floating-Point Arithmetic
+//! and Fast Robust Geometric Predicates*, 1997).
+//!
+//! An expansion is a sum of non-overlapping `f64` components stored in increasing order of
+//! magnitude, with zeros eliminated. Every operation here is exact under IEEE 754
+//! round-to-nearest, which Rust guarantees for `f64` on every target (no extended
+//! precision, no implicit fused multiply-add), so results are bit-identical everywhere.
+
+use std::ops::{Add, Mul, Sub};
+
+/// 2^27 + 1, for splitting a double into two 26-bit halves.
+const SPLITTER: f64 = 134_217_729.0;
+
+/// `a + b` as `(sum, roundoff)`.
+#[inline]
+pub(super) fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    (x, (a - a_virtual) + (b - b_virtual))
+}
+
+/// `a - b` as `(difference, roundoff)`.
+#[inline]
+pub(super) fn two_diff(a: f64, b: f64) -> (f64, f64) {
+    let x = a - b;
+    let b_virtual = a - x;
+    let a_virtual = x + b_virtual;
+    (x, (a - a_virtual) + (b_virtual - b))
+}
+
+/// `a + b` for `|a| >= |b|`.
+#[inline]
+fn fast_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    (x, b - (x - a))
+}
+
+#[inline]
+fn split(a: f64) -> (f64, f64) {
+    let c = SPLITTER * a;
+    let big = c - a;
+    let hi = c - big;
+    (hi, a - hi)
+}
+
+/// `a * b` as `(product, roundoff)`.
+#[inline]
+pub(super) fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    let (a_hi, a_lo) = split(a);
+    let (b_hi, b_lo) = split(b);
+    let err1 = x - a_hi * b_hi;
+    let err2 = err1 - a_lo * b_hi;
+    let err3 = err2 - a_hi * b_lo;
+    (x, a_lo * b_lo - err3)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Expansion(Vec<f64>);
+
+impl Expansion {
+    /// The exact difference `a - b`.
+    pub(super) fn diff(a: f64, b: f64) -> Self {
+        let (x, y) = two_diff(a, b);
+        Self::from_pair(x, y)
+    }
+
+    fn from_pair(hi: f64, lo: f64) -> Self {
+        Self([lo, hi].into_iter().filter(|&c| c != 0.0).collect())
+    }
+
+    /// The components summed in `f64`; within one ulp of the exact value.
+    pub(super) fn estimate(&self) -> f64 {
+        self.0.iter().sum()
+    }
+
+    /// Adds one double (Shewchuk's `GROW-EXPANSION`, zero-eliminating).
+    fn grow(&self, b: f64) -> Self {
+        let mut out = Vec::with_capacity(self.0.len() + 1);
+        let mut q = b;
+        for &e in &self.0 {
+            let (sum, err) = two_sum(q, e);
+            q = sum;
+            if err != 0.0 {
+                out.push(err);
+            }
+        }
+        if q != 0.0 {
+            out.push(q);
+        }
+        Self(out)
+    }
+
+    /// Multiplies by one double (`SCALE-EXPANSION`, zero-eliminating).
+    fn scale(&self, b: f64) -> Self {
+        let mut out = Vec::with_capacity(self.0.len() * 2);
+        let Some((&first, rest)) = self.0.split_first() else {
+            return Self(out);
+        };
+        let (mut q, err) = two_product(first, b);
+        if err != 0.0 {
+            out.push(err);
+        }
+        for &e in rest {
+            let (p_hi, p_lo) = two_product(e, b);
+            let (sum, err) = two_sum(q, p_lo);
+            if err != 0.0 {
+                out.push(err);
+            }
+            let (next, err) = fast_two_sum(p_hi, sum);
+            if err != 0.0 {
+                out.push(err);
+            }
+            q = next;
+        }
+        if q != 0.0 {
+            out.push(q);
+        }
+        Self(out)
+    }
+}
+
+impl Add for &Expansion {
+    type Output = Expansion;
+
+    fn add(self, other: &Expansion) -> Expansion {
+        other.0.iter().fold(self.clone(), |acc, &c| acc.grow(c))
+    }
+}
+
+impl Sub for &Expansion {
+    type Output = Expansion;
+
+    fn sub(self, other: &Expansion) -> Expansion {
+        other.0.iter().fold(self.clone(), |acc, &c| acc.grow(-c))
+    }
+}
+
+impl Mul for &Expansion {
+    type Output = Expansion;
+
+    fn mul(self, other: &Expansion) -> Expansion {
+        other
+            .0
+            .iter()
+            .fold(Expansion(Vec::new()), |acc, &c| &acc + &self.scale(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Expansion, two_product, two_sum};
+
+    #[test]
+    fn operations_are_exact() {
+        assert_eq!(two_sum(1.0, 1e-20), (1.0, 1e-20));
+        let third = 1.0 / 3.0;
+        let (p, e) = two_product(third, 3.0);
+        assert_eq!(p, 1.0);
+        assert_eq!(e, -f64::EPSILON / 4.0);
+
+        // (1 + 2^-60) - 1 is 2^-60 exactly, though neither side is representable in f64.
+        let tiny = 2f64.powi(-60);
+        let a = &Expansion::diff(1.0, -tiny) - &Expansion::diff(1.0, 0.0);
+        assert_eq!(a.estimate(), tiny);
+        let square = &a * &a;
+        assert_eq!(square.estimate(), tiny * tiny);
+        assert_eq!((&square - &square).estimate(), 0.0);
+    }
+}
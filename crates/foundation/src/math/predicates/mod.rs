@@ -0,0 +1,382 @@
+//! Robust geometric predicates.
+//!
+//! Each predicate first evaluates its determinant in plain `f64` together with a forward
+//! error bound (Shewchuk 1997); only when the sign is not certified does it fall back to
+//! exact expansion arithmetic. The sign returned is therefore always the sign of the exact
+//! determinant of the input coordinates, and identical on every platform. Geometry code
+//! should branch on these instead of on hand-rolled cross products.
+
+mod expansion;
+
+use expansion::Expansion;
+
+/// Half an ulp of 1.0 (2^-53), the unit roundoff of `f64`.
+const EPSILON: f64 = f64::EPSILON * 0.5;
+const ORIENT2D_BOUND: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+const ORIENT3D_BOUND: f64 = (7.0 + 56.0 * EPSILON) * EPSILON;
+const INCIRCLE_BOUND: f64 = (10.0 + 96.0 * EPSILON) * EPSILON;
+
+/// Twice the signed area of triangle `abc`: positive when the points run counter-clockwise,
+/// negative when clockwise, and zero exactly when they are collinear.
+pub fn orient2d(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    let left = (a[0] - c[0]) * (b[1] - c[1]);
+    let right = (a[1] - c[1]) * (b[0] - c[0]);
+    let det = left - right;
+    // Terms of opposite sign cannot cancel, so the rounded result already has the right sign.
+    if (left > 0.0 && right <= 0.0) || (left < 0.0 && right >= 0.0) || left == 0.0 {
+        return det;
+    }
+    if det.abs() >= ORIENT2D_BOUND * (left.abs() + right.abs()) {
+        return det;
+    }
+    orient2d_exact(a, b, c)
+}
+
+fn orient2d_exact(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    let acx = Expansion::diff(a[0], c[0]);
+    let acy = Expansion::diff(a[1], c[1]);
+    let bcx = Expansion::diff(b[0], c[0]);
+    let bcy = Expansion::diff(b[1], c[1]);
+    (&(&acx * &bcy) - &(&acy * &bcx)).estimate()
+}
+
+/// Six times the signed volume of tetrahedron `abcd`: positive when `d` lies below the plane
+/// through `a`, `b`, `c` (which appear counter-clockwise seen from above), negative when above,
+/// and zero exactly when the four points are coplanar.
+pub fn orient3d(a: [f64; 3], b: [f64; 3], c: [f64; 3], d: [f64; 3]) -> f64 {
+    let [adx, ady, adz] = sub3(a, d);
+    let [bdx, bdy, bdz] = sub3(b, d);
+    let [cdx, cdy, cdz] = sub3(c, d);
+
+    let bdxcdy = bdx * cdy;
+    let cdxbdy = cdx * bdy;
+    let cdxady = cdx * ady;
+    let adxcdy = adx * cdy;
+    let adxbdy = adx * bdy;
+    let bdxady = bdx * ady;
+
+    let det = adz * (bdxcdy - cdxbdy) + bdz * (cdxady - adxcdy) + cdz * (adxbdy - bdxady);
+    let permanent = (bdxcdy.abs() + cdxbdy.abs()) * adz.abs()
+        + (cdxady.abs() + adxcdy.abs()) * bdz.abs()
+        + (adxbdy.abs() + bdxady.abs()) * cdz.abs();
+    if det.abs() > ORIENT3D_BOUND * permanent {
+        return det;
+    }
+    orient3d_exact(a, b, c, d)
+}
+
+fn orient3d_exact(a: [f64; 3], b: [f64; 3], c: [f64; 3], d: [f64; 3]) -> f64 {
+    let [adx, ady, adz] = diff3(a, d);
+    let [bdx, bdy, bdz] = diff3(b, d);
+    let [cdx, cdy, cdz] = diff3(c, d);
+    let bc = &(&bdx * &cdy) - &(&cdx * &bdy);
+    let ca = &(&cdx * &ady) - &(&adx * &cdy);
+    let ab = &(&adx * &bdy) - &(&bdx * &ady);
+    (&(&(&adz * &bc) + &(&bdz * &ca)) + &(&cdz * &ab)).estimate()
+}
+
+/// Positive when `d` lies inside the circle through `a`, `b`, `c` (taken counter-clockwise),
+/// negative when outside, and zero exactly when the four points are cocircular. The sign
+/// flips when `a`, `b`, `c` are clockwise.
+pub fn incircle(a: [f64; 2], b: [f64; 2], c: [f64; 2], d: [f64; 2]) -> f64 {
+    let (adx, ady) = (a[0] - d[0], a[1] - d[1]);
+    let (bdx, bdy) = (b[0] - d[0], b[1] - d[1]);
+    let (cdx, cdy) = (c[0] - d[0], c[1] - d[1]);
+
+    let bdxcdy = bdx * cdy;
+    let cdxbdy = cdx * bdy;
+    let alift = adx * adx + ady * ady;
+    let cdxady = cdx * ady;
+    let adxcdy = adx * cdy;
+    let blift = bdx * bdx + bdy * bdy;
+    let adxbdy = adx * bdy;
+    let bdxady = bdx * ady;
+    let clift = cdx * cdx + cdy * cdy;
+
+    let det = alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+    let permanent = (bdxcdy.abs() + cdxbdy.abs()) * alift
+        + (cdxady.abs() + adxcdy.abs()) * blift
+        + (adxbdy.abs() + bdxady.abs()) * clift;
+    if det.abs() > INCIRCLE_BOUND * permanent {
+        return det;
+    }
+    incircle_exact(a, b, c, d)
+}
+
+fn incircle_exact(a: [f64; 2], b: [f64; 2], c: [f64; 2], d: [f64; 2]) -> f64 {
+    let (adx, ady) = (Expansion::diff(a[0], d[0]), Expansion::diff(a[1], d[1]));
+    let (bdx, bdy) = (Expansion::diff(b[0], d[0]), Expansion::diff(b[1], d[1]));
+    let (cdx, cdy) = (Expansion::diff(c[0], d[0]), Expansion::diff(c[1], d[1]));
+    let alift = &(&adx * &adx) + &(&ady * &ady);
+    let blift = &(&bdx * &bdx) + &(&bdy * &bdy);
+    let clift = &(&cdx * &cdx) + &(&cdy * &cdy);
+    let bc = &(&bdx * &cdy) - &(&cdx * &bdy);
+    let ca = &(&cdx * &ady) - &(&adx * &cdy);
+    let ab = &(&adx * &bdy) - &(&bdx * &ady);
+    (&(&(&alift * &bc) + &(&blift * &ca)) + &(&clift * &ab)).estimate()
+}
+
+/// How two closed segments meet, decided exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentIntersection {
+    Disjoint,
+    /// The interiors cross at a single point.
+    Crossing,
+    /// A single shared point that is an endpoint of at least one segment.
+    Touching,
+    /// Collinear segments sharing more than one point.
+    Overlapping,
+}
+
+impl SegmentIntersection {
+    pub fn intersects(self) -> bool {
+        self != SegmentIntersection::Disjoint
+    }
+}
+
+/// Classifies segments `ab` and `cd`. Degenerate segments (`a == b`) behave as points.
+pub fn segment_intersection(
+    a: [f64; 2],
+    b: [f64; 2],
+    c: [f64; 2],
+    d: [f64; 2],
+) -> SegmentIntersection {
+    let oa = sign(orient2d(c, d, a));
+    let ob = sign(orient2d(c, d, b));
+    let oc = sign(orient2d(a, b, c));
+    let od = sign(orient2d(a, b, d));
+
+    if oa == 0 && ob == 0 && oc == 0 && od == 0 {
+        return collinear_overlap(a, b, c, d);
+    }
+    if oa * ob < 0 && oc * od < 0 {
+        return SegmentIntersection::Crossing;
+    }
+    let touches = (oa == 0 && within_box(a, c, d))
+        || (ob == 0 && within_box(b, c, d))
+        || (oc == 0 && within_box(c, a, b))
+        || (od == 0 && within_box(d, a, b));
+    if touches {
+        SegmentIntersection::Touching
+    } else {
+        SegmentIntersection::Disjoint
+    }
+}
+
+/// All four points lie on one line: compare the two intervals along the axis on which the
+/// points spread the most, where order along the line is preserved.
+fn collinear_overlap(a: [f64; 2], b: [f64; 2], c: [f64; 2], d: [f64; 2]) -> SegmentIntersection {
+    let spread = |axis: usize| {
+        let values = [a[axis], b[axis], c[axis], d[axis]];
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        max - min
+    };
+    let axis = if spread(0) >= spread(1) { 0 } else { 1 };
+    let lo = a[axis].min(b[axis]).max(c[axis].min(d[axis]));
+    let hi = a[axis].max(b[axis]).min(c[axis].max(d[axis]));
+    if lo < hi {
+        SegmentIntersection::Overlapping
+    } else if lo == hi {
+        SegmentIntersection::Touching
+    } else {
+        SegmentIntersection::Disjoint
+    }
+}
+
+/// Whether `p`, known to be collinear with `ab`, lies on the closed segment.
+fn within_box(p: [f64; 2], a: [f64; 2], b: [f64; 2]) -> bool {
+    p[0] >= a[0].min(b[0])
+        && p[0] <= a[0].max(b[0])
+        && p[1] >= a[1].min(b[1])
+        && p[1] <= a[1].max(b[1])
+}
+
+fn sign(value: f64) -> i8 {
+    if value > 0.0 {
+        1
+    } else if value < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
+
+fn sub3(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn diff3(a: [f64; 3], b: [f64; 3]) -> [Expansion; 3] {
+    [
+        Expansion::diff(a[0], b[0]),
+        Expansion::diff(a[1], b[1]),
+        Expansion::diff(a[2], b[2]),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SegmentIntersection, incircle, orient2d, orient3d, segment_intersection, sign};
+
+    /// Deterministic xorshift so the cases are the same on every run and platform.
+    struct Rng(u64);
+
+    impl Rng {
+        fn below(&mut self, bits: u32) -> i64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 >> (64 - bits)) as i64
+        }
+    }
+
+    fn exact_sign(v: i128) -> i8 {
+        v.signum() as i8
+    }
+
+    fn f(p: &[i64]) -> Vec<f64> {
+        p.iter().map(|&v| v as f64).collect()
+    }
+
+    #[test]
+    fn orient2d_is_exact_where_naive_arithmetic_fails() {
+        // Shewchuk's example: points 0.5 + k ulp against the line y = x. The exact sign is
+        // sign(j - i); the naive determinant gets many of these wrong.
+        let ulp = f64::EPSILON / 2.0;
+        let mut naive_wrong = 0;
+        for i in 0..32i32 {
+            for j in 0..32 {
+                let p = [0.5 + i as f64 * ulp, 0.5 + j as f64 * ulp];
+                let (q, r) = ([12.0, 12.0], [24.0, 24.0]);
+                let expected = (j - i).signum() as i8;
+                assert_eq!(sign(orient2d(p, q, r)), expected, "i={i} j={j}");
+                let naive = (p[0] - r[0]) * (q[1] - r[1]) - (p[1] - r[1]) * (q[0] - r[0]);
+                if sign(naive) != expected {
+                    naive_wrong += 1;
+                }
+            }
+        }
+        assert!(naive_wrong > 0);
+        assert!(orient2d([0.0, 0.0], [1.0, 0.0], [0.0, 1.0]) > 0.0);
+    }
+
+    #[test]
+    fn orientations_match_integer_arithmetic() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..2_000 {
+            // c is the reflection of a through b, nudged by at most one unit.
+            let (ax, ay, bx, by) = (rng.below(50), rng.below(50), rng.below(50), rng.below(50));
+            let (cx, cy) = (
+                2 * bx - ax + rng.below(2) - 1,
+                2 * by - ay + rng.below(2) - 1,
+            );
+            let [ax_, ay_, bx_, by_, cx_, cy_] = f(&[ax, ay, bx, by, cx, cy])[..] else {
+                unreachable!()
+            };
+            let exact =
+                (ax - cx) as i128 * (by - cy) as i128 - (ay - cy) as i128 * (bx - cx) as i128;
+            assert_eq!(
+                sign(orient2d([ax_, ay_], [bx_, by_], [cx_, cy_])),
+                exact_sign(exact)
+            );
+
+            // d = b + c - a lies on the plane abc, then nudged off it.
+            let a: Vec<i64> = (0..3).map(|_| rng.below(36)).collect();
+            let b: Vec<i64> = (0..3).map(|_| rng.below(36)).collect();
+            let c: Vec<i64> = (0..3).map(|_| rng.below(36)).collect();
+            let d: Vec<i64> = (0..3)
+                .map(|k| b[k] + c[k] - a[k] + rng.below(2) - 1)
+                .collect();
+            let m = |p: &[i64], k: usize| (p[k] - d[k]) as i128;
+            let exact = m(&a, 2) * (m(&b, 0) * m(&c, 1) - m(&c, 0) * m(&b, 1))
+                + m(&b, 2) * (m(&c, 0) * m(&a, 1) - m(&a, 0) * m(&c, 1))
+                + m(&c, 2) * (m(&a, 0) * m(&b, 1) - m(&b, 0) * m(&a, 1));
+            let p = |v: &[i64]| -> [f64; 3] { f(v).try_into().unwrap() };
+            assert_eq!(
+                sign(orient3d(p(&a), p(&b), p(&c), p(&d))),
+                exact_sign(exact)
+            );
+        }
+        assert!(
+            orient3d(
+                [0.0; 3],
+                [1.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [0.2, 0.2, -1e-300]
+            ) > 0.0
+        );
+    }
+
+    #[test]
+    fn incircle_decides_cocircular_points() {
+        // Lattice points on a circle of radius 5, scaled and shifted far enough that the lifted
+        // terms no longer fit in a double.
+        let circle = [
+            (5, 0),
+            (4, 3),
+            (3, 4),
+            (0, 5),
+            (-3, 4),
+            (-4, -3),
+            (0, -5),
+            (3, -4),
+        ];
+        let (scale, offset) = ((1i64 << 26) + 1, 1i64 << 20);
+        let pt = |(x, y): (i64, i64)| (offset + scale * x, offset + scale * y);
+        let mut zeros = 0;
+        for (i, &a) in circle.iter().enumerate() {
+            let (b, c, d) = (
+                circle[(i + 1) % 8],
+                circle[(i + 3) % 8],
+                circle[(i + 6) % 8],
+            );
+            for (dx, dy) in [(0, 0), (1, 0), (0, -1), (-1, 1)] {
+                let [a, b, c] = [pt(a), pt(b), pt(c)];
+                let d = (pt(d).0 + dx, pt(d).1 + dy);
+                let m = |p: (i64, i64)| ((p.0 - d.0) as i128, (p.1 - d.1) as i128);
+                let ((adx, ady), (bdx, bdy), (cdx, cdy)) = (m(a), m(b), m(c));
+                let exact = (adx * adx + ady * ady) * (bdx * cdy - cdx * bdy)
+                    + (bdx * bdx + bdy * bdy) * (cdx * ady - adx * cdy)
+                    + (cdx * cdx + cdy * cdy) * (adx * bdy - bdx * ady);
+                let v = |p: (i64, i64)| [p.0 as f64, p.1 as f64];
+                let got = incircle(v(a), v(b), v(c), v(d));
+                assert_eq!(sign(got), exact_sign(exact));
+                if exact == 0 {
+                    zeros += 1;
+                    assert_eq!(got, 0.0);
+                }
+            }
+        }
+        assert_eq!(zeros, 8);
+        assert!(incircle([0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [0.4, 0.4]) > 0.0);
+        assert!(incircle([0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [0.4, 0.4]) < 0.0);
+    }
+
+    #[test]
+    fn classifies_segment_pairs() {
+        use SegmentIntersection::*;
+        let s =
+            |a: [f64; 2], b: [f64; 2], c: [f64; 2], d: [f64; 2]| segment_intersection(a, b, c, d);
+        assert_eq!(s([0.0, 0.0], [2.0, 2.0], [0.0, 2.0], [2.0, 0.0]), Crossing);
+        assert_eq!(s([0.0, 0.0], [2.0, 2.0], [1.0, 1.0], [2.0, 0.0]), Touching);
+        assert_eq!(s([0.0, 0.0], [1.0, 1.0], [1.0, 1.0], [3.0, 3.0]), Touching);
+        assert_eq!(
+            s([0.0, 0.0], [2.0, 2.0], [1.0, 1.0], [3.0, 3.0]),
+            Overlapping
+        );
+        assert_eq!(s([0.0, 0.0], [1.0, 1.0], [2.0, 2.0], [3.0, 3.0]), Disjoint);
+        assert_eq!(s([0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]), Disjoint);
+        assert_eq!(
+            s([0.0, 5.0], [0.0, 0.0], [0.0, 1.0], [0.0, 2.0]),
+            Overlapping
+        );
+
+        // 1/3 rounds down, so p sits a fraction of an ulp below the line through a and b:
+        // a segment rising from p crosses it, one falling from p misses it.
+        let (a, b) = ([0.0, 0.0], [3.0, 1.0]);
+        let p = [1.0, 1.0 / 3.0];
+        assert_eq!(s(a, b, p, [1.0, 5.0]), Crossing);
+        assert_eq!(s(a, b, p, [1.0, -5.0]), Disjoint);
+        assert!(!s(a, b, [5.0, 5.0], [6.0, 6.0]).intersects());
+    }
+}
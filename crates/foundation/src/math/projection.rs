@@ -1,4 +0,0 @@
-/// Projection helpers (placeholder)
-pub fn project_point(x: f64, y: f64) -> (f64, f64) {
-    (x, y)
-}
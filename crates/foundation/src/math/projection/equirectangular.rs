@@ -0,0 +1,79 @@
+use super::{Projection, is_finite_geo, is_finite_xy};
+use crate::math::{Geodetic, Vec2, WGS84_A};
+
+/// Equidistant cylindrical (plate carrée) projection on a sphere of radius `WGS84_A`.
+///
+/// `lat_ts_rad` is the latitude of true scale; `0.0` gives plain plate carrée
+/// where both axes are arc lengths along the equator/meridian.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Equirectangular {
+    pub lon0_rad: f64,
+    pub lat_ts_rad: f64,
+}
+
+impl Equirectangular {
+    pub fn new(lon0_rad: f64, lat_ts_rad: f64) -> Self {
+        Self {
+            lon0_rad,
+            lat_ts_rad,
+        }
+    }
+
+    /// Normalized grid coordinates in `[0, 1]` for a lon/lat in degrees, with
+    /// `(0, 0)` at the north-west corner (lon -180, lat 90) and `v` increasing
+    /// southwards. This is the layout used by global equirectangular tile pyramids.
+    #[inline]
+    pub fn unit_uv(lon_deg: f64, lat_deg: f64) -> (f64, f64) {
+        ((lon_deg + 180.0) / 360.0, (90.0 - lat_deg) / 180.0)
+    }
+}
+
+impl Projection for Equirectangular {
+    fn forward(&self, geo: Geodetic) -> Option<Vec2> {
+        if !is_finite_geo(geo) {
+            return None;
+        }
+        let x = WGS84_A * (geo.lon_rad - self.lon0_rad) * self.lat_ts_rad.cos();
+        let y = WGS84_A * geo.lat_rad;
+        Some(Vec2::new(x, y))
+    }
+
+    fn inverse(&self, xy: Vec2) -> Option<Geodetic> {
+        let cos_ts = self.lat_ts_rad.cos();
+        if !is_finite_xy(xy) || cos_ts.abs() < 1e-12 {
+            return None;
+        }
+        let lon = self.lon0_rad + xy.x / (WGS84_A * cos_ts);
+        let lat = xy.y / WGS84_A;
+        Some(Geodetic::new(lat, lon, 0.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Equirectangular;
+    use crate::math::projection::Projection;
+    use crate::math::projection::test_util::{assert_close, assert_round_trip};
+    use crate::math::{Geodetic, WGS84_A};
+
+    #[test]
+    fn plate_carree_is_arc_length() {
+        let p = Equirectangular::default();
+        let xy = p.forward(Geodetic::from_degrees(45.0, 90.0, 0.0)).unwrap();
+        assert_close(xy.x, WGS84_A * std::f64::consts::FRAC_PI_2, 1e-6);
+        assert_close(xy.y, WGS84_A * std::f64::consts::FRAC_PI_4, 1e-6);
+    }
+
+    #[test]
+    fn round_trips_with_true_scale_latitude() {
+        let p = Equirectangular::new(10f64.to_radians(), 30f64.to_radians());
+        assert_round_trip(&p, &[(0.0, 0.0), (-60.0, 100.0), (89.0, -170.0)], 1e-12);
+    }
+
+    #[test]
+    fn unit_uv_corners() {
+        assert_eq!(Equirectangular::unit_uv(-180.0, 90.0), (0.0, 0.0));
+        assert_eq!(Equirectangular::unit_uv(180.0, -90.0), (1.0, 1.0));
+        assert_eq!(Equirectangular::unit_uv(0.0, 0.0), (0.5, 0.5));
+    }
+}
@@ -0,0 +1,228 @@
+use std::f64::consts::FRAC_PI_2;
+
+use super::{Projection, is_finite_geo, is_finite_xy, wgs84_e, wrap_lon_rad};
+use crate::math::{Geodetic, Vec2, WGS84_A, WGS84_E2};
+
+/// Ellipsoidal Lambert azimuthal equal-area projection (EPSG method 9820).
+///
+/// Supports oblique, equatorial and polar aspects. The antipode of the
+/// projection center cannot be represented and `forward` returns `None` there.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LambertAzimuthalEqualArea {
+    pub lat0_rad: f64,
+    pub lon0_rad: f64,
+    pub false_easting: f64,
+    pub false_northing: f64,
+}
+
+impl LambertAzimuthalEqualArea {
+    pub fn new(lat0_rad: f64, lon0_rad: f64) -> Self {
+        Self {
+            lat0_rad,
+            lon0_rad,
+            false_easting: 0.0,
+            false_northing: 0.0,
+        }
+    }
+
+    pub fn with_false_origin(mut self, false_easting: f64, false_northing: f64) -> Self {
+        self.false_easting = false_easting;
+        self.false_northing = false_northing;
+        self
+    }
+
+    /// ETRS89-extended / LAEA Europe (EPSG:3035) parameters, applied on WGS84.
+    pub fn europe() -> Self {
+        Self::new(52f64.to_radians(), 10f64.to_radians())
+            .with_false_origin(4_321_000.0, 3_210_000.0)
+    }
+
+    fn polar_sign(&self) -> Option<f64> {
+        if (self.lat0_rad.abs() - FRAC_PI_2).abs() < 1e-10 {
+            Some(self.lat0_rad.signum())
+        } else {
+            None
+        }
+    }
+}
+
+/// Authalic `q` for a latitude given as `sin(phi)`.
+fn authalic_q(sin_phi: f64, e: f64) -> f64 {
+    let es = e * sin_phi;
+    (1.0 - WGS84_E2) * (sin_phi / (1.0 - es * es) - (0.5 / e) * ((1.0 - es) / (1.0 + es)).ln())
+}
+
+/// Geodetic latitude for an authalic `q` (series start + Newton refinement).
+fn lat_from_q(q: f64, e: f64) -> f64 {
+    let qp = authalic_q(1.0, e);
+    let ratio = (q / qp).clamp(-1.0, 1.0);
+    if (ratio.abs() - 1.0).abs() < 1e-15 {
+        return FRAC_PI_2.copysign(ratio);
+    }
+
+    let e2 = WGS84_E2;
+    let e4 = e2 * e2;
+    let e6 = e4 * e2;
+    let beta = ratio.asin();
+    let mut phi = beta
+        + (e2 / 3.0 + 31.0 * e4 / 180.0 + 517.0 * e6 / 5040.0) * (2.0 * beta).sin()
+        + (23.0 * e4 / 360.0 + 251.0 * e6 / 3780.0) * (4.0 * beta).sin()
+        + (761.0 * e6 / 45360.0) * (6.0 * beta).sin();
+
+    for _ in 0..4 {
+        let s = phi.sin();
+        let c = phi.cos();
+        if c.abs() < 1e-12 {
+            break;
+        }
+        let es2 = 1.0 - e2 * s * s;
+        let dphi = es2 * es2 / (2.0 * c)
+            * (q / (1.0 - e2) - s / es2 + (0.5 / e) * ((1.0 - e * s) / (1.0 + e * s)).ln());
+        phi += dphi;
+        if dphi.abs() < 1e-15 {
+            break;
+        }
+    }
+    phi
+}
+
+impl Projection for LambertAzimuthalEqualArea {
+    fn forward(&self, geo: Geodetic) -> Option<Vec2> {
+        if !is_finite_geo(geo) {
+            return None;
+        }
+        let e = wgs84_e();
+        let qp = authalic_q(1.0, e);
+        let q = authalic_q(geo.lat_rad.sin(), e);
+        let dlon = wrap_lon_rad(geo.lon_rad - self.lon0_rad);
+
+        let (dx, dy) = if let Some(sign) = self.polar_sign() {
+            let rho = WGS84_A * (qp - sign * q).max(0.0).sqrt();
+            (rho * dlon.sin(), -sign * rho * dlon.cos())
+        } else {
+            let rq = WGS84_A * (0.5 * qp).sqrt();
+            let beta = (q / qp).clamp(-1.0, 1.0).asin();
+            let beta0 = (authalic_q(self.lat0_rad.sin(), e) / qp)
+                .clamp(-1.0, 1.0)
+                .asin();
+            let sin0 = self.lat0_rad.sin();
+            let m0 = self.lat0_rad.cos() / (1.0 - WGS84_E2 * sin0 * sin0).sqrt();
+            let d = WGS84_A * m0 / (rq * beta0.cos());
+
+            let denom = 1.0 + beta0.sin() * beta.sin() + beta0.cos() * beta.cos() * dlon.cos();
+            if denom <= 1e-12 {
+                return None;
+            }
+            let b = rq * (2.0 / denom).sqrt();
+            (
+                b * d * beta.cos() * dlon.sin(),
+                (b / d) * (beta0.cos() * beta.sin() - beta0.sin() * beta.cos() * dlon.cos()),
+            )
+        };
+
+        let out = Vec2::new(self.false_easting + dx, self.false_northing + dy);
+        is_finite_xy(out).then_some(out)
+    }
+
+    fn inverse(&self, xy: Vec2) -> Option<Geodetic> {
+        if !is_finite_xy(xy) {
+            return None;
+        }
+        let e = wgs84_e();
+        let qp = authalic_q(1.0, e);
+        let dx = xy.x - self.false_easting;
+        let dy = xy.y - self.false_northing;
+
+        let (q, dlon) = if let Some(sign) = self.polar_sign() {
+            let rho = dx.hypot(dy);
+            let q = sign * (qp - rho * rho / (WGS84_A * WGS84_A));
+            if q.abs() > qp * (1.0 + 1e-12) {
+                return None;
+            }
+            (q, dx.atan2(-sign * dy))
+        } else {
+            let rq = WGS84_A * (0.5 * qp).sqrt();
+            let beta0 = (authalic_q(self.lat0_rad.sin(), e) / qp)
+                .clamp(-1.0, 1.0)
+                .asin();
+            let sin0 = self.lat0_rad.sin();
+            let m0 = self.lat0_rad.cos() / (1.0 - WGS84_E2 * sin0 * sin0).sqrt();
+            let d = WGS84_A * m0 / (rq * beta0.cos());
+
+            let rho = (dx / d).hypot(d * dy);
+            if rho == 0.0 {
+                return Some(Geodetic::new(self.lat0_rad, self.lon0_rad, 0.0));
+            }
+            let s = rho / (2.0 * rq);
+            if s > 1.0 + 1e-12 {
+                return None;
+            }
+            let c = 2.0 * s.min(1.0).asin();
+            let beta = (c.cos() * beta0.sin() + d * dy * c.sin() * beta0.cos() / rho)
+                .clamp(-1.0, 1.0)
+                .asin();
+            let dlon = (dx * c.sin())
+                .atan2(d * rho * beta0.cos() * c.cos() - d * d * dy * beta0.sin() * c.sin());
+            (qp * beta.sin(), dlon)
+        };
+
+        let geo = Geodetic::new(lat_from_q(q, e), wrap_lon_rad(self.lon0_rad + dlon), 0.0);
+        is_finite_geo(geo).then_some(geo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LambertAzimuthalEqualArea;
+    use crate::math::projection::Projection;
+    use crate::math::projection::test_util::{assert_close, assert_round_trip};
+    use crate::math::{Geodetic, Vec2};
+
+    #[test]
+    fn center_maps_to_false_origin() {
+        let p = LambertAzimuthalEqualArea::europe();
+        let xy = p.forward(Geodetic::from_degrees(52.0, 10.0, 0.0)).unwrap();
+        assert_close(xy.x, 4_321_000.0, 1e-6);
+        assert_close(xy.y, 3_210_000.0, 1e-6);
+    }
+
+    #[test]
+    fn europe_matches_epsg_example() {
+        // EPSG Guidance Note 7-2, method 9820 example (ETRS89 ~ WGS84 at this precision).
+        let p = LambertAzimuthalEqualArea::europe();
+        let xy = p.forward(Geodetic::from_degrees(50.0, 5.0, 0.0)).unwrap();
+        assert_close(xy.x, 3_962_799.45, 1e-2);
+        assert_close(xy.y, 2_999_718.85, 1e-2);
+    }
+
+    #[test]
+    fn round_trips_oblique_equatorial_and_polar() {
+        let samples = [(52.0, 10.0), (35.0, -10.0), (70.0, 40.0), (-20.0, 60.0)];
+        assert_round_trip(&LambertAzimuthalEqualArea::europe(), &samples, 1e-11);
+        assert_round_trip(
+            &LambertAzimuthalEqualArea::new(0.0, 0.0),
+            &[(0.0, 0.0), (45.0, 45.0), (-60.0, -100.0)],
+            1e-11,
+        );
+
+        let north = LambertAzimuthalEqualArea::new(90f64.to_radians(), 0.0);
+        assert_round_trip(
+            &north,
+            &[(89.0, 0.0), (60.0, 120.0), (-45.0, -170.0)],
+            1e-11,
+        );
+        let south = LambertAzimuthalEqualArea::new(-90f64.to_radians(), 0.0);
+        assert_round_trip(
+            &south,
+            &[(-89.0, 10.0), (-60.0, -120.0), (10.0, 90.0)],
+            1e-11,
+        );
+    }
+
+    #[test]
+    fn antipode_and_outside_disk_are_rejected() {
+        let p = LambertAzimuthalEqualArea::new(0.0, 0.0);
+        assert!(p.forward(Geodetic::from_degrees(0.0, 180.0, 0.0)).is_none());
+        assert!(p.inverse(Vec2::new(3.0e7, 0.0)).is_none());
+    }
+}
@@ -0,0 +1,107 @@
+//! Map projections.
+//!
+//! Every projection maps WGS84 geodetic coordinates to planar easting/northing
+//! in meters and back. Altitude is ignored on the way in and returned as `0.0`
+//! on the way out.
+//!
+//! `forward`/`inverse` return `None` for inputs outside the projection's
+//! domain (non-finite values, the antipode of an azimuthal projection, ...)
+//! instead of producing NaN or infinities.
+
+pub mod equirectangular;
+pub mod lambert_azimuthal;
+pub mod polar_stereographic;
+pub mod transverse_mercator;
+pub mod utm;
+pub mod web_mercator;
+
+pub use equirectangular::*;
+pub use lambert_azimuthal::*;
+pub use polar_stereographic::*;
+pub use transverse_mercator::*;
+pub use utm::*;
+pub use web_mercator::*;
+
+use super::{Geodetic, Vec2, WGS84_E2};
+
+/// A forward/inverse map projection on the WGS84 ellipsoid (or sphere).
+pub trait Projection {
+    /// Project a geodetic position to planar meters.
+    fn forward(&self, geo: Geodetic) -> Option<Vec2>;
+
+    /// Unproject planar meters back to a geodetic position (altitude `0.0`).
+    fn inverse(&self, xy: Vec2) -> Option<Geodetic>;
+}
+
+/// WGS84 first eccentricity.
+#[inline]
+pub(crate) fn wgs84_e() -> f64 {
+    WGS84_E2.sqrt()
+}
+
+/// Wrap a longitude difference into `[-pi, pi)`.
+#[inline]
+pub(crate) fn wrap_lon_rad(lon: f64) -> f64 {
+    use std::f64::consts::PI;
+    (lon + PI).rem_euclid(2.0 * PI) - PI
+}
+
+/// Conformal latitude, as `tan(chi)`, for a geodetic `tan(phi)` (Karney 2011, eq. 7).
+pub(crate) fn conformal_tau(tau: f64, e: f64) -> f64 {
+    let sigma = (e * (e * tau / tau.hypot(1.0)).atanh()).sinh();
+    tau * sigma.hypot(1.0) - sigma * tau.hypot(1.0)
+}
+
+/// Inverse of `conformal_tau` by Newton iteration (Karney 2011, eqs. 19-21).
+///
+/// Converges to machine precision in two or three steps for all latitudes.
+pub(crate) fn tau_from_conformal(tau_p: f64, e: f64) -> f64 {
+    let one_e2 = 1.0 - e * e;
+    let mut tau = tau_p;
+    for _ in 0..8 {
+        let tau_i = conformal_tau(tau, e);
+        let dtau = (tau_p - tau_i) / tau_i.hypot(1.0) * (1.0 + one_e2 * tau * tau)
+            / (one_e2 * tau.hypot(1.0));
+        tau += dtau;
+        if dtau.abs() <= f64::EPSILON * tau.abs().max(1.0) {
+            break;
+        }
+    }
+    tau
+}
+
+#[inline]
+fn is_finite_geo(geo: Geodetic) -> bool {
+    geo.lat_rad.is_finite() && geo.lon_rad.is_finite()
+}
+
+#[inline]
+fn is_finite_xy(xy: Vec2) -> bool {
+    xy.x.is_finite() && xy.y.is_finite()
+}
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use super::Projection;
+    use crate::math::Geodetic;
+
+    pub fn assert_close(a: f64, b: f64, eps: f64) {
+        let diff = (a - b).abs();
+        assert!(diff <= eps, "expected {a} ~= {b} (diff {diff})");
+    }
+
+    /// Forward then inverse for each `(lat_deg, lon_deg)` sample.
+    pub fn assert_round_trip(p: &impl Projection, samples: &[(f64, f64)], eps_rad: f64) {
+        for &(lat, lon) in samples {
+            let geo = Geodetic::from_degrees(lat, lon, 0.0);
+            let xy = p
+                .forward(geo)
+                .unwrap_or_else(|| panic!("forward failed at ({lat}, {lon})"));
+            let rt = p
+                .inverse(xy)
+                .unwrap_or_else(|| panic!("inverse failed at ({lat}, {lon})"));
+            assert_close(rt.lat_rad, geo.lat_rad, eps_rad);
+            assert_close(super::wrap_lon_rad(rt.lon_rad - geo.lon_rad), 0.0, eps_rad);
+        }
+    }
+}
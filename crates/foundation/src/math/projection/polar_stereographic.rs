@@ -0,0 +1,185 @@
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+use super::{
+    Hemisphere, Projection, conformal_tau, is_finite_geo, is_finite_xy, tau_from_conformal,
+    wgs84_e, wrap_lon_rad,
+};
+use crate::math::{Geodetic, Vec2, WGS84_A, WGS84_E2};
+
+/// Ellipsoidal polar stereographic projection (EPSG methods 9810 and 9829).
+///
+/// The pole of `hemisphere` maps to the false origin; the opposite pole
+/// cannot be represented and `forward` returns `None` there.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PolarStereographic {
+    pub hemisphere: Hemisphere,
+    pub lon0_rad: f64,
+    /// Scale factor at the pole.
+    pub k0: f64,
+    pub false_easting: f64,
+    pub false_northing: f64,
+}
+
+impl PolarStereographic {
+    /// Variant A: scale factor `k0` given at the pole.
+    pub fn new(hemisphere: Hemisphere, lon0_rad: f64, k0: f64) -> Self {
+        Self {
+            hemisphere,
+            lon0_rad,
+            k0,
+            false_easting: 0.0,
+            false_northing: 0.0,
+        }
+    }
+
+    /// Variant B: unit scale along the parallel `lat_ts_rad`.
+    ///
+    /// NSIDC sea-ice grids use this form (EPSG:3413 is 70°N / -45°, EPSG:3031 is 71°S / 0°).
+    pub fn with_true_scale_lat(hemisphere: Hemisphere, lon0_rad: f64, lat_ts_rad: f64) -> Self {
+        let phi = lat_ts_rad.abs();
+        let k0 = if (phi - FRAC_PI_2).abs() < 1e-12 {
+            1.0
+        } else {
+            let sin = phi.sin();
+            let m = phi.cos() / (1.0 - WGS84_E2 * sin * sin).sqrt();
+            m * c_factor() / (2.0 * t_of(phi))
+        };
+        Self::new(hemisphere, lon0_rad, k0)
+    }
+
+    /// Universal Polar Stereographic zone for a hemisphere.
+    pub fn ups(hemisphere: Hemisphere) -> Self {
+        Self::new(hemisphere, 0.0, 0.994).with_false_origin(2_000_000.0, 2_000_000.0)
+    }
+
+    pub fn with_false_origin(mut self, false_easting: f64, false_northing: f64) -> Self {
+        self.false_easting = false_easting;
+        self.false_northing = false_northing;
+        self
+    }
+
+    fn sign(&self) -> f64 {
+        match self.hemisphere {
+            Hemisphere::North => 1.0,
+            Hemisphere::South => -1.0,
+        }
+    }
+}
+
+/// `sqrt((1+e)^(1+e) * (1-e)^(1-e))`.
+fn c_factor() -> f64 {
+    let e = wgs84_e();
+    ((1.0 + e).powf(1.0 + e) * (1.0 - e).powf(1.0 - e)).sqrt()
+}
+
+/// Snyder's `t` for a latitude measured towards the projection pole.
+fn t_of(phi: f64) -> f64 {
+    let chi = conformal_tau(phi.tan(), wgs84_e()).atan();
+    (FRAC_PI_4 - 0.5 * chi).tan()
+}
+
+impl Projection for PolarStereographic {
+    fn forward(&self, geo: Geodetic) -> Option<Vec2> {
+        if !is_finite_geo(geo) {
+            return None;
+        }
+        let s = self.sign();
+        let phi = s * geo.lat_rad;
+        if phi <= -FRAC_PI_2 + 1e-12 {
+            return None;
+        }
+        let rho = 2.0 * WGS84_A * self.k0 * t_of(phi) / c_factor();
+        let dlon = wrap_lon_rad(geo.lon_rad - self.lon0_rad);
+
+        let out = Vec2::new(
+            self.false_easting + rho * dlon.sin(),
+            self.false_northing - s * rho * dlon.cos(),
+        );
+        is_finite_xy(out).then_some(out)
+    }
+
+    fn inverse(&self, xy: Vec2) -> Option<Geodetic> {
+        if !is_finite_xy(xy) {
+            return None;
+        }
+        let s = self.sign();
+        let dx = xy.x - self.false_easting;
+        let dy = xy.y - self.false_northing;
+        let rho = dx.hypot(dy);
+
+        let t = rho * c_factor() / (2.0 * WGS84_A * self.k0);
+        let chi = FRAC_PI_2 - 2.0 * t.atan();
+        let phi = tau_from_conformal(chi.tan(), wgs84_e()).atan();
+        let dlon = if rho == 0.0 { 0.0 } else { dx.atan2(-s * dy) };
+
+        let geo = Geodetic::new(s * phi, wrap_lon_rad(self.lon0_rad + dlon), 0.0);
+        is_finite_geo(geo).then_some(geo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PolarStereographic;
+    use crate::math::Geodetic;
+    use crate::math::projection::test_util::{assert_close, assert_round_trip};
+    use crate::math::projection::{Hemisphere, Projection};
+
+    #[test]
+    fn ups_north_matches_epsg_example() {
+        // EPSG Guidance Note 7-2, method 9810 example.
+        let p = PolarStereographic::ups(Hemisphere::North);
+        let xy = p.forward(Geodetic::from_degrees(73.0, 44.0, 0.0)).unwrap();
+        assert_close(xy.x, 3_320_416.75, 1e-2);
+        assert_close(xy.y, 632_668.43, 1e-2);
+    }
+
+    #[test]
+    fn pole_maps_to_false_origin() {
+        let p = PolarStereographic::ups(Hemisphere::South);
+        let xy = p.forward(Geodetic::from_degrees(-90.0, 0.0, 0.0)).unwrap();
+        assert_close(xy.x, 2_000_000.0, 1e-6);
+        assert_close(xy.y, 2_000_000.0, 1e-6);
+        assert!(p.forward(Geodetic::from_degrees(90.0, 0.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn true_scale_latitude_has_unit_scale() {
+        let p = PolarStereographic::with_true_scale_lat(
+            Hemisphere::North,
+            -45f64.to_radians(),
+            70f64.to_radians(),
+        );
+        // Scale along the 70° parallel: d(rho)/d(arc) should be ~1 there.
+        let dlat = 1e-6f64;
+        let a = p.forward(Geodetic::from_degrees(70.0, -45.0, 0.0)).unwrap();
+        let b = p
+            .forward(Geodetic::from_degrees(70.0 + dlat, -45.0, 0.0))
+            .unwrap();
+        let lat = 70f64.to_radians();
+        let e2 = crate::math::WGS84_E2;
+        let m_radius = crate::math::WGS84_A * (1.0 - e2) / (1.0 - e2 * lat.sin().powi(2)).powf(1.5);
+        let arc = m_radius * dlat.to_radians();
+        assert_close((b.y - a.y).abs() / arc, 1.0, 1e-6);
+    }
+
+    #[test]
+    fn round_trips_both_hemispheres() {
+        let north = PolarStereographic::with_true_scale_lat(
+            Hemisphere::North,
+            -45f64.to_radians(),
+            70f64.to_radians(),
+        );
+        assert_round_trip(
+            &north,
+            &[(89.999, 0.0), (80.0, -45.0), (60.0, 135.0), (0.0, 10.0)],
+            1e-11,
+        );
+
+        let south = PolarStereographic::ups(Hemisphere::South);
+        assert_round_trip(
+            &south,
+            &[(-85.0, 30.0), (-60.0, -170.0), (-1.0, 90.0)],
+            1e-11,
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use super::{
+    Projection, conformal_tau, is_finite_geo, is_finite_xy, tau_from_conformal, wrap_lon_rad,
+};
+use crate::math::{Ellipsoid, Geodetic, Vec2};
+
+/// Transverse Mercator (Gauss-Krüger) on an arbitrary ellipsoid.
+///
+/// Uses the Krüger series to fourth order in `n` (Karney 2011), which is
+/// accurate to well below a millimeter within a zone and stays well-behaved
+/// several thousand kilometers from the central meridian.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TransverseMercator {
+    pub ellipsoid: Ellipsoid,
+    /// Latitude of natural origin.
+    pub lat0_rad: f64,
+    /// Central meridian.
+    pub lon0_rad: f64,
+    /// Scale factor on the central meridian.
+    pub k0: f64,
+    pub false_easting: f64,
+    pub false_northing: f64,
+}
+
+impl TransverseMercator {
+    pub fn new(ellipsoid: Ellipsoid, lat0_rad: f64, lon0_rad: f64, k0: f64) -> Self {
+        Self {
+            ellipsoid,
+            lat0_rad,
+            lon0_rad,
+            k0,
+            false_easting: 0.0,
+            false_northing: 0.0,
+        }
+    }
+
+    pub fn with_false_origin(mut self, false_easting: f64, false_northing: f64) -> Self {
+        self.false_easting = false_easting;
+        self.false_northing = false_northing;
+        self
+    }
+
+    fn series(&self) -> Kruger {
+        kruger(self.ellipsoid)
+    }
+
+    /// Unscaled `xi` (northing / A) of the natural origin on the central meridian.
+    fn xi0(&self, k: &Kruger) -> f64 {
+        if self.lat0_rad == 0.0 {
+            return 0.0;
+        }
+        let xi_p = conformal_tau(self.lat0_rad.tan(), self.ellipsoid.e()).atan();
+        let mut xi = xi_p;
+        for (j, a) in k.alpha.iter().enumerate() {
+            xi += a * (2.0 * (j + 1) as f64 * xi_p).sin();
+        }
+        xi
+    }
+}
+
+/// Krüger series coefficients for an ellipsoid.
+struct Kruger {
+    /// Rectifying radius `A` (meters).
+    a: f64,
+    alpha: [f64; 4],
+    beta: [f64; 4],
+}
+
+fn kruger(ellipsoid: Ellipsoid) -> Kruger {
+    let n = ellipsoid.n();
+    let n2 = n * n;
+    let n3 = n2 * n;
+    let n4 = n3 * n;
+    Kruger {
+        a: ellipsoid.a / (1.0 + n) * (1.0 + n2 / 4.0 + n4 / 64.0),
+        alpha: [
+            n / 2.0 - 2.0 * n2 / 3.0 + 5.0 * n3 / 16.0 + 41.0 * n4 / 180.0,
+            13.0 * n2 / 48.0 - 3.0 * n3 / 5.0 + 557.0 * n4 / 1440.0,
+            61.0 * n3 / 240.0 - 103.0 * n4 / 140.0,
+            49561.0 * n4 / 161280.0,
+        ],
+        beta: [
+            n / 2.0 - 2.0 * n2 / 3.0 + 37.0 * n3 / 96.0 - n4 / 360.0,
+            n2 / 48.0 + n3 / 15.0 - 437.0 * n4 / 1440.0,
+            17.0 * n3 / 480.0 - 37.0 * n4 / 840.0,
+            4397.0 * n4 / 161280.0,
+        ],
+    }
+}
+
+impl Projection for TransverseMercator {
+    fn forward(&self, geo: Geodetic) -> Option<Vec2> {
+        if !is_finite_geo(geo) {
+            return None;
+        }
+        let k = self.series();
+        let lam = wrap_lon_rad(geo.lon_rad - self.lon0_rad);
+        let tau_p = conformal_tau(geo.lat_rad.tan(), self.ellipsoid.e());
+
+        let xi_p = tau_p.atan2(lam.cos());
+        let eta_p = (lam.sin() / tau_p.hypot(lam.cos())).asinh();
+
+        let mut xi = xi_p;
+        let mut eta = eta_p;
+        for (j, a) in k.alpha.iter().enumerate() {
+            let m = 2.0 * (j + 1) as f64;
+            xi += a * (m * xi_p).sin() * (m * eta_p).cosh();
+            eta += a * (m * xi_p).cos() * (m * eta_p).sinh();
+        }
+
+        let out = Vec2::new(
+            self.false_easting + self.k0 * k.a * eta,
+            self.false_northing + self.k0 * k.a * (xi - self.xi0(&k)),
+        );
+        is_finite_xy(out).then_some(out)
+    }
+
+    fn inverse(&self, xy: Vec2) -> Option<Geodetic> {
+        if !is_finite_xy(xy) {
+            return None;
+        }
+        let k = self.series();
+        let xi = (xy.y - self.false_northing) / (self.k0 * k.a) + self.xi0(&k);
+        let eta = (xy.x - self.false_easting) / (self.k0 * k.a);
+
+        let mut xi_p = xi;
+        let mut eta_p = eta;
+        for (j, b) in k.beta.iter().enumerate() {
+            let m = 2.0 * (j + 1) as f64;
+            xi_p -= b * (m * xi).sin() * (m * eta).cosh();
+            eta_p -= b * (m * xi).cos() * (m * eta).sinh();
+        }
+
+        let tau_p = xi_p.sin() / eta_p.sinh().hypot(xi_p.cos());
+        let lam = eta_p.sinh().atan2(xi_p.cos());
+        let lat = tau_from_conformal(tau_p, self.ellipsoid.e()).atan();
+        let lon = wrap_lon_rad(self.lon0_rad + lam);
+
+        let geo = Geodetic::new(lat, lon, 0.0);
+        is_finite_geo(geo).then_some(geo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TransverseMercator;
+    use crate::math::projection::Projection;
+    use crate::math::projection::test_util::{assert_close, assert_round_trip};
+    use crate::math::{Ellipsoid, Geodetic};
+
+    fn british_national_grid() -> TransverseMercator {
+        TransverseMercator::new(
+            Ellipsoid::AIRY_1830,
+            49f64.to_radians(),
+            (-2f64).to_radians(),
+            0.999_601_271_7,
+        )
+        .with_false_origin(400_000.0, -100_000.0)
+    }
+
+    fn dms(d: f64, m: f64, s: f64) -> f64 {
+        d + m / 60.0 + s / 3600.0
+    }
+
+    #[test]
+    fn matches_ordnance_survey_worked_example() {
+        // "A guide to coordinate systems in Great Britain", Annex C.
+        let geo = Geodetic::from_degrees(dms(52.0, 39.0, 27.2531), dms(1.0, 43.0, 4.5177), 0.0);
+        let xy = british_national_grid().forward(geo).unwrap();
+        assert_close(xy.x, 651_409.903, 1e-3);
+        assert_close(xy.y, 313_177.270, 1e-3);
+    }
+
+    #[test]
+    fn natural_origin_maps_to_false_origin() {
+        let tm = british_national_grid();
+        let xy = tm.forward(Geodetic::from_degrees(49.0, -2.0, 0.0)).unwrap();
+        assert_close(xy.x, 400_000.0, 1e-6);
+        assert_close(xy.y, -100_000.0, 1e-6);
+    }
+
+    #[test]
+    fn round_trips_with_nonzero_origin() {
+        assert_round_trip(
+            &british_national_grid(),
+            &[(49.0, -2.0), (51.5, -0.12), (58.6, -6.2), (60.8, 1.0)],
+            1e-11,
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use super::{Projection, TransverseMercator, wrap_lon_rad};
+use crate::math::{Ellipsoid, Geodetic, Vec2};
+
+/// UTM central scale factor.
+pub const UTM_K0: f64 = 0.9996;
+/// UTM false easting (meters).
+pub const UTM_FALSE_EASTING: f64 = 500_000.0;
+/// UTM false northing for southern-hemisphere zones (meters).
+pub const UTM_FALSE_NORTHING_SOUTH: f64 = 10_000_000.0;
+
+/// Hemisphere selector for projections with north/south variants.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Hemisphere {
+    North,
+    South,
+}
+
+impl Hemisphere {
+    pub fn of_lat(lat_rad: f64) -> Self {
+        if lat_rad < 0.0 {
+            Hemisphere::South
+        } else {
+            Hemisphere::North
+        }
+    }
+}
+
+/// Universal Transverse Mercator zone on WGS84.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Utm {
+    zone: u8,
+    hemisphere: Hemisphere,
+}
+
+impl Utm {
+    /// Returns `None` if `zone` is outside `1..=60`.
+    pub fn new(zone: u8, hemisphere: Hemisphere) -> Option<Self> {
+        (1..=60)
+            .contains(&zone)
+            .then_some(Self { zone, hemisphere })
+    }
+
+    /// Standard zone for a position, including the Norway and Svalbard exceptions.
+    pub fn for_geodetic(geo: Geodetic) -> Self {
+        let lat = geo.lat_deg();
+        let lon = wrap_lon_rad(geo.lon_rad).to_degrees();
+        let mut zone = (((lon + 180.0) / 6.0).floor() as i32 + 1).clamp(1, 60) as u8;
+
+        if (56.0..64.0).contains(&lat) && (3.0..12.0).contains(&lon) {
+            zone = 32;
+        } else if (72.0..=84.0).contains(&lat) && (0.0..42.0).contains(&lon) {
+            zone = if lon < 9.0 {
+                31
+            } else if lon < 21.0 {
+                33
+            } else if lon < 33.0 {
+                35
+            } else {
+                37
+            };
+        }
+
+        Self {
+            zone,
+            hemisphere: Hemisphere::of_lat(geo.lat_rad),
+        }
+    }
+
+    pub fn zone(self) -> u8 {
+        self.zone
+    }
+
+    pub fn hemisphere(self) -> Hemisphere {
+        self.hemisphere
+    }
+
+    /// Central meridian of the zone (radians).
+    pub fn central_meridian_rad(self) -> f64 {
+        (-183.0 + 6.0 * self.zone as f64).to_radians()
+    }
+
+    /// The zone as a generic transverse Mercator projection on WGS84.
+    pub fn transverse_mercator(self) -> TransverseMercator {
+        let false_northing = match self.hemisphere {
+            Hemisphere::North => 0.0,
+            Hemisphere::South => UTM_FALSE_NORTHING_SOUTH,
+        };
+        TransverseMercator::new(Ellipsoid::WGS84, 0.0, self.central_meridian_rad(), UTM_K0)
+            .with_false_origin(UTM_FALSE_EASTING, false_northing)
+    }
+}
+
+impl Projection for Utm {
+    fn forward(&self, geo: Geodetic) -> Option<Vec2> {
+        self.transverse_mercator().forward(geo)
+    }
+
+    fn inverse(&self, xy: Vec2) -> Option<Geodetic> {
+        self.transverse_mercator().inverse(xy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Hemisphere, UTM_FALSE_EASTING, UTM_K0, Utm};
+    use crate::math::projection::Projection;
+    use crate::math::projection::test_util::{assert_close, assert_round_trip};
+    use crate::math::{Geodetic, Vec2};
+
+    /// WGS84 meridian arc length from the equator to the pole (meters).
+    const QUARTER_MERIDIAN_M: f64 = 10_001_965.729;
+
+    #[test]
+    fn central_meridian_maps_to_false_easting() {
+        let utm = Utm::new(31, Hemisphere::North).unwrap();
+        let xy = utm.forward(Geodetic::from_degrees(0.0, 3.0, 0.0)).unwrap();
+        assert_close(xy.x, UTM_FALSE_EASTING, 1e-6);
+        assert_close(xy.y, 0.0, 1e-6);
+
+        let pole = utm.forward(Geodetic::from_degrees(90.0, 3.0, 0.0)).unwrap();
+        assert_close(pole.y, UTM_K0 * QUARTER_MERIDIAN_M, 1e-2);
+    }
+
+    #[test]
+    fn zone_selection_handles_exceptions() {
+        let z = |lat: f64, lon: f64| Utm::for_geodetic(Geodetic::from_degrees(lat, lon, 0.0));
+        assert_eq!(z(0.0, -180.0).zone(), 1);
+        assert_eq!(z(0.0, 179.9).zone(), 60);
+        assert_eq!(z(-33.9, 18.4).zone(), 34);
+        assert_eq!(z(-33.9, 18.4).hemisphere(), Hemisphere::South);
+        // Bergen, Norway sits in 31V by longitude but is assigned to 32V.
+        assert_eq!(z(60.39, 5.32).zone(), 32);
+        // Svalbard.
+        assert_eq!(z(78.2, 15.6).zone(), 33);
+        assert!(Utm::new(0, Hemisphere::North).is_none());
+        assert!(Utm::new(61, Hemisphere::North).is_none());
+    }
+
+    #[test]
+    fn round_trips_within_and_beyond_zone() {
+        let north = Utm::new(33, Hemisphere::North).unwrap();
+        assert_round_trip(
+            &north,
+            &[
+                (0.0, 15.0),
+                (45.0, 12.0),
+                (60.0, 20.9),
+                (84.0, 0.0),
+                (10.0, 40.0),
+            ],
+            1e-11,
+        );
+
+        let south = Utm::new(56, Hemisphere::South).unwrap();
+        assert_round_trip(&south, &[(-33.86, 151.21), (-80.0, 160.0)], 1e-11);
+    }
+
+    #[test]
+    fn symmetric_about_central_meridian_and_equator() {
+        let utm = Utm::new(18, Hemisphere::North).unwrap();
+        let cm = utm.central_meridian_rad().to_degrees();
+        let e = utm
+            .forward(Geodetic::from_degrees(40.0, cm + 2.0, 0.0))
+            .unwrap();
+        let w = utm
+            .forward(Geodetic::from_degrees(40.0, cm - 2.0, 0.0))
+            .unwrap();
+        assert_close(e.x - UTM_FALSE_EASTING, UTM_FALSE_EASTING - w.x, 1e-6);
+        assert_close(e.y, w.y, 1e-6);
+
+        assert!(utm.inverse(Vec2::new(f64::NAN, 0.0)).is_none());
+    }
+}
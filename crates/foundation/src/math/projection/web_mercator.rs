@@ -0,0 +1,123 @@
+use std::f64::consts::FRAC_PI_2;
+
+use super::{Projection, is_finite_geo, is_finite_xy};
+use crate::math::{Geodetic, Vec2, WGS84_A};
+
+/// Latitude limit (degrees) at which Web Mercator becomes a square world.
+pub const WEB_MERCATOR_MAX_LAT_DEG: f64 = 85.051_128_779_806_59;
+
+/// Half the Web Mercator world extent (meters); the world spans `[-E, E]` on both axes.
+pub const WEB_MERCATOR_HALF_EXTENT_M: f64 = std::f64::consts::PI * WGS84_A;
+
+/// Spherical ("pseudo") Mercator used by XYZ web tiles (EPSG:3857).
+///
+/// Latitudes beyond `WEB_MERCATOR_MAX_LAT_DEG` are clamped so that poles map
+/// onto the edge of the square world rather than to infinity.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct WebMercator;
+
+impl WebMercator {
+    /// Easting (meters) for a longitude in degrees.
+    #[inline]
+    pub fn x_m(lon_deg: f64) -> f64 {
+        WGS84_A * lon_deg.to_radians()
+    }
+
+    /// Northing (meters) for a latitude in degrees (clamped to the valid range).
+    #[inline]
+    pub fn y_m(lat_deg: f64) -> f64 {
+        let lat = lat_deg
+            .clamp(-WEB_MERCATOR_MAX_LAT_DEG, WEB_MERCATOR_MAX_LAT_DEG)
+            .to_radians();
+        WGS84_A * (0.5 * (FRAC_PI_2 + lat)).tan().ln()
+    }
+
+    /// Longitude in degrees for an easting in meters.
+    #[inline]
+    pub fn lon_deg(x_m: f64) -> f64 {
+        (x_m / WGS84_A).to_degrees()
+    }
+
+    /// Latitude in degrees for a northing in meters.
+    #[inline]
+    pub fn lat_deg(y_m: f64) -> f64 {
+        (2.0 * (y_m / WGS84_A).exp().atan() - FRAC_PI_2).to_degrees()
+    }
+}
+
+impl Projection for WebMercator {
+    fn forward(&self, geo: Geodetic) -> Option<Vec2> {
+        if !is_finite_geo(geo) {
+            return None;
+        }
+        Some(Vec2::new(
+            Self::x_m(geo.lon_deg()),
+            Self::y_m(geo.lat_deg()),
+        ))
+    }
+
+    fn inverse(&self, xy: Vec2) -> Option<Geodetic> {
+        if !is_finite_xy(xy) {
+            return None;
+        }
+        Some(Geodetic::from_degrees(
+            Self::lat_deg(xy.y),
+            Self::lon_deg(xy.x),
+            0.0,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WEB_MERCATOR_HALF_EXTENT_M, WEB_MERCATOR_MAX_LAT_DEG, WebMercator};
+    use crate::math::projection::Projection;
+    use crate::math::projection::test_util::{assert_close, assert_round_trip};
+    use crate::math::{Geodetic, Vec2};
+
+    #[test]
+    fn origin_and_world_edges() {
+        let p = WebMercator;
+        let o = p.forward(Geodetic::new(0.0, 0.0, 0.0)).unwrap();
+        assert_close(o.x, 0.0, 1e-9);
+        assert_close(o.y, 0.0, 1e-9);
+
+        let ne = p
+            .forward(Geodetic::from_degrees(WEB_MERCATOR_MAX_LAT_DEG, 180.0, 0.0))
+            .unwrap();
+        assert_close(ne.x, WEB_MERCATOR_HALF_EXTENT_M, 1e-6);
+        assert_close(ne.y, WEB_MERCATOR_HALF_EXTENT_M, 1e-3);
+    }
+
+    #[test]
+    fn clamps_poles() {
+        let p = WebMercator;
+        let pole = p.forward(Geodetic::from_degrees(90.0, 0.0, 0.0)).unwrap();
+        assert!(pole.y.is_finite());
+        assert_close(pole.y, WebMercator::y_m(WEB_MERCATOR_MAX_LAT_DEG), 1e-9);
+    }
+
+    #[test]
+    fn round_trips() {
+        assert_round_trip(
+            &WebMercator,
+            &[
+                (0.0, 0.0),
+                (51.4778, -0.0015),
+                (-33.86, 151.21),
+                (85.0, -179.9),
+            ],
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn rejects_non_finite() {
+        assert!(WebMercator.inverse(Vec2::new(f64::NAN, 0.0)).is_none());
+        assert!(
+            WebMercator
+                .forward(Geodetic::new(0.0, f64::INFINITY, 0.0))
+                .is_none()
+        );
+    }
+}
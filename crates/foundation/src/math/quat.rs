@@ -0,0 +1,217 @@
+use super::Vec3;
+
+/// Rotation quaternion `x i + y j + z k + w`.
+///
+/// Rotation methods assume a unit quaternion; `q` and `-q` describe the same rotation.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Quat {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+impl Default for Quat {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Quat {
+    pub const IDENTITY: Quat = Quat::new(0.0, 0.0, 0.0, 1.0);
+
+    pub const fn new(x: f64, y: f64, z: f64, w: f64) -> Self {
+        Self { x, y, z, w }
+    }
+
+    /// `[x, y, z, w]`.
+    pub fn from_array(a: [f64; 4]) -> Self {
+        Self::new(a[0], a[1], a[2], a[3])
+    }
+
+    pub fn to_array(self) -> [f64; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+
+    /// Right-handed rotation of `angle_rad` about `axis` (normalized here).
+    pub fn from_axis_angle(axis: Vec3, angle_rad: f64) -> Self {
+        let axis = axis.normalize();
+        let (s, c) = (angle_rad * 0.5).sin_cos();
+        Self::new(axis.x * s, axis.y * s, axis.z * s, c)
+    }
+
+    /// Shortest rotation taking unit vector `from` onto unit vector `to`.
+    ///
+    /// Opposite vectors rotate half a turn about an arbitrary perpendicular axis.
+    pub fn from_rotation_arc(from: Vec3, to: Vec3) -> Self {
+        let dot = from.dot(to).clamp(-1.0, 1.0);
+        if dot < -0.999_999 {
+            let mut axis = Vec3::X.cross(from);
+            if axis.length_squared() < 1e-12 {
+                axis = Vec3::Y.cross(from);
+            }
+            let axis = axis.normalize();
+            return Self::new(axis.x, axis.y, axis.z, 0.0);
+        }
+        if dot > 0.999_999 {
+            return Self::IDENTITY;
+        }
+        let axis = from.cross(to);
+        Self::new(axis.x, axis.y, axis.z, 1.0 + dot).normalize()
+    }
+
+    pub fn dot(self, other: Self) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    pub fn length(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    /// Unit quaternion, or identity when the length is (nearly) zero.
+    pub fn normalize(self) -> Self {
+        let n = self.length();
+        if n > 1e-10 {
+            Self::new(self.x / n, self.y / n, self.z / n, self.w / n)
+        } else {
+            Self::IDENTITY
+        }
+    }
+
+    /// Conjugate; the inverse rotation for a unit quaternion.
+    pub fn conjugate(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    pub fn inverse(self) -> Self {
+        let n2 = self.dot(self);
+        let c = self.conjugate();
+        Self::new(c.x / n2, c.y / n2, c.z / n2, c.w / n2)
+    }
+
+    /// Rotate `v` by this unit quaternion.
+    pub fn rotate(self, v: Vec3) -> Vec3 {
+        let qv = Vec3::new(self.x, self.y, self.z);
+        let t = qv.cross(v) * 2.0;
+        v + t * self.w + qv.cross(t)
+    }
+
+    /// Rotation angle in radians, in `[0, π]`.
+    pub fn angle(self) -> f64 {
+        2.0 * self.w.abs().min(1.0).acos()
+    }
+
+    /// Spherical linear interpolation along the shorter arc.
+    ///
+    /// `t` outside `[0, 1]` extrapolates along the same great circle.
+    pub fn slerp(self, other: Self, t: f64) -> Self {
+        let mut dot = self.dot(other);
+        let mut other = other;
+        if dot < 0.0 {
+            other = -other;
+            dot = -dot;
+        }
+        // Nearly parallel: normalized lerp avoids dividing by sin(θ) ≈ 0.
+        if dot > 0.9995 {
+            return Self::new(
+                self.x + t * (other.x - self.x),
+                self.y + t * (other.y - self.y),
+                self.z + t * (other.z - self.z),
+                self.w + t * (other.w - self.w),
+            )
+            .normalize();
+        }
+        let theta_0 = dot.min(1.0).acos();
+        let theta = theta_0 * t;
+        let s1 = theta.sin() / theta_0.sin();
+        let s0 = theta.cos() - dot * s1;
+        Self::new(
+            s0 * self.x + s1 * other.x,
+            s0 * self.y + s1 * other.y,
+            s0 * self.z + s1 * other.z,
+            s0 * self.w + s1 * other.w,
+        )
+    }
+}
+
+impl std::ops::Mul for Quat {
+    type Output = Self;
+
+    /// Hamilton product: `(a * b).rotate(v) == a.rotate(b.rotate(v))`.
+    fn mul(self, b: Self) -> Self::Output {
+        let a = self;
+        Self::new(
+            a.w * b.x + a.x * b.w + a.y * b.z - a.z * b.y,
+            a.w * b.y - a.x * b.z + a.y * b.w + a.z * b.x,
+            a.w * b.z + a.x * b.y - a.y * b.x + a.z * b.w,
+            a.w * b.w - a.x * b.x - a.y * b.y - a.z * b.z,
+        )
+    }
+}
+
+impl std::ops::Mul<Vec3> for Quat {
+    type Output = Vec3;
+
+    fn mul(self, v: Vec3) -> Self::Output {
+        self.rotate(v)
+    }
+}
+
+impl std::ops::Neg for Quat {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y, -self.z, -self.w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Quat;
+    use crate::math::Vec3;
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    fn assert_vec_close(a: Vec3, b: Vec3, eps: f64) {
+        let diff = (a - b).length();
+        assert!(diff <= eps, "expected {a:?} ~= {b:?} (diff {diff})");
+    }
+
+    #[test]
+    fn axis_angle_rotates_right_handed_and_composes() {
+        let qz = Quat::from_axis_angle(Vec3::Z, FRAC_PI_2);
+        assert_vec_close(qz * Vec3::X, Vec3::Y, 1e-12);
+        let qx = Quat::from_axis_angle(Vec3::X, FRAC_PI_2);
+        let v = Vec3::new(1.0, 2.0, 3.0);
+        assert_vec_close((qx * qz) * v, qx * (qz * v), 1e-12);
+        assert_vec_close(qz.conjugate() * (qz * v), v, 1e-12);
+        assert_vec_close(Quat::IDENTITY * v, v, 0.0);
+        assert!((qz.angle() - FRAC_PI_2).abs() < 1e-12);
+        assert!(((-qz).angle() - FRAC_PI_2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn rotation_arc_maps_from_onto_to() {
+        let pairs = [
+            (Vec3::X, Vec3::Y),
+            (Vec3::new(1.0, 1.0, 0.0).normalize(), Vec3::Z),
+            (Vec3::X, -Vec3::X),
+            (Vec3::Y, Vec3::Y),
+        ];
+        for (a, b) in pairs {
+            assert_vec_close(Quat::from_rotation_arc(a, b) * a, b, 1e-9);
+        }
+    }
+
+    #[test]
+    fn slerp_interpolates_angle_linearly() {
+        let a = Quat::IDENTITY;
+        let b = Quat::from_axis_angle(Vec3::Y, PI * 0.8);
+        let mid = a.slerp(b, 0.5);
+        assert!((mid.angle() - PI * 0.4).abs() < 1e-12);
+        assert!((mid.length() - 1.0).abs() < 1e-12);
+        assert_vec_close(a.slerp(b, 1.0) * Vec3::X, b * Vec3::X, 1e-12);
+        // Takes the short way round when the inputs are in opposite hemispheres.
+        let short = a.slerp(-b, 0.5);
+        assert!((short.angle() - PI * 0.4).abs() < 1e-12);
+    }
+}
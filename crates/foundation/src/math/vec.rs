@@ -34,13 +34,62 @@ pub struct Vec3 {
 }
 
 impl Vec3 {
-    pub fn new(x: f64, y: f64, z: f64) -> Self {
+    pub const ZERO: Vec3 = Vec3::new(0.0, 0.0, 0.0);
+    pub const X: Vec3 = Vec3::new(1.0, 0.0, 0.0);
+    pub const Y: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+    pub const Z: Vec3 = Vec3::new(0.0, 0.0, 1.0);
+
+    pub const fn new(x: f64, y: f64, z: f64) -> Self {
         Self { x, y, z }
     }
 
+    pub fn from_array(a: [f64; 3]) -> Self {
+        Self::new(a[0], a[1], a[2])
+    }
+
+    pub fn to_array(self) -> [f64; 3] {
+        [self.x, self.y, self.z]
+    }
+
     pub fn dot(self, other: Self) -> f64 {
         self.x * other.x + self.y * other.y + self.z * other.z
     }
+
+    /// Right-handed cross product.
+    pub fn cross(self, other: Self) -> Self {
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    pub fn length_squared(self) -> f64 {
+        self.dot(self)
+    }
+
+    pub fn length(self) -> f64 {
+        self.length_squared().sqrt()
+    }
+
+    pub fn distance(self, other: Self) -> f64 {
+        (self - other).length()
+    }
+
+    /// Unit vector, or `None` for zero/non-finite length.
+    pub fn try_normalize(self) -> Option<Self> {
+        let len = self.length();
+        (len > 0.0 && len.is_finite()).then(|| self / len)
+    }
+
+    /// Unit vector, or `Vec3::ZERO` when the length is zero.
+    pub fn normalize(self) -> Self {
+        self.try_normalize().unwrap_or(Self::ZERO)
+    }
+
+    pub fn lerp(self, other: Self, t: f64) -> Self {
+        self + (other - self) * t
+    }
 }
 
 impl std::ops::Add for Vec3 {
@@ -59,6 +108,48 @@ impl std::ops::Sub for Vec3 {
     }
 }
 
+impl std::ops::Mul<f64> for Vec3 {
+    type Output = Self;
+
+    fn mul(self, s: f64) -> Self::Output {
+        Self::new(self.x * s, self.y * s, self.z * s)
+    }
+}
+
+impl std::ops::Div<f64> for Vec3 {
+    type Output = Self;
+
+    fn div(self, s: f64) -> Self::Output {
+        Self::new(self.x / s, self.y / s, self.z / s)
+    }
+}
+
+impl std::ops::Neg for Vec3 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl std::ops::AddAssign for Vec3 {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl std::ops::SubAssign for Vec3 {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl std::ops::MulAssign<f64> for Vec3 {
+    fn mul_assign(&mut self, s: f64) {
+        *self = *self * s;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{Vec2, Vec3};
@@ -79,4 +170,17 @@ mod tests {
         assert_eq!(a - b, Vec3::new(0.5, 4.0, -4.0));
         assert_eq!(a.dot(b), -6.5);
     }
+
+    #[test]
+    fn vec3_cross_length_normalize() {
+        assert_eq!(Vec3::X.cross(Vec3::Y), Vec3::Z);
+        assert_eq!(Vec3::Y.cross(Vec3::X), -Vec3::Z);
+        let v = Vec3::new(3.0, 4.0, 0.0);
+        assert_eq!(v.length(), 5.0);
+        assert_eq!(v.normalize(), Vec3::new(0.6, 0.8, 0.0));
+        assert_eq!(Vec3::ZERO.normalize(), Vec3::ZERO);
+        assert!(Vec3::ZERO.try_normalize().is_none());
+        assert_eq!(v.lerp(Vec3::ZERO, 0.5) * 2.0, v);
+        assert_eq!(v.distance(Vec3::ZERO), 5.0);
+    }
 }
@@ -0,0 +1,150 @@
+//! Proleptic Gregorian calendar arithmetic, counted in days since 1970-01-01.
+
+use super::Time;
+
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+pub const fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// Days in `month` (1-12) of `year`; 0 for an invalid month.
+pub const fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+/// Days since 1970-01-01 for a civil date (Hinnant's algorithm).
+pub const fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = if month > 2 { month - 3 } else { month + 9 } as i64;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Civil `(year, month, day)` for days since 1970-01-01.
+pub const fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Broken-down UTC date and time of day.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CivilDateTime {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    pub nanosecond: u32,
+}
+
+impl CivilDateTime {
+    pub fn from_date(year: i64, month: u32, day: u32) -> Self {
+        Self {
+            year,
+            month,
+            day,
+            hour: 0,
+            minute: 0,
+            second: 0,
+            nanosecond: 0,
+        }
+    }
+
+    /// Split a finite `Time` into calendar fields (nanoseconds are rounded).
+    pub fn from_time(t: Time) -> Option<Self> {
+        if !t.0.is_finite() {
+            return None;
+        }
+        let mut whole = t.0.floor();
+        let mut nanos = ((t.0 - whole) * 1e9).round() as u32;
+        if nanos >= 1_000_000_000 {
+            whole += 1.0;
+            nanos -= 1_000_000_000;
+        }
+        let whole = whole as i64;
+        let days = whole.div_euclid(SECONDS_PER_DAY);
+        let secs = whole.rem_euclid(SECONDS_PER_DAY) as u32;
+        let (year, month, day) = civil_from_days(days);
+        Some(Self {
+            year,
+            month,
+            day,
+            hour: secs / 3600,
+            minute: secs / 60 % 60,
+            second: secs % 60,
+            nanosecond: nanos,
+        })
+    }
+
+    pub fn to_time(self) -> Time {
+        let days = days_from_civil(self.year, self.month, self.day);
+        let secs = days * SECONDS_PER_DAY
+            + self.hour as i64 * 3600
+            + self.minute as i64 * 60
+            + self.second as i64;
+        Time(secs as f64 + self.nanosecond as f64 * 1e-9)
+    }
+
+    /// Add calendar months, clamping the day to the end of the target month.
+    pub fn add_months(self, months: i64) -> Self {
+        let total = self.year * 12 + (self.month as i64 - 1) + months;
+        let year = total.div_euclid(12);
+        let month = total.rem_euclid(12) as u32 + 1;
+        Self {
+            year,
+            month,
+            day: self.day.min(days_in_month(year, month)),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CivilDateTime, civil_from_days, days_from_civil, days_in_month};
+    use crate::time::Time;
+
+    #[test]
+    fn civil_days_round_trip() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        for days in (-800_000..800_000).step_by(997) {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+        assert_eq!(days_in_month(1900, 2), 28);
+        assert_eq!(days_in_month(2000, 2), 29);
+    }
+
+    #[test]
+    fn add_months_clamps_day() {
+        let jan31 = CivilDateTime::from_date(2020, 1, 31);
+        assert_eq!(jan31.add_months(1), CivilDateTime::from_date(2020, 2, 29));
+        assert_eq!(jan31.add_months(-2), CivilDateTime::from_date(2019, 11, 30));
+        let t = CivilDateTime::from_time(Time(-0.5)).unwrap();
+        assert_eq!(
+            (t.year, t.hour, t.second, t.nanosecond),
+            (1969, 23, 59, 500_000_000)
+        );
+    }
+}
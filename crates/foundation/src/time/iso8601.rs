@@ -0,0 +1,544 @@
+//! ISO 8601 / RFC 3339 date-times, durations and intervals.
+//!
+//! Supported forms:
+//! - date-times: `YYYY`, `YYYY-MM`, `YYYY-MM-DD`, optionally followed by `T` (or a space)
+//!   and `hh:mm[:ss[.fff]]` with `Z`, `±hh:mm`, `±hhmm` or `±hh`; no offset means UTC
+//! - durations: `[-]PnYnMnWnDTnHnMnS` (fractions allowed from weeks down)
+//! - intervals: `start/end`, `start/duration`, `duration/end`, with `..` or an empty
+//!   side for an unbounded end
+
+use std::fmt;
+
+use super::{
+    CivilDateTime, SECONDS_PER_DAY, Time, TimeSpan, days_in_month, is_leap_second_midnight,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeParseError {
+    InvalidDateTime { input: String, reason: &'static str },
+    InvalidDuration { input: String, reason: &'static str },
+    InvalidInterval { input: String, reason: &'static str },
+}
+
+impl fmt::Display for TimeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeParseError::InvalidDateTime { input, reason } => {
+                write!(f, "invalid date-time {input:?}: {reason}")
+            }
+            TimeParseError::InvalidDuration { input, reason } => {
+                write!(f, "invalid duration {input:?}: {reason}")
+            }
+            TimeParseError::InvalidInterval { input, reason } => {
+                write!(f, "invalid interval {input:?}: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TimeParseError {}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(s: &'a str) -> Self {
+        Self {
+            bytes: s.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, b: u8) -> bool {
+        if self.peek() == Some(b) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn done(&self) -> bool {
+        self.pos == self.bytes.len()
+    }
+
+    /// Exactly `n` ASCII digits.
+    fn fixed(&mut self, n: usize) -> Option<u32> {
+        let end = self.pos + n;
+        let slice = self.bytes.get(self.pos..end)?;
+        if !slice.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        self.pos = end;
+        Some(slice.iter().fold(0, |acc, d| acc * 10 + (d - b'0') as u32))
+    }
+
+    /// One or more digits with an optional `.`/`,` fraction.
+    fn decimal(&mut self) -> Option<f64> {
+        let start = self.pos;
+        while self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return None;
+        }
+        let mut text = String::from_utf8_lossy(&self.bytes[start..self.pos]).into_owned();
+        if matches!(self.peek(), Some(b'.' | b',')) {
+            self.pos += 1;
+            let frac_start = self.pos;
+            while self.peek().is_some_and(|b| b.is_ascii_digit()) {
+                self.pos += 1;
+            }
+            if self.pos == frac_start {
+                return None;
+            }
+            text.push('.');
+            text.push_str(&String::from_utf8_lossy(&self.bytes[frac_start..self.pos]));
+        }
+        text.parse().ok()
+    }
+}
+
+impl Time {
+    /// Parse an RFC 3339 / ISO 8601 date-time (see module docs for accepted forms).
+    pub fn parse_iso8601(input: &str) -> Result<Time, TimeParseError> {
+        let err = |reason| TimeParseError::InvalidDateTime {
+            input: input.to_string(),
+            reason,
+        };
+        let mut c = Cursor::new(input.trim());
+
+        // Years beyond four digits need an explicit sign (ISO 8601 expanded representation).
+        let negative = c.eat(b'-');
+        let expanded = negative || c.eat(b'+');
+        let mut year = c.fixed(4).ok_or_else(|| err("expected four-digit year"))? as i64;
+        while expanded && let Some(d) = c.fixed(1) {
+            year = year * 10 + d as i64;
+        }
+        if negative {
+            year = -year;
+        }
+        let mut civil = CivilDateTime::from_date(year, 1, 1);
+        if c.eat(b'-') {
+            civil.month = c.fixed(2).ok_or_else(|| err("expected two-digit month"))?;
+            if !(1..=12).contains(&civil.month) {
+                return Err(err("month out of range"));
+            }
+            if c.eat(b'-') {
+                civil.day = c.fixed(2).ok_or_else(|| err("expected two-digit day"))?;
+                if civil.day == 0 || civil.day > days_in_month(year, civil.month) {
+                    return Err(err("day out of range"));
+                }
+            }
+        }
+        if c.done() {
+            return Ok(civil.to_time());
+        }
+        if !(c.eat(b'T') || c.eat(b't') || c.eat(b' ')) {
+            return Err(err("expected 'T' before time of day"));
+        }
+
+        civil.hour = c.fixed(2).ok_or_else(|| err("expected two-digit hour"))?;
+        if !c.eat(b':') {
+            return Err(err("expected ':' after hour"));
+        }
+        civil.minute = c.fixed(2).ok_or_else(|| err("expected two-digit minute"))?;
+        let mut frac = 0.0;
+        if c.eat(b':') {
+            let seconds = c.decimal().ok_or_else(|| err("expected seconds"))?;
+            civil.second = seconds.trunc() as u32;
+            frac = seconds.fract();
+        }
+        if civil.minute > 59 || civil.second > 60 {
+            return Err(err("time of day out of range"));
+        }
+        let end_of_day = civil.hour == 24;
+        if civil.hour > 24 || (end_of_day && (civil.minute, civil.second, frac) != (0, 0, 0.0)) {
+            return Err(err("time of day out of range"));
+        }
+
+        let offset_s = match c.peek() {
+            Some(b'Z' | b'z') => {
+                c.pos += 1;
+                0
+            }
+            Some(sign @ (b'+' | b'-')) => {
+                c.pos += 1;
+                let hours = c.fixed(2).ok_or_else(|| err("expected offset hours"))?;
+                let colon = c.eat(b':');
+                let minutes = match c.fixed(2) {
+                    Some(m) => m,
+                    None if colon => return Err(err("expected offset minutes")),
+                    None => 0,
+                };
+                if hours > 23 || minutes > 59 {
+                    return Err(err("offset out of range"));
+                }
+                let s = (hours * 3600 + minutes * 60) as i64;
+                if sign == b'-' { -s } else { s }
+            }
+            None => 0,
+            Some(_) => return Err(err("unexpected characters after time")),
+        };
+        if !c.done() {
+            return Err(err("unexpected characters after offset"));
+        }
+
+        let leap = civil.second == 60;
+        if leap {
+            civil.second = 59;
+        }
+        if end_of_day {
+            civil.hour = 0;
+        }
+        let mut secs = civil.to_time().0
+            + if end_of_day {
+                SECONDS_PER_DAY as f64
+            } else {
+                0.0
+            };
+        secs -= offset_s as f64;
+        if leap {
+            // 23:59:60 is only valid immediately before a leap-second midnight (UTC).
+            secs += 1.0;
+            if !is_leap_second_midnight(Time(secs)) {
+                return Err(err("second 60 outside a leap second"));
+            }
+        }
+        Ok(Time(secs + frac))
+    }
+
+    /// Format as RFC 3339 in UTC (`Z`), with up to microsecond fractional seconds.
+    ///
+    /// Non-finite times format as `..`, the ISO 8601-2 marker for an open end.
+    pub fn to_rfc3339(self) -> String {
+        let Some(mut civil) = CivilDateTime::from_time(self) else {
+            return "..".to_string();
+        };
+        let mut micros = (civil.nanosecond as f64 / 1000.0).round() as u32;
+        if micros == 1_000_000 {
+            civil = CivilDateTime::from_time(Time(self.0.floor() + 1.0)).unwrap_or(civil);
+            micros = 0;
+        }
+        let year = if (0..=9999).contains(&civil.year) {
+            format!("{:04}", civil.year)
+        } else {
+            format!("{:+05}", civil.year)
+        };
+        let mut out = format!(
+            "{year}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            civil.month, civil.day, civil.hour, civil.minute, civil.second
+        );
+        if micros != 0 {
+            let frac = format!("{micros:06}");
+            out.push('.');
+            out.push_str(frac.trim_end_matches('0'));
+        }
+        out.push('Z');
+        out
+    }
+}
+
+/// Nominal ISO 8601 duration: calendar months and days are applied on the calendar.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct IsoDuration {
+    pub months: i64,
+    pub days: i64,
+    pub seconds: f64,
+}
+
+impl IsoDuration {
+    pub fn parse(input: &str) -> Result<IsoDuration, TimeParseError> {
+        let err = |reason| TimeParseError::InvalidDuration {
+            input: input.to_string(),
+            reason,
+        };
+        let mut c = Cursor::new(input.trim());
+        let negative = c.eat(b'-');
+        if !c.eat(b'P') {
+            return Err(err("expected 'P'"));
+        }
+        let mut out = IsoDuration::default();
+        let mut any = false;
+        let mut in_time = false;
+        // Designators must appear in order; `rank` tracks the last one seen.
+        let mut rank = 0;
+        while !c.done() {
+            if c.eat(b'T') {
+                if in_time {
+                    return Err(err("repeated 'T'"));
+                }
+                in_time = true;
+                if c.done() {
+                    return Err(err("'T' without time components"));
+                }
+                continue;
+            }
+            let value = c.decimal().ok_or_else(|| err("expected number"))?;
+            let designator = c.peek().ok_or_else(|| err("missing designator"))?;
+            c.pos += 1;
+            let (next_rank, fractional_ok) = match (in_time, designator) {
+                (false, b'Y') => (1, false),
+                (false, b'M') => (2, false),
+                (false, b'W') => (3, true),
+                (false, b'D') => (4, true),
+                (true, b'H') => (5, true),
+                (true, b'M') => (6, true),
+                (true, b'S') => (7, true),
+                _ => return Err(err("unknown designator")),
+            };
+            if next_rank <= rank {
+                return Err(err("designators out of order"));
+            }
+            if !fractional_ok && value.fract() != 0.0 {
+                return Err(err("fractional years or months"));
+            }
+            rank = next_rank;
+            any = true;
+            match next_rank {
+                1 => out.months += value as i64 * 12,
+                2 => out.months += value as i64,
+                3 | 4 => {
+                    let days = value * if next_rank == 3 { 7.0 } else { 1.0 };
+                    out.days += days.trunc() as i64;
+                    out.seconds += days.fract() * SECONDS_PER_DAY as f64;
+                }
+                5 => out.seconds += value * 3600.0,
+                6 => out.seconds += value * 60.0,
+                _ => out.seconds += value,
+            }
+        }
+        if !any {
+            return Err(err("no components"));
+        }
+        Ok(if negative { out.negated() } else { out })
+    }
+
+    pub fn negated(self) -> Self {
+        Self {
+            months: -self.months,
+            days: -self.days,
+            seconds: -self.seconds,
+        }
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.months < 0 || self.days < 0 || self.seconds < 0.0
+    }
+
+    /// Add to a time: months on the calendar (day clamped), then days, then seconds.
+    pub fn add_to(self, t: Time) -> Time {
+        let Some(civil) = CivilDateTime::from_time(t) else {
+            return t;
+        };
+        let shifted = if self.months != 0 {
+            civil.add_months(self.months).to_time()
+        } else {
+            t
+        };
+        Time(shifted.0 + (self.days * SECONDS_PER_DAY) as f64 + self.seconds)
+    }
+
+    pub fn subtract_from(self, t: Time) -> Time {
+        self.negated().add_to(t)
+    }
+
+    /// Length assuming mean Gregorian months (30.436875 days).
+    pub fn approx_seconds(&self) -> f64 {
+        let day = SECONDS_PER_DAY as f64;
+        self.months as f64 * 30.436_875 * day + self.days as f64 * day + self.seconds
+    }
+}
+
+impl fmt::Display for IsoDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let d = if self.is_negative() {
+            write!(f, "-")?;
+            self.negated()
+        } else {
+            *self
+        };
+        write!(f, "P")?;
+        let (years, months) = (d.months / 12, d.months % 12);
+        if years != 0 {
+            write!(f, "{years}Y")?;
+        }
+        if months != 0 {
+            write!(f, "{months}M")?;
+        }
+        if d.days != 0 {
+            write!(f, "{}D", d.days)?;
+        }
+        if d.seconds != 0.0 || (d.months == 0 && d.days == 0) {
+            write!(f, "T")?;
+            let hours = (d.seconds / 3600.0).floor();
+            let minutes = ((d.seconds - hours * 3600.0) / 60.0).floor();
+            let seconds = d.seconds - hours * 3600.0 - minutes * 60.0;
+            if hours != 0.0 {
+                write!(f, "{hours}H")?;
+            }
+            if minutes != 0.0 {
+                write!(f, "{minutes}M")?;
+            }
+            if seconds != 0.0 || (hours == 0.0 && minutes == 0.0) {
+                write!(f, "{seconds}S")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+enum IntervalPart {
+    Open,
+    Time(Time),
+    Duration(IsoDuration),
+}
+
+fn parse_interval_part(part: &str) -> Result<IntervalPart, TimeParseError> {
+    let part = part.trim();
+    if part.is_empty() || part == ".." {
+        Ok(IntervalPart::Open)
+    } else if part.starts_with('P') || part.starts_with("-P") {
+        IsoDuration::parse(part).map(IntervalPart::Duration)
+    } else {
+        Time::parse_iso8601(part).map(IntervalPart::Time)
+    }
+}
+
+impl TimeSpan {
+    /// Parse an ISO 8601 interval into a half-open span `[start, end)`.
+    ///
+    /// Unbounded sides become infinite.
+    pub fn parse_iso8601(input: &str) -> Result<TimeSpan, TimeParseError> {
+        let err = |reason| TimeParseError::InvalidInterval {
+            input: input.to_string(),
+            reason,
+        };
+        let Some((a, b)) = input.split_once('/') else {
+            return Err(err("expected '/'"));
+        };
+        let (start, end) = match (parse_interval_part(a)?, parse_interval_part(b)?) {
+            (IntervalPart::Time(s), IntervalPart::Time(e)) => (s, e),
+            (IntervalPart::Time(s), IntervalPart::Duration(d)) => (s, d.add_to(s)),
+            (IntervalPart::Duration(d), IntervalPart::Time(e)) => (d.subtract_from(e), e),
+            (IntervalPart::Time(s), IntervalPart::Open) => (s, Time(f64::INFINITY)),
+            (IntervalPart::Open, IntervalPart::Time(e)) => (Time(f64::NEG_INFINITY), e),
+            (IntervalPart::Open, IntervalPart::Open) => {
+                (Time(f64::NEG_INFINITY), Time(f64::INFINITY))
+            }
+            _ => return Err(err("a duration needs a date-time on the other side")),
+        };
+        if start.0 > end.0 {
+            return Err(err("end precedes start"));
+        }
+        Ok(TimeSpan::half_open(start, end))
+    }
+
+    /// Format as an ISO 8601 `start/end` interval (`..` for unbounded sides).
+    ///
+    /// ISO 8601 has no notation for endpoint inclusion, so `bounds` is not encoded.
+    pub fn to_iso8601(&self) -> String {
+        format!("{}/{}", self.start.to_rfc3339(), self.end.to_rfc3339())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IsoDuration, TimeParseError};
+    use crate::time::{SpanBounds, Time, TimeSpan};
+
+    fn t(s: &str) -> Time {
+        Time::parse_iso8601(s).unwrap()
+    }
+
+    #[test]
+    fn parses_rfc3339_and_reduced_precision() {
+        assert_eq!(t("1970-01-01T00:00:00Z"), Time(0.0));
+        assert_eq!(t("2020-01-01"), Time(1_577_836_800.0));
+        assert_eq!(t("2020"), t("2020-01-01T00:00:00Z"));
+        assert_eq!(t("2020-03"), t("2020-03-01T00:00Z"));
+        assert_eq!(t("2020-01-01T01:30:00+01:30"), t("2020-01-01T00:00:00Z"));
+        assert_eq!(t("2019-12-31 19:00:00-0500"), t("2020-01-01T00:00:00Z"));
+        assert_eq!(t("2019-12-31T24:00:00Z"), t("2020-01-01"));
+        assert_eq!(t("2020-01-01T00:00:00.25Z").0, 1_577_836_800.25);
+    }
+
+    #[test]
+    fn leap_second_is_only_accepted_on_leap_days() {
+        assert_eq!(t("2016-12-31T23:59:60Z"), t("2017-01-01T00:00:00Z"));
+        assert!(Time::parse_iso8601("2018-12-31T23:59:60Z").is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        for bad in [
+            "",
+            "20-01-01",
+            "20200101",
+            "2020-13-01",
+            "2021-02-29",
+            "2020-01-01T25:00",
+            "2020-01-01Tx",
+        ] {
+            assert!(
+                matches!(
+                    Time::parse_iso8601(bad),
+                    Err(TimeParseError::InvalidDateTime { .. })
+                ),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn formats_rfc3339() {
+        assert_eq!(Time(0.0).to_rfc3339(), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            t("2024-02-29T12:34:56.5Z").to_rfc3339(),
+            "2024-02-29T12:34:56.5Z"
+        );
+        assert_eq!(Time(-1.0).to_rfc3339(), "1969-12-31T23:59:59Z");
+        assert_eq!(Time(f64::INFINITY).to_rfc3339(), "..");
+    }
+
+    #[test]
+    fn durations_parse_format_and_apply() {
+        let d = IsoDuration::parse("P1Y2M3DT4H5M6.5S").unwrap();
+        assert_eq!((d.months, d.days), (14, 3));
+        assert_eq!(d.seconds, 4.0 * 3600.0 + 5.0 * 60.0 + 6.5);
+        assert_eq!(d.to_string(), "P1Y2M3DT4H5M6.5S");
+        assert_eq!(IsoDuration::parse("P2W").unwrap().days, 14);
+        assert_eq!(IsoDuration::parse("PT0S").unwrap().to_string(), "PT0S");
+        assert_eq!(IsoDuration::parse("-P1D").unwrap().to_string(), "-P1D");
+        assert!(IsoDuration::parse("P").is_err());
+        assert!(IsoDuration::parse("P1DT").is_err());
+        assert!(IsoDuration::parse("PT1S2M").is_err());
+        assert!(IsoDuration::parse("P1.5M").is_err());
+
+        let month = IsoDuration::parse("P1M").unwrap();
+        assert_eq!(month.add_to(t("2020-01-31")), t("2020-02-29"));
+    }
+
+    #[test]
+    fn intervals_are_half_open() {
+        let span = TimeSpan::parse_iso8601("2020-01-01/P1M").unwrap();
+        assert_eq!(span.start, t("2020-01-01"));
+        assert_eq!(span.end, t("2020-02-01"));
+        assert_eq!(span.bounds, SpanBounds::ClosedOpen);
+        assert!(!span.contains(t("2020-02-01")));
+
+        let back = TimeSpan::parse_iso8601("P1D/2020-01-02T00:00:00Z").unwrap();
+        assert_eq!(back.start, t("2020-01-01"));
+        let open = TimeSpan::parse_iso8601("2020-01-01/..").unwrap();
+        assert_eq!(open.end, Time(f64::INFINITY));
+        assert_eq!(open.to_iso8601(), "2020-01-01T00:00:00Z/..");
+        assert!(TimeSpan::parse_iso8601("P1D/P2D").is_err());
+        assert!(TimeSpan::parse_iso8601("2021-01-01/2020-01-01").is_err());
+    }
+}
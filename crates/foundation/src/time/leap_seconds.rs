@@ -0,0 +1,133 @@
+//! UTC <-> TAI using the IERS leap-second table.
+
+use super::{SECONDS_PER_DAY, Time, days_from_civil};
+
+/// `(year, month, TAI - UTC)` from 00:00:00 UTC on the first of the month.
+///
+/// Extend this table when IERS Bulletin C announces a new leap second.
+pub const LEAP_SECONDS: [(i64, u32, i32); 28] = [
+    (1972, 1, 10),
+    (1972, 7, 11),
+    (1973, 1, 12),
+    (1974, 1, 13),
+    (1975, 1, 14),
+    (1976, 1, 15),
+    (1977, 1, 16),
+    (1978, 1, 17),
+    (1979, 1, 18),
+    (1980, 1, 19),
+    (1981, 7, 20),
+    (1982, 7, 21),
+    (1983, 7, 22),
+    (1985, 7, 23),
+    (1988, 1, 24),
+    (1990, 1, 25),
+    (1991, 1, 26),
+    (1992, 7, 27),
+    (1993, 7, 28),
+    (1994, 7, 29),
+    (1996, 1, 30),
+    (1997, 7, 31),
+    (1999, 1, 32),
+    (2006, 1, 33),
+    (2009, 1, 34),
+    (2012, 7, 35),
+    (2015, 7, 36),
+    (2017, 1, 37),
+];
+
+/// Instant on the TAI scale, in seconds since 1970-01-01T00:00:00 TAI.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TaiTime(pub f64);
+
+fn effective_from(entry: (i64, u32, i32)) -> f64 {
+    (days_from_civil(entry.0, entry.1, 1) * SECONDS_PER_DAY) as f64
+}
+
+/// TAI - UTC in seconds at a UTC instant.
+///
+/// Before 1972 UTC used fractional rate offsets; the 1972 value (10 s) is used instead.
+pub fn tai_minus_utc(t: Time) -> i32 {
+    LEAP_SECONDS
+        .iter()
+        .rev()
+        .find(|entry| effective_from(**entry) <= t.0)
+        .map(|entry| entry.2)
+        .unwrap_or(LEAP_SECONDS[0].2)
+}
+
+/// True when a leap second (23:59:60) was inserted just before this UTC midnight.
+pub fn is_leap_second_midnight(t: Time) -> bool {
+    LEAP_SECONDS
+        .iter()
+        .skip(1)
+        .any(|entry| effective_from(*entry) == t.0)
+}
+
+impl Time {
+    pub fn to_tai(self) -> TaiTime {
+        TaiTime(self.0 + tai_minus_utc(self) as f64)
+    }
+
+    /// Inverse of `to_tai`.
+    ///
+    /// POSIX time cannot represent 23:59:60, so instants inside an inserted leap second
+    /// map to the following UTC midnight.
+    pub fn from_tai(tai: TaiTime) -> Time {
+        let mut offset = LEAP_SECONDS[0].2;
+        for entry in LEAP_SECONDS.iter() {
+            let utc = effective_from(*entry);
+            if tai.0 >= utc + entry.2 as f64 {
+                offset = entry.2;
+            } else {
+                if tai.0 >= utc + offset as f64 {
+                    return Time(utc);
+                }
+                break;
+            }
+        }
+        Time(tai.0 - offset as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TaiTime, is_leap_second_midnight, tai_minus_utc};
+    use crate::time::{CivilDateTime, Time};
+
+    fn utc(y: i64, mo: u32, d: u32, h: u32, mi: u32, s: u32) -> Time {
+        CivilDateTime {
+            hour: h,
+            minute: mi,
+            second: s,
+            ..CivilDateTime::from_date(y, mo, d)
+        }
+        .to_time()
+    }
+
+    #[test]
+    fn offsets_step_at_leap_seconds() {
+        assert_eq!(tai_minus_utc(utc(1960, 1, 1, 0, 0, 0)), 10);
+        assert_eq!(tai_minus_utc(utc(2016, 12, 31, 23, 59, 59)), 36);
+        assert_eq!(tai_minus_utc(utc(2017, 1, 1, 0, 0, 0)), 37);
+        assert!(is_leap_second_midnight(utc(2017, 1, 1, 0, 0, 0)));
+        assert!(!is_leap_second_midnight(utc(2018, 1, 1, 0, 0, 0)));
+    }
+
+    #[test]
+    fn tai_round_trips_and_leap_second_maps_forward() {
+        for t in [
+            utc(1999, 6, 1, 12, 0, 0),
+            utc(2017, 1, 1, 0, 0, 0),
+            utc(2024, 2, 29, 1, 2, 3),
+        ] {
+            assert_eq!(Time::from_tai(t.to_tai()), t);
+        }
+        let midnight = utc(2017, 1, 1, 0, 0, 0);
+        let before = utc(2016, 12, 31, 23, 59, 59);
+        // TAI advances two seconds across the inserted leap second.
+        assert_eq!(midnight.to_tai().0 - before.to_tai().0, 2.0);
+        let in_leap = TaiTime(before.to_tai().0 + 1.5);
+        assert_eq!(Time::from_tai(in_leap), midnight);
+    }
+}
@@ -0,0 +1,143 @@
+//! Time primitives.
+//!
+//! `Time` is POSIX seconds since 1970-01-01T00:00:00Z (UTC, leap seconds not counted).
+//! Calendar conversion, ISO 8601 text and the TAI scale live in the submodules.
+
+pub mod calendar;
+pub mod iso8601;
+pub mod leap_seconds;
+
+pub use calendar::*;
+pub use iso8601::*;
+pub use leap_seconds::*;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Time(pub f64); // seconds
+
+/// Which ends of a `TimeSpan` are included.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SpanBounds {
+    /// `[start, end]`
+    #[default]
+    Closed,
+    /// `(start, end)`
+    Open,
+    /// `[start, end)`
+    ClosedOpen,
+    /// `(start, end]`
+    OpenClosed,
+}
+
+impl SpanBounds {
+    pub fn new(start_closed: bool, end_closed: bool) -> Self {
+        match (start_closed, end_closed) {
+            (true, true) => SpanBounds::Closed,
+            (false, false) => SpanBounds::Open,
+            (true, false) => SpanBounds::ClosedOpen,
+            (false, true) => SpanBounds::OpenClosed,
+        }
+    }
+
+    pub fn start_closed(self) -> bool {
+        matches!(self, SpanBounds::Closed | SpanBounds::ClosedOpen)
+    }
+
+    pub fn end_closed(self) -> bool {
+        matches!(self, SpanBounds::Closed | SpanBounds::OpenClosed)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TimeSpan {
+    pub start: Time,
+    pub end: Time,
+    pub bounds: SpanBounds,
+}
+
+impl TimeSpan {
+    /// Closed span `[start, end]`.
+    pub fn new(start: Time, end: Time) -> Self {
+        Self::with_bounds(start, end, SpanBounds::Closed)
+    }
+
+    pub fn with_bounds(start: Time, end: Time, bounds: SpanBounds) -> Self {
+        Self { start, end, bounds }
+    }
+
+    /// Half-open span `[start, end)`, the usual convention for ISO 8601 intervals.
+    pub fn half_open(start: Time, end: Time) -> Self {
+        Self::with_bounds(start, end, SpanBounds::ClosedOpen)
+    }
+
+    pub fn forever() -> Self {
+        Self::new(Time(f64::NEG_INFINITY), Time(f64::INFINITY))
+    }
+
+    pub fn instant(t: Time) -> Self {
+        Self::new(t, t)
+    }
+
+    pub fn duration(&self) -> f64 {
+        (self.end.0 - self.start.0).max(0.0)
+    }
+
+    /// True when no instant satisfies the bounds.
+    pub fn is_empty(&self) -> bool {
+        if self.start.0 == self.end.0 {
+            return self.bounds != SpanBounds::Closed;
+        }
+        self.start.0.partial_cmp(&self.end.0) != Some(std::cmp::Ordering::Less)
+    }
+
+    pub fn contains(&self, t: Time) -> bool {
+        let after_start = if self.bounds.start_closed() {
+            t.0 >= self.start.0
+        } else {
+            t.0 > self.start.0
+        };
+        let before_end = if self.bounds.end_closed() {
+            t.0 <= self.end.0
+        } else {
+            t.0 < self.end.0
+        };
+        after_start && before_end
+    }
+
+    /// True when some instant lies in both spans.
+    pub fn overlaps(&self, other: &TimeSpan) -> bool {
+        let lo = self.start.0.max(other.start.0);
+        let hi = self.end.0.min(other.end.0);
+        if lo < hi {
+            return !self.is_empty() && !other.is_empty();
+        }
+        lo == hi && self.contains(Time(lo)) && other.contains(Time(lo))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SpanBounds, Time, TimeSpan};
+
+    #[test]
+    fn bounds_control_endpoint_membership() {
+        let closed = TimeSpan::new(Time(0.0), Time(10.0));
+        let half = TimeSpan::half_open(Time(0.0), Time(10.0));
+        let open = TimeSpan::with_bounds(Time(0.0), Time(10.0), SpanBounds::Open);
+        assert!(closed.contains(Time(10.0)));
+        assert!(half.contains(Time(0.0)) && !half.contains(Time(10.0)));
+        assert!(!open.contains(Time(0.0)) && open.contains(Time(5.0)));
+        assert!(TimeSpan::forever().contains(Time(1e12)));
+    }
+
+    #[test]
+    fn touching_spans_overlap_only_when_both_include_the_endpoint() {
+        let a = TimeSpan::half_open(Time(0.0), Time(10.0));
+        let b = TimeSpan::new(Time(10.0), Time(20.0));
+        let c = TimeSpan::new(Time(0.0), Time(10.0));
+        assert!(!a.overlaps(&b));
+        assert!(c.overlaps(&b));
+        assert!(TimeSpan::instant(Time(3.0)).overlaps(&a));
+        let empty = TimeSpan::half_open(Time(5.0), Time(5.0));
+        assert!(empty.is_empty() && !empty.overlaps(&c));
+    }
+}
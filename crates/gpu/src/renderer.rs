@@ -1,4 +1,4 @@
-use foundation::math::{Vec2, Vec3};
+use foundation::math::{Mat4, Vec2, Vec3};
 use foundation::time::Time;
 use scene::components::{Shape2D, Shape3D, Transform};
 use scene::world::World;
@@ -19,8 +19,10 @@ impl Camera2D {
 pub struct Camera3D {
     pub position: Vec3,
     pub target: Vec3,
+    pub up: Vec3,
     pub fov_y_rad: f64,
     pub near: f64,
+    /// Far clip distance; `f64::INFINITY` selects an infinite far plane.
     pub far: f64,
 }
 
@@ -29,11 +31,43 @@ impl Camera3D {
         Self {
             position,
             target,
+            up: Vec3::Y,
             fov_y_rad,
             near,
             far,
         }
     }
+
+    pub fn with_up(mut self, up: Vec3) -> Self {
+        self.up = up;
+        self
+    }
+
+    pub fn view_matrix(&self) -> Mat4 {
+        Mat4::look_at_rh(self.position, self.target, self.up)
+    }
+
+    /// Projection with depth 0 at `near` and 1 at `far`.
+    pub fn projection_matrix(&self, aspect: f64) -> Mat4 {
+        if self.far.is_finite() {
+            Mat4::perspective_rh(self.fov_y_rad, aspect, self.near, self.far)
+        } else {
+            Mat4::perspective_rh_infinite(self.fov_y_rad, aspect, self.near)
+        }
+    }
+
+    /// Reversed-Z projection (depth 1 at `near`, 0 at `far`) for use with a `Greater` depth test.
+    pub fn projection_matrix_reversed_z(&self, aspect: f64) -> Mat4 {
+        if self.far.is_finite() {
+            Mat4::perspective_rh_reversed_z(self.fov_y_rad, aspect, self.near, self.far)
+        } else {
+            Mat4::perspective_rh_infinite_reversed_z(self.fov_y_rad, aspect, self.near)
+        }
+    }
+
+    pub fn view_proj(&self, aspect: f64) -> Mat4 {
+        self.projection_matrix(aspect) * self.view_matrix()
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -128,4 +162,19 @@ mod tests {
             [RenderCommand::Draw3D { .. }]
         ));
     }
+
+    #[test]
+    fn camera_3d_projects_target_to_screen_center() {
+        let camera = Camera3D::look_at(Vec3::new(0.0, 0.0, 10.0), Vec3::ZERO, 1.0, 0.1, 1000.0);
+        let ndc = camera.view_proj(16.0 / 9.0).transform_point3(Vec3::ZERO);
+        assert!(ndc.x.abs() < 1e-12 && ndc.y.abs() < 1e-12);
+        assert!(ndc.z > 0.0 && ndc.z < 1.0);
+
+        let infinite = Camera3D {
+            far: f64::INFINITY,
+            ..camera
+        };
+        let reversed = infinite.projection_matrix_reversed_z(1.0) * infinite.view_matrix();
+        assert!((reversed.transform_point3(Vec3::new(0.0, 0.0, 9.9)).z - 1.0).abs() < 1e-12);
+    }
 }
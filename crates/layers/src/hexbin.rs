@@ -0,0 +1,113 @@
+use std::collections::BTreeMap;
+
+use foundation::math::{Ecef, Geodetic, HexCell, Vec3, ecef_to_geodetic, geodetic_to_ecef};
+use scene::World;
+use scene::components::VectorGeometry;
+
+use crate::layer::{Layer, LayerId};
+
+/// Point density drawn as hexagonal grid cells.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct HexBinLayer {
+    id: LayerId,
+    resolution: u8,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct HexBinLayerSnapshot {
+    /// Cells in id order, with one value each.
+    pub cells: Vec<HexCell>,
+    pub values: Vec<f64>,
+    /// Flat triangle list (3 vertices per triangle) in world coordinates.
+    pub triangles: Vec<Vec3>,
+    /// Index into `cells` for each triangle.
+    pub triangle_cells: Vec<usize>,
+    /// Cell outline segments (A,B) in world coordinates.
+    pub outline_segments: Vec<[Vec3; 2]>,
+}
+
+impl HexBinLayer {
+    pub fn new(id: u64, resolution: u8) -> Self {
+        Self {
+            id: LayerId(id),
+            resolution,
+        }
+    }
+
+    pub fn resolution(&self) -> u8 {
+        self.resolution
+    }
+
+    /// Bins the world's point geometries and meshes each occupied cell, valued by count.
+    pub fn extract(&self, world: &World) -> HexBinLayerSnapshot {
+        let mut counts: BTreeMap<HexCell, f64> = BTreeMap::new();
+        for (_entity, _transform, component) in world.vector_geometries_by_entity() {
+            if let Some(VectorGeometry::Point { position }) = world.vector_geometry(component.id) {
+                let geo = ecef_to_geodetic(Ecef::new(position.x, position.y, position.z));
+                *counts
+                    .entry(HexCell::from_geodetic(geo, self.resolution))
+                    .or_insert(0.0) += 1.0;
+            }
+        }
+        HexBinLayerSnapshot::from_cells(counts)
+    }
+}
+
+impl HexBinLayerSnapshot {
+    /// Meshes cells on the ellipsoid surface: a triangle fan from each cell centre.
+    pub fn from_cells(cells: impl IntoIterator<Item = (HexCell, f64)>) -> Self {
+        let mut out = Self::default();
+        for (cell, value) in cells {
+            let index = out.cells.len();
+            out.cells.push(cell);
+            out.values.push(value);
+            let center = surface_point(cell.center());
+            let boundary: Vec<Vec3> = cell.boundary().into_iter().map(surface_point).collect();
+            for (i, &a) in boundary.iter().enumerate() {
+                let b = boundary[(i + 1) % boundary.len()];
+                out.triangles.extend([center, a, b]);
+                out.triangle_cells.push(index);
+                out.outline_segments.push([a, b]);
+            }
+        }
+        out
+    }
+}
+
+fn surface_point(geo: Geodetic) -> Vec3 {
+    let e = geodetic_to_ecef(geo);
+    Vec3::new(e.x, e.y, e.z)
+}
+
+impl Layer for HexBinLayer {
+    fn id(&self) -> LayerId {
+        self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HexBinLayer, HexBinLayerSnapshot};
+    use formats::load_world_from_package_dir;
+    use foundation::math::HexCell;
+
+    #[test]
+    fn meshes_cells_as_fans() {
+        let hex = HexCell::from_lon_lat_deg(10.0, 45.0, 4);
+        let pentagon = HexCell::from_lon_lat_deg(0.0, 90.0, 4);
+        let snap = HexBinLayerSnapshot::from_cells([(hex, 2.0), (pentagon, 5.0)]);
+        assert_eq!(snap.values, vec![2.0, 5.0]);
+        assert_eq!(snap.triangles.len(), 3 * 11);
+        assert_eq!(snap.triangle_cells.iter().filter(|&&c| c == 1).count(), 5);
+        assert_eq!(snap.outline_segments.len(), 11);
+    }
+
+    #[test]
+    fn extracts_demo_point_counts() {
+        let root = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../apps/web/assets");
+        let world = load_world_from_package_dir(root).expect("load world");
+        let snap = HexBinLayer::new(1, 3).extract(&world);
+        assert!(!snap.cells.is_empty());
+        assert!(snap.values.iter().all(|&v| v >= 1.0));
+    }
+}
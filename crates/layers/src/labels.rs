@@ -297,7 +297,7 @@ fn line_midpoint(vertices: &[Vec3]) -> Option<Vec3> {
     for pair in vertices.windows(2) {
         let a = pair[0];
         let b = pair[1];
-        let len = vec3_len(vec3_sub(b, a));
+        let len = b.distance(a);
         if !len.is_finite() || len <= 0.0 {
             continue;
         }
@@ -313,7 +313,7 @@ fn line_midpoint(vertices: &[Vec3]) -> Option<Vec3> {
     for (a, b, len) in segments {
         if acc + len >= target {
             let t = (target - acc) / len;
-            return Some(vec3_lerp(a, b, t));
+            return Some(a.lerp(b, t));
         }
         acc += len;
     }
@@ -330,40 +330,20 @@ fn area_centroid(rings: &[Vec<Vec3>]) -> Option<Vec3> {
     let mut count = 0.0_f64;
     for v in outer {
         if is_finite_vec3(*v) {
-            sum = vec3_add(sum, *v);
+            sum += *v;
             count += 1.0;
         }
     }
     if count <= 0.0 {
         return None;
     }
-    Some(vec3_mul(sum, 1.0 / count))
+    Some(sum / count)
 }
 
 fn is_finite_vec3(v: Vec3) -> bool {
     v.x.is_finite() && v.y.is_finite() && v.z.is_finite()
 }
 
-fn vec3_add(a: Vec3, b: Vec3) -> Vec3 {
-    Vec3::new(a.x + b.x, a.y + b.y, a.z + b.z)
-}
-
-fn vec3_sub(a: Vec3, b: Vec3) -> Vec3 {
-    Vec3::new(a.x - b.x, a.y - b.y, a.z - b.z)
-}
-
-fn vec3_mul(v: Vec3, s: f64) -> Vec3 {
-    Vec3::new(v.x * s, v.y * s, v.z * s)
-}
-
-fn vec3_len(v: Vec3) -> f64 {
-    (v.x * v.x + v.y * v.y + v.z * v.z).sqrt()
-}
-
-fn vec3_lerp(a: Vec3, b: Vec3, t: f64) -> Vec3 {
-    vec3_add(a, vec3_mul(vec3_sub(b, a), t))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,9 +1,11 @@
+pub mod hexbin;
 pub mod labels;
 pub mod layer;
 pub mod objects;
 pub mod query;
 pub mod raster;
 pub mod symbology;
+pub mod terminator;
 pub mod terrain;
 pub mod vector;
 
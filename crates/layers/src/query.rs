@@ -53,7 +53,7 @@ fn time_allows(span: Option<TimeSpan>, time: Option<Time>) -> bool {
         // If unset, treat as always visible.
         return true;
     };
-    !(time.0 < span.start.0 || time.0 > span.end.0)
+    span.contains(time)
 }
 
 fn properties_match(world: &World, entity: EntityId, filters: &[PropertyFilter]) -> bool {
@@ -0,0 +1,151 @@
+use foundation::astro::{
+    ASTRONOMICAL_TWILIGHT_DEG, CIVIL_TWILIGHT_DEG, NAUTICAL_TWILIGHT_DEG, night_ring,
+    subsolar_point,
+};
+use foundation::math::{Geodetic, Vec3, geodetic_to_ecef};
+use foundation::time::Time;
+
+use crate::layer::{Layer, LayerId};
+
+/// Default number of edges along each terminator curve.
+pub const TERMINATOR_SEGMENTS: usize = 180;
+
+/// Day/night terminator and, optionally, the civil, nautical and astronomical twilight
+/// boundaries for a given time.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TerminatorLayer {
+    id: LayerId,
+    segments: usize,
+    twilight: bool,
+}
+
+/// One boundary: the edge of the region where the Sun is below `elevation_deg`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerminatorBand {
+    pub elevation_deg: f64,
+    /// Closed `[lon, lat]` degree ring of the dark side, as from `night_ring`.
+    pub ring: Vec<[f64; 2]>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerminatorLayerSnapshot {
+    pub time: Time,
+    /// Subsolar point on the ellipsoid, in world coordinates.
+    pub subsolar: Vec3,
+    /// Terminator first, then twilight boundaries from lightest to darkest.
+    pub bands: Vec<TerminatorBand>,
+    /// Curve segments (A,B) in world coordinates, excluding the closing legs through a pole.
+    pub outline_segments: Vec<[Vec3; 2]>,
+    /// Index into `bands` for each outline segment.
+    pub segment_bands: Vec<usize>,
+}
+
+impl TerminatorLayer {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: LayerId(id),
+            segments: TERMINATOR_SEGMENTS,
+            twilight: true,
+        }
+    }
+
+    pub fn with_segments(mut self, segments: usize) -> Self {
+        self.segments = segments.max(3);
+        self
+    }
+
+    pub fn with_twilight(mut self, twilight: bool) -> Self {
+        self.twilight = twilight;
+        self
+    }
+
+    pub fn segments(&self) -> usize {
+        self.segments
+    }
+
+    pub fn twilight(&self) -> bool {
+        self.twilight
+    }
+
+    pub fn extract(&self, t: Time) -> TerminatorLayerSnapshot {
+        let mut elevations = vec![0.0];
+        if self.twilight {
+            elevations.extend([
+                CIVIL_TWILIGHT_DEG,
+                NAUTICAL_TWILIGHT_DEG,
+                ASTRONOMICAL_TWILIGHT_DEG,
+            ]);
+        }
+
+        let mut out = TerminatorLayerSnapshot {
+            time: t,
+            subsolar: surface_point(subsolar_point(t)),
+            bands: Vec::new(),
+            outline_segments: Vec::new(),
+            segment_bands: Vec::new(),
+        };
+        for elevation_deg in elevations {
+            let ring = night_ring(t, elevation_deg, self.segments);
+            let index = out.bands.len();
+            let curve: Vec<Vec3> = ring
+                .iter()
+                .map(|&[lon, lat]| surface_point(Geodetic::from_degrees(lat, lon, 0.0)))
+                .collect();
+            // A ring closed through a pole has two extra vertices; only the curve between
+            // them is drawn. A small circle is drawn closed.
+            if ring.len() > self.segments {
+                for pair in curve[..=self.segments].windows(2) {
+                    out.outline_segments.push([pair[0], pair[1]]);
+                    out.segment_bands.push(index);
+                }
+            } else {
+                for (i, &a) in curve.iter().enumerate() {
+                    out.outline_segments.push([a, curve[(i + 1) % curve.len()]]);
+                    out.segment_bands.push(index);
+                }
+            }
+            out.bands.push(TerminatorBand {
+                elevation_deg,
+                ring,
+            });
+        }
+        out
+    }
+}
+
+fn surface_point(geo: Geodetic) -> Vec3 {
+    let e = geodetic_to_ecef(geo);
+    Vec3::new(e.x, e.y, e.z)
+}
+
+impl Layer for TerminatorLayer {
+    fn id(&self) -> LayerId {
+        self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TerminatorLayer;
+    use foundation::math::WGS84_A;
+    use foundation::time::Time;
+
+    #[test]
+    fn extracts_terminator_and_twilight() {
+        let t = Time::parse_iso8601("2024-06-20T12:00:00Z").unwrap();
+        let snap = TerminatorLayer::new(1).with_segments(90).extract(t);
+        assert_eq!(snap.bands.len(), 4);
+        assert_eq!(snap.bands[0].elevation_deg, 0.0);
+        assert_eq!(snap.outline_segments.len(), snap.segment_bands.len());
+        assert_eq!(snap.segment_bands.iter().filter(|&&b| b == 0).count(), 90);
+        // The terminator is a great circle 90° from the subsolar point.
+        for [a, _] in &snap.outline_segments[..90] {
+            let cos = a.normalize().dot(snap.subsolar.normalize());
+            assert!(cos.abs() < 0.01, "{cos}");
+            assert!((a.length() - WGS84_A).abs() < 30_000.0);
+        }
+
+        let plain = TerminatorLayer::new(2).with_twilight(false).extract(t);
+        assert_eq!(plain.bands.len(), 1);
+    }
+}
@@ -97,6 +97,7 @@ fn outline_segments_from_area_rings(rings: &[Vec<Vec3>]) -> Vec<[Vec3; 2]> {
 }
 
 fn triangulate_area_rings(rings: &[Vec<Vec3>]) -> Vec<Vec3> {
+    runtime::trace_span!("triangulate_area_rings", "triangulate");
     const MAX_RING_VERTICES: usize = 100_000;
     const MAX_TOTAL_VERTICES: usize = 500_000;
 
@@ -128,12 +129,12 @@ fn triangulate_area_rings(rings: &[Vec<Vec3>]) -> Vec<Vec3> {
     } else {
         Vec3::new(0.0, 1.0, 0.0)
     };
-    let east = normalize(cross(up, n));
-    let north = cross(n, east);
+    let east = up.cross(n).normalize();
+    let north = n.cross(east);
 
     // Degenerate basis (or invalid normal) -> skip triangulation rather than risk NaNs.
-    let east_l2 = dot(east, east);
-    let north_l2 = dot(north, north);
+    let east_l2 = east.length_squared();
+    let north_l2 = north.length_squared();
     if !(east_l2.is_finite() && north_l2.is_finite()) || east_l2 < 1e-20 || north_l2 < 1e-20 {
         return Vec::new();
     }
@@ -170,8 +171,8 @@ fn triangulate_area_rings(rings: &[Vec<Vec3>]) -> Vec<Vec3> {
         let mut projection_ok = true;
         for p in ring_pts {
             let v = Vec3::new(p.x - origin.x, p.y - origin.y, p.z - origin.z);
-            let x = dot(v, east);
-            let y = dot(v, north);
+            let x = v.dot(east);
+            let y = v.dot(north);
             if !(x.is_finite() && y.is_finite()) {
                 projection_ok = false;
                 break;
@@ -235,7 +236,7 @@ fn ellipsoid_normal_ecef(p: Vec3) -> Vec3 {
     // Normal is gradient of (x^2/A^2 + y^2/A^2 + z^2/B^2).
     let a2 = WGS84_A * WGS84_A;
     let b2 = WGS84_B * WGS84_B;
-    normalize(Vec3::new(p.x / a2, p.y / a2, p.z / b2))
+    Vec3::new(p.x / a2, p.y / a2, p.z / b2).normalize()
 }
 
 fn centroid(vertices: &[Vec3]) -> Vec3 {
@@ -251,27 +252,6 @@ fn centroid(vertices: &[Vec3]) -> Vec3 {
     Vec3::new(sx / n, sy / n, sz / n)
 }
 
-fn dot(a: Vec3, b: Vec3) -> f64 {
-    a.x * b.x + a.y * b.y + a.z * b.z
-}
-
-fn cross(a: Vec3, b: Vec3) -> Vec3 {
-    Vec3::new(
-        a.y * b.z - a.z * b.y,
-        a.z * b.x - a.x * b.z,
-        a.x * b.y - a.y * b.x,
-    )
-}
-
-fn normalize(v: Vec3) -> Vec3 {
-    let l2 = dot(v, v);
-    if l2 <= 0.0 {
-        return v;
-    }
-    let inv = 1.0 / l2.sqrt();
-    Vec3::new(v.x * inv, v.y * inv, v.z * inv)
-}
-
 impl Layer for VectorLayer {
     fn id(&self) -> LayerId {
         self.id
@@ -0,0 +1,139 @@
+//! Compares `WorkQueue` with the previous Vec-backed queue on a tile-streaming workload:
+//! submit `n` requests, reprioritize half of them (camera moved), cancel a quarter and
+//! drain the rest.
+//!
+//! Run with `cargo bench -p runtime --bench work_queue`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use runtime::work_queue::{WorkId, WorkQueue};
+
+/// The queue before the indexed heap: linear scans for cancel, reprioritize and pop.
+struct VecQueue {
+    next_id: u64,
+    items: Vec<(i32, u64, bool)>,
+}
+
+impl VecQueue {
+    fn new() -> Self {
+        Self {
+            next_id: 0,
+            items: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, priority: i32) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.items.push((priority, id, false));
+        id
+    }
+
+    fn cancel(&mut self, id: u64) -> bool {
+        match self.items.iter_mut().find(|i| i.1 == id) {
+            Some(item) => {
+                item.2 = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn reprioritize(&mut self, id: u64, priority: i32) -> bool {
+        match self.items.iter_mut().find(|i| i.1 == id && !i.2) {
+            Some(item) => {
+                item.0 = priority;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn pop_next(&mut self) -> Option<u64> {
+        let (idx, _) = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| !i.2)
+            .min_by_key(|(_, i)| (i.0, i.1))?;
+        Some(self.items.swap_remove(idx).1)
+    }
+}
+
+/// Deterministic pseudo-random priorities in `0..64`, like distance-based tile priorities.
+fn priorities(n: usize, salt: u64) -> Vec<i32> {
+    let mut state = 0x9e37_79b9_7f4a_7c15u64 ^ salt;
+    (0..n)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % 64) as i32
+        })
+        .collect()
+}
+
+fn run_heap(initial: &[i32], moved: &[i32]) -> u64 {
+    let mut q = WorkQueue::new();
+    let ids: Vec<WorkId> = initial.iter().map(|&p| q.push(p, ())).collect();
+    for (i, id) in ids.iter().enumerate().step_by(2) {
+        q.reprioritize(*id, moved[i]);
+    }
+    for id in ids.iter().step_by(4) {
+        q.cancel(*id);
+    }
+    let mut checksum = 0u64;
+    while let Some((id, _, ())) = q.pop_next() {
+        checksum = checksum.wrapping_mul(31).wrapping_add(id.0);
+    }
+    checksum
+}
+
+fn run_vec(initial: &[i32], moved: &[i32]) -> u64 {
+    let mut q = VecQueue::new();
+    let ids: Vec<u64> = initial.iter().map(|&p| q.push(p)).collect();
+    for (i, id) in ids.iter().enumerate().step_by(2) {
+        q.reprioritize(*id, moved[i]);
+    }
+    for id in ids.iter().step_by(4) {
+        q.cancel(*id);
+    }
+    let mut checksum = 0u64;
+    while let Some(id) = q.pop_next() {
+        checksum = checksum.wrapping_mul(31).wrapping_add(id);
+    }
+    checksum
+}
+
+fn time(iterations: u32, mut f: impl FnMut() -> u64) -> Duration {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        black_box(f());
+    }
+    start.elapsed() / iterations
+}
+
+fn main() {
+    println!(
+        "{:>8} {:>14} {:>14} {:>8}",
+        "items", "vec (µs)", "heap (µs)", "speedup"
+    );
+    for n in [100, 1_000, 5_000, 20_000] {
+        let initial = priorities(n, 1);
+        let moved = priorities(n, 2);
+        // Both queues must drain in exactly the same order.
+        assert_eq!(run_heap(&initial, &moved), run_vec(&initial, &moved));
+
+        let iterations = (200_000 / n as u32).max(1);
+        let vec = time(iterations.min(20), || run_vec(&initial, &moved));
+        let heap = time(iterations, || run_heap(&initial, &moved));
+        println!(
+            "{:>8} {:>14.1} {:>14.1} {:>7.1}x",
+            n,
+            vec.as_secs_f64() * 1e6,
+            heap.as_secs_f64() * 1e6,
+            vec.as_secs_f64() / heap.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,233 @@
+use crate::budget::FrameBudget;
+
+/// Tuning for [`BudgetController`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BudgetControllerConfig {
+    /// Frame time to converge to (milliseconds).
+    pub target_frame_ms: f64,
+    pub initial_units: u32,
+    pub min_units: u32,
+    pub max_units: u32,
+    /// Weight of the newest sample in the smoothed frame time (0..=1).
+    pub smoothing: f64,
+    /// Fraction of the relative error corrected per frame.
+    pub gain: f64,
+    /// Largest relative change of the unit count in one frame.
+    pub max_step: f64,
+    /// Relative error below which the unit count is left alone.
+    pub deadband: f64,
+}
+
+impl Default for BudgetControllerConfig {
+    fn default() -> Self {
+        Self {
+            target_frame_ms: 1000.0 / 60.0,
+            initial_units: 64,
+            min_units: 1,
+            max_units: 65_536,
+            smoothing: 0.2,
+            gain: 0.5,
+            max_step: 0.25,
+            deadband: 0.05,
+        }
+    }
+}
+
+/// Adapts the per-frame work budget to measured frame times.
+///
+/// Each [`BudgetController::record_frame`] smooths the frame time and scales the unit count
+/// by a damped, clamped proportional step towards the target. Only `+ - * /` on `f64`
+/// is used, so feeding the same recorded timings yields the same budgets on every platform.
+/// Units are split between named queues by integer weight.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetController {
+    config: BudgetControllerConfig,
+    units: f64,
+    smoothed_ms: Option<f64>,
+    shares: Vec<(&'static str, u32)>,
+}
+
+impl BudgetController {
+    pub fn new(config: BudgetControllerConfig) -> Self {
+        let units = config
+            .initial_units
+            .clamp(config.min_units, config.max_units) as f64;
+        Self {
+            config,
+            units,
+            smoothed_ms: None,
+            shares: Vec::new(),
+        }
+    }
+
+    pub fn config(&self) -> &BudgetControllerConfig {
+        &self.config
+    }
+
+    /// Sets the weight of `queue`; a weight of 0 removes it.
+    pub fn set_share(&mut self, queue: &'static str, weight: u32) {
+        self.shares.retain(|(q, _)| *q != queue);
+        if weight > 0 {
+            self.shares.push((queue, weight));
+            self.shares.sort_by_key(|(q, _)| *q);
+        }
+    }
+
+    /// Feeds the duration of the frame that just finished. Non-finite or negative samples
+    /// are ignored.
+    pub fn record_frame(&mut self, frame_ms: f64) {
+        if !frame_ms.is_finite() || frame_ms < 0.0 {
+            return;
+        }
+        let c = &self.config;
+        let smoothed = match self.smoothed_ms {
+            Some(s) => s + c.smoothing * (frame_ms - s),
+            None => frame_ms,
+        };
+        self.smoothed_ms = Some(smoothed);
+
+        // Relative headroom: positive when frames are faster than the target.
+        let error = (c.target_frame_ms - smoothed) / c.target_frame_ms.max(f64::MIN_POSITIVE);
+        if error.abs() <= c.deadband {
+            return;
+        }
+        let step = (c.gain * error).clamp(-c.max_step, c.max_step);
+        self.units = (self.units * (1.0 + step)).clamp(c.min_units as f64, c.max_units as f64);
+    }
+
+    pub fn smoothed_frame_ms(&self) -> Option<f64> {
+        self.smoothed_ms
+    }
+
+    /// Units available to the whole frame.
+    pub fn units(&self) -> u32 {
+        self.units as u32
+    }
+
+    pub fn budget(&self) -> FrameBudget {
+        FrameBudget::new(self.units())
+    }
+
+    /// Units of each queue, in queue name order. Shares are split by weight with the
+    /// leftover units going to the largest remainders (ties by name), so they sum to
+    /// [`BudgetController::units`].
+    pub fn shares(&self) -> Vec<(&'static str, u32)> {
+        let total_weight: u64 = self.shares.iter().map(|(_, w)| *w as u64).sum();
+        if total_weight == 0 {
+            return Vec::new();
+        }
+        let units = self.units() as u64;
+        let mut out: Vec<(&'static str, u32)> = Vec::with_capacity(self.shares.len());
+        let mut remainders: Vec<(u64, usize)> = Vec::with_capacity(self.shares.len());
+        let mut assigned = 0u64;
+        for (i, &(queue, weight)) in self.shares.iter().enumerate() {
+            let exact = units * weight as u64;
+            let base = exact / total_weight;
+            assigned += base;
+            out.push((queue, base as u32));
+            remainders.push((exact % total_weight, i));
+        }
+        remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        for &(_, i) in remainders.iter().take((units - assigned) as usize) {
+            out[i].1 += 1;
+        }
+        out
+    }
+
+    /// Budget for one queue; empty for queues without a share.
+    pub fn budget_for(&self, queue: &str) -> FrameBudget {
+        let units = self
+            .shares()
+            .into_iter()
+            .find(|(q, _)| *q == queue)
+            .map_or(0, |(_, u)| u);
+        FrameBudget::new(units)
+    }
+}
+
+impl Default for BudgetController {
+    fn default() -> Self {
+        Self::new(BudgetControllerConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BudgetController, BudgetControllerConfig};
+
+    /// Frame time of a device that spends `fixed_ms` per frame plus `ms_per_unit` per unit.
+    fn simulate(
+        controller: &mut BudgetController,
+        fixed_ms: f64,
+        ms_per_unit: f64,
+        frames: usize,
+    ) -> Vec<u32> {
+        (0..frames)
+            .map(|_| {
+                let units = controller.units();
+                controller.record_frame(fixed_ms + units as f64 * ms_per_unit);
+                controller.units()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn converges_to_target_on_fast_and_slow_devices() {
+        for ms_per_unit in [0.01, 0.2] {
+            let mut c = BudgetController::default();
+            let history = simulate(&mut c, 4.0, ms_per_unit, 200);
+            let ideal = (1000.0 / 60.0 - 4.0) / ms_per_unit;
+            let last = *history.last().unwrap() as f64;
+            assert!(
+                (last - ideal).abs() / ideal < 0.1,
+                "{ms_per_unit}: {last} vs {ideal}"
+            );
+            // Settled: no oscillation over the final frames.
+            let tail = &history[180..];
+            let spread = tail.iter().max().unwrap() - tail.iter().min().unwrap();
+            assert!(spread as f64 <= ideal * 0.02);
+        }
+    }
+
+    #[test]
+    fn recorded_timings_replay_identically() {
+        let timings: Vec<f64> = (0..100)
+            .map(|i| 10.0 + (i * 37 % 23) as f64 * 0.7)
+            .collect();
+        let run = || {
+            let mut c = BudgetController::new(BudgetControllerConfig {
+                max_units: 500,
+                ..Default::default()
+            });
+            timings
+                .iter()
+                .map(|&ms| {
+                    c.record_frame(ms);
+                    c.units()
+                })
+                .collect::<Vec<_>>()
+        };
+        let a = run();
+        assert_eq!(a, run());
+        assert!(a.iter().all(|&u| (1..=500).contains(&u)));
+    }
+
+    #[test]
+    fn splits_units_by_weight() {
+        let mut c = BudgetController::new(BudgetControllerConfig {
+            initial_units: 10,
+            ..Default::default()
+        });
+        c.set_share("streaming", 2);
+        c.set_share("compute", 1);
+        c.set_share("labels", 1);
+        assert_eq!(
+            c.shares(),
+            vec![("compute", 3), ("labels", 2), ("streaming", 5)]
+        );
+        assert_eq!(c.budget_for("streaming").remaining_units(), 5);
+        assert_eq!(c.budget_for("missing").remaining_units(), 0);
+        c.set_share("labels", 0);
+        assert_eq!(c.shares(), vec![("compute", 3), ("streaming", 7)]);
+    }
+}
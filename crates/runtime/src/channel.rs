@@ -0,0 +1,341 @@
+//! Typed publish/subscribe event channels.
+//!
+//! Unlike the trace-oriented [`crate::EventBus`], a channel carries one payload type, lets
+//! each subscriber keep its own cursor and topic filter, and retains a bounded window of
+//! history. Events published during a frame are staged and only become readable when the
+//! frame ends, so every subscriber sees whole frames regardless of job order.
+
+use std::any::{Any, TypeId};
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::frame::Frame;
+
+/// Default number of committed events a channel keeps for slow subscribers.
+pub const DEFAULT_CHANNEL_RETENTION: usize = 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Envelope<T> {
+    /// Position in the channel, increasing from 0.
+    pub seq: u64,
+    pub frame_index: u64,
+    /// Slash-separated topic such as `"streaming/tile"`.
+    pub topic: &'static str,
+    pub payload: T,
+}
+
+/// Which topics a subscriber receives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TopicFilter {
+    All,
+    /// Any of these exact topics.
+    Topics(Vec<&'static str>),
+    /// The topic itself and everything below it: `"streaming"` matches `"streaming/tile"`.
+    Prefix(&'static str),
+}
+
+impl TopicFilter {
+    pub fn matches(&self, topic: &str) -> bool {
+        match self {
+            TopicFilter::All => true,
+            TopicFilter::Topics(topics) => topics.contains(&topic),
+            TopicFilter::Prefix(prefix) => topic
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/')),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SubscriberId(pub u32);
+
+#[derive(Debug, Clone)]
+struct Subscriber {
+    /// Next sequence number to deliver.
+    cursor: u64,
+    filter: TopicFilter,
+}
+
+/// Events handed to one subscriber by [`EventChannel::read`].
+#[derive(Debug, PartialEq)]
+pub struct Received<'a, T> {
+    pub events: Vec<&'a Envelope<T>>,
+    /// Events of any topic that were dropped by retention before this subscriber read them.
+    pub missed: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct EventChannel<T> {
+    retention: usize,
+    next_seq: u64,
+    committed: VecDeque<Envelope<T>>,
+    pending: Vec<Envelope<T>>,
+    next_subscriber: u32,
+    subscribers: BTreeMap<SubscriberId, Subscriber>,
+}
+
+impl<T> Default for EventChannel<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_CHANNEL_RETENTION)
+    }
+}
+
+impl<T> EventChannel<T> {
+    /// A channel keeping at most `retention` committed events.
+    pub fn new(retention: usize) -> Self {
+        Self {
+            retention,
+            next_seq: 0,
+            committed: VecDeque::new(),
+            pending: Vec::new(),
+            next_subscriber: 0,
+            subscribers: BTreeMap::new(),
+        }
+    }
+
+    pub fn retention(&self) -> usize {
+        self.retention
+    }
+
+    /// Subscribes to events committed from now on.
+    pub fn subscribe(&mut self, filter: TopicFilter) -> SubscriberId {
+        let id = SubscriberId(self.next_subscriber);
+        self.next_subscriber += 1;
+        self.subscribers.insert(
+            id,
+            Subscriber {
+                cursor: self.committed_end(),
+                filter,
+            },
+        );
+        id
+    }
+
+    pub fn unsubscribe(&mut self, id: SubscriberId) -> bool {
+        self.subscribers.remove(&id).is_some()
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.len()
+    }
+
+    /// Stages an event; it becomes readable at [`EventChannel::end_frame`].
+    pub fn publish(&mut self, frame: Frame, topic: &'static str, payload: T) {
+        self.pending.push(Envelope {
+            seq: self.next_seq,
+            frame_index: frame.index,
+            topic,
+            payload,
+        });
+        self.next_seq += 1;
+    }
+
+    /// Commits the staged events in publish order and applies retention.
+    pub fn end_frame(&mut self) {
+        self.committed.extend(self.pending.drain(..));
+        let excess = self.committed.len().saturating_sub(self.retention);
+        self.committed.drain(..excess);
+    }
+
+    /// Committed events this subscriber has not read yet that match its filter, advancing
+    /// its cursor past everything committed. Unknown subscribers receive nothing.
+    pub fn read(&mut self, id: SubscriberId) -> Received<'_, T> {
+        let end = self.committed_end();
+        let oldest = end - self.committed.len() as u64;
+        let Some(sub) = self.subscribers.get_mut(&id) else {
+            return Received {
+                events: Vec::new(),
+                missed: 0,
+            };
+        };
+        let missed = oldest.saturating_sub(sub.cursor);
+        let start = sub.cursor.max(oldest);
+        sub.cursor = end;
+
+        let filter = &sub.filter;
+        let events = self
+            .committed
+            .range((start - oldest) as usize..)
+            .filter(|e| filter.matches(e.topic))
+            .collect();
+        Received { events, missed }
+    }
+
+    /// Committed events still retained, oldest first.
+    pub fn committed(&self) -> impl Iterator<Item = &Envelope<T>> {
+        self.committed.iter()
+    }
+
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Sequence number of the first staged (not yet committed) event.
+    fn committed_end(&self) -> u64 {
+        self.next_seq - self.pending.len() as u64
+    }
+}
+
+trait AnyChannel: Any + Send + Sync {
+    fn end_frame(&mut self);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Send + Sync + 'static> AnyChannel for EventChannel<T> {
+    fn end_frame(&mut self) {
+        EventChannel::end_frame(self);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// One [`EventChannel`] per payload type, committed together at the end of each frame.
+///
+/// Store it in [`crate::Resources`] so jobs can publish and read through their context.
+#[derive(Default)]
+pub struct EventChannels {
+    channels: BTreeMap<TypeId, Box<dyn AnyChannel>>,
+}
+
+impl EventChannels {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The channel for `T`, created with [`DEFAULT_CHANNEL_RETENTION`] on first use.
+    pub fn channel_mut<T: Send + Sync + 'static>(&mut self) -> &mut EventChannel<T> {
+        self.channels
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(EventChannel::<T>::default()))
+            .as_any_mut()
+            .downcast_mut()
+            .unwrap()
+    }
+
+    pub fn channel<T: Send + Sync + 'static>(&self) -> Option<&EventChannel<T>> {
+        self.channels
+            .get(&TypeId::of::<T>())?
+            .as_any()
+            .downcast_ref()
+    }
+
+    /// Replaces the channel for `T`, e.g. to set a different retention.
+    pub fn insert<T: Send + Sync + 'static>(&mut self, channel: EventChannel<T>) {
+        self.channels.insert(TypeId::of::<T>(), Box::new(channel));
+    }
+
+    pub fn publish<T: Send + Sync + 'static>(
+        &mut self,
+        frame: Frame,
+        topic: &'static str,
+        payload: T,
+    ) {
+        self.channel_mut::<T>().publish(frame, topic, payload);
+    }
+
+    pub fn subscribe<T: Send + Sync + 'static>(&mut self, filter: TopicFilter) -> SubscriberId {
+        self.channel_mut::<T>().subscribe(filter)
+    }
+
+    pub fn read<T: Send + Sync + 'static>(&mut self, id: SubscriberId) -> Received<'_, T> {
+        self.channel_mut::<T>().read(id)
+    }
+
+    pub fn end_frame(&mut self) {
+        for channel in self.channels.values_mut() {
+            channel.end_frame();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EventChannel, EventChannels, TopicFilter};
+    use crate::frame::Frame;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Streaming {
+        Loaded(u32),
+        Failed(u32),
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct SelectionChanged(Vec<u64>);
+
+    #[test]
+    fn delivers_whole_frames_per_subscriber() {
+        let mut ch = EventChannel::new(16);
+        let all = ch.subscribe(TopicFilter::All);
+        let failures = ch.subscribe(TopicFilter::Topics(vec!["streaming/failed"]));
+        let f0 = Frame::new(0, 1.0);
+
+        ch.publish(f0, "streaming/loaded", Streaming::Loaded(1));
+        ch.publish(f0, "streaming/failed", Streaming::Failed(2));
+        assert!(ch.read(all).events.is_empty());
+        ch.end_frame();
+
+        let got: Vec<_> = ch
+            .read(all)
+            .events
+            .iter()
+            .map(|e| e.payload.clone())
+            .collect();
+        assert_eq!(got, vec![Streaming::Loaded(1), Streaming::Failed(2)]);
+        assert!(ch.read(all).events.is_empty());
+
+        ch.publish(f0.next(), "streaming/failed", Streaming::Failed(3));
+        ch.end_frame();
+        let got = ch.read(failures);
+        assert_eq!(got.events.len(), 2);
+        assert_eq!((got.events[1].seq, got.events[1].frame_index), (2, 1));
+
+        // A late subscriber starts at the next event.
+        let late = ch.subscribe(TopicFilter::Prefix("streaming"));
+        assert!(ch.read(late).events.is_empty());
+        assert!(!TopicFilter::Prefix("stream").matches("streaming/failed"));
+    }
+
+    #[test]
+    fn bounded_retention_reports_missed_events() {
+        let mut ch = EventChannel::new(3);
+        let slow = ch.subscribe(TopicFilter::All);
+        let f = Frame::new(0, 1.0);
+        for i in 0..5 {
+            ch.publish(f, "n", i);
+        }
+        ch.end_frame();
+        let got = ch.read(slow);
+        assert_eq!(got.missed, 2);
+        assert_eq!(
+            got.events.iter().map(|e| e.payload).collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+        assert_eq!(ch.committed().count(), 3);
+    }
+
+    #[test]
+    fn registry_keeps_one_channel_per_type() {
+        let mut channels = EventChannels::new();
+        let sel = channels.subscribe::<SelectionChanged>(TopicFilter::All);
+        let tiles = channels.subscribe::<Streaming>(TopicFilter::Prefix("streaming"));
+        let f = Frame::new(4, 1.0);
+        channels.publish(f, "selection", SelectionChanged(vec![7, 9]));
+        channels.publish(f, "streaming/loaded", Streaming::Loaded(5));
+        channels.end_frame();
+
+        let got = channels.read::<SelectionChanged>(sel);
+        assert_eq!(got.events[0].payload, SelectionChanged(vec![7, 9]));
+        assert_eq!(channels.read::<Streaming>(tiles).events.len(), 1);
+        assert_eq!(
+            channels.channel::<Streaming>().unwrap().subscriber_count(),
+            1
+        );
+        assert!(channels.channel::<u8>().is_none());
+    }
+}
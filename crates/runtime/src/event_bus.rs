@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use crate::frame::Frame;
 
 /// Minimal event type for traceability.
@@ -7,7 +9,8 @@ use crate::frame::Frame;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Event {
     pub frame_index: u64,
-    pub kind: &'static str,
+    /// Static for events emitted by code; owned for events read back from a replay log.
+    pub kind: Cow<'static, str>,
     pub message: String,
 }
 
@@ -24,11 +27,16 @@ impl EventBus {
     pub fn emit(&mut self, frame: Frame, kind: &'static str, message: impl Into<String>) {
         self.events.push(Event {
             frame_index: frame.index,
-            kind,
+            kind: Cow::Borrowed(kind),
             message: message.into(),
         });
     }
 
+    /// Appends already-recorded events, e.g. those buffered by a job on a worker thread.
+    pub fn extend(&mut self, events: impl IntoIterator<Item = Event>) {
+        self.events.extend(events);
+    }
+
     pub fn events(&self) -> &[Event] {
         &self.events
     }
@@ -0,0 +1,402 @@
+//! Budgeted async task executor.
+//!
+//! Futures (HTTP fetches, file reads, IndexedDB calls) are registered with an [`Executor`]
+//! and only polled from [`Executor::run_frame`], so async work advances inside the frame
+//! loop and never exceeds the frame's [`FrameBudget`]. Each poll costs the task's work
+//! units. The executor is single-threaded and needs no `Send` futures, so it runs the
+//! same way on wasm and native; wakers are thread-safe, so I/O completing on another
+//! thread can still wake a task.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+
+use crate::budget::FrameBudget;
+use crate::event_bus::EventBus;
+use crate::frame::Frame;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TaskId(pub u64);
+
+impl fmt::Display for TaskId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+type Woken = Arc<Mutex<BTreeSet<TaskId>>>;
+
+struct TaskWaker {
+    id: TaskId,
+    woken: Woken,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        lock(&self.woken).insert(self.id);
+    }
+}
+
+fn lock<T>(m: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    m.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+struct Task {
+    name: &'static str,
+    cost_units: u32,
+    future: Pin<Box<dyn Future<Output = ()>>>,
+    canceled: Arc<AtomicBool>,
+    waker: Waker,
+}
+
+/// Cancels a task from anywhere; the executor drops it at its next frame.
+#[derive(Debug, Clone)]
+pub struct CancelHandle {
+    id: TaskId,
+    canceled: Arc<AtomicBool>,
+}
+
+impl CancelHandle {
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    pub fn cancel(&self) {
+        self.canceled.store(true, Ordering::Release);
+    }
+
+    pub fn is_canceled(&self) -> bool {
+        self.canceled.load(Ordering::Acquire)
+    }
+}
+
+/// Result slot and cancellation handle of a spawned task.
+#[derive(Debug)]
+pub struct TaskHandle<T> {
+    cancel: CancelHandle,
+    output: Arc<Mutex<Option<T>>>,
+}
+
+impl<T> TaskHandle<T> {
+    pub fn id(&self) -> TaskId {
+        self.cancel.id
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+    }
+
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.cancel.clone()
+    }
+
+    /// The task's output, once it completed. Returns `Some` only once.
+    pub fn try_take(&self) -> Option<T> {
+        lock(&self.output).take()
+    }
+}
+
+/// What one [`Executor::run_frame`] did.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct ExecutorRunSummary {
+    pub polled: usize,
+    pub completed: usize,
+    pub canceled: usize,
+    /// Woken tasks left for a later frame because the budget ran out.
+    pub deferred: usize,
+}
+
+/// Polls registered futures within a per-frame budget.
+///
+/// Woken tasks are polled in [`TaskId`] order (spawn order). A task whose cost does not
+/// fit the remaining budget ends the frame's polling, like
+/// [`crate::WorkQueue::pop_next_with_budget`]. Completions and cancellations are
+/// reported on the [`EventBus`] as `task.completed` / `task.canceled` events whose
+/// message is `"<name> #<id>"`.
+#[derive(Default)]
+pub struct Executor {
+    next_id: u64,
+    tasks: BTreeMap<TaskId, Task>,
+    woken: Woken,
+}
+
+impl Executor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `future` with a poll cost of 1 unit.
+    pub fn spawn<T: 'static>(
+        &mut self,
+        name: &'static str,
+        future: impl Future<Output = T> + 'static,
+    ) -> TaskHandle<T> {
+        self.spawn_with_cost(name, 1, future)
+    }
+
+    /// Registers `future`; every poll consumes `cost_units` of the frame budget. The
+    /// task is first polled at the next [`Executor::run_frame`].
+    pub fn spawn_with_cost<T: 'static>(
+        &mut self,
+        name: &'static str,
+        cost_units: u32,
+        future: impl Future<Output = T> + 'static,
+    ) -> TaskHandle<T> {
+        let id = TaskId(self.next_id);
+        self.next_id += 1;
+
+        let output = Arc::new(Mutex::new(None));
+        let slot = Arc::clone(&output);
+        let canceled = Arc::new(AtomicBool::new(false));
+        let waker = Waker::from(Arc::new(TaskWaker {
+            id,
+            woken: Arc::clone(&self.woken),
+        }));
+        self.tasks.insert(
+            id,
+            Task {
+                name,
+                cost_units,
+                future: Box::pin(async move {
+                    let value = future.await;
+                    *lock(&slot) = Some(value);
+                }),
+                canceled: Arc::clone(&canceled),
+                waker,
+            },
+        );
+        lock(&self.woken).insert(id);
+
+        TaskHandle {
+            cancel: CancelHandle { id, canceled },
+            output,
+        }
+    }
+
+    /// Requests cancellation; the task is dropped at the next frame. Returns `false` for
+    /// tasks that already finished.
+    pub fn cancel(&mut self, id: TaskId) -> bool {
+        match self.tasks.get(&id) {
+            Some(task) => {
+                task.canceled.store(true, Ordering::Release);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Tasks that have neither completed nor been dropped after cancellation.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Whether any task is waiting to be polled.
+    pub fn has_woken(&self) -> bool {
+        !lock(&self.woken).is_empty()
+    }
+
+    pub fn run_frame(
+        &mut self,
+        frame: Frame,
+        bus: &mut EventBus,
+        budget: &mut FrameBudget,
+    ) -> ExecutorRunSummary {
+        let mut summary = ExecutorRunSummary::default();
+
+        let canceled: Vec<TaskId> = self
+            .tasks
+            .iter()
+            .filter(|(_, t)| t.canceled.load(Ordering::Acquire))
+            .map(|(id, _)| *id)
+            .collect();
+        for id in canceled {
+            let task = self.tasks.remove(&id).unwrap();
+            bus.emit(frame, "task.canceled", format!("{} {id}", task.name));
+            summary.canceled += 1;
+        }
+
+        // Only tasks woken before this frame are polled; wakes during polling (e.g. a
+        // task yielding) wait for the next frame so a frame always terminates.
+        let ready = std::mem::take(&mut *lock(&self.woken));
+        let mut ready = ready.into_iter();
+        for id in ready.by_ref() {
+            let Some(task) = self.tasks.get_mut(&id) else {
+                continue;
+            };
+            if !budget.try_consume(task.cost_units) {
+                lock(&self.woken).insert(id);
+                summary.deferred += 1;
+                break;
+            }
+            summary.polled += 1;
+            let waker = task.waker.clone();
+            if task
+                .future
+                .as_mut()
+                .poll(&mut Context::from_waker(&waker))
+                .is_ready()
+            {
+                let task = self.tasks.remove(&id).unwrap();
+                bus.emit(frame, "task.completed", format!("{} {id}", task.name));
+                summary.completed += 1;
+            }
+        }
+        let mut woken = lock(&self.woken);
+        for id in ready {
+            if self.tasks.contains_key(&id) {
+                woken.insert(id);
+                summary.deferred += 1;
+            }
+        }
+        summary
+    }
+}
+
+/// Suspends the current task until the next frame, letting long computations spread
+/// over several frames.
+pub fn yield_now() -> impl Future<Output = ()> {
+    let mut yielded = false;
+    std::future::poll_fn(move |cx| {
+        if yielded {
+            Poll::Ready(())
+        } else {
+            yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::future::Future;
+    use std::rc::Rc;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Poll, Waker};
+
+    use super::{Executor, yield_now};
+    use crate::budget::FrameBudget;
+    use crate::event_bus::EventBus;
+    use crate::frame::Frame;
+
+    /// A future completed from outside, like a fetch resolved by the browser.
+    #[derive(Clone, Default)]
+    struct Pending {
+        state: Arc<Mutex<(Option<u32>, Option<Waker>)>>,
+    }
+
+    impl Pending {
+        fn resolve(&self, value: u32) {
+            let mut s = self.state.lock().unwrap();
+            s.0 = Some(value);
+            if let Some(w) = s.1.take() {
+                w.wake();
+            }
+        }
+
+        fn wait(&self) -> impl Future<Output = u32> + 'static {
+            let state = Arc::clone(&self.state);
+            std::future::poll_fn(move |cx| {
+                let mut s = state.lock().unwrap();
+                match s.0 {
+                    Some(v) => Poll::Ready(v),
+                    None => {
+                        s.1 = Some(cx.waker().clone());
+                        Poll::Pending
+                    }
+                }
+            })
+        }
+    }
+
+    #[test]
+    fn completes_woken_tasks_and_reports_events() {
+        let mut ex = Executor::new();
+        let fetch = Pending::default();
+        let handle = ex.spawn("fetch", {
+            let wait = fetch.wait();
+            async move { wait.await * 2 }
+        });
+        let mut bus = EventBus::new();
+        let mut frame = Frame::new(0, 1.0);
+
+        let s = ex.run_frame(frame, &mut bus, &mut FrameBudget::unlimited());
+        assert_eq!((s.polled, s.completed), (1, 0));
+        frame = frame.next();
+        // Not woken: nothing is polled.
+        let s = ex.run_frame(frame, &mut bus, &mut FrameBudget::unlimited());
+        assert_eq!(s.polled, 0);
+
+        fetch.resolve(21);
+        frame = frame.next();
+        let s = ex.run_frame(frame, &mut bus, &mut FrameBudget::unlimited());
+        assert_eq!(s.completed, 1);
+        assert_eq!(handle.try_take(), Some(42));
+        assert_eq!(handle.try_take(), None);
+        assert!(ex.is_empty());
+        let e = &bus.events()[0];
+        assert_eq!(
+            (e.frame_index, &*e.kind, &*e.message),
+            (2, "task.completed", "fetch #0")
+        );
+    }
+
+    #[test]
+    fn polls_within_budget_in_spawn_order() {
+        let mut ex = Executor::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        for (name, cost) in [("a", 2), ("b", 2), ("c", 1)] {
+            let log = Rc::clone(&log);
+            ex.spawn_with_cost(name, cost, async move {
+                log.borrow_mut().push(name);
+                yield_now().await;
+                log.borrow_mut().push(name);
+            });
+        }
+        let mut bus = EventBus::new();
+        let frame = Frame::new(0, 1.0);
+
+        // "b" does not fit; "c" waits behind it even though it would.
+        let s = ex.run_frame(frame, &mut bus, &mut FrameBudget::new(3));
+        assert_eq!((s.polled, s.deferred), (1, 2));
+        ex.run_frame(frame.next(), &mut bus, &mut FrameBudget::new(5));
+        assert_eq!(*log.borrow(), vec!["a", "a", "b", "c"]);
+        ex.run_frame(frame.next().next(), &mut bus, &mut FrameBudget::new(5));
+        assert_eq!(*log.borrow(), vec!["a", "a", "b", "c", "b", "c"]);
+        assert!(ex.is_empty());
+    }
+
+    #[test]
+    fn cancellation_drops_the_future() {
+        let mut ex = Executor::new();
+        let fetch = Pending::default();
+        let handle = ex.spawn("tile", fetch.wait());
+        let other = ex.spawn("other", async { 1 });
+        let cancel = handle.cancel_handle();
+        let mut bus = EventBus::new();
+        let frame = Frame::new(0, 1.0);
+        ex.run_frame(frame, &mut bus, &mut FrameBudget::unlimited());
+        assert!(!ex.cancel(other.id()));
+
+        cancel.cancel();
+        fetch.resolve(7);
+        let s = ex.run_frame(frame.next(), &mut bus, &mut FrameBudget::unlimited());
+        assert_eq!((s.canceled, s.polled), (1, 0));
+        assert_eq!(handle.try_take(), None);
+        assert_eq!(bus.events()[1].kind, "task.canceled");
+        assert!(ex.is_empty());
+    }
+}
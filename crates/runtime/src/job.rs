@@ -1,16 +1,28 @@
+use std::any::Any;
+
 use crate::event_bus::EventBus;
 use crate::frame::Frame;
+use crate::resources::{JobContext, ResourceKey};
+
+/// Job body: a closure that may own state across frames.
+pub type JobRun = Box<dyn FnMut(&mut JobContext<'_>) + Send>;
 
 /// A deterministic unit of work executed by the [`Scheduler`].
 ///
-/// Jobs are run in a stable order based on their `(priority, id)`.
+/// Jobs are run in a stable order based on their `(priority, id)`. Jobs that declare
+/// conflicting resource access (see [`Job::reading`] and [`Job::writing`]) never overlap
+/// and keep that order between them; other jobs may run concurrently.
 pub struct Job {
     pub id: &'static str,
     /// Smaller values run earlier.
     pub priority: i32,
     /// Abstract cost used by frame budgeting.
     pub cost_units: u32,
-    pub run: fn(frame: Frame, bus: &mut EventBus),
+    pub run: JobRun,
+    /// Resources this job only reads.
+    pub reads: Vec<ResourceKey>,
+    /// Resources this job mutates.
+    pub writes: Vec<ResourceKey>,
 }
 
 impl Job {
@@ -19,7 +31,9 @@ impl Job {
             id,
             priority: 0,
             cost_units: 1,
-            run,
+            run: stateless(run),
+            reads: Vec::new(),
+            writes: Vec::new(),
         }
     }
 
@@ -32,7 +46,9 @@ impl Job {
             id,
             priority,
             cost_units: 1,
-            run,
+            run: stateless(run),
+            reads: Vec::new(),
+            writes: Vec::new(),
         }
     }
 
@@ -45,7 +61,9 @@ impl Job {
             id,
             priority: 0,
             cost_units,
-            run,
+            run: stateless(run),
+            reads: Vec::new(),
+            writes: Vec::new(),
         }
     }
 
@@ -59,7 +77,109 @@ impl Job {
             id,
             priority,
             cost_units,
-            run,
+            run: stateless(run),
+            reads: Vec::new(),
+            writes: Vec::new(),
+        }
+    }
+
+    /// A job backed by a closure, which can capture state and reach [`crate::Resources`]
+    /// through its context.
+    pub fn stateful(
+        id: &'static str,
+        run: impl FnMut(&mut JobContext<'_>) + Send + 'static,
+    ) -> Self {
+        Self {
+            id,
+            priority: 0,
+            cost_units: 1,
+            run: Box::new(run),
+            reads: Vec::new(),
+            writes: Vec::new(),
+        }
+    }
+
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn cost(mut self, cost_units: u32) -> Self {
+        self.cost_units = cost_units;
+        self
+    }
+
+    /// Declares a shared read of the abstract resource `name`. This only orders jobs; it
+    /// does not give access to any value in [`crate::Resources`].
+    pub fn reading(mut self, name: &'static str) -> Self {
+        self.reads.push(ResourceKey::named(name));
+        self
+    }
+
+    /// Declares exclusive write access to the abstract resource `name` (see
+    /// [`Job::reading`]).
+    pub fn writing(mut self, name: &'static str) -> Self {
+        self.writes.push(ResourceKey::named(name));
+        self
+    }
+
+    /// Declares a shared borrow of the `T` resource.
+    pub fn reading_resource<T: Any + Send + Sync>(mut self) -> Self {
+        self.reads.push(ResourceKey::of::<T>());
+        self
+    }
+
+    /// Declares an exclusive borrow of the `T` resource.
+    pub fn writing_resource<T: Any + Send + Sync>(mut self) -> Self {
+        self.writes.push(ResourceKey::of::<T>());
+        self
+    }
+
+    /// Whether the two jobs must not run at the same time: one writes a resource the other
+    /// reads or writes.
+    pub fn conflicts_with(&self, other: &Job) -> bool {
+        self.writes
+            .iter()
+            .any(|r| other.writes.contains(r) || other.reads.contains(r))
+            || other.writes.iter().any(|r| self.reads.contains(r))
+    }
+}
+
+fn stateless(run: fn(frame: Frame, bus: &mut EventBus)) -> JobRun {
+    Box::new(move |ctx: &mut JobContext<'_>| run(ctx.frame, ctx.bus))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Job;
+    use crate::event_bus::EventBus;
+    use crate::frame::Frame;
+
+    fn noop(_frame: Frame, _bus: &mut EventBus) {}
+
+    #[test]
+    fn conflicts_only_through_writes() {
+        let reader = Job::new("r", noop).reading("terrain");
+        let other_reader = Job::new("r2", noop).reading("terrain");
+        let writer = Job::new("w", noop).writing("terrain").reading("camera");
+        assert!(!reader.conflicts_with(&other_reader));
+        assert!(reader.conflicts_with(&writer));
+        assert!(writer.conflicts_with(&reader));
+        assert!(writer.conflicts_with(&writer));
+        assert!(!Job::new("n", noop).conflicts_with(&writer));
+    }
+
+    #[test]
+    fn typed_declarations_conflict_by_type_not_name() {
+        struct Terrain;
+        mod other {
+            pub struct Terrain;
         }
+
+        let typed = Job::new("t", noop).writing_resource::<Terrain>();
+        assert!(typed.conflicts_with(&Job::new("r", noop).reading_resource::<Terrain>()));
+        assert!(!typed.conflicts_with(&Job::new("o", noop).writing_resource::<other::Terrain>()));
+        let named = Job::new("n", noop).writing(std::any::type_name::<Terrain>());
+        assert!(!typed.conflicts_with(&named));
     }
 }
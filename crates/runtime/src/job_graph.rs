@@ -0,0 +1,328 @@
+use std::any::Any;
+use std::collections::BTreeSet;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::thread::JoinHandle;
+
+use crate::event_bus::{Event, EventBus};
+use crate::frame::Frame;
+use crate::job::Job;
+use crate::resources::{JobContext, Resources};
+
+/// Dependency DAG over jobs in their canonical run order.
+///
+/// Job `j` depends on every earlier job `i` whose declared resource access conflicts with
+/// it, so any execution that respects the edges observes the same resource state as
+/// running the jobs one after another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobGraph {
+    dependencies: Vec<Vec<usize>>,
+    dependents: Vec<Vec<usize>>,
+}
+
+impl JobGraph {
+    pub fn build(jobs: &[&Job]) -> Self {
+        let mut dependencies = vec![Vec::new(); jobs.len()];
+        let mut dependents = vec![Vec::new(); jobs.len()];
+        for (j, job) in jobs.iter().enumerate() {
+            for (i, earlier) in jobs[..j].iter().enumerate() {
+                if earlier.conflicts_with(job) {
+                    dependencies[j].push(i);
+                    dependents[i].push(j);
+                }
+            }
+        }
+        Self {
+            dependencies,
+            dependents,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.dependencies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dependencies.is_empty()
+    }
+
+    /// Earlier jobs that must finish before job `index` starts.
+    pub fn dependencies(&self, index: usize) -> &[usize] {
+        &self.dependencies[index]
+    }
+
+    pub fn dependents(&self, index: usize) -> &[usize] {
+        &self.dependents[index]
+    }
+}
+
+struct RunState {
+    /// Ready jobs; the lowest canonical index is started first.
+    ready: BTreeSet<usize>,
+    pending: Vec<usize>,
+    finished: usize,
+    events: Vec<Vec<Event>>,
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+/// Everything the workers share for one frame. The scheduler moves its jobs and resources
+/// in and takes them back once every worker has let go of the batch.
+struct Batch {
+    frame: Frame,
+    graph: JobGraph,
+    resources: Resources,
+    jobs: Vec<Mutex<Option<Job>>>,
+    state: Mutex<RunState>,
+    wake: Condvar,
+}
+
+type Task = Box<dyn FnOnce() + Send>;
+
+/// Long-lived worker threads owned by a [`crate::Scheduler`], so a frame does not pay for
+/// spawning threads. Threads exit when the pool is dropped.
+pub(crate) struct WorkerPool {
+    senders: Vec<Sender<Task>>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl WorkerPool {
+    pub(crate) fn new(threads: usize) -> Self {
+        let (senders, threads) = (0..threads.max(1))
+            .map(|i| {
+                let (tx, rx) = mpsc::channel::<Task>();
+                let thread = std::thread::Builder::new()
+                    .name(format!("runtime-worker-{i}"))
+                    .spawn(move || {
+                        while let Ok(task) = rx.recv() {
+                            task();
+                        }
+                    })
+                    .expect("failed to spawn worker thread");
+                (tx, thread)
+            })
+            .unzip();
+        Self { senders, threads }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.senders.len()
+    }
+}
+
+impl Drop for WorkerPool {
+    fn drop(&mut self) {
+        self.senders.clear();
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Moves the jobs and resources of a batch back into the caller's slots once every worker
+/// has let go of it. Runs on drop so they also come back when [`run_parallel`] unwinds.
+struct HandBack<'a> {
+    batch: Option<Arc<Batch>>,
+    jobs: &'a mut Vec<Job>,
+    resources: &'a mut Resources,
+}
+
+impl HandBack<'_> {
+    fn batch(&self) -> &Arc<Batch> {
+        self.batch.as_ref().expect("batch already handed back")
+    }
+}
+
+impl Drop for HandBack<'_> {
+    fn drop(&mut self) {
+        let Some(batch) = self
+            .batch
+            .take()
+            .and_then(|batch| Arc::try_unwrap(batch).ok())
+        else {
+            return;
+        };
+        *self.resources = batch.resources;
+        self.jobs.extend(
+            batch
+                .jobs
+                .into_iter()
+                .filter_map(|slot| slot.into_inner().unwrap_or_else(PoisonError::into_inner)),
+        );
+    }
+}
+
+/// Runs `jobs` on the pool's workers, respecting `graph`, and returns each job's events
+/// (indexed like the jobs) or the payload of the first panic. Each job emits into its own
+/// bus; merging the returned event lists in order gives the same stream on any number of
+/// threads.
+///
+/// `jobs` and `resources` are moved into the batch and put back before this returns, also
+/// when a job panicked or the run itself unwinds, so the caller can restore its state
+/// before resuming the panic.
+pub(crate) fn run_parallel(
+    pool: &WorkerPool,
+    jobs: &mut Vec<Job>,
+    graph: JobGraph,
+    frame: Frame,
+    resources: &mut Resources,
+) -> Result<Vec<Vec<Event>>, Box<dyn Any + Send>> {
+    let count = jobs.len();
+    let batch = Arc::new(Batch {
+        frame,
+        state: Mutex::new(RunState {
+            ready: (0..count)
+                .filter(|&i| graph.dependencies(i).is_empty())
+                .collect(),
+            pending: (0..count).map(|i| graph.dependencies(i).len()).collect(),
+            finished: 0,
+            events: vec![Vec::new(); count],
+            panic: None,
+        }),
+        graph,
+        resources: std::mem::take(resources),
+        jobs: jobs.drain(..).map(|job| Mutex::new(Some(job))).collect(),
+        wake: Condvar::new(),
+    });
+    let hand_back = HandBack {
+        batch: Some(batch),
+        jobs,
+        resources,
+    };
+
+    let (done_tx, done_rx) = mpsc::channel();
+    let workers = pool.len().min(count);
+    for sender in &pool.senders[..workers] {
+        let batch = Arc::clone(hand_back.batch());
+        let done = done_tx.clone();
+        sender
+            .send(Box::new(move || {
+                work(&batch);
+                drop(batch);
+                let _ = done.send(());
+            }))
+            .expect("worker thread exited");
+    }
+    drop(done_tx);
+    for _ in 0..workers {
+        done_rx.recv().expect("worker thread exited");
+    }
+
+    let mut state = hand_back.batch().state.lock().unwrap();
+    match state.panic.take() {
+        Some(payload) => Err(payload),
+        None => Ok(std::mem::take(&mut state.events)),
+    }
+}
+
+/// One worker's share of a batch: start ready jobs until all are done or one panicked.
+fn work(batch: &Batch) {
+    loop {
+        let index = {
+            let mut s = batch.state.lock().unwrap();
+            loop {
+                if s.panic.is_some() || s.finished == batch.jobs.len() {
+                    return;
+                }
+                if let Some(index) = s.ready.pop_first() {
+                    break index;
+                }
+                s = batch.wake.wait(s).unwrap();
+            }
+        };
+
+        let mut job = batch.jobs[index]
+            .lock()
+            .unwrap()
+            .take()
+            .expect("job started twice");
+        let mut bus = EventBus::new();
+        let outcome = catch_unwind(AssertUnwindSafe(|| {
+            crate::trace_span!(job.id, "job");
+            let mut ctx = JobContext::new(
+                batch.frame,
+                &mut bus,
+                job.id,
+                &batch.resources,
+                &job.reads,
+                &job.writes,
+            );
+            (job.run)(&mut ctx);
+        }));
+        *batch.jobs[index].lock().unwrap() = Some(job);
+
+        let mut s = batch.state.lock().unwrap();
+        match outcome {
+            Ok(()) => {
+                s.events[index] = bus.drain();
+                s.finished += 1;
+                for &next in batch.graph.dependents(index) {
+                    s.pending[next] -= 1;
+                    if s.pending[next] == 0 {
+                        s.ready.insert(next);
+                    }
+                }
+            }
+            Err(payload) => {
+                s.panic.get_or_insert(payload);
+            }
+        }
+        batch.wake.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use super::{JobGraph, WorkerPool, run_parallel};
+    use crate::event_bus::EventBus;
+    use crate::frame::Frame;
+    use crate::job::Job;
+    use crate::resources::Resources;
+
+    fn noop(_frame: Frame, _bus: &mut EventBus) {}
+
+    #[test]
+    fn links_conflicting_jobs_in_order() {
+        let jobs = [
+            Job::new("load", noop).writing("tiles"),
+            Job::new("stats", noop).reading("tiles"),
+            Job::new("camera", noop).writing("camera"),
+            Job::new("draw", noop).reading("tiles").reading("camera"),
+            Job::new("evict", noop).writing("tiles"),
+        ];
+        let refs: Vec<&Job> = jobs.iter().collect();
+        let graph = JobGraph::build(&refs);
+        assert_eq!(graph.dependencies(0), &[] as &[usize]);
+        assert_eq!(graph.dependencies(1), &[0]);
+        assert_eq!(graph.dependencies(2), &[] as &[usize]);
+        assert_eq!(graph.dependencies(3), &[0, 2]);
+        assert_eq!(graph.dependencies(4), &[0, 1, 3]);
+        assert_eq!(graph.dependents(0), &[1, 3, 4]);
+    }
+
+    #[test]
+    fn jobs_and_resources_come_back_when_the_run_unwinds() {
+        // A worker whose thread is gone makes the run panic outside any job.
+        let (sender, receiver) = mpsc::channel();
+        drop(receiver);
+        let pool = WorkerPool {
+            senders: vec![sender],
+            threads: Vec::new(),
+        };
+        let mut jobs = vec![Job::new("a", noop), Job::new("b", noop)];
+        let refs: Vec<&Job> = jobs.iter().collect();
+        let graph = JobGraph::build(&refs);
+        let mut resources = Resources::new();
+        resources.insert(7_u32);
+
+        let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            run_parallel(&pool, &mut jobs, graph, Frame::new(0, 1.0), &mut resources)
+        }));
+        assert!(outcome.is_err());
+        let ids: Vec<&str> = jobs.iter().map(|job| job.id).collect();
+        assert_eq!(ids, ["a", "b"]);
+        assert_eq!(*resources.read::<u32>(), 7);
+    }
+}
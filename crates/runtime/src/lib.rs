@@ -1,14 +1,27 @@
 pub mod budget;
+pub mod budget_controller;
+pub mod channel;
 pub mod event_bus;
+pub mod executor;
 pub mod frame;
 pub mod job;
+pub mod job_graph;
 pub mod metrics;
+pub mod replay;
+pub mod resources;
 pub mod scheduler;
+pub mod trace;
 pub mod work_queue;
 
 pub use budget::*;
+pub use budget_controller::*;
+pub use channel::*;
 pub use event_bus::*;
+pub use executor::*;
 pub use frame::*;
 pub use job::*;
+pub use job_graph::*;
+pub use replay::*;
+pub use resources::*;
 pub use scheduler::*;
 pub use work_queue::*;
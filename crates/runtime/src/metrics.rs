@@ -9,17 +9,65 @@ pub struct Metrics {
     counters: BTreeMap<String, u64>,
     gauges: BTreeMap<String, i64>,
     histograms: BTreeMap<String, Histogram>,
+    /// Bucket layouts from `register_histogram`, by metric name (without labels).
+    histogram_buckets: BTreeMap<String, Vec<i64>>,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+/// Default histogram bucket upper bounds: a 1-2-5 series from 1 to 10^7 (for example
+/// microseconds up to ten seconds).
+pub const DEFAULT_HISTOGRAM_BUCKETS: [i64; 22] = [
+    1, 2, 5, 10, 20, 50, 100, 200, 500, 1_000, 2_000, 5_000, 10_000, 20_000, 50_000, 100_000,
+    200_000, 500_000, 1_000_000, 2_000_000, 5_000_000, 10_000_000,
+];
+
+/// Bucketed histogram of `i64` samples.
+///
+/// Bucket `i` counts samples in `(bounds[i - 1], bounds[i]]`; one extra overflow bucket
+/// counts samples above the last bound.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Histogram {
     pub count: u64,
     pub sum: i64,
     pub min: i64,
     pub max: i64,
+    bounds: Vec<i64>,
+    counts: Vec<u64>,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self::with_buckets(DEFAULT_HISTOGRAM_BUCKETS.to_vec())
+    }
 }
 
 impl Histogram {
+    /// A histogram with the given bucket upper bounds (sorted and deduplicated).
+    pub fn with_buckets(mut bounds: Vec<i64>) -> Self {
+        bounds.sort_unstable();
+        bounds.dedup();
+        let counts = vec![0; bounds.len() + 1];
+        Self {
+            count: 0,
+            sum: 0,
+            min: 0,
+            max: 0,
+            bounds,
+            counts,
+        }
+    }
+
+    /// `count` bounds starting at `start` and growing by `factor`, rounded to integers.
+    pub fn exponential_buckets(start: i64, factor: f64, count: usize) -> Vec<i64> {
+        let mut bound = start as f64;
+        (0..count)
+            .map(|_| {
+                let b = bound.round() as i64;
+                bound *= factor;
+                b
+            })
+            .collect()
+    }
+
     pub fn record(&mut self, value: i64) {
         if self.count == 0 {
             self.min = value;
@@ -30,9 +78,80 @@ impl Histogram {
         }
         self.count += 1;
         self.sum += value;
+        let bucket = self.bounds.partition_point(|&b| b < value);
+        self.counts[bucket] += 1;
+    }
+
+    pub fn bounds(&self) -> &[i64] {
+        &self.bounds
+    }
+
+    /// Per-bucket (non-cumulative) counts; the last entry is the overflow bucket.
+    pub fn bucket_counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// Estimates the `q` quantile (0..=1) by interpolating linearly inside the bucket that
+    /// holds it, with the outer edges clamped to the observed min and max.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        if self.count == 0 {
+            return None;
+        }
+        let rank = q.clamp(0.0, 1.0) * self.count as f64;
+        let mut below = 0u64;
+        for (i, &n) in self.counts.iter().enumerate() {
+            if n > 0 && (below + n) as f64 >= rank {
+                let lower = if i == 0 {
+                    self.min
+                } else {
+                    self.bounds[i - 1].max(self.min)
+                };
+                let upper = self.bounds.get(i).map_or(self.max, |&b| b.min(self.max));
+                let frac = ((rank - below as f64) / n as f64).clamp(0.0, 1.0);
+                return Some(lower as f64 + (upper - lower) as f64 * frac);
+            }
+            below += n;
+        }
+        Some(self.max as f64)
+    }
+
+    pub fn p50(&self) -> Option<f64> {
+        self.quantile(0.5)
+    }
+
+    pub fn p95(&self) -> Option<f64> {
+        self.quantile(0.95)
+    }
+
+    pub fn p99(&self) -> Option<f64> {
+        self.quantile(0.99)
     }
 }
 
+/// Key of a labelled metric: `name{k="v",...}` with labels sorted by name and values
+/// escaped as in the Prometheus text format. Without labels the key is just `name`.
+pub fn metric_key(name: &str, labels: &[(&str, &str)]) -> String {
+    if labels.is_empty() {
+        return name.to_string();
+    }
+    let mut labels = labels.to_vec();
+    labels.sort();
+    let body: Vec<String> = labels
+        .iter()
+        .map(|(k, v)| format!("{}=\"{}\"", sanitize_name(k, false), escape_label_value(v)))
+        .collect();
+    format!("{name}{{{}}}", body.join(","))
+}
+
+/// Text exposition format for [`MetricsSnapshot::encode`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExpositionFormat {
+    /// Prometheus text format 0.0.4.
+    Prometheus,
+    /// OpenMetrics 1.0: counters get a `_total` suffix and the output ends with `# EOF`.
+    OpenMetrics,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MetricsSnapshot {
     pub counters: Vec<(String, u64)>,
@@ -45,6 +164,7 @@ impl Metrics {
         Self::default()
     }
 
+    /// Drops all recorded values. Registered histogram layouts are kept.
     pub fn clear(&mut self) {
         self.counters.clear();
         self.gauges.clear();
@@ -68,15 +188,48 @@ impl Metrics {
         self.gauges.insert(name.into(), value);
     }
 
+    pub fn inc_counter_with(&mut self, name: &str, labels: &[(&str, &str)], by: u64) {
+        self.inc_counter(metric_key(name, labels), by);
+    }
+
+    pub fn set_gauge_with(&mut self, name: &str, labels: &[(&str, &str)], value: i64) {
+        self.set_gauge(metric_key(name, labels), value);
+    }
+
+    /// Sets the bucket layout of histogram `name` and of every labelled series of it,
+    /// discarding samples recorded so far. Series are created on their first record.
+    pub fn register_histogram(&mut self, name: impl Into<String>, bounds: Vec<i64>) {
+        let name = name.into();
+        let layout = Histogram::with_buckets(bounds);
+        for (key, h) in &mut self.histograms {
+            if metric_name(key) == name {
+                *h = layout.clone();
+            }
+        }
+        self.histogram_buckets
+            .insert(name, layout.bounds().to_vec());
+    }
+
+    pub fn record_histogram_with(&mut self, name: &str, labels: &[(&str, &str)], value: i64) {
+        self.record_histogram(metric_key(name, labels), value);
+    }
+
+    /// Records into the histogram with key `name` (plain or labelled), created with the
+    /// bucket layout registered for its metric name or the default one.
     pub fn record_histogram(&mut self, name: impl Into<String>, value: i64) {
+        let key = name.into();
+        let buckets = &self.histogram_buckets;
         self.histograms
-            .entry(name.into())
-            .or_default()
+            .entry(key)
+            .or_insert_with_key(|key| match buckets.get(metric_name(key)) {
+                Some(bounds) => Histogram::with_buckets(bounds.clone()),
+                None => Histogram::default(),
+            })
             .record(value);
     }
 
     pub fn histogram(&self, name: &str) -> Option<Histogram> {
-        self.histograms.get(name).copied()
+        self.histograms.get(name).cloned()
     }
 
     /// Returns a stable, sorted snapshot suitable for logs/debug UI.
@@ -87,15 +240,118 @@ impl Metrics {
             histograms: self
                 .histograms
                 .iter()
-                .map(|(k, v)| (k.clone(), *v))
+                .map(|(k, v)| (k.clone(), v.clone()))
                 .collect(),
         }
     }
+
+    pub fn encode(&self, format: ExpositionFormat) -> String {
+        self.snapshot().encode(format)
+    }
+}
+
+impl MetricsSnapshot {
+    /// Renders the snapshot in a Prometheus/OpenMetrics text format, one family per metric
+    /// name in name order. Names are sanitized to `[a-zA-Z_:][a-zA-Z0-9_:]*`.
+    pub fn encode(&self, format: ExpositionFormat) -> String {
+        let open_metrics = format == ExpositionFormat::OpenMetrics;
+        let mut families: BTreeMap<String, (&str, Vec<String>)> = BTreeMap::new();
+
+        for (key, value) in &self.counters {
+            let (name, labels) = split_key(key);
+            let suffix = if open_metrics { "_total" } else { "" };
+            family(&mut families, &name, "counter").push(format!("{name}{suffix}{labels} {value}"));
+        }
+        for (key, value) in &self.gauges {
+            let (name, labels) = split_key(key);
+            family(&mut families, &name, "gauge").push(format!("{name}{labels} {value}"));
+        }
+        for (key, h) in &self.histograms {
+            let (name, labels) = split_key(key);
+            let lines = family(&mut families, &name, "histogram");
+            let mut cumulative = 0u64;
+            for (bound, n) in h.bounds().iter().zip(h.bucket_counts()) {
+                cumulative += n;
+                let le = with_label(labels, "le", &bound.to_string());
+                lines.push(format!("{name}_bucket{le} {cumulative}"));
+            }
+            let le = with_label(labels, "le", "+Inf");
+            lines.push(format!("{name}_bucket{le} {}", h.count));
+            lines.push(format!("{name}_sum{labels} {}", h.sum));
+            lines.push(format!("{name}_count{labels} {}", h.count));
+        }
+
+        let mut out = String::new();
+        for (name, (kind, lines)) in &families {
+            out.push_str(&format!("# TYPE {name} {kind}\n"));
+            for line in lines {
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+        if open_metrics {
+            out.push_str("# EOF\n");
+        }
+        out
+    }
+}
+
+fn family<'a>(
+    families: &'a mut BTreeMap<String, (&'static str, Vec<String>)>,
+    name: &str,
+    kind: &'static str,
+) -> &'a mut Vec<String> {
+    &mut families
+        .entry(name.to_string())
+        .or_insert_with(|| (kind, Vec::new()))
+        .1
+}
+
+/// The metric name of a `name{labels}` key.
+fn metric_name(key: &str) -> &str {
+    key.split_once('{').map_or(key, |(name, _)| name)
+}
+
+/// Splits a [`metric_key`] into its sanitized name and its `{...}` label set.
+fn split_key(key: &str) -> (String, &str) {
+    let (name, labels) = key.split_at(key.find('{').unwrap_or(key.len()));
+    (sanitize_name(name, true), labels)
+}
+
+fn with_label(labels: &str, name: &str, value: &str) -> String {
+    match labels.strip_prefix('{').and_then(|l| l.strip_suffix('}')) {
+        Some(inner) => format!("{{{inner},{name}=\"{value}\"}}"),
+        None => format!("{{{name}=\"{value}\"}}"),
+    }
+}
+
+fn sanitize_name(name: &str, allow_colon: bool) -> String {
+    let mut out: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || (allow_colon && c == ':') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+    out
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Histogram, Metrics};
+    use super::{DEFAULT_HISTOGRAM_BUCKETS, ExpositionFormat, Histogram, Metrics};
 
     #[test]
     fn counters_accumulate() {
@@ -150,4 +406,87 @@ mod tests {
         assert_eq!(snap.histograms[0].0, "h1".to_string());
         assert_eq!(snap.histograms[1].0, "h2".to_string());
     }
+
+    #[test]
+    fn buckets_estimate_quantiles() {
+        let mut h = Histogram::with_buckets(vec![10, 20, 50, 100]);
+        for v in 1..=100 {
+            h.record(v);
+        }
+        assert_eq!(h.bucket_counts(), &[10, 10, 30, 50, 0]);
+        assert_eq!(h.p50(), Some(50.0));
+        assert_eq!(h.p95(), Some(95.0));
+        assert_eq!(h.quantile(0.0), Some(1.0));
+        assert_eq!(h.quantile(1.0), Some(100.0));
+
+        // Samples beyond the last bound interpolate towards the observed max.
+        let mut h = Histogram::with_buckets(Histogram::exponential_buckets(1, 2.0, 4));
+        assert_eq!(h.bounds(), &[1, 2, 4, 8]);
+        assert_eq!(h.p99(), None);
+        h.record(7);
+        h.record(1_000);
+        assert_eq!(h.bucket_counts(), &[0, 0, 0, 1, 1]);
+        assert_eq!(h.quantile(1.0), Some(1_000.0));
+        assert_eq!(h.quantile(0.25), Some(7.5));
+    }
+
+    #[test]
+    fn exports_labelled_metrics_as_text() {
+        let mut m = Metrics::new();
+        m.inc_counter_with("tiles.loaded", &[("source", "osm"), ("kind", "vector")], 3);
+        m.inc_counter("tiles.loaded", 1);
+        m.set_gauge_with("queue_depth", &[("queue", "say \"hi\"")], 7);
+        m.register_histogram("frame_us", vec![1_000, 16_667]);
+        m.record_histogram("frame_us", 900);
+        m.record_histogram("frame_us", 12_000);
+        m.record_histogram("frame_us", 40_000);
+
+        assert_eq!(
+            m.encode(ExpositionFormat::Prometheus),
+            "# TYPE frame_us histogram
+frame_us_bucket{le=\"1000\"} 1
+frame_us_bucket{le=\"16667\"} 2
+frame_us_bucket{le=\"+Inf\"} 3
+frame_us_sum 52900
+frame_us_count 3
+# TYPE queue_depth gauge
+queue_depth{queue=\"say \\\"hi\\\"\"} 7
+# TYPE tiles_loaded counter
+tiles_loaded 1
+tiles_loaded{kind=\"vector\",source=\"osm\"} 3
+"
+        );
+        let om = m.encode(ExpositionFormat::OpenMetrics);
+        assert!(om.contains("tiles_loaded_total{kind=\"vector\",source=\"osm\"} 3\n"));
+        assert!(om.ends_with("# EOF\n"));
+    }
+
+    #[test]
+    fn labelled_histograms_use_registered_buckets() {
+        let mut m = Metrics::new();
+        m.record_histogram_with("fetch_ms", &[("source", "osm")], 3);
+        m.register_histogram("fetch_ms", vec![10, 100]);
+        m.record_histogram_with("fetch_ms", &[("source", "osm")], 50);
+        m.record_histogram_with("fetch_ms", &[("source", "dem")], 500);
+        m.record_histogram_with("fetch_ms_total", &[("source", "osm")], 5);
+
+        // Registering alone does not add an empty unlabelled series.
+        assert!(m.histogram("fetch_ms").is_none());
+        m.record_histogram("fetch_ms", 7);
+        for key in [
+            "fetch_ms",
+            "fetch_ms{source=\"dem\"}",
+            "fetch_ms{source=\"osm\"}",
+        ] {
+            assert_eq!(m.histogram(key).unwrap().bounds(), &[10, 100], "{key}");
+        }
+        assert_eq!(m.histogram("fetch_ms{source=\"osm\"}").unwrap().count, 1);
+        let other = m.histogram("fetch_ms_total{source=\"osm\"}").unwrap();
+        assert_eq!(other.bounds(), &DEFAULT_HISTOGRAM_BUCKETS);
+
+        m.clear();
+        m.record_histogram_with("fetch_ms", &[("source", "osm")], 1);
+        let h = m.histogram("fetch_ms{source=\"osm\"}").unwrap();
+        assert_eq!(h.bounds(), &[10, 100]);
+    }
 }
@@ -0,0 +1,556 @@
+//! Frame recording and deterministic replay.
+//!
+//! A [`ReplayLog`] holds everything that enters the engine from outside, frame by frame
+//! (fixed timestep, budget, injected events, streaming arrivals, user commands), plus a hash
+//! of the semantic state after each frame. [`replay`] re-drives a scheduler from the log and
+//! stops at the first frame whose state hash differs.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::hash::Hasher;
+use std::path::Path;
+
+use foundation::time::Time;
+
+use crate::budget::FrameBudget;
+use crate::event_bus::{Event, EventBus};
+use crate::frame::Frame;
+use crate::resources::Resources;
+use crate::scheduler::{BudgetRunSummary, Scheduler};
+
+const MAGIC: [u8; 4] = *b"ATRP";
+const VERSION_V1: u16 = 1;
+const VERSION_LATEST: u16 = VERSION_V1;
+
+const TAG_EVENT: u8 = 1;
+const TAG_ARRIVAL: u8 = 2;
+const TAG_COMMAND: u8 = 3;
+
+/// One external input to a frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordedInput {
+    /// An event injected into the frame's bus before jobs run.
+    Event { kind: String, message: String },
+    /// Streamed data that arrived for `key` (tile, chunk, ...).
+    Arrival { key: String, payload: Vec<u8> },
+    /// A user command such as a camera move or selection.
+    Command { name: String, args: String },
+}
+
+/// Everything needed to run one frame again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameInput {
+    pub frame: Frame,
+    pub budget_units: u32,
+    pub inputs: Vec<RecordedInput>,
+}
+
+impl FrameInput {
+    pub fn new(frame: Frame) -> Self {
+        Self {
+            frame,
+            budget_units: u32::MAX,
+            inputs: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameRecord {
+    pub input: FrameInput,
+    /// [`ReplayHost::state_hash`] after the frame ran.
+    pub state_hash: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ReplayLog {
+    pub frames: Vec<FrameRecord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    UnexpectedEof,
+    Io {
+        source: String,
+    },
+    InvalidMagic,
+    UnsupportedVersion {
+        found: u16,
+    },
+    InvalidUtf8,
+    InvalidTag {
+        tag: u8,
+    },
+    /// Replay diverged from the recording.
+    StateMismatch {
+        frame_index: u64,
+        expected: u64,
+        found: u64,
+    },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::UnexpectedEof => write!(f, "unexpected EOF"),
+            ReplayError::Io { source } => write!(f, "I/O error: {source}"),
+            ReplayError::InvalidMagic => write!(f, "invalid ATRP magic"),
+            ReplayError::UnsupportedVersion { found } => {
+                write!(f, "unsupported ATRP version: {found}")
+            }
+            ReplayError::InvalidUtf8 => write!(f, "invalid utf-8"),
+            ReplayError::InvalidTag { tag } => write!(f, "invalid input tag: {tag}"),
+            ReplayError::StateMismatch {
+                frame_index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "state hash mismatch at frame {frame_index}: expected {expected:016x}, found {found:016x}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+/// Connects recorded inputs to engine state.
+pub trait ReplayHost {
+    /// Applies an arrival or command before the frame's jobs run. Events never reach this;
+    /// they are injected into the bus.
+    fn apply_input(&mut self, input: &RecordedInput, resources: &mut Resources);
+
+    /// Fingerprint of the semantic state, typically built with [`StateHasher`].
+    fn state_hash(&self, resources: &Resources) -> u64;
+}
+
+/// Runs one frame from its inputs: arrivals and commands go to `host`, events into `bus`,
+/// then the scheduler runs within the recorded budget. Recording and replay share this
+/// path so they cannot drift apart.
+pub fn run_frame_input(
+    scheduler: &mut Scheduler,
+    resources: &mut Resources,
+    host: &mut impl ReplayHost,
+    input: &FrameInput,
+    bus: &mut EventBus,
+) -> BudgetRunSummary {
+    for item in &input.inputs {
+        match item {
+            RecordedInput::Event { kind, message } => bus.extend([Event {
+                frame_index: input.frame.index,
+                kind: Cow::Owned(kind.clone()),
+                message: message.clone(),
+            }]),
+            _ => host.apply_input(item, resources),
+        }
+    }
+    let mut budget = FrameBudget::new(input.budget_units);
+    scheduler.run_frame_with_resources(input.frame, resources, bus, &mut budget)
+}
+
+/// Builds a [`ReplayLog`] while frames run.
+#[derive(Debug, Default)]
+pub struct Recorder {
+    log: ReplayLog,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs the frame through [`run_frame_input`] and records it with the resulting state hash.
+    pub fn run_frame(
+        &mut self,
+        scheduler: &mut Scheduler,
+        resources: &mut Resources,
+        host: &mut impl ReplayHost,
+        input: FrameInput,
+        bus: &mut EventBus,
+    ) -> BudgetRunSummary {
+        let summary = run_frame_input(scheduler, resources, host, &input, bus);
+        let state_hash = host.state_hash(resources);
+        self.log.frames.push(FrameRecord { input, state_hash });
+        summary
+    }
+
+    pub fn log(&self) -> &ReplayLog {
+        &self.log
+    }
+
+    pub fn into_log(self) -> ReplayLog {
+        self.log
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayReport {
+    pub frames: usize,
+    /// Every event of the replayed frames, in order.
+    pub events: Vec<Event>,
+}
+
+/// Re-runs `log` against a scheduler and resources in the recording's initial state,
+/// checking the state hash after every frame.
+pub fn replay(
+    log: &ReplayLog,
+    scheduler: &mut Scheduler,
+    resources: &mut Resources,
+    host: &mut impl ReplayHost,
+) -> Result<ReplayReport, ReplayError> {
+    let mut bus = EventBus::new();
+    for record in &log.frames {
+        run_frame_input(scheduler, resources, host, &record.input, &mut bus);
+        let found = host.state_hash(resources);
+        if found != record.state_hash {
+            return Err(ReplayError::StateMismatch {
+                frame_index: record.input.frame.index,
+                expected: record.state_hash,
+                found,
+            });
+        }
+    }
+    Ok(ReplayReport {
+        frames: log.frames.len(),
+        events: bus.drain(),
+    })
+}
+
+impl ReplayLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Little-endian binary encoding, versioned by the header.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&MAGIC);
+        out.extend_from_slice(&VERSION_LATEST.to_le_bytes());
+        // flags (reserved)
+        out.extend_from_slice(&0u16.to_le_bytes());
+        out.extend_from_slice(&(self.frames.len() as u32).to_le_bytes());
+        for record in &self.frames {
+            let input = &record.input;
+            out.extend_from_slice(&input.frame.index.to_le_bytes());
+            out.extend_from_slice(&input.frame.dt_s.to_bits().to_le_bytes());
+            out.extend_from_slice(&input.frame.time.0.to_bits().to_le_bytes());
+            out.extend_from_slice(&input.budget_units.to_le_bytes());
+            out.extend_from_slice(&record.state_hash.to_le_bytes());
+            out.extend_from_slice(&(input.inputs.len() as u32).to_le_bytes());
+            for item in &input.inputs {
+                let (tag, a, b) = match item {
+                    RecordedInput::Event { kind, message } => {
+                        (TAG_EVENT, kind.as_bytes(), message.as_bytes())
+                    }
+                    RecordedInput::Arrival { key, payload } => {
+                        (TAG_ARRIVAL, key.as_bytes(), payload.as_slice())
+                    }
+                    RecordedInput::Command { name, args } => {
+                        (TAG_COMMAND, name.as_bytes(), args.as_bytes())
+                    }
+                };
+                out.push(tag);
+                push_bytes(&mut out, a);
+                push_bytes(&mut out, b);
+            }
+        }
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ReplayError> {
+        let mut r = Reader { bytes, pos: 0 };
+        if r.take(4)? != MAGIC {
+            return Err(ReplayError::InvalidMagic);
+        }
+        let version = u16::from_le_bytes(r.array()?);
+        if version != VERSION_V1 {
+            return Err(ReplayError::UnsupportedVersion { found: version });
+        }
+        let _flags = u16::from_le_bytes(r.array()?);
+
+        let frame_count = u32::from_le_bytes(r.array()?);
+        let mut frames = Vec::new();
+        for _ in 0..frame_count {
+            let frame = Frame {
+                index: u64::from_le_bytes(r.array()?),
+                dt_s: f64::from_bits(u64::from_le_bytes(r.array()?)),
+                time: Time(f64::from_bits(u64::from_le_bytes(r.array()?))),
+            };
+            let budget_units = u32::from_le_bytes(r.array()?);
+            let state_hash = u64::from_le_bytes(r.array()?);
+            let input_count = u32::from_le_bytes(r.array()?);
+            let mut inputs = Vec::new();
+            for _ in 0..input_count {
+                let tag = r.take(1)?[0];
+                inputs.push(match tag {
+                    TAG_EVENT => RecordedInput::Event {
+                        kind: r.string()?,
+                        message: r.string()?,
+                    },
+                    TAG_ARRIVAL => RecordedInput::Arrival {
+                        key: r.string()?,
+                        payload: r.bytes()?.to_vec(),
+                    },
+                    TAG_COMMAND => RecordedInput::Command {
+                        name: r.string()?,
+                        args: r.string()?,
+                    },
+                    tag => return Err(ReplayError::InvalidTag { tag }),
+                });
+            }
+            frames.push(FrameRecord {
+                input: FrameInput {
+                    frame,
+                    budget_units,
+                    inputs,
+                },
+                state_hash,
+            });
+        }
+        Ok(Self { frames })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ReplayError> {
+        std::fs::write(path, self.to_bytes()).map_err(|e| ReplayError::Io {
+            source: e.to_string(),
+        })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        let bytes = std::fs::read(path).map_err(|e| ReplayError::Io {
+            source: e.to_string(),
+        })?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+fn push_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    out.extend_from_slice(bytes);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], ReplayError> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or(ReplayError::UnexpectedEof)?;
+        let out = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(out)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ReplayError> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], ReplayError> {
+        let len = u32::from_le_bytes(self.array()?) as usize;
+        self.take(len)
+    }
+
+    fn string(&mut self) -> Result<String, ReplayError> {
+        let bytes = self.bytes()?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| ReplayError::InvalidUtf8)
+    }
+}
+
+/// FNV-1a (64-bit) hasher for state fingerprints.
+///
+/// Unlike `DefaultHasher` it is stable across releases and processes, and integers are
+/// hashed little-endian at fixed width (`usize` as `u64`), so `Hash` impls give the same
+/// value on every platform.
+#[derive(Debug, Clone, Copy)]
+pub struct StateHasher(u64);
+
+impl StateHasher {
+    pub fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Default for StateHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for StateHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = (self.0 ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16);
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32);
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128);
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_u64(i as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::{Hash, Hasher};
+
+    use super::{
+        FrameInput, RecordedInput, Recorder, ReplayError, ReplayHost, ReplayLog, StateHasher,
+        replay,
+    };
+    use crate::event_bus::EventBus;
+    use crate::frame::Frame;
+    use crate::job::Job;
+    use crate::resources::Resources;
+    use crate::scheduler::Scheduler;
+
+    #[derive(Default)]
+    struct Tiles(Vec<String>);
+
+    struct Host;
+
+    impl ReplayHost for Host {
+        fn apply_input(&mut self, input: &RecordedInput, resources: &mut Resources) {
+            if let RecordedInput::Arrival { key, .. } = input {
+                resources.get_mut::<Tiles>().unwrap().0.push(key.clone());
+            }
+        }
+
+        fn state_hash(&self, resources: &Resources) -> u64 {
+            let mut h = StateHasher::new();
+            resources.read::<Tiles>().0.hash(&mut h);
+            h.finish()
+        }
+    }
+
+    fn scheduler(extra_tile: bool) -> Scheduler {
+        let mut sched = Scheduler::with_worker_threads(2);
+        sched.add_job(
+            Job::stateful("evict", move |ctx| {
+                let mut tiles = ctx.write::<Tiles>();
+                if tiles.0.len() > 2 {
+                    tiles.0.remove(0);
+                }
+                if extra_tile && ctx.frame.index == 2 {
+                    tiles.0.push("ghost".into());
+                }
+            })
+            .writing_resource::<Tiles>(),
+        );
+        sched.add_job(Job::stateful("count", |ctx| {
+            ctx.emit("frame", ctx.frame.index.to_string());
+        }));
+        sched
+    }
+
+    fn record() -> ReplayLog {
+        let mut sched = scheduler(false);
+        let mut resources = Resources::new();
+        resources.insert(Tiles::default());
+        let mut recorder = Recorder::new();
+        let mut bus = EventBus::new();
+        let mut frame = Frame::new(0, 1.0 / 60.0);
+        for i in 0..4 {
+            let mut input = FrameInput::new(frame);
+            input.inputs.push(RecordedInput::Arrival {
+                key: format!("tile/{i}"),
+                payload: vec![i as u8; 3],
+            });
+            input.inputs.push(RecordedInput::Event {
+                kind: "input".into(),
+                message: format!("click {i}"),
+            });
+            recorder.run_frame(&mut sched, &mut resources, &mut Host, input, &mut bus);
+            frame = frame.next();
+        }
+        recorder.into_log()
+    }
+
+    #[test]
+    fn replays_a_recording_through_its_binary_form() {
+        let log = record();
+        let decoded = ReplayLog::from_bytes(&log.to_bytes()).unwrap();
+        assert_eq!(decoded, log);
+
+        let mut resources = Resources::new();
+        resources.insert(Tiles::default());
+        let report = replay(&decoded, &mut scheduler(false), &mut resources, &mut Host).unwrap();
+        assert_eq!(report.frames, 4);
+        assert_eq!(report.events.len(), 8);
+        assert_eq!(report.events[0].kind, "input");
+        assert_eq!(report.events[7].message, "3");
+        assert_eq!(resources.read::<Tiles>().0, vec!["tile/2", "tile/3"]);
+    }
+
+    #[test]
+    fn reports_the_first_divergent_frame() {
+        let log = record();
+        let mut resources = Resources::new();
+        resources.insert(Tiles::default());
+        let err = replay(&log, &mut scheduler(true), &mut resources, &mut Host).unwrap_err();
+        assert!(matches!(
+            err,
+            ReplayError::StateMismatch { frame_index: 2, .. }
+        ));
+
+        let bytes = log.to_bytes();
+        assert_eq!(
+            ReplayLog::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(ReplayError::UnexpectedEof)
+        );
+        assert_eq!(
+            ReplayLog::from_bytes(b"ATVC\x01\x00"),
+            Err(ReplayError::InvalidMagic)
+        );
+    }
+
+    #[test]
+    fn state_hash_is_fixed() {
+        let mut h = StateHasher::new();
+        (42usize, "tile", -1i32).hash(&mut h);
+        assert_eq!(h.finish(), 0xc918_b0a1_7320_aa88);
+    }
+}
@@ -0,0 +1,290 @@
+use std::any::{Any, TypeId, type_name};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+
+use crate::event_bus::EventBus;
+use crate::frame::Frame;
+
+type Slot = RwLock<Box<dyn Any + Send + Sync>>;
+
+/// A resource a job declares access to: a type stored in [`Resources`], identified by its
+/// `TypeId`, or an abstract name that only orders jobs.
+///
+/// Keys compare by identity alone; the type name is kept for diagnostics. A named key never
+/// equals a typed one, so `.writing("World")` neither conflicts with nor grants access to
+/// the `World` resource.
+#[derive(Copy, Clone)]
+pub struct ResourceKey {
+    id: ResourceId,
+    name: &'static str,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ResourceId {
+    Type(TypeId),
+    Named(&'static str),
+}
+
+impl ResourceKey {
+    pub fn of<T: Any>() -> Self {
+        Self {
+            id: ResourceId::Type(TypeId::of::<T>()),
+            name: type_name::<T>(),
+        }
+    }
+
+    pub fn named(name: &'static str) -> Self {
+        Self {
+            id: ResourceId::Named(name),
+            name,
+        }
+    }
+
+    /// Type name or declared name, for messages only.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl PartialEq for ResourceKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for ResourceKey {}
+
+impl fmt::Debug for ResourceKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.id {
+            ResourceId::Type(_) => write!(f, "{}", self.name),
+            ResourceId::Named(name) => write!(f, "{name:?}"),
+        }
+    }
+}
+
+/// Engine state shared with jobs, one value per type (the `scene::World`, caches, ...).
+///
+/// Jobs reach resources through [`JobContext`] and only the types they declared with
+/// [`crate::Job::reading_resource`] or [`crate::Job::writing_resource`]; the scheduler
+/// never runs two jobs with conflicting declarations at once, so borrows never wait.
+#[derive(Default)]
+pub struct Resources {
+    slots: BTreeMap<TypeId, Slot>,
+}
+
+impl Resources {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts `value`, returning the previous value of the same type.
+    pub fn insert<T: Any + Send + Sync>(&mut self, value: T) -> Option<T> {
+        self.slots
+            .insert(TypeId::of::<T>(), RwLock::new(Box::new(value)))
+            .map(|slot| *into_inner(slot).downcast::<T>().unwrap())
+    }
+
+    pub fn remove<T: Any + Send + Sync>(&mut self) -> Option<T> {
+        self.slots
+            .remove(&TypeId::of::<T>())
+            .map(|slot| *into_inner(slot).downcast::<T>().unwrap())
+    }
+
+    pub fn contains<T: Any + Send + Sync>(&self) -> bool {
+        self.slots.contains_key(&TypeId::of::<T>())
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    pub fn get_mut<T: Any + Send + Sync>(&mut self) -> Option<&mut T> {
+        let slot = self.slots.get_mut(&TypeId::of::<T>())?;
+        slot.get_mut()
+            .unwrap_or_else(|e| e.into_inner())
+            .downcast_mut()
+    }
+
+    /// Shared borrow of the `T` resource.
+    ///
+    /// # Panics
+    /// If there is no `T`, or it is mutably borrowed.
+    pub fn read<T: Any + Send + Sync>(&self) -> Res<'_, T> {
+        let guard = match self.slot::<T>().try_read() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(e)) => e.into_inner(),
+            Err(TryLockError::WouldBlock) => {
+                panic!("resource {} is already mutably borrowed", type_name::<T>())
+            }
+        };
+        Res {
+            guard,
+            _type: PhantomData,
+        }
+    }
+
+    /// Exclusive borrow of the `T` resource.
+    ///
+    /// # Panics
+    /// If there is no `T`, or it is already borrowed.
+    pub fn write<T: Any + Send + Sync>(&self) -> ResMut<'_, T> {
+        let guard = match self.slot::<T>().try_write() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(e)) => e.into_inner(),
+            Err(TryLockError::WouldBlock) => {
+                panic!("resource {} is already borrowed", type_name::<T>())
+            }
+        };
+        ResMut {
+            guard,
+            _type: PhantomData,
+        }
+    }
+
+    fn slot<T: Any>(&self) -> &Slot {
+        self.slots
+            .get(&TypeId::of::<T>())
+            .unwrap_or_else(|| panic!("missing resource {}", type_name::<T>()))
+    }
+}
+
+fn into_inner(slot: Slot) -> Box<dyn Any + Send + Sync> {
+    slot.into_inner().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Shared borrow of a resource.
+pub struct Res<'a, T> {
+    guard: RwLockReadGuard<'a, Box<dyn Any + Send + Sync>>,
+    _type: PhantomData<&'a T>,
+}
+
+impl<T: Any> Deref for Res<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.guard.downcast_ref().unwrap()
+    }
+}
+
+/// Exclusive borrow of a resource.
+pub struct ResMut<'a, T> {
+    guard: RwLockWriteGuard<'a, Box<dyn Any + Send + Sync>>,
+    _type: PhantomData<&'a mut T>,
+}
+
+impl<T: Any> Deref for ResMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.guard.downcast_ref().unwrap()
+    }
+}
+
+impl<T: Any> DerefMut for ResMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.guard.downcast_mut().unwrap()
+    }
+}
+
+/// What a running job sees: the frame, its event bus and the resources it declared.
+///
+/// `bus` starts empty for every job and only holds the job's own events; the scheduler
+/// appends it to the frame's bus once the job returns.
+pub struct JobContext<'a> {
+    pub frame: Frame,
+    pub bus: &'a mut EventBus,
+    job: &'static str,
+    resources: &'a Resources,
+    reads: &'a [ResourceKey],
+    writes: &'a [ResourceKey],
+}
+
+impl<'a> JobContext<'a> {
+    pub(crate) fn new(
+        frame: Frame,
+        bus: &'a mut EventBus,
+        job: &'static str,
+        resources: &'a Resources,
+        reads: &'a [ResourceKey],
+        writes: &'a [ResourceKey],
+    ) -> Self {
+        Self {
+            frame,
+            bus,
+            job,
+            resources,
+            reads,
+            writes,
+        }
+    }
+
+    pub fn emit(&mut self, kind: &'static str, message: impl Into<String>) {
+        self.bus.emit(self.frame, kind, message);
+    }
+
+    /// Shared borrow of a resource the job declared it reads or writes.
+    ///
+    /// # Panics
+    /// If the access was not declared, or the resource is missing.
+    pub fn read<T: Any + Send + Sync>(&self) -> Res<'a, T> {
+        let key = ResourceKey::of::<T>();
+        if !self.reads.contains(&key) && !self.writes.contains(&key) {
+            panic!("job {} reads undeclared resource {}", self.job, key.name());
+        }
+        self.resources.read()
+    }
+
+    /// Exclusive borrow of a resource the job declared it writes.
+    ///
+    /// # Panics
+    /// If the write was not declared, or the resource is missing.
+    pub fn write<T: Any + Send + Sync>(&self) -> ResMut<'a, T> {
+        let key = ResourceKey::of::<T>();
+        if !self.writes.contains(&key) {
+            panic!("job {} writes undeclared resource {}", self.job, key.name());
+        }
+        self.resources.write()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Resources;
+
+    #[test]
+    fn stores_one_value_per_type() {
+        let mut res = Resources::new();
+        assert_eq!(res.insert(1u32), None);
+        assert_eq!(res.insert(2u32), Some(1));
+        res.insert(String::from("tiles"));
+        assert_eq!(res.len(), 2);
+
+        *res.write::<u32>() += 5;
+        assert_eq!(*res.read::<u32>(), 7);
+        {
+            let a = res.read::<String>();
+            let b = res.read::<String>();
+            assert_eq!(a.len() + b.len(), 10);
+        }
+        res.get_mut::<String>().unwrap().push('!');
+        assert_eq!(res.remove::<String>().as_deref(), Some("tiles!"));
+        assert!(!res.contains::<String>());
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn rejects_overlapping_writes() {
+        let mut res = Resources::new();
+        res.insert(0u8);
+        let _a = res.read::<u8>();
+        let _b = res.write::<u8>();
+    }
+}
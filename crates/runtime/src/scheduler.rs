@@ -1,7 +1,11 @@
+use std::panic::resume_unwind;
+
 use crate::budget::FrameBudget;
 use crate::event_bus::EventBus;
 use crate::frame::Frame;
 use crate::job::Job;
+use crate::job_graph::{JobGraph, WorkerPool, run_parallel};
+use crate::resources::{JobContext, Resources};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct BudgetRunSummary {
@@ -9,10 +13,27 @@ pub struct BudgetRunSummary {
     pub skipped_jobs: usize,
 }
 
-#[derive(Default)]
+/// Runs jobs once per frame.
+///
+/// With more than one worker thread, jobs whose declared resource access does not
+/// conflict run concurrently (see [`JobGraph`]). Every job emits into its own empty bus,
+/// on any thread count, and the buffers are merged into the caller's bus in the sequential
+/// order, so a frame produces the same event stream on any number of threads. On wasm the
+/// scheduler always runs single-threaded.
+///
+/// Worker threads are spawned on the first frame that runs jobs in parallel and live as
+/// long as the scheduler.
 pub struct Scheduler {
     next_order: u64,
     jobs: Vec<(u64, Job)>,
+    worker_threads: usize,
+    pool: Option<WorkerPool>,
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Scheduler {
@@ -20,6 +41,25 @@ impl Scheduler {
         Self {
             next_order: 0,
             jobs: Vec::new(),
+            worker_threads: 1,
+            pool: None,
+        }
+    }
+
+    /// A scheduler that runs independent jobs on up to `threads` worker threads.
+    pub fn with_worker_threads(threads: usize) -> Self {
+        Self {
+            worker_threads: threads.max(1),
+            ..Self::new()
+        }
+    }
+
+    /// Worker threads actually used: always 1 on targets without threads.
+    pub fn worker_threads(&self) -> usize {
+        if cfg!(target_arch = "wasm32") {
+            1
+        } else {
+            self.worker_threads
         }
     }
 
@@ -49,6 +89,18 @@ impl Scheduler {
         bus: &mut EventBus,
         budget: &mut FrameBudget,
     ) -> BudgetRunSummary {
+        self.run_frame_with_resources(frame, &mut Resources::new(), bus, budget)
+    }
+
+    /// Like [`Scheduler::run_frame_with_budget`], giving jobs access to `resources`.
+    pub fn run_frame_with_resources(
+        &mut self,
+        frame: Frame,
+        resources: &mut Resources,
+        bus: &mut EventBus,
+        budget: &mut FrameBudget,
+    ) -> BudgetRunSummary {
+        crate::trace_span!("run_frame", "scheduler");
         // Total ordering: (priority, id, insertion_order). This stays deterministic even if
         // callers accidentally register duplicate job ids.
         self.jobs.sort_by(|(oa, a), (ob, b)| {
@@ -58,29 +110,83 @@ impl Scheduler {
                 .then_with(|| oa.cmp(ob))
         });
 
-        let mut ran = 0usize;
-        for (_order, job) in &self.jobs {
-            if !budget.try_consume(job.cost_units) {
-                break;
+        let workers = self.worker_threads();
+        let total = self.jobs.len();
+        let ran = self
+            .jobs
+            .iter()
+            .take_while(|(_order, job)| budget.try_consume(job.cost_units))
+            .count();
+
+        if workers <= 1 || ran <= 1 {
+            for (_order, job) in &mut self.jobs[..ran] {
+                crate::trace_span!(job.id, "job");
+                let mut events = EventBus::new();
+                let mut ctx = JobContext::new(
+                    frame,
+                    &mut events,
+                    job.id,
+                    resources,
+                    &job.reads,
+                    &job.writes,
+                );
+                (job.run)(&mut ctx);
+                bus.extend(events.drain());
+            }
+        } else {
+            let selected: Vec<&Job> = self.jobs[..ran].iter().map(|(_, job)| job).collect();
+            let graph = JobGraph::build(&selected);
+            // Jobs move into the batch shared with the pool; `Restore` splices them back in
+            // their slots however the run ends, and `run_parallel` returns the resources.
+            let (orders, jobs) = self.jobs.drain(..ran).unzip();
+            let mut restore = Restore {
+                slots: &mut self.jobs,
+                orders,
+                jobs,
+            };
+            let pool = self.pool.get_or_insert_with(|| WorkerPool::new(workers));
+            let events = run_parallel(pool, &mut restore.jobs, graph, frame, resources);
+            drop(restore);
+            match events {
+                Ok(events) => events.into_iter().for_each(|events| bus.extend(events)),
+                Err(payload) => resume_unwind(payload),
             }
-            ran += 1;
-            (job.run)(frame, bus);
         }
 
         BudgetRunSummary {
             ran_jobs: ran,
-            skipped_jobs: self.jobs.len().saturating_sub(ran),
+            skipped_jobs: total.saturating_sub(ran),
         }
     }
 }
 
+/// Puts jobs taken out for a parallel run back at the front of the queue on drop.
+struct Restore<'a> {
+    slots: &'a mut Vec<(u64, Job)>,
+    orders: Vec<u64>,
+    jobs: Vec<Job>,
+}
+
+impl Drop for Restore<'_> {
+    fn drop(&mut self) {
+        let orders = std::mem::take(&mut self.orders);
+        let jobs = std::mem::take(&mut self.jobs);
+        self.slots.splice(0..0, orders.into_iter().zip(jobs));
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
+    use std::sync::{Arc, Mutex};
+
     use super::Scheduler;
+    use crate::Resources;
     use crate::budget::FrameBudget;
-    use crate::event_bus::EventBus;
+    use crate::event_bus::{Event, EventBus};
     use crate::frame::Frame;
     use crate::job::Job;
+    use crate::job_graph::JobGraph;
 
     fn job_a(frame: Frame, bus: &mut EventBus) {
         bus.emit(frame, "job", "a");
@@ -140,4 +246,221 @@ mod tests {
         assert_eq!(summary.ran_jobs, 1);
         assert_eq!(summary.skipped_jobs, 1);
     }
+
+    fn emit_index(frame: Frame, bus: &mut EventBus) {
+        for i in 0..3 {
+            bus.emit(frame, "job", format!("{}:{i}", frame.index));
+        }
+    }
+
+    fn events_with_threads(threads: usize) -> Vec<Event> {
+        let mut sched = Scheduler::with_worker_threads(threads);
+        let ids = ["e", "c", "a", "d", "b", "f", "h", "g"];
+        for (i, id) in ids.into_iter().enumerate() {
+            let job = Job::with_priority(
+                id,
+                (i % 3) as i32,
+                if i % 2 == 0 { job_a } else { emit_index },
+            );
+            sched.add_job(if i % 4 == 0 {
+                job.writing("state")
+            } else {
+                job.reading("state")
+            });
+        }
+        let mut bus = EventBus::new();
+        sched.run_frame(Frame::new(7, 1.0), &mut bus);
+        bus.drain()
+    }
+
+    #[test]
+    fn merges_events_identically_on_any_thread_count() {
+        let sequential = events_with_threads(1);
+        assert_eq!(sequential.len(), 16);
+        for threads in [2, 4, 16] {
+            assert_eq!(events_with_threads(threads), sequential);
+        }
+    }
+
+    fn count_visible(frame: Frame, bus: &mut EventBus) {
+        let seen = bus.drain().len();
+        bus.emit(frame, "job", seen.to_string());
+    }
+
+    #[test]
+    fn jobs_only_see_their_own_events() {
+        for (threads, jobs) in [(1, 1), (1, 2), (4, 1), (16, 2)] {
+            let mut sched = Scheduler::with_worker_threads(threads);
+            for id in ["a", "b"].into_iter().take(jobs) {
+                sched.add_job(Job::new(id, count_visible));
+            }
+            let mut bus = EventBus::new();
+            bus.emit(Frame::new(0, 1.0), "app", "earlier");
+            sched.run_frame(Frame::new(0, 1.0), &mut bus);
+            let msgs: Vec<_> = bus.events().iter().map(|e| e.message.as_str()).collect();
+            let mut expected = vec!["earlier"];
+            expected.extend(std::iter::repeat_n("0", jobs));
+            assert_eq!(msgs, expected, "{threads} threads, {jobs} jobs");
+        }
+    }
+
+    #[test]
+    fn conflicting_jobs_wait_for_earlier_writers() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let logging = |id: &'static str| {
+            let log = Arc::clone(&log);
+            Job::stateful(id, move |ctx| {
+                log.lock().unwrap().push(id);
+                ctx.emit("job", id);
+            })
+        };
+        let jobs = [
+            logging("write").writing("tiles"),
+            logging("read").priority(1).reading("tiles"),
+            logging("other").priority(1).writing("camera"),
+        ];
+        let graph = JobGraph::build(&jobs.iter().collect::<Vec<_>>());
+        assert_eq!(graph.dependencies(1), &[0]);
+        assert!(graph.dependencies(2).is_empty());
+
+        let mut sched = Scheduler::with_worker_threads(4);
+        jobs.into_iter().for_each(|job| sched.add_job(job));
+        let mut bus = EventBus::new();
+        sched.run_frame(Frame::new(0, 1.0), &mut bus);
+        let log = log.lock().unwrap();
+        let position = |id| log.iter().position(|&l| l == id).unwrap();
+        assert!(position("write") < position("read"));
+        let msgs: Vec<_> = bus.events().iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(msgs, vec!["write", "other", "read"]);
+    }
+
+    #[derive(Default)]
+    struct Tiles(Vec<u64>);
+
+    struct Label(String);
+
+    #[test]
+    fn stateful_jobs_borrow_declared_resources() {
+        let mut sched = Scheduler::with_worker_threads(4);
+        let mut loaded = 0u64;
+        sched.add_job(
+            Job::stateful("load", move |ctx| {
+                loaded += 10;
+                ctx.write::<Tiles>().0.push(loaded + ctx.frame.index);
+            })
+            .writing_resource::<Tiles>(),
+        );
+        sched.add_job(
+            Job::stateful("label", |ctx| {
+                let count = ctx.read::<Tiles>().0.len();
+                ctx.write::<Label>().0 = format!("{count} tiles");
+            })
+            .priority(1)
+            .reading_resource::<Tiles>()
+            .writing_resource::<Label>(),
+        );
+        sched.add_job(
+            Job::stateful("report", |ctx| {
+                let total: u64 = ctx.read::<Tiles>().0.iter().sum();
+                ctx.emit("tiles", total.to_string());
+            })
+            .priority(1)
+            .reading_resource::<Tiles>(),
+        );
+
+        let mut resources = Resources::new();
+        resources.insert(Tiles::default());
+        resources.insert(Label(String::new()));
+        let mut bus = EventBus::new();
+        let mut frame = Frame::new(0, 1.0);
+        for _ in 0..3 {
+            let mut budget = FrameBudget::unlimited();
+            sched.run_frame_with_resources(frame, &mut resources, &mut bus, &mut budget);
+            frame = frame.next();
+        }
+        assert_eq!(resources.read::<Tiles>().0, vec![10, 21, 32]);
+        assert_eq!(resources.read::<Label>().0, "3 tiles");
+        let msgs: Vec<_> = bus.events().iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(msgs, vec!["10", "31", "63"]);
+    }
+
+    #[test]
+    fn worker_threads_persist_across_frames() {
+        let threads = Arc::new(Mutex::new(BTreeSet::new()));
+        let mut sched = Scheduler::with_worker_threads(2);
+        for id in ["a", "b", "c", "d"] {
+            let threads = Arc::clone(&threads);
+            sched.add_job(Job::stateful(id, move |_ctx| {
+                let name = std::thread::current()
+                    .name()
+                    .unwrap_or_default()
+                    .to_string();
+                threads.lock().unwrap().insert(name);
+            }));
+        }
+        let mut frame = Frame::new(0, 1.0);
+        for _ in 0..5 {
+            sched.run_frame(frame, &mut EventBus::new());
+            frame = frame.next();
+        }
+        let seen = threads.lock().unwrap();
+        assert!(!seen.is_empty() && seen.len() <= 2, "{seen:?}");
+        assert!(seen.iter().all(|name| name.starts_with("runtime-worker-")));
+    }
+
+    #[test]
+    fn panicking_job_hands_back_jobs_and_resources() {
+        let mut sched = Scheduler::with_worker_threads(2);
+        sched.add_job(
+            Job::stateful("load", |ctx| ctx.write::<Tiles>().0.push(1)).writing_resource::<Tiles>(),
+        );
+        sched.add_job(Job::stateful("boom", |ctx| {
+            if ctx.frame.index == 0 {
+                panic!("boom");
+            }
+        }));
+        let mut resources = Resources::new();
+        resources.insert(Tiles::default());
+        let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let mut budget = FrameBudget::unlimited();
+            sched.run_frame_with_resources(
+                Frame::new(0, 1.0),
+                &mut resources,
+                &mut EventBus::new(),
+                &mut budget,
+            )
+        }));
+        assert!(outcome.is_err());
+        assert_eq!(sched.job_count(), 2);
+        assert!(resources.contains::<Tiles>());
+
+        let mut budget = FrameBudget::unlimited();
+        let summary = sched.run_frame_with_resources(
+            Frame::new(1, 1.0),
+            &mut resources,
+            &mut EventBus::new(),
+            &mut budget,
+        );
+        assert_eq!(summary.ran_jobs, 2);
+        assert!(!resources.read::<Tiles>().0.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "job sneaky writes undeclared resource")]
+    fn undeclared_access_panics() {
+        let mut sched = Scheduler::new();
+        sched.add_job(
+            Job::stateful("sneaky", |ctx| ctx.write::<Tiles>().0.clear())
+                .reading_resource::<Tiles>(),
+        );
+        let mut resources = Resources::new();
+        resources.insert(Tiles::default());
+        let mut budget = FrameBudget::unlimited();
+        sched.run_frame_with_resources(
+            Frame::new(0, 1.0),
+            &mut resources,
+            &mut EventBus::new(),
+            &mut budget,
+        );
+    }
 }
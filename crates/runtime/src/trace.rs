@@ -0,0 +1,324 @@
+//! Scoped timing spans exported as Chrome Trace Event JSON.
+//!
+//! Spans are opened with [`crate::trace_span!`], which expands to nothing unless the
+//! runtime is built with the `trace` feature, so instrumented code costs nothing in
+//! regular builds. Spans are only recorded between [`start_capture`] and [`stop_capture`];
+//! the resulting [`TraceCapture`] renders JSON that `chrome://tracing` and Perfetto open.
+//!
+//! Timings are wall-clock observations and must never feed back into engine state.
+
+use std::cell::Cell;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+/// Spans kept per capture; later spans are counted in [`TraceCapture::dropped`].
+pub const MAX_TRACE_EVENTS: usize = 1 << 20;
+
+/// One completed span.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceEvent {
+    pub name: &'static str,
+    /// Chrome trace category, e.g. `"job"` or `"gpu"`.
+    pub category: &'static str,
+    /// Start time in microseconds on the capture clock.
+    pub start_us: f64,
+    pub duration_us: f64,
+    /// Small per-thread id, stable for the life of the thread.
+    pub thread: u32,
+}
+
+#[derive(Debug, Default)]
+struct Collector {
+    events: Vec<TraceEvent>,
+    threads: Vec<(u32, String)>,
+    dropped: u64,
+}
+
+static CAPTURING: AtomicBool = AtomicBool::new(false);
+static COLLECTOR: Mutex<Collector> = Mutex::new(Collector {
+    events: Vec::new(),
+    threads: Vec::new(),
+    dropped: 0,
+});
+static CLOCK: OnceLock<fn() -> f64> = OnceLock::new();
+static NEXT_THREAD: AtomicU32 = AtomicU32::new(0);
+
+thread_local! {
+    static THREAD: Cell<Option<u32>> = const { Cell::new(None) };
+}
+
+/// Installs the clock spans are timed with, in microseconds. Returns `false` if a clock
+/// was already in use.
+///
+/// Native builds default to [`std::time::Instant`]; `wasm32` has no usable default, so the
+/// web app installs `performance.now()` before capturing (otherwise spans last 0 µs).
+pub fn set_clock(now_us: fn() -> f64) -> bool {
+    CLOCK.set(now_us).is_ok()
+}
+
+fn now_us() -> f64 {
+    CLOCK.get_or_init(|| default_clock)()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn default_clock() -> f64 {
+    static EPOCH: OnceLock<std::time::Instant> = OnceLock::new();
+    EPOCH
+        .get_or_init(std::time::Instant::now)
+        .elapsed()
+        .as_secs_f64()
+        * 1e6
+}
+
+#[cfg(target_arch = "wasm32")]
+fn default_clock() -> f64 {
+    0.0
+}
+
+fn lock() -> std::sync::MutexGuard<'static, Collector> {
+    COLLECTOR.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn thread_id() -> u32 {
+    THREAD.with(|slot| match slot.get() {
+        Some(id) => id,
+        None => {
+            let id = NEXT_THREAD.fetch_add(1, Ordering::Relaxed);
+            slot.set(Some(id));
+            id
+        }
+    })
+}
+
+/// Clears previously recorded spans and starts recording.
+pub fn start_capture() {
+    let mut collector = lock();
+    collector.events.clear();
+    collector.threads.clear();
+    collector.dropped = 0;
+    CAPTURING.store(true, Ordering::Release);
+}
+
+/// Stops recording and returns everything recorded since [`start_capture`].
+pub fn stop_capture() -> TraceCapture {
+    CAPTURING.store(false, Ordering::Release);
+    let mut collector = lock();
+    let mut events = std::mem::take(&mut collector.events);
+    let mut threads = std::mem::take(&mut collector.threads);
+    let dropped = std::mem::take(&mut collector.dropped);
+    events.sort_by(|a, b| {
+        a.start_us
+            .total_cmp(&b.start_us)
+            .then(a.thread.cmp(&b.thread))
+            .then(b.duration_us.total_cmp(&a.duration_us))
+    });
+    threads.sort();
+    TraceCapture {
+        events,
+        threads,
+        dropped,
+    }
+}
+
+pub fn is_capturing() -> bool {
+    CAPTURING.load(Ordering::Acquire)
+}
+
+/// An open span; records a [`TraceEvent`] when dropped. Usually created by
+/// [`crate::trace_span!`].
+#[must_use = "the span ends when the guard is dropped"]
+pub struct Span {
+    name: &'static str,
+    category: &'static str,
+    start_us: f64,
+}
+
+impl Span {
+    /// Opens a span, or returns `None` when no capture is running.
+    pub fn enter(name: &'static str, category: &'static str) -> Option<Span> {
+        if !is_capturing() {
+            return None;
+        }
+        Some(Span {
+            name,
+            category,
+            start_us: now_us(),
+        })
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let end_us = now_us();
+        if !is_capturing() {
+            return;
+        }
+        let thread = thread_id();
+        let mut collector = lock();
+        if collector.events.len() >= MAX_TRACE_EVENTS {
+            collector.dropped += 1;
+            return;
+        }
+        if !collector.threads.iter().any(|(id, _)| *id == thread) {
+            let name = std::thread::current()
+                .name()
+                .map_or_else(|| format!("thread-{thread}"), str::to_string);
+            collector.threads.push((thread, name));
+        }
+        collector.events.push(TraceEvent {
+            name: self.name,
+            category: self.category,
+            start_us: self.start_us,
+            duration_us: (end_us - self.start_us).max(0.0),
+            thread,
+        });
+    }
+}
+
+/// Opens a span that lasts until the end of the enclosing block.
+///
+/// `trace_span!("decode")` uses the `"engine"` category; `trace_span!("upload", "gpu")`
+/// sets one explicitly. Expands to nothing without the runtime's `trace` feature.
+#[cfg(feature = "trace")]
+#[macro_export]
+macro_rules! trace_span {
+    ($name:expr) => {
+        $crate::trace_span!($name, "engine")
+    };
+    ($name:expr, $category:expr) => {
+        let _trace_span = $crate::trace::Span::enter($name, $category);
+    };
+}
+
+/// Opens a span that lasts until the end of the enclosing block.
+///
+/// `trace_span!("decode")` uses the `"engine"` category; `trace_span!("upload", "gpu")`
+/// sets one explicitly. Expands to nothing without the runtime's `trace` feature.
+#[cfg(not(feature = "trace"))]
+#[macro_export]
+macro_rules! trace_span {
+    ($name:expr) => {};
+    ($name:expr, $category:expr) => {};
+}
+
+/// Spans recorded by one capture, ordered by start time.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TraceCapture {
+    pub events: Vec<TraceEvent>,
+    /// `(thread id, thread name)` of every thread that recorded a span.
+    pub threads: Vec<(u32, String)>,
+    /// Spans discarded after [`MAX_TRACE_EVENTS`] was reached.
+    pub dropped: u64,
+}
+
+impl TraceCapture {
+    /// Chrome Trace Event JSON (object form): complete (`"X"`) events plus thread-name
+    /// metadata, loadable in Perfetto and `chrome://tracing`.
+    pub fn to_chrome_json(&self) -> String {
+        let mut out = String::from("{\"traceEvents\":[");
+        let mut first = true;
+        let mut sep = |out: &mut String| {
+            if !first {
+                out.push(',');
+            }
+            first = false;
+        };
+        for (tid, name) in &self.threads {
+            sep(&mut out);
+            out.push_str("{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":1,\"tid\":");
+            let _ = write!(out, "{tid},\"args\":{{\"name\":");
+            push_json_string(&mut out, name);
+            out.push_str("}}");
+        }
+        for e in &self.events {
+            sep(&mut out);
+            out.push_str("{\"name\":");
+            push_json_string(&mut out, e.name);
+            out.push_str(",\"cat\":");
+            push_json_string(&mut out, e.category);
+            let _ = write!(
+                out,
+                ",\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":1,\"tid\":{}}}",
+                e.start_us, e.duration_us, e.thread
+            );
+        }
+        let _ = write!(
+            out,
+            "],\"displayTimeUnit\":\"ms\",\"otherData\":{{\"dropped\":{}}}}}",
+            self.dropped
+        );
+        out
+    }
+}
+
+fn push_json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Span, TraceCapture, TraceEvent, start_capture, stop_capture};
+
+    #[test]
+    fn records_nested_spans_while_capturing() {
+        assert!(Span::enter("before", "test").is_none());
+        start_capture();
+        {
+            let _outer = Span::enter("outer", "test");
+            let _inner = Span::enter("inner", "test");
+        }
+        let capture = stop_capture();
+        assert!(Span::enter("after", "test").is_none());
+
+        // Other tests may run instrumented scheduler jobs concurrently.
+        let ours: Vec<_> = capture
+            .events
+            .iter()
+            .filter(|e| e.category == "test")
+            .collect();
+        assert_eq!(ours.len(), 2);
+        assert_eq!((ours[0].name, ours[1].name), ("outer", "inner"));
+        assert!(ours[0].start_us <= ours[1].start_us);
+        assert!(ours[0].duration_us >= ours[1].duration_us);
+        assert!(capture.threads.iter().any(|(id, _)| *id == ours[0].thread));
+    }
+
+    #[test]
+    fn writes_chrome_trace_json() {
+        let capture = TraceCapture {
+            events: vec![TraceEvent {
+                name: "tile \"3/4/2\"",
+                category: "streaming",
+                start_us: 12.5,
+                duration_us: 100.0,
+                thread: 0,
+            }],
+            threads: vec![(0, "main".into())],
+            dropped: 0,
+        };
+        assert_eq!(
+            capture.to_chrome_json(),
+            concat!(
+                r#"{"traceEvents":["#,
+                r#"{"name":"thread_name","ph":"M","pid":1,"tid":0,"args":{"name":"main"}},"#,
+                r#"{"name":"tile \"3/4/2\"","cat":"streaming","ph":"X","ts":12.500,"dur":100.000,"pid":1,"tid":0}"#,
+                r#"],"displayTimeUnit":"ms","otherData":{"dropped":0}}"#
+            )
+        );
+    }
+}
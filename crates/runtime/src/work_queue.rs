@@ -1,14 +1,16 @@
+use std::collections::HashMap;
+
 /// Deterministic work queue for streaming/compute style task scheduling.
 ///
 /// Key properties:
 /// - Total ordering on `(priority, id)`.
 /// - Equal priorities are processed in insertion order.
-/// - Cancellation does not perturb the order of remaining items.
+/// - Cancellation and reprioritization do not perturb the order of remaining items.
 /// - Optional backpressure via a deterministic maximum pending length.
 /// - Optional frame budgeting via abstract work units.
 ///
-/// This is intentionally simple (Vec-backed) because MVP correctness and
-/// determinism are more important than asymptotic performance.
+/// Backed by an indexed binary heap: push, pop, `cancel` and `reprioritize` are
+/// O(log n). The order only depends on `(priority, id)`, never on heap layout.
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct WorkId(pub u64);
@@ -45,13 +47,15 @@ struct Item<T> {
     key: Key,
     payload: T,
     cost_units: u32,
-    canceled: bool,
 }
 
 #[derive(Debug)]
 pub struct WorkQueue<T> {
     next_id: u64,
-    items: Vec<Item<T>>,
+    /// Min-heap on `Key`.
+    heap: Vec<Item<T>>,
+    /// Heap position of every queued item.
+    positions: HashMap<WorkId, usize>,
     max_len: Option<usize>,
 }
 
@@ -59,7 +63,8 @@ impl<T> Default for WorkQueue<T> {
     fn default() -> Self {
         Self {
             next_id: 0,
-            items: Vec::new(),
+            heap: Vec::new(),
+            positions: HashMap::new(),
             max_len: None,
         }
     }
@@ -82,11 +87,22 @@ impl<T> WorkQueue<T> {
     }
 
     pub fn len(&self) -> usize {
-        self.items.iter().filter(|i| !i.canceled).count()
+        self.heap.len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.len() == 0
+        self.heap.is_empty()
+    }
+
+    pub fn contains(&self, id: WorkId) -> bool {
+        self.positions.contains_key(&id)
+    }
+
+    /// Current priority of a queued item.
+    pub fn priority(&self, id: WorkId) -> Option<i32> {
+        self.positions
+            .get(&id)
+            .map(|&pos| self.heap[pos].key.priority)
     }
 
     pub fn push(&mut self, priority: i32, payload: T) -> WorkId {
@@ -104,12 +120,14 @@ impl<T> WorkQueue<T> {
     fn push_unchecked_with_cost(&mut self, priority: i32, cost_units: u32, payload: T) -> WorkId {
         let id = WorkId(self.next_id);
         self.next_id = self.next_id.wrapping_add(1);
-        self.items.push(Item {
+        let pos = self.heap.len();
+        self.heap.push(Item {
             key: Key { priority, id },
             payload,
             cost_units,
-            canceled: false,
         });
+        self.positions.insert(id, pos);
+        self.sift_up(pos);
         id
     }
 
@@ -128,33 +146,45 @@ impl<T> WorkQueue<T> {
         Ok(self.push_unchecked_with_cost(priority, cost_units, payload))
     }
 
+    /// Removes a queued item. Returns `false` if it was already popped or canceled.
     pub fn cancel(&mut self, id: WorkId) -> bool {
-        if let Some(item) = self.items.iter_mut().find(|i| i.key.id == id) {
-            item.canceled = true;
-            return true;
+        match self.positions.get(&id) {
+            Some(&pos) => {
+                self.remove_at(pos);
+                true
+            }
+            None => false,
         }
-        false
+    }
+
+    /// Changes the priority of a queued item. Among equal priorities it keeps its
+    /// original insertion position. Returns `false` if the item is no longer queued.
+    pub fn reprioritize(&mut self, id: WorkId, priority: i32) -> bool {
+        let Some(&pos) = self.positions.get(&id) else {
+            return false;
+        };
+        let old = self.heap[pos].key.priority;
+        self.heap[pos].key.priority = priority;
+        if priority < old {
+            self.sift_up(pos);
+        } else {
+            self.sift_down(pos);
+        }
+        true
+    }
+
+    /// The next item [`WorkQueue::pop_next`] would return.
+    pub fn peek_next(&self) -> Option<(WorkId, i32, &T)> {
+        let item = self.heap.first()?;
+        Some((item.key.id, item.key.priority, &item.payload))
     }
 
     /// Pops the next (highest priority, then oldest) item.
     pub fn pop_next(&mut self) -> Option<(WorkId, i32, T)> {
-        let mut best_idx: Option<usize> = None;
-        for (idx, item) in self.items.iter().enumerate() {
-            if item.canceled {
-                continue;
-            }
-            match best_idx {
-                None => best_idx = Some(idx),
-                Some(best) => {
-                    if item.key < self.items[best].key {
-                        best_idx = Some(idx);
-                    }
-                }
-            }
+        if self.heap.is_empty() {
+            return None;
         }
-
-        let idx = best_idx?;
-        let item = self.items.swap_remove(idx);
+        let item = self.remove_at(0);
         Some((item.key.id, item.key.priority, item.payload))
     }
 
@@ -169,35 +199,66 @@ impl<T> WorkQueue<T> {
         &mut self,
         budget: &mut crate::budget::FrameBudget,
     ) -> Option<(WorkId, i32, T)> {
-        let mut best_idx: Option<usize> = None;
-        for (idx, item) in self.items.iter().enumerate() {
-            if item.canceled {
-                continue;
-            }
-            match best_idx {
-                None => best_idx = Some(idx),
-                Some(best) => {
-                    if item.key < self.items[best].key {
-                        best_idx = Some(idx);
-                    }
-                }
+        let cost_units = self.heap.first()?.cost_units;
+        if !budget.try_consume(cost_units) {
+            return None;
+        }
+        self.pop_next()
+    }
+
+    fn remove_at(&mut self, pos: usize) -> Item<T> {
+        let item = self.heap.swap_remove(pos);
+        self.positions.remove(&item.key.id);
+        if pos < self.heap.len() {
+            self.positions.insert(self.heap[pos].key.id, pos);
+            // The moved-in last element may belong above or below `pos`.
+            let pos = self.sift_up(pos);
+            self.sift_down(pos);
+        }
+        item
+    }
+
+    fn sift_up(&mut self, mut pos: usize) -> usize {
+        while pos > 0 {
+            let parent = (pos - 1) / 2;
+            if self.heap[pos].key >= self.heap[parent].key {
+                break;
             }
+            self.swap(pos, parent);
+            pos = parent;
         }
+        pos
+    }
 
-        let idx = best_idx?;
-        let cost_units = self.items[idx].cost_units;
-        if !budget.try_consume(cost_units) {
-            return None;
+    fn sift_down(&mut self, mut pos: usize) {
+        loop {
+            let left = 2 * pos + 1;
+            let right = left + 1;
+            let mut smallest = pos;
+            if left < self.heap.len() && self.heap[left].key < self.heap[smallest].key {
+                smallest = left;
+            }
+            if right < self.heap.len() && self.heap[right].key < self.heap[smallest].key {
+                smallest = right;
+            }
+            if smallest == pos {
+                return;
+            }
+            self.swap(pos, smallest);
+            pos = smallest;
         }
+    }
 
-        let item = self.items.swap_remove(idx);
-        Some((item.key.id, item.key.priority, item.payload))
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.positions.insert(self.heap[a].key.id, a);
+        self.positions.insert(self.heap[b].key.id, b);
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{WorkQueue, WorkQueueFull};
+    use super::{WorkId, WorkQueue, WorkQueueFull};
     use crate::budget::FrameBudget;
 
     #[test]
@@ -258,4 +319,62 @@ mod tests {
         assert_eq!(v, "expensive");
         assert!(q.is_empty());
     }
+
+    #[test]
+    fn reprioritize_moves_item_and_keeps_insertion_tie_break() {
+        let mut q = WorkQueue::new();
+        let a = q.push(5, "a");
+        let b = q.push(1, "b");
+        q.push(1, "c");
+        assert!(q.reprioritize(a, 1));
+        assert_eq!(q.priority(a), Some(1));
+        // `a` was inserted first, so it now leads the priority-1 items.
+        let order: Vec<_> = std::iter::from_fn(|| q.pop_next().map(|(_, _, v)| v)).collect();
+        assert_eq!(order, vec!["a", "b", "c"]);
+        assert!(!q.reprioritize(b, 0));
+        assert!(!q.cancel(b));
+    }
+
+    #[test]
+    fn matches_sorted_reference_under_random_operations() {
+        // Reference model: a plain list ordered by (priority, id) on every pop.
+        let mut q = WorkQueue::new();
+        let mut reference: Vec<(i32, u64)> = Vec::new();
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        let mut next = move |n: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % n
+        };
+        for _ in 0..5000 {
+            match next(5) {
+                0 | 1 => {
+                    let priority = next(20) as i32 - 10;
+                    let id = q.push(priority, ());
+                    reference.push((priority, id.0));
+                }
+                2 if !reference.is_empty() => {
+                    let idx = next(reference.len() as u64) as usize;
+                    let (_, id) = reference.swap_remove(idx);
+                    assert!(q.cancel(WorkId(id)));
+                }
+                3 if !reference.is_empty() => {
+                    let idx = next(reference.len() as u64) as usize;
+                    let priority = next(20) as i32 - 10;
+                    reference[idx].0 = priority;
+                    assert!(q.reprioritize(WorkId(reference[idx].1), priority));
+                }
+                _ => {
+                    let expected = reference.iter().copied().min();
+                    if let Some(e) = expected {
+                        reference.retain(|&r| r != e);
+                    }
+                    let got = q.pop_next().map(|(id, p, ())| (p, id.0));
+                    assert_eq!(got, expected);
+                }
+            }
+            assert_eq!(q.len(), reference.len());
+        }
+    }
 }
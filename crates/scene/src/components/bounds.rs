@@ -1,3 +1,4 @@
+use foundation::bounds::Aabb3;
 use foundation::math::Vec3;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -19,6 +20,13 @@ impl ComponentBounds {
             && point.z >= self.min.z
             && point.z <= self.max.z
     }
+
+    pub fn to_aabb3(&self) -> Aabb3 {
+        Aabb3::new(
+            [self.min.x, self.min.y, self.min.z],
+            [self.max.x, self.max.y, self.max.z],
+        )
+    }
 }
 
 #[cfg(test)]
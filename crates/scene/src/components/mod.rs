@@ -1,6 +1,7 @@
 pub mod bounds;
 pub mod drawable2d;
 pub mod drawable3d;
+pub mod orbit;
 pub mod properties;
 pub mod time_span;
 pub mod transform;
@@ -10,6 +11,7 @@ pub mod visibility;
 pub use bounds::*;
 pub use drawable2d::*;
 pub use drawable3d::*;
+pub use orbit::*;
 pub use properties::*;
 pub use time_span::*;
 pub use transform::*;
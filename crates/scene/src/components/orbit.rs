@@ -0,0 +1,13 @@
+use foundation::astro::Sgp4;
+
+/// Drives the entity's `Transform` from an SGP4 propagator; see `World::update_orbits`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ComponentOrbit {
+    pub propagator: Sgp4,
+}
+
+impl ComponentOrbit {
+    pub fn new(propagator: Sgp4) -> Self {
+        Self { propagator }
+    }
+}
@@ -1,7 +1,19 @@
+use foundation::handles::Handle;
 use foundation::math::Vec3;
 
+/// Generational id of a geometry stored in a `World`; stale once the geometry is freed.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub struct VectorGeometryId(pub u32);
+pub struct VectorGeometryId(pub Handle);
+
+impl VectorGeometryId {
+    pub fn index(&self) -> u32 {
+        self.0.index()
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.0.generation()
+    }
+}
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum VectorGeometryKind {
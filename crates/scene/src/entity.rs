@@ -7,4 +7,8 @@ impl EntityId {
     pub fn index(&self) -> u32 {
         self.0.index()
     }
+
+    pub fn generation(&self) -> u32 {
+        self.0.generation()
+    }
 }
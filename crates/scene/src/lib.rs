@@ -5,6 +5,7 @@ pub mod prefabs;
 pub mod query;
 pub mod selection;
 pub mod spatial;
+pub mod storage;
 pub mod temporal;
 pub mod visibility;
 pub mod world;
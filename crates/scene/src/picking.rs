@@ -4,7 +4,6 @@ use foundation::math::precision::stable_total_cmp_f64;
 use crate::World;
 use crate::components::VectorGeometryKind;
 use crate::entity::EntityId;
-use crate::spatial::{Bvh, Item as BvhItem};
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Ray {
@@ -46,51 +45,23 @@ impl Default for PickOptions {
 /// - Otherwise, the closest hit along the (normalized) ray wins.
 ///
 /// Notes:
-/// - This uses entity bounds (`World::bounds`) for intersection.
+/// - This uses entity bounds (`World::bounds`) for intersection, via the world's spatial
+///   index (`World::entities_on_ray`).
 /// - Entities without explicit bounds are ignored.
-/// - Visibility gating is inherited from `World::vector_geometries_by_entity()`.
+/// - Visibility gating matches `World::vector_geometries_by_entity()`.
 pub fn pick_ray(world: &World, ray: Ray, opts: PickOptions) -> Option<PickHit> {
     let dir = normalize(ray.dir)?;
 
-    // Collect visible vector entities and build a BVH over those with explicit bounds.
-    let geoms = world.vector_geometries_by_entity();
-    let mut kinds_by_index: Vec<Option<VectorGeometryKind>> = Vec::new();
-    let mut bvh_items: Vec<BvhItem> = Vec::new();
-
-    for (entity, _transform, component) in &geoms {
-        let idx = entity.index() as usize;
-        if kinds_by_index.len() <= idx {
-            kinds_by_index.resize(idx + 1, None);
-        }
-        kinds_by_index[idx] = Some(component.kind);
-
-        let Some(b) = world.bounds(*entity) else {
-            continue;
-        };
-        bvh_items.push(BvhItem {
-            entity: *entity,
-            bounds: foundation::bounds::Aabb3::new(
-                [b.min.x, b.min.y, b.min.z],
-                [b.max.x, b.max.y, b.max.z],
-            ),
-        });
-    }
-
-    if bvh_items.is_empty() {
-        return None;
-    }
-
-    let bvh = Bvh::build(bvh_items);
     let origin = [ray.origin.x, ray.origin.y, ray.origin.z];
     let dir_a = [dir.x, dir.y, dir.z];
 
     let mut best: Option<(f64, EntityId, VectorGeometryKind)> = None;
 
-    for entity in bvh.query_ray(origin, dir_a, 0.0, opts.max_distance) {
+    for entity in world.entities_on_ray(origin, dir_a, 0.0, opts.max_distance) {
         let Some(b) = world.bounds(entity) else {
             continue;
         };
-        let Some(kind) = kinds_by_index.get(entity.index() as usize).and_then(|k| *k) else {
+        let Some(kind) = world.visible_vector_geometry(entity).map(|c| c.kind) else {
             continue;
         };
 
@@ -1,3 +1,5 @@
 pub mod globe;
+pub mod satellite;
 
 pub use globe::*;
+pub use satellite::*;
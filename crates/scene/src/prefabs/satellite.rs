@@ -0,0 +1,162 @@
+use foundation::astro::{Sgp4, Sgp4Error, Tle};
+use foundation::math::{Ecef, Vec3, geodetic_to_ecef};
+use foundation::time::{Time, TimeSpan};
+
+use crate::World;
+use crate::components::{
+    ComponentBounds, ComponentOrbit, ComponentProperties, ComponentTimeSpan,
+    ComponentVectorGeometry, Drawable3D, Transform, VectorGeometry, VectorGeometryKind,
+};
+use crate::entity::EntityId;
+
+/// Radius of the marker sphere drawn at a satellite's position (meters).
+pub const SATELLITE_MARKER_RADIUS_M: f64 = 50_000.0;
+
+/// Spawns a satellite that follows `tle`, positioned at `time`.
+///
+/// Call `World::update_orbits` each frame to move it. Properties carry the name, catalog
+/// number and international designator from the element set.
+pub fn spawn_satellite(world: &mut World, tle: &Tle, time: Time) -> Result<EntityId, Sgp4Error> {
+    let propagator = Sgp4::new(tle)?;
+    let entity = world.spawn();
+    world.set_orbit(entity, ComponentOrbit::new(propagator));
+    world.set_drawable_3d(entity, Drawable3D::sphere(SATELLITE_MARKER_RADIUS_M));
+    world.set_properties(
+        entity,
+        ComponentProperties::new(vec![
+            ("name".to_string(), tle.name.clone()),
+            ("norad_id".to_string(), tle.norad_id.to_string()),
+            (
+                "international_designator".to_string(),
+                tle.international_designator.clone(),
+            ),
+        ]),
+    );
+    world.update_orbits(time);
+    Ok(entity)
+}
+
+/// Spawns the sub-satellite track across `span`, sampled every `step_s` seconds, as a
+/// line on the ellipsoid that is active only during `span`. Returns `None` when fewer than
+/// two samples propagate.
+pub fn spawn_ground_track(
+    world: &mut World,
+    propagator: &Sgp4,
+    span: TimeSpan,
+    step_s: f64,
+) -> Option<EntityId> {
+    let vertices: Vec<Vec3> = propagator
+        .ground_track(span, step_s)
+        .into_iter()
+        .map(|geo| vec3(geodetic_to_ecef(geo)))
+        .collect();
+    let entity = spawn_line(world, vertices)?;
+    world.set_time_span(entity, ComponentTimeSpan::new(span));
+    Some(entity)
+}
+
+/// Spawns one revolution of the orbit starting at `time`, as a closed line around the
+/// globe in the Earth's orientation at that instant.
+pub fn spawn_orbit_line(
+    world: &mut World,
+    propagator: &Sgp4,
+    time: Time,
+    segments: usize,
+) -> Option<EntityId> {
+    let mut vertices: Vec<Vec3> = propagator
+        .orbit_line(time, segments)
+        .into_iter()
+        .map(vec3)
+        .collect();
+    if let Some(&first) = vertices.first() {
+        vertices.push(first);
+    }
+    spawn_line(world, vertices)
+}
+
+fn spawn_line(world: &mut World, vertices: Vec<Vec3>) -> Option<EntityId> {
+    if vertices.len() < 2 {
+        return None;
+    }
+    let mut min = vertices[0];
+    let mut max = vertices[0];
+    for v in &vertices {
+        min = Vec3::new(min.x.min(v.x), min.y.min(v.y), min.z.min(v.z));
+        max = Vec3::new(max.x.max(v.x), max.y.max(v.y), max.z.max(v.z));
+    }
+
+    let entity = world.spawn();
+    world.set_transform(entity, Transform::translate((min + max) * 0.5));
+    world.set_bounds(entity, ComponentBounds::new(min, max));
+    let id = world.add_vector_geometry(VectorGeometry::Line { vertices });
+    world.set_vector_geometry(
+        entity,
+        ComponentVectorGeometry::new(id, VectorGeometryKind::Line),
+    );
+    Some(entity)
+}
+
+fn vec3(e: Ecef) -> Vec3 {
+    Vec3::new(e.x, e.y, e.z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{spawn_ground_track, spawn_orbit_line, spawn_satellite};
+    use crate::World;
+    use crate::components::VectorGeometry;
+    use foundation::astro::{Sgp4, Tle};
+    use foundation::math::WGS84_A;
+    use foundation::time::{Time, TimeSpan};
+
+    fn iss() -> Tle {
+        Tle::parse_named(
+            "ISS (ZARYA)",
+            "1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927",
+            "2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn satellite_moves_with_time() {
+        let tle = iss();
+        let mut world = World::new();
+        let sat = spawn_satellite(&mut world, &tle, tle.epoch).unwrap();
+        let p0 = world.transform(sat).unwrap().position;
+        assert!((p0.length() - WGS84_A - 350_000.0).abs() < 50_000.0);
+        assert_eq!(world.properties(sat).unwrap().pairs[0].1, "ISS (ZARYA)");
+
+        // About 7.7 km/s along the orbit.
+        assert_eq!(world.update_orbits(Time(tle.epoch.0 + 60.0)), 1);
+        let p1 = world.transform(sat).unwrap().position;
+        assert!(((p1 - p0).length() - 460_000.0).abs() < 20_000.0);
+        assert_eq!(world.bounds(sat).unwrap().min, p1);
+        assert_eq!(world.drawables_3d().len(), 1);
+    }
+
+    #[test]
+    fn spawns_track_and_orbit_lines() {
+        let tle = iss();
+        let mut world = World::new();
+        let sgp4 = Sgp4::new(&tle).unwrap();
+        let span = TimeSpan::new(tle.epoch, Time(tle.epoch.0 + 5_400.0));
+        let track = spawn_ground_track(&mut world, &sgp4, span, 60.0).unwrap();
+        let orbit = spawn_orbit_line(&mut world, &sgp4, tle.epoch, 64).unwrap();
+        assert_eq!(world.time_span(track), Some(span));
+
+        let lines = world.vector_geometries_by_entity();
+        assert_eq!(lines.len(), 2);
+        let Some(VectorGeometry::Line { vertices }) = world.vector_geometry(lines[0].2.id) else {
+            panic!("expected a ground track line");
+        };
+        assert_eq!(vertices.len(), 91);
+        assert!(vertices.iter().all(|v| v.length() < WGS84_A + 1.0));
+        let Some(VectorGeometry::Line { vertices }) = world.vector_geometry(lines[1].2.id) else {
+            panic!("expected an orbit line");
+        };
+        assert_eq!(vertices.len(), 65);
+        assert_eq!(vertices[0], vertices[64]);
+        assert!(world.bounds(orbit).is_some());
+    }
+}
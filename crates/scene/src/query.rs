@@ -1,12 +1,10 @@
 use foundation::bounds::Aabb3;
 use foundation::time::{Time, TimeSpan};
-use std::collections::BTreeMap;
 
 use crate::World;
 use crate::components::VectorGeometryKind;
 use crate::entity::EntityId;
 use crate::selection::SelectionSet;
-use crate::spatial::{Bvh, Item as BvhItem};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PropertyOp {
@@ -69,8 +67,8 @@ fn time_allows(span: Option<TimeSpan>, filter: Option<TimeFilter>) -> bool {
     };
 
     match filter {
-        TimeFilter::At(t) => !(t.0 < span.start.0 || t.0 > span.end.0),
-        TimeFilter::Overlaps(q) => !(span.end.0 < q.start.0 || span.start.0 > q.end.0),
+        TimeFilter::At(t) => span.contains(t),
+        TimeFilter::Overlaps(q) => span.overlaps(&q),
     }
 }
 
@@ -107,28 +105,10 @@ fn properties_match(world: &World, entity: EntityId, filters: &[PropertyFilter])
 pub fn query_vector_entities(world: &World, query: &VectorEntityQuery) -> SelectionSet {
     let mut out = SelectionSet::new();
 
-    let geoms = world.vector_geometries_by_entity();
-
     if let Some(aabb) = query.bbox_world_ecef {
-        // Build deterministic lookup for candidate retrieval.
-        let mut by_index: BTreeMap<u32, VectorGeometryKind> = BTreeMap::new();
-        let mut bvh_items: Vec<BvhItem> = Vec::new();
-
-        for (entity, _transform, component) in &geoms {
-            by_index.insert(entity.index(), component.kind);
-
-            let Some(b) = world.bounds(*entity) else {
-                continue;
-            };
-            bvh_items.push(BvhItem {
-                entity: *entity,
-                bounds: Aabb3::new([b.min.x, b.min.y, b.min.z], [b.max.x, b.max.y, b.max.z]),
-            });
-        }
-
-        let bvh = Bvh::build(bvh_items);
-        for entity in bvh.query_aabb(&aabb) {
-            let Some(kind) = by_index.get(&entity.index()).copied() else {
+        // Candidates come from the world's spatial index, already in index order.
+        for entity in world.entities_in_aabb(&aabb) {
+            let Some(kind) = world.visible_vector_geometry(entity).map(|c| c.kind) else {
                 continue;
             };
 
@@ -155,7 +135,7 @@ pub fn query_vector_entities(world: &World, query: &VectorEntityQuery) -> Select
         return out;
     }
 
-    for (entity, _transform, component) in geoms {
+    for (entity, _transform, component) in world.vector_geometries_by_entity() {
         if let Some(kind) = query.kind
             && component.kind != kind
         {
@@ -193,10 +173,7 @@ mod tests {
     };
 
     fn span(a: f64, b: f64) -> TimeSpan {
-        TimeSpan {
-            start: Time(a),
-            end: Time(b),
-        }
+        TimeSpan::new(Time(a), Time(b))
     }
 
     #[test]
@@ -310,4 +287,43 @@ mod tests {
         let got: Vec<u32> = hits.iter_indices().collect();
         assert_eq!(got, vec![e1.index(), e2.index()]);
     }
+    #[test]
+    fn results_carry_generations_of_respawned_entities() {
+        let mut world = World::new();
+        let spawn_point = |world: &mut World| {
+            let e = world.spawn();
+            world.set_transform(e, Transform::identity());
+            world.set_bounds(e, ComponentBounds::new(Vec3::ZERO, Vec3::ZERO));
+            let g = world.add_vector_geometry(VectorGeometry::Point {
+                position: Vec3::ZERO,
+            });
+            world.set_vector_geometry(
+                e,
+                ComponentVectorGeometry::new(g, VectorGeometryKind::Point),
+            );
+            e
+        };
+        let q = VectorEntityQuery {
+            bbox_world_ecef: Some(Aabb3::new([-1.0, -1.0, -1.0], [1.0, 1.0, 1.0])),
+            ..Default::default()
+        };
+
+        let old = spawn_point(&mut world);
+        let before = query_vector_entities(&world, &q);
+        world.despawn(old);
+        let new = spawn_point(&mut world);
+        assert_eq!(new.index(), old.index());
+
+        for hits in [
+            query_vector_entities(&world, &q),
+            query_vector_entities(&world, &Default::default()),
+        ] {
+            let got: Vec<_> = hits.iter_entities().collect();
+            assert_eq!(got, vec![new]);
+            assert!(got.iter().all(|&e| world.is_alive(e)));
+        }
+        assert!(!before.contains(new));
+        assert_eq!(before.iter_entities().collect::<Vec<_>>(), vec![old]);
+        assert!(!world.is_alive(old));
+    }
 }
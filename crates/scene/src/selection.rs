@@ -3,13 +3,23 @@ use foundation::handles::Handle;
 
 /// Deterministic selection set backed by a bitset.
 ///
-/// Membership is tracked by `EntityId::index()`.
+/// Membership is tracked by `EntityId::index()`, and the generation of each member is kept
+/// alongside, so `iter_entities` returns the exact handles that were inserted. Entity-based
+/// operations (`contains`, `remove` and the set operations) only match a member with the
+/// same generation; a selection kept across a despawn therefore never matches the entity
+/// that later reuses the index. The `*_index` methods ignore generations and insert
+/// generation 0.
+///
+/// A set holds at most one entity per index: inserting a different generation at a selected
+/// index replaces it, and `union` keeps `self`'s member on such a clash.
 ///
 /// Ordering contract:
 /// - Iteration yields indices (and `EntityId`s) in ascending index order.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct SelectionSet {
     words: Vec<u64>,
+    /// Generation per index; 0 where the bit is clear.
+    generations: Vec<u32>,
     len: usize,
 }
 
@@ -26,6 +36,7 @@ impl SelectionSet {
 
     pub fn clear(&mut self) {
         self.words.clear();
+        self.generations.clear();
         self.len = 0;
     }
 
@@ -39,6 +50,7 @@ impl SelectionSet {
 
     pub fn contains(&self, entity: EntityId) -> bool {
         self.contains_index(entity.index())
+            && self.generations[entity.index() as usize] == entity.generation()
     }
 
     pub fn contains_index(&self, index: u32) -> bool {
@@ -48,11 +60,16 @@ impl SelectionSet {
             .is_some_and(|w| (w & (1u64 << bit)) != 0)
     }
 
-    /// Inserts `entity` into the set.
+    /// Inserts `entity` into the set, replacing another generation at the same index.
     ///
     /// Returns `true` if the set changed.
     pub fn insert(&mut self, entity: EntityId) -> bool {
-        self.insert_index(entity.index())
+        let index = entity.index();
+        let inserted = self.insert_index(index);
+        let generation = &mut self.generations[index as usize];
+        let replaced = *generation != entity.generation();
+        *generation = entity.generation();
+        inserted || replaced
     }
 
     pub fn insert_index(&mut self, index: u32) -> bool {
@@ -68,11 +85,11 @@ impl SelectionSet {
         true
     }
 
-    /// Removes `entity` from the set.
+    /// Removes `entity` from the set; another generation at its index stays.
     ///
     /// Returns `true` if the set changed.
     pub fn remove(&mut self, entity: EntityId) -> bool {
-        self.remove_index(entity.index())
+        self.contains(entity) && self.remove_index(entity.index())
     }
 
     pub fn remove_index(&mut self, index: u32) -> bool {
@@ -85,6 +102,7 @@ impl SelectionSet {
             return false;
         }
         *w &= !mask;
+        self.generations[index as usize] = 0;
         self.len -= 1;
         true
     }
@@ -111,7 +129,13 @@ impl SelectionSet {
     pub fn union_in_place(&mut self, other: &Self) {
         let max_words = other.words.len().max(self.words.len());
         self.words.resize(max_words, 0);
+        self.generations.resize(max_words * 64, 0);
         for (idx, ow) in other.words.iter().copied().enumerate() {
+            let added = ow & !self.words[idx];
+            for bit in set_bits(added) {
+                let i = idx * 64 + bit as usize;
+                self.generations[i] = other.generations[i];
+            }
             self.words[idx] |= ow;
         }
         self.recount_len();
@@ -120,11 +144,13 @@ impl SelectionSet {
     pub fn intersect_in_place(&mut self, other: &Self) {
         let min_words = other.words.len().min(self.words.len());
         for idx in 0..min_words {
-            self.words[idx] &= other.words[idx];
+            let both = self.words[idx] & other.words[idx];
+            self.words[idx] = both & self.same_generation_mask(other, idx, both);
         }
         for idx in min_words..self.words.len() {
             self.words[idx] = 0;
         }
+        self.clear_unselected_generations();
         self.recount_len();
     }
 
@@ -132,11 +158,31 @@ impl SelectionSet {
     pub fn diff_in_place(&mut self, other: &Self) {
         let min_words = other.words.len().min(self.words.len());
         for idx in 0..min_words {
-            self.words[idx] &= !other.words[idx];
+            let both = self.words[idx] & other.words[idx];
+            self.words[idx] &= !self.same_generation_mask(other, idx, both);
         }
+        self.clear_unselected_generations();
         self.recount_len();
     }
 
+    /// Bits of `candidates` (in word `idx`) whose generation is the same in both sets.
+    fn same_generation_mask(&self, other: &Self, idx: usize, candidates: u64) -> u64 {
+        set_bits(candidates)
+            .filter(|&bit| {
+                let i = idx * 64 + bit as usize;
+                self.generations[i] == other.generations[i]
+            })
+            .fold(0, |mask, bit| mask | (1u64 << bit))
+    }
+
+    fn clear_unselected_generations(&mut self) {
+        for (idx, &w) in self.words.iter().enumerate() {
+            for bit in set_bits(!w) {
+                self.generations[idx * 64 + bit as usize] = 0;
+            }
+        }
+    }
+
     /// Iterates selected entity indices in ascending order.
     pub fn iter_indices(&self) -> impl Iterator<Item = u32> + '_ {
         SelectionIndexIter {
@@ -147,17 +193,19 @@ impl SelectionSet {
         }
     }
 
-    /// Iterates selected entities in ascending index order.
-    ///
-    /// Note: this uses generation 0 handles, matching the current `World` behavior.
+    /// Iterates selected entities, with the generations they were inserted with, in
+    /// ascending index order. Check them with `World::is_alive` before use when entities
+    /// may have been despawned since.
     pub fn iter_entities(&self) -> impl Iterator<Item = EntityId> + '_ {
-        self.iter_indices().map(|idx| EntityId(Handle::new(idx, 0)))
+        self.iter_indices()
+            .map(|idx| EntityId(Handle::new(idx, self.generations[idx as usize])))
     }
 
     fn ensure_capacity(&mut self, index: u32) {
         let (word, _bit) = word_bit(index);
         if self.words.len() <= word {
             self.words.resize(word + 1, 0);
+            self.generations.resize((word + 1) * 64, 0);
         }
     }
 
@@ -166,6 +214,10 @@ impl SelectionSet {
     }
 }
 
+fn set_bits(word: u64) -> impl Iterator<Item = u32> {
+    (0..64).filter(move |bit| word & (1u64 << bit) != 0)
+}
+
 fn word_bit(index: u32) -> (usize, u32) {
     let word = (index / 64) as usize;
     let bit = index % 64;
@@ -257,4 +309,38 @@ mod tests {
         let d: Vec<u32> = a.diff(&b).iter_indices().collect();
         assert_eq!(d, vec![1, 100]);
     }
+
+    #[test]
+    fn keeps_generations_of_members() {
+        let old = EntityId(Handle::new(3, 0));
+        let new = EntityId(Handle::new(3, 1));
+        let mut a = SelectionSet::new();
+        a.insert(e(1));
+        a.insert(new);
+        assert!(a.contains(new));
+        assert!(!a.contains(old));
+        assert!(!a.remove(old));
+        assert_eq!(a.iter_entities().collect::<Vec<_>>(), vec![e(1), new]);
+
+        let mut b = SelectionSet::new();
+        b.insert(old);
+        b.insert(e(1));
+        b.insert(EntityId(Handle::new(70, 4)));
+        assert_eq!(
+            a.intersect(&b).iter_entities().collect::<Vec<_>>(),
+            vec![e(1)]
+        );
+        assert_eq!(a.diff(&b).iter_entities().collect::<Vec<_>>(), vec![new]);
+        let u: Vec<_> = a.union(&b).iter_entities().collect();
+        assert_eq!(u, vec![e(1), new, EntityId(Handle::new(70, 4))]);
+
+        assert!(a.insert(old));
+        assert_eq!(a.len(), 2);
+        assert!(a.remove(old));
+        assert_eq!(a, {
+            let mut only = SelectionSet::with_max_index(3);
+            only.insert(e(1));
+            only
+        });
+    }
 }
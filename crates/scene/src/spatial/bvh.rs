@@ -186,11 +186,11 @@ fn build_node(nodes: &mut Vec<Node>, items: &mut [Item]) -> usize {
     idx
 }
 
-fn centroid_axis(aabb: &Aabb3, axis: usize) -> f64 {
+pub(super) fn centroid_axis(aabb: &Aabb3, axis: usize) -> f64 {
     (aabb.min[axis] + aabb.max[axis]) * 0.5
 }
 
-fn split_axis(bounds: &Aabb3) -> usize {
+pub(super) fn split_axis(bounds: &Aabb3) -> usize {
     let ex = bounds.max[0] - bounds.min[0];
     let ey = bounds.max[1] - bounds.min[1];
     let ez = bounds.max[2] - bounds.min[2];
@@ -213,7 +213,7 @@ fn bounds_for_items(items: &[Item]) -> Aabb3 {
     b
 }
 
-fn union_aabb3(a: &Aabb3, b: &Aabb3) -> Aabb3 {
+pub(super) fn union_aabb3(a: &Aabb3, b: &Aabb3) -> Aabb3 {
     Aabb3::new(
         [
             a.min[0].min(b.min[0]),
@@ -228,7 +228,7 @@ fn union_aabb3(a: &Aabb3, b: &Aabb3) -> Aabb3 {
     )
 }
 
-fn ray_intersects_aabb(
+pub(super) fn ray_intersects_aabb(
     origin: [f64; 3],
     dir: [f64; 3],
     aabb: &Aabb3,
@@ -0,0 +1,436 @@
+use foundation::bounds::Aabb3;
+use foundation::math::precision::stable_total_cmp_f64;
+
+use super::bvh::{centroid_axis, ray_intersects_aabb, split_axis, union_aabb3};
+use crate::entity::EntityId;
+
+const NONE: u32 = u32::MAX;
+
+/// Edits between automatic rebuilds never drop below this, so small trees are not rebuilt
+/// on every change.
+const MIN_EDITS_BEFORE_REBUILD: usize = 64;
+
+/// An incrementally maintained BVH with one entity per leaf.
+///
+/// Unlike [`super::Bvh`], which is built once from a snapshot, this tree supports
+/// `insert`, `remove` and in-place refits in O(log n) (for a reasonably balanced tree).
+/// Incremental edits slowly degrade the tree, so it is rebuilt top-down once the number
+/// of edits since the last rebuild exceeds the number of items, keeping the amortized
+/// cost logarithmic.
+///
+/// Ordering contract:
+/// - All queries return entities in ascending `EntityId::index()` order.
+/// - The tree shape only depends on the sequence of edits, so it is deterministic.
+#[derive(Debug, Clone, Default)]
+pub struct DynamicBvh {
+    nodes: Vec<Node>,
+    free: Vec<u32>,
+    root: u32,
+    /// Entity index -> leaf node.
+    leaves: Vec<u32>,
+    len: usize,
+    edits_since_rebuild: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    bounds: Aabb3,
+    parent: u32,
+    /// Children of an internal node; `NONE` for leaves.
+    left: u32,
+    right: u32,
+    entity: Option<EntityId>,
+}
+
+impl DynamicBvh {
+    pub fn new() -> Self {
+        Self {
+            root: NONE,
+            ..Self::default()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn leaf(&self, index: u32) -> Option<u32> {
+        match self.leaves.get(index as usize) {
+            Some(&node) if node != NONE => Some(node),
+            _ => None,
+        }
+    }
+
+    /// Stored bounds of `entity` (matched by index and generation).
+    pub fn bounds(&self, entity: EntityId) -> Option<Aabb3> {
+        let node = &self.nodes[self.leaf(entity.index())? as usize];
+        (node.entity == Some(entity)).then_some(node.bounds)
+    }
+
+    /// Inserts `entity`, or moves it to `bounds` if its index is already stored (a stale
+    /// generation at that index is replaced).
+    pub fn insert(&mut self, entity: EntityId, bounds: Aabb3) {
+        if let Some(leaf) = self.leaf(entity.index()) {
+            self.nodes[leaf as usize].entity = Some(entity);
+            self.refit_leaf(leaf, bounds);
+        } else {
+            let leaf = self.alloc(Node {
+                bounds,
+                parent: NONE,
+                left: NONE,
+                right: NONE,
+                entity: Some(entity),
+            });
+            let index = entity.index() as usize;
+            if self.leaves.len() <= index {
+                self.leaves.resize(index + 1, NONE);
+            }
+            self.leaves[index] = leaf;
+            self.insert_leaf(leaf);
+            self.len += 1;
+        }
+        self.note_edit();
+    }
+
+    /// Removes whatever is stored at `entity`'s index. Returns `false` if nothing was.
+    pub fn remove(&mut self, entity: EntityId) -> bool {
+        let Some(leaf) = self.leaf(entity.index()) else {
+            return false;
+        };
+        self.leaves[entity.index() as usize] = NONE;
+        self.detach_leaf(leaf);
+        self.free.push(leaf);
+        self.len -= 1;
+        self.note_edit();
+        true
+    }
+
+    /// Rebuilds the tree top-down from the stored leaves.
+    pub fn rebuild(&mut self) {
+        let mut items: Vec<(EntityId, Aabb3)> = self
+            .leaves
+            .iter()
+            .filter(|&&leaf| leaf != NONE)
+            .map(|&leaf| {
+                let node = &self.nodes[leaf as usize];
+                (node.entity.unwrap(), node.bounds)
+            })
+            .collect();
+        self.nodes.clear();
+        self.free.clear();
+        self.leaves.iter_mut().for_each(|leaf| *leaf = NONE);
+        self.root = if items.is_empty() {
+            NONE
+        } else {
+            self.build(&mut items, NONE)
+        };
+        self.edits_since_rebuild = 0;
+    }
+
+    /// Entities whose bounds satisfy `visit`, which is also called on internal node bounds
+    /// to prune subtrees. `visit` must hold for a node whenever it holds for anything inside it
+    /// (true for intersection tests).
+    pub fn query_with(&self, mut visit: impl FnMut(&Aabb3) -> bool) -> Vec<EntityId> {
+        let mut hits = Vec::new();
+        if self.root == NONE {
+            return hits;
+        }
+        let mut stack = vec![self.root];
+        while let Some(idx) = stack.pop() {
+            let node = &self.nodes[idx as usize];
+            if !visit(&node.bounds) {
+                continue;
+            }
+            match node.entity {
+                Some(entity) => hits.push(entity),
+                None => {
+                    stack.push(node.right);
+                    stack.push(node.left);
+                }
+            }
+        }
+        hits.sort_by_key(|e| e.index());
+        hits
+    }
+
+    pub fn query_aabb(&self, query: &Aabb3) -> Vec<EntityId> {
+        self.query_with(|b| b.intersects(query))
+    }
+
+    /// Entities whose bounds intersect the ray segment `[t_min, t_max]`.
+    pub fn query_ray(
+        &self,
+        origin: [f64; 3],
+        dir: [f64; 3],
+        t_min: f64,
+        t_max: f64,
+    ) -> Vec<EntityId> {
+        self.query_with(|b| ray_intersects_aabb(origin, dir, b, t_min, t_max))
+    }
+
+    /// Height of the tree (0 when empty), for diagnostics.
+    pub fn height(&self) -> usize {
+        fn walk(nodes: &[Node], idx: u32) -> usize {
+            if idx == NONE {
+                return 0;
+            }
+            let node = &nodes[idx as usize];
+            1 + walk(nodes, node.left).max(walk(nodes, node.right))
+        }
+        walk(&self.nodes, self.root)
+    }
+
+    fn note_edit(&mut self) {
+        self.edits_since_rebuild += 1;
+        if self.edits_since_rebuild > self.len.max(MIN_EDITS_BEFORE_REBUILD) {
+            self.rebuild();
+        }
+    }
+
+    fn alloc(&mut self, node: Node) -> u32 {
+        match self.free.pop() {
+            Some(idx) => {
+                self.nodes[idx as usize] = node;
+                idx
+            }
+            None => {
+                self.nodes.push(node);
+                (self.nodes.len() - 1) as u32
+            }
+        }
+    }
+
+    /// Moves a leaf to `bounds`: refits in place while the parent still encloses it,
+    /// otherwise reinserts it.
+    fn refit_leaf(&mut self, leaf: u32, bounds: Aabb3) {
+        let parent = self.nodes[leaf as usize].parent;
+        self.nodes[leaf as usize].bounds = bounds;
+        if parent == NONE || contains(&self.nodes[parent as usize].bounds, &bounds) {
+            self.refit_ancestors(parent);
+        } else {
+            self.detach_leaf(leaf);
+            self.insert_leaf(leaf);
+        }
+    }
+
+    /// Links a detached leaf next to the sibling that grows the least, by surface area.
+    fn insert_leaf(&mut self, leaf: u32) {
+        if self.root == NONE {
+            self.root = leaf;
+            self.nodes[leaf as usize].parent = NONE;
+            return;
+        }
+        let bounds = self.nodes[leaf as usize].bounds;
+        let mut sibling = self.root;
+        while self.nodes[sibling as usize].entity.is_none() {
+            let node = &self.nodes[sibling as usize];
+            let area = surface_area(&node.bounds);
+            let combined = surface_area(&union_aabb3(&node.bounds, &bounds));
+            // Cost of pairing with this node vs. pushing the leaf further down.
+            let cost_here = 2.0 * combined;
+            let inherited = 2.0 * (combined - area);
+            let descend_cost = |child: u32| {
+                let child = &self.nodes[child as usize];
+                let grown = surface_area(&union_aabb3(&child.bounds, &bounds));
+                let own = if child.entity.is_some() {
+                    0.0
+                } else {
+                    surface_area(&child.bounds)
+                };
+                grown - own + inherited
+            };
+            let (cost_left, cost_right) = (descend_cost(node.left), descend_cost(node.right));
+            if cost_here < cost_left && cost_here < cost_right {
+                break;
+            }
+            sibling = if cost_left <= cost_right {
+                node.left
+            } else {
+                node.right
+            };
+        }
+
+        let old_parent = self.nodes[sibling as usize].parent;
+        let sibling_bounds = self.nodes[sibling as usize].bounds;
+        let parent = self.alloc(Node {
+            bounds: union_aabb3(&sibling_bounds, &bounds),
+            parent: old_parent,
+            left: sibling,
+            right: leaf,
+            entity: None,
+        });
+        self.nodes[sibling as usize].parent = parent;
+        self.nodes[leaf as usize].parent = parent;
+        if old_parent == NONE {
+            self.root = parent;
+        } else {
+            let p = &mut self.nodes[old_parent as usize];
+            if p.left == sibling {
+                p.left = parent;
+            } else {
+                p.right = parent;
+            }
+        }
+        self.refit_ancestors(old_parent);
+    }
+
+    /// Unlinks a leaf, replacing its parent by its sibling. The leaf node stays allocated.
+    fn detach_leaf(&mut self, leaf: u32) {
+        let parent = self.nodes[leaf as usize].parent;
+        if parent == NONE {
+            self.root = NONE;
+            return;
+        }
+        let p = &self.nodes[parent as usize];
+        let sibling = if p.left == leaf { p.right } else { p.left };
+        let grandparent = p.parent;
+        self.nodes[sibling as usize].parent = grandparent;
+        if grandparent == NONE {
+            self.root = sibling;
+        } else {
+            let g = &mut self.nodes[grandparent as usize];
+            if g.left == parent {
+                g.left = sibling;
+            } else {
+                g.right = sibling;
+            }
+        }
+        self.free.push(parent);
+        self.nodes[leaf as usize].parent = NONE;
+        self.refit_ancestors(grandparent);
+    }
+
+    fn refit_ancestors(&mut self, mut idx: u32) {
+        while idx != NONE {
+            let node = &self.nodes[idx as usize];
+            let bounds = union_aabb3(
+                &self.nodes[node.left as usize].bounds,
+                &self.nodes[node.right as usize].bounds,
+            );
+            let parent = node.parent;
+            self.nodes[idx as usize].bounds = bounds;
+            idx = parent;
+        }
+    }
+
+    /// Median split on the longest axis, like [`super::Bvh::build`], one item per leaf.
+    fn build(&mut self, items: &mut [(EntityId, Aabb3)], parent: u32) -> u32 {
+        if let [(entity, bounds)] = items {
+            let leaf = self.alloc(Node {
+                bounds: *bounds,
+                parent,
+                left: NONE,
+                right: NONE,
+                entity: Some(*entity),
+            });
+            self.leaves[entity.index() as usize] = leaf;
+            return leaf;
+        }
+
+        let bounds = items[1..]
+            .iter()
+            .fold(items[0].1, |acc, (_, b)| union_aabb3(&acc, b));
+        let axis = split_axis(&bounds);
+        items.sort_by(|a, b| {
+            stable_total_cmp_f64(centroid_axis(&a.1, axis), centroid_axis(&b.1, axis))
+                .then_with(|| a.0.index().cmp(&b.0.index()))
+        });
+        let idx = self.alloc(Node {
+            bounds,
+            parent,
+            left: NONE,
+            right: NONE,
+            entity: None,
+        });
+        let (left_items, right_items) = items.split_at_mut(items.len() / 2);
+        let left = self.build(left_items, idx);
+        let right = self.build(right_items, idx);
+        let node = &mut self.nodes[idx as usize];
+        node.left = left;
+        node.right = right;
+        idx
+    }
+}
+
+fn surface_area(b: &Aabb3) -> f64 {
+    let dx = b.max[0] - b.min[0];
+    let dy = b.max[1] - b.min[1];
+    let dz = b.max[2] - b.min[2];
+    dx * dy + dy * dz + dz * dx
+}
+
+fn contains(outer: &Aabb3, inner: &Aabb3) -> bool {
+    (0..3).all(|axis| outer.min[axis] <= inner.min[axis] && inner.max[axis] <= outer.max[axis])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DynamicBvh;
+    use crate::entity::EntityId;
+    use foundation::bounds::Aabb3;
+    use foundation::handles::Handle;
+
+    fn e(idx: u32) -> EntityId {
+        EntityId(Handle::new(idx, 0))
+    }
+
+    fn cube(x: f64, y: f64, size: f64) -> Aabb3 {
+        Aabb3::new([x, y, 0.0], [x + size, y + size, size])
+    }
+
+    #[test]
+    fn incremental_edits_match_brute_force() {
+        let mut tree = DynamicBvh::new();
+        let mut reference: Vec<Option<Aabb3>> = vec![None; 300];
+        let mut seed = 0x9e37_79b9_7f4a_7c15u64;
+        let mut next = move |n: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % n
+        };
+        for step in 0..4000 {
+            let idx = next(300) as u32;
+            if next(4) == 0 {
+                assert_eq!(tree.remove(e(idx)), reference[idx as usize].is_some());
+                reference[idx as usize] = None;
+            } else {
+                let b = cube(next(1000) as f64, next(1000) as f64, 1.0 + next(20) as f64);
+                tree.insert(e(idx), b);
+                reference[idx as usize] = Some(b);
+            }
+
+            if step % 100 == 0 {
+                let q = cube(next(900) as f64, next(900) as f64, 150.0);
+                let expected: Vec<_> = (0..300)
+                    .filter(|&i| reference[i as usize].is_some_and(|b| b.intersects(&q)))
+                    .map(e)
+                    .collect();
+                assert_eq!(tree.query_aabb(&q), expected);
+                assert_eq!(tree.len(), reference.iter().flatten().count());
+            }
+        }
+        assert_eq!(tree.bounds(e(7)), reference[7]);
+    }
+
+    #[test]
+    fn rebuild_keeps_contents_and_balances() {
+        let mut tree = DynamicBvh::new();
+        // Sorted inserts are the worst case for greedy insertion.
+        for i in 0..1024 {
+            tree.insert(e(i), cube(i as f64 * 2.0, 0.0, 1.0));
+        }
+        tree.rebuild();
+        assert_eq!(tree.height(), 11);
+        assert_eq!(
+            tree.query_ray([-1.0, 0.5, 0.5], [1.0, 0.0, 0.0], 0.0, 5.0),
+            vec![e(0), e(1), e(2)]
+        );
+        assert!(tree.remove(e(1)));
+        assert_eq!(tree.query_aabb(&cube(0.0, 0.0, 4.0)), vec![e(0), e(2)]);
+    }
+}
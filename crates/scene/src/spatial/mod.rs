@@ -1,5 +1,7 @@
 pub mod bvh;
+pub mod dynamic_bvh;
 pub mod quadtree;
 
 pub use bvh::*;
+pub use dynamic_bvh::*;
 pub use quadtree::*;
@@ -0,0 +1,367 @@
+//! Generic component storage: one sparse set per component type.
+//!
+//! [`crate::World`] keeps both its built-in components (`Transform`, `Bounds`, ...) and
+//! application-defined ones here. Each set maps an entity index to a slot in a densely
+//! packed array, so insert, lookup and removal are O(1) and iteration touches only the
+//! entities that have the component. Query results are always in ascending entity index
+//! order, independent of insertion history; that order is cached per set and only
+//! rebuilt after its membership changed out of order.
+
+use std::any::{Any, TypeId, type_name};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::entity::EntityId;
+
+/// Ticks of the last insert and the last mutable access of a component.
+///
+/// Ticks come from [`crate::World::change_tick`]; a component counts as changed since
+/// tick `t` when `changed > t`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ComponentTicks {
+    pub added: u64,
+    pub changed: u64,
+}
+
+const EMPTY: u32 = u32::MAX;
+
+/// Components of one type, keyed by entity index.
+pub struct SparseSet<T> {
+    /// Entity index -> position in the dense arrays, or `EMPTY`.
+    sparse: Vec<u32>,
+    entities: Vec<EntityId>,
+    values: Vec<T>,
+    ticks: Vec<ComponentTicks>,
+    /// `entities` in ascending index order, built on first use after it was cleared.
+    sorted: OnceLock<Vec<EntityId>>,
+}
+
+impl<T> Default for SparseSet<T> {
+    fn default() -> Self {
+        Self {
+            sparse: Vec::new(),
+            entities: Vec::new(),
+            values: Vec::new(),
+            ticks: Vec::new(),
+            sorted: OnceLock::new(),
+        }
+    }
+}
+
+impl<T> SparseSet<T> {
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    fn position(&self, index: u32) -> Option<usize> {
+        match self.sparse.get(index as usize) {
+            Some(&pos) if pos != EMPTY => Some(pos as usize),
+            _ => None,
+        }
+    }
+
+    pub fn contains(&self, index: u32) -> bool {
+        self.position(index).is_some()
+    }
+
+    /// Inserts or replaces the component of `entity`, returning the previous value.
+    pub fn insert(&mut self, entity: EntityId, value: T, tick: u64) -> Option<T> {
+        let index = entity.index();
+        if let Some(pos) = self.position(index) {
+            if self.entities[pos] != entity {
+                self.sorted.take();
+            }
+            self.entities[pos] = entity;
+            self.ticks[pos] = ComponentTicks {
+                added: tick,
+                changed: tick,
+            };
+            return Some(std::mem::replace(&mut self.values[pos], value));
+        }
+        if self.sparse.len() <= index as usize {
+            self.sparse.resize(index as usize + 1, EMPTY);
+        }
+        self.sparse[index as usize] = self.values.len() as u32;
+        // Appending past the highest index (e.g. freshly spawned entities) keeps the
+        // cached order valid.
+        match self.sorted.get_mut() {
+            Some(sorted) if sorted.last().is_none_or(|last| last.index() < index) => {
+                sorted.push(entity)
+            }
+            _ => {
+                self.sorted.take();
+            }
+        }
+        self.entities.push(entity);
+        self.values.push(value);
+        self.ticks.push(ComponentTicks {
+            added: tick,
+            changed: tick,
+        });
+        None
+    }
+
+    pub fn get(&self, index: u32) -> Option<&T> {
+        self.position(index).map(|pos| &self.values[pos])
+    }
+
+    /// Mutable access; marks the component changed at `tick`.
+    pub fn get_mut(&mut self, index: u32, tick: u64) -> Option<&mut T> {
+        let pos = self.position(index)?;
+        self.ticks[pos].changed = tick;
+        Some(&mut self.values[pos])
+    }
+
+    pub fn ticks(&self, index: u32) -> Option<ComponentTicks> {
+        self.position(index).map(|pos| self.ticks[pos])
+    }
+
+    pub fn remove(&mut self, index: u32) -> Option<T> {
+        let pos = self.position(index)?;
+        self.sparse[index as usize] = EMPTY;
+        self.sorted.take();
+        self.entities.swap_remove(pos);
+        self.ticks.swap_remove(pos);
+        let value = self.values.swap_remove(pos);
+        if let Some(moved) = self.entities.get(pos) {
+            self.sparse[moved.index() as usize] = pos as u32;
+        }
+        Some(value)
+    }
+
+    /// Entities with this component, in ascending entity index order.
+    pub fn entities(&self) -> &[EntityId] {
+        self.sorted.get_or_init(|| {
+            let mut sorted = self.entities.clone();
+            sorted.sort_unstable_by_key(|e| e.index());
+            sorted
+        })
+    }
+
+    /// `(entity, component)` pairs in ascending entity index order.
+    pub fn iter(&self) -> impl Iterator<Item = (EntityId, &T)> {
+        self.entities()
+            .iter()
+            .map(|&e| (e, &self.values[self.sparse[e.index() as usize] as usize]))
+    }
+}
+
+trait AnyStorage: Any + Send + Sync {
+    fn remove_entity(&mut self, index: u32);
+    fn type_name(&self) -> &'static str;
+    fn len(&self) -> usize;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Any + Send + Sync> AnyStorage for SparseSet<T> {
+    fn remove_entity(&mut self, index: u32) {
+        self.remove(index);
+    }
+
+    fn type_name(&self) -> &'static str {
+        type_name::<T>()
+    }
+
+    fn len(&self) -> usize {
+        SparseSet::len(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// One [`SparseSet`] per component type.
+#[derive(Default)]
+pub struct Components {
+    sets: BTreeMap<TypeId, Box<dyn AnyStorage>>,
+}
+
+impl fmt::Debug for Components {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.sets.values().map(|s| (s.type_name(), s.len())))
+            .finish()
+    }
+}
+
+impl Components {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn storage<T: Any + Send + Sync>(&self) -> Option<&SparseSet<T>> {
+        self.sets.get(&TypeId::of::<T>())?.as_any().downcast_ref()
+    }
+
+    /// The set for `T`, created empty on first use.
+    pub fn storage_mut<T: Any + Send + Sync>(&mut self) -> &mut SparseSet<T> {
+        self.sets
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(SparseSet::<T>::default()))
+            .as_any_mut()
+            .downcast_mut()
+            .unwrap()
+    }
+
+    /// Removes every component of the entity at `index`.
+    pub fn remove_entity(&mut self, index: u32) {
+        for set in self.sets.values_mut() {
+            set.remove_entity(index);
+        }
+    }
+
+    /// Entities matching `Q`, in ascending entity index order.
+    pub fn query<Q: ComponentQuery>(&self) -> Vec<(EntityId, Q::Item<'_>)> {
+        let Some(driver) = Q::driver(self) else {
+            return Vec::new();
+        };
+        driver
+            .iter()
+            .filter_map(|&e| Q::fetch(self, e.index()).map(|item| (e, item)))
+            .collect()
+    }
+}
+
+/// Component types fetched together by [`crate::World::query`]: `&T` or a tuple of up
+/// to four of them, e.g. `(&Transform, &TrackState)`.
+pub trait ComponentQuery {
+    type Item<'w>;
+
+    /// Entities of the smallest set involved in ascending index order, or `None` when a
+    /// set does not exist (then nothing can match).
+    fn driver(components: &Components) -> Option<&[EntityId]>;
+
+    fn fetch(components: &Components, index: u32) -> Option<Self::Item<'_>>;
+}
+
+impl<T: Any + Send + Sync> ComponentQuery for &T {
+    type Item<'w> = &'w T;
+
+    fn driver(components: &Components) -> Option<&[EntityId]> {
+        components.storage::<T>().map(SparseSet::entities)
+    }
+
+    fn fetch(components: &Components, index: u32) -> Option<&T> {
+        components.storage::<T>()?.get(index)
+    }
+}
+
+macro_rules! tuple_query {
+    ($($q:ident),+) => {
+        impl<$($q: ComponentQuery),+> ComponentQuery for ($($q,)+) {
+            type Item<'w> = ($($q::Item<'w>,)+);
+
+            fn driver(components: &Components) -> Option<&[EntityId]> {
+                let drivers = [$($q::driver(components)?),+];
+                drivers.into_iter().min_by_key(|d| d.len())
+            }
+
+            fn fetch(components: &Components, index: u32) -> Option<Self::Item<'_>> {
+                Some(($($q::fetch(components, index)?,)+))
+            }
+        }
+    };
+}
+
+tuple_query!(A);
+tuple_query!(A, B);
+tuple_query!(A, B, C);
+tuple_query!(A, B, C, D);
+
+#[cfg(test)]
+mod tests {
+    use super::{ComponentTicks, Components, SparseSet};
+    use crate::entity::EntityId;
+    use foundation::handles::Handle;
+
+    fn e(idx: u32) -> EntityId {
+        EntityId(Handle::new(idx, 0))
+    }
+
+    #[test]
+    fn sparse_set_insert_remove_keeps_lookups_valid() {
+        let mut set = SparseSet::default();
+        for i in [5, 1, 9, 3] {
+            assert_eq!(set.insert(e(i), i * 10, 1), None);
+        }
+        assert_eq!(set.insert(e(9), 91, 2), Some(90));
+        assert_eq!(set.remove(1), Some(10));
+        assert_eq!(set.remove(1), None);
+        // Removing moved the last dense entry; it must still resolve.
+        assert_eq!(set.get(3), Some(&30));
+        *set.get_mut(5, 4).unwrap() += 1;
+        assert_eq!(
+            set.ticks(5),
+            Some(ComponentTicks {
+                added: 1,
+                changed: 4
+            })
+        );
+        let items: Vec<_> = set.iter().map(|(e, v)| (e.index(), *v)).collect();
+        assert_eq!(items, vec![(3, 30), (5, 51), (9, 91)]);
+    }
+
+    #[test]
+    fn cached_index_order_follows_membership_changes() {
+        let mut set = SparseSet::default();
+        let indices = |set: &SparseSet<u32>| -> Vec<u32> {
+            set.entities().iter().map(|e| e.index()).collect()
+        };
+        for i in [2, 6] {
+            set.insert(e(i), i, 1);
+        }
+        assert_eq!(indices(&set), [2, 6]);
+        set.insert(e(8), 8, 1);
+        set.insert(e(0), 0, 1);
+        assert_eq!(indices(&set), [0, 2, 6, 8]);
+        set.remove(2);
+        set.insert(EntityId(Handle::new(6, 1)), 61, 2);
+        let items: Vec<_> = set.iter().map(|(e, v)| (e.index(), e.0, *v)).collect();
+        assert_eq!(
+            items,
+            [
+                (0, Handle::new(0, 0), 0),
+                (6, Handle::new(6, 1), 61),
+                (8, Handle::new(8, 0), 8)
+            ]
+        );
+    }
+
+    #[test]
+    fn queries_join_sets_in_index_order() {
+        #[derive(Debug, PartialEq)]
+        struct Score(u32);
+        #[derive(Debug, PartialEq)]
+        struct Track(&'static str);
+
+        let mut c = Components::new();
+        for i in [4, 0, 2, 7] {
+            c.storage_mut::<Score>().insert(e(i), Score(i), 1);
+        }
+        c.storage_mut::<Track>().insert(e(7), Track("b"), 1);
+        c.storage_mut::<Track>().insert(e(2), Track("a"), 1);
+
+        let joined: Vec<_> = c
+            .query::<(&Score, &Track)>()
+            .into_iter()
+            .map(|(e, (s, t))| (e.index(), s.0, t.0))
+            .collect();
+        assert_eq!(joined, vec![(2, 2, "a"), (7, 7, "b")]);
+        assert_eq!(c.query::<&Score>().len(), 4);
+        assert!(c.query::<(&Score, &u8)>().is_empty());
+
+        c.remove_entity(7);
+        assert_eq!(c.query::<(&Track, &Score)>().len(), 1);
+    }
+}
@@ -124,20 +124,11 @@ fn choose_center(items: &[IntervalItem]) -> f64 {
     endpoints[endpoints.len() / 2]
 }
 
-fn contains_time(span: TimeSpan, t: f64) -> bool {
-    // Treat endpoints as inclusive.
-    t >= span.start.0 && t <= span.end.0
-}
-
-fn overlaps(a: TimeSpan, b: TimeSpan) -> bool {
-    !(a.end.0 < b.start.0 || a.start.0 > b.end.0)
-}
-
 fn query_time(nodes: &[Node], idx: usize, t: f64, out: &mut Vec<EntityId>) {
     let node = &nodes[idx];
 
     for item in &node.items {
-        if contains_time(item.span, t) {
+        if item.span.contains(Time(t)) {
             out.push(item.entity);
         }
     }
@@ -155,7 +146,7 @@ fn query_overlaps(nodes: &[Node], idx: usize, span: TimeSpan, out: &mut Vec<Enti
     let node = &nodes[idx];
 
     for item in &node.items {
-        if overlaps(item.span, span) {
+        if item.span.overlaps(&span) {
             out.push(item.entity);
         }
     }
@@ -184,10 +175,7 @@ mod tests {
     }
 
     fn span(a: f64, b: f64) -> TimeSpan {
-        TimeSpan {
-            start: Time(a),
-            end: Time(b),
-        }
+        TimeSpan::new(Time(a), Time(b))
     }
 
     #[test]
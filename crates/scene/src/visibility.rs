@@ -162,9 +162,10 @@ impl Default for FrustumCullQuery {
 /// Cull vector entities against a world-space frustum.
 ///
 /// Notes:
-/// - This uses entity bounds (`World::bounds`) as a proxy for geometry.
+/// - This uses entity bounds (`World::bounds`) as a proxy for geometry; whole subtrees of
+///   the world's spatial index outside the frustum are skipped.
 /// - Entities without explicit bounds are ignored.
-/// - Visibility gating is inherited from `World::vector_geometries_by_entity()`.
+/// - Visibility gating matches `World::vector_geometries_by_entity()`.
 pub fn cull_vector_entities_in_frustum(
     world: &World,
     frustum: &Frustum,
@@ -172,22 +173,16 @@ pub fn cull_vector_entities_in_frustum(
 ) -> SelectionSet {
     let mut out = SelectionSet::new();
 
-    for (entity, _transform, component) in world.vector_geometries_by_entity() {
+    for entity in world.entities_where(|b| frustum.intersects_aabb(b)) {
+        let Some(component) = world.visible_vector_geometry(entity) else {
+            continue;
+        };
         if let Some(kind) = query.kind
             && component.kind != kind
         {
             continue;
         }
 
-        let Some(b) = world.bounds(entity) else {
-            continue;
-        };
-
-        let aabb = Aabb3::new([b.min.x, b.min.y, b.min.z], [b.max.x, b.max.y, b.max.z]);
-        if !frustum.intersects_aabb(&aabb) {
-            continue;
-        }
-
         out.insert(entity);
         if out.len() >= query.limit {
             break;
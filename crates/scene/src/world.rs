@@ -1,23 +1,150 @@
+use std::any::{Any, TypeId};
+use std::collections::BTreeSet;
+use std::sync::{Mutex, MutexGuard};
+
 use crate::components::{
-    ComponentBounds, ComponentProperties, ComponentTimeSpan, ComponentVectorGeometry, Drawable2D,
-    Drawable3D, Transform, VectorGeometry, VectorGeometryId, Visibility,
+    ComponentBounds, ComponentOrbit, ComponentProperties, ComponentTimeSpan,
+    ComponentVectorGeometry, Drawable2D, Drawable3D, Transform, VectorGeometry, VectorGeometryId,
+    Visibility,
 };
 use crate::entity::EntityId;
-use foundation::handles::Handle;
+use crate::spatial::DynamicBvh;
+use crate::storage::{ComponentQuery, ComponentTicks, Components};
+use foundation::bounds::Aabb3;
+use foundation::handles::{Handle, HandleAllocator};
+use foundation::math::Vec3;
 use foundation::time::{Time, TimeSpan};
 
-#[derive(Debug, Default)]
+/// Entities and their components.
+///
+/// Components of any `'static + Send + Sync` type live in per-type sparse sets
+/// ([`crate::storage`]); the built-in ones (`Transform`, `ComponentBounds`, ...) are
+/// stored the same way and keep their dedicated accessors. Applications attach their own
+/// data with [`World::insert`] and read it with [`World::get`] or [`World::query`].
+///
+/// Entities are generational handles: [`World::despawn`] clears every component of the
+/// entity and recycles its index with a bumped generation, so stale [`EntityId`]s read
+/// back as missing and writes through them are ignored.
+///
+/// Vector geometries are reference counted by the [`ComponentVectorGeometry`] components
+/// pointing at them: when the last one is removed, replaced or despawned, the geometry is
+/// dropped and its slot reused under a new [`VectorGeometryId`] generation.
+///
+/// Entities with [`ComponentBounds`] are kept in a [`DynamicBvh`]. Writes only record
+/// which entities changed; the next spatial query ([`World::entities_in_aabb`], ...)
+/// applies them to the tree, so a query costs O(log n + changes) rather than a rebuild.
+#[derive(Debug)]
 pub struct World {
-    next_index: u32,
-    transforms: Vec<Option<Transform>>,
-    bounds: Vec<Option<ComponentBounds>>,
-    visibility: Vec<Option<Visibility>>,
-    time_spans: Vec<Option<ComponentTimeSpan>>,
-    properties: Vec<Option<ComponentProperties>>,
-    drawables_2d: Vec<Option<Drawable2D>>,
-    drawables_3d: Vec<Option<Drawable3D>>,
-    vector_geometry: Vec<Option<ComponentVectorGeometry>>,
-    vector_geometries: Vec<VectorGeometry>,
+    entities: HandleAllocator,
+    components: Components,
+    vector_geometries: GeometryStore,
+    change_tick: u64,
+    spatial: Mutex<SpatialIndex>,
+}
+
+#[derive(Debug, Default)]
+struct GeometryStore {
+    handles: HandleAllocator,
+    slots: Vec<Option<VectorGeometry>>,
+    /// Components referencing each slot.
+    refs: Vec<u32>,
+    /// Set by `get_mut` on an existing [`ComponentVectorGeometry`], which may retarget it;
+    /// `refs` is recounted by [`GeometryStore::sync`] before the next change to geometries
+    /// or component storage.
+    refs_stale: bool,
+}
+
+impl GeometryStore {
+    fn add(&mut self, geometry: VectorGeometry) -> VectorGeometryId {
+        let id = VectorGeometryId(self.handles.alloc());
+        let index = id.index() as usize;
+        if self.slots.len() <= index {
+            self.slots.resize(index + 1, None);
+            self.refs.resize(index + 1, 0);
+        }
+        self.slots[index] = Some(geometry);
+        id
+    }
+
+    fn get(&self, id: VectorGeometryId) -> Option<&VectorGeometry> {
+        if !self.handles.is_valid(id.0) {
+            return None;
+        }
+        self.slots.get(id.index() as usize)?.as_ref()
+    }
+
+    fn len(&self) -> usize {
+        self.handles.capacity() - self.handles.free_len()
+    }
+
+    fn retain(&mut self, id: VectorGeometryId) {
+        if self.handles.is_valid(id.0) {
+            self.refs[id.index() as usize] += 1;
+        }
+    }
+
+    /// Drops a reference; frees the geometry when it was the last.
+    fn release(&mut self, id: VectorGeometryId) {
+        if !self.handles.is_valid(id.0) {
+            return;
+        }
+        let refs = &mut self.refs[id.index() as usize];
+        *refs = refs.saturating_sub(1);
+        if *refs == 0 {
+            self.free(id.index());
+        }
+    }
+
+    fn free(&mut self, index: u32) {
+        let generation = self.handles.generation(index).unwrap_or_default();
+        self.handles.free(Handle::new(index, generation));
+        self.slots[index as usize] = None;
+        self.refs[index as usize] = 0;
+    }
+
+    /// Rebuilds `refs` from `components` after a possible retarget; geometries that lost
+    /// their last reference are freed. Must run while `components` still matches `refs`,
+    /// i.e. before the storage change that the following `retain`/`release` accounts for.
+    fn sync(&mut self, components: &Components) {
+        if !std::mem::take(&mut self.refs_stale) {
+            return;
+        }
+        let before = std::mem::replace(&mut self.refs, vec![0; self.slots.len()]);
+        if let Some(set) = components.storage::<ComponentVectorGeometry>() {
+            for (_, component) in set.iter() {
+                if self.handles.is_valid(component.id.0) {
+                    self.refs[component.id.index() as usize] += 1;
+                }
+            }
+        }
+        for (index, &count) in before.iter().enumerate() {
+            if count > 0 && self.refs[index] == 0 && self.slots[index].is_some() {
+                self.free(index as u32);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct SpatialIndex {
+    tree: DynamicBvh,
+    /// Entity indices whose bounds may differ from the tree.
+    dirty: BTreeSet<u32>,
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self {
+            entities: HandleAllocator::new(),
+            components: Components::new(),
+            vector_geometries: GeometryStore::default(),
+            change_tick: 1,
+            spatial: Mutex::new(SpatialIndex {
+                tree: DynamicBvh::new(),
+                dirty: BTreeSet::new(),
+            }),
+        }
+    }
 }
 
 impl World {
@@ -26,207 +153,374 @@ impl World {
     }
 
     pub fn spawn(&mut self) -> EntityId {
-        let id = EntityId(Handle::new(self.next_index, 0));
-        self.next_index += 1;
-        let idx = id.index() as usize;
-        self.ensure_capacity(idx);
-        id
+        EntityId(self.entities.alloc())
+    }
+
+    /// Removes `entity` and all of its components. Returns `false` for stale or unknown
+    /// ids. The index is reused by a later [`World::spawn`] with a new generation.
+    pub fn despawn(&mut self, entity: EntityId) -> bool {
+        if !self.entities.free(entity.0) {
+            return false;
+        }
+        self.vector_geometries.sync(&self.components);
+        let geometry = self
+            .components
+            .storage::<ComponentVectorGeometry>()
+            .and_then(|set| set.get(entity.index()))
+            .map(|c| c.id);
+        self.components.remove_entity(entity.index());
+        if let Some(id) = geometry {
+            self.vector_geometries.release(id);
+        }
+        self.mark_bounds_dirty(entity);
+        true
+    }
+
+    pub fn is_alive(&self, entity: EntityId) -> bool {
+        self.entities.is_valid(entity.0)
+    }
+
+    /// Number of live entities.
+    pub fn entity_count(&self) -> usize {
+        self.entities.capacity() - self.entities.free_len()
+    }
+
+    /// The live entity stored at `index`, whatever its generation.
+    pub fn entity_at(&self, index: u32) -> Option<EntityId> {
+        let entity = EntityId(Handle::new(index, self.entities.generation(index)?));
+        self.is_alive(entity).then_some(entity)
+    }
+
+    /// Tick recorded by inserts and mutable accesses; starts at 1.
+    pub fn change_tick(&self) -> u64 {
+        self.change_tick
+    }
+
+    /// Starts a new change tick (typically once per frame) and returns it. Systems that
+    /// remember the tick they last ran at see later writes via [`World::changed_since`].
+    pub fn advance_change_tick(&mut self) -> u64 {
+        self.change_tick += 1;
+        self.change_tick
+    }
+
+    /// Attaches `component` to a live entity, replacing and returning a previous value of
+    /// the same type. Stale ids are ignored (the value is dropped and `None` returned).
+    pub fn insert<T: Any + Send + Sync>(&mut self, entity: EntityId, component: T) -> Option<T> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        self.note_write::<T>(entity);
+        self.vector_geometries.sync(&self.components);
+        let geometry = geometry_of(&component);
+        let previous =
+            self.components
+                .storage_mut::<T>()
+                .insert(entity, component, self.change_tick);
+        if let Some(id) = geometry {
+            self.vector_geometries.retain(id);
+        }
+        if let Some(id) = previous.as_ref().and_then(geometry_of) {
+            self.vector_geometries.release(id);
+        }
+        previous
+    }
+
+    pub fn get<T: Any + Send + Sync>(&self, entity: EntityId) -> Option<&T> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        self.components.storage::<T>()?.get(entity.index())
+    }
+
+    /// Mutable access; marks the component changed at the current tick.
+    pub fn get_mut<T: Any + Send + Sync>(&mut self, entity: EntityId) -> Option<&mut T> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        self.note_write::<T>(entity);
+        if TypeId::of::<T>() == TypeId::of::<ComponentVectorGeometry>()
+            && self.has::<ComponentVectorGeometry>(entity)
+        {
+            self.vector_geometries.refs_stale = true;
+        }
+        let tick = self.change_tick;
+        self.components
+            .storage_mut::<T>()
+            .get_mut(entity.index(), tick)
+    }
+
+    pub fn remove<T: Any + Send + Sync>(&mut self, entity: EntityId) -> Option<T> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        self.note_write::<T>(entity);
+        self.vector_geometries.sync(&self.components);
+        let removed = self.components.storage_mut::<T>().remove(entity.index());
+        if let Some(id) = removed.as_ref().and_then(geometry_of) {
+            self.vector_geometries.release(id);
+        }
+        removed
+    }
+
+    pub fn has<T: Any + Send + Sync>(&self, entity: EntityId) -> bool {
+        self.get::<T>(entity).is_some()
+    }
+
+    pub fn ticks<T: Any + Send + Sync>(&self, entity: EntityId) -> Option<ComponentTicks> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        self.components.storage::<T>()?.ticks(entity.index())
+    }
+
+    /// Entities having every component of `Q`, in ascending entity index order:
+    /// `world.query::<(&Transform, &TrackState)>()`.
+    pub fn query<Q: ComponentQuery>(&self) -> Vec<(EntityId, Q::Item<'_>)> {
+        self.components.query::<Q>()
+    }
+
+    /// `T` components inserted or mutably accessed after tick `since`, in ascending
+    /// entity index order.
+    pub fn changed_since<T: Any + Send + Sync>(&self, since: u64) -> Vec<(EntityId, &T)> {
+        let Some(set) = self.components.storage::<T>() else {
+            return Vec::new();
+        };
+        set.iter()
+            .filter(|(e, _)| set.ticks(e.index()).is_some_and(|t| t.changed > since))
+            .collect()
+    }
+
+    pub fn components(&self) -> &Components {
+        &self.components
+    }
+
+    fn note_write<T: Any>(&mut self, entity: EntityId) {
+        if TypeId::of::<T>() == TypeId::of::<ComponentBounds>() {
+            self.mark_bounds_dirty(entity);
+        }
+    }
+
+    fn mark_bounds_dirty(&mut self, entity: EntityId) {
+        let spatial = self.spatial.get_mut().unwrap_or_else(|e| e.into_inner());
+        spatial.dirty.insert(entity.index());
+    }
+
+    /// The spatial index with pending bounds changes applied.
+    fn spatial_index(&self) -> MutexGuard<'_, SpatialIndex> {
+        let mut spatial = self.spatial.lock().unwrap_or_else(|e| e.into_inner());
+        let SpatialIndex { tree, dirty } = &mut *spatial;
+        for index in std::mem::take(dirty) {
+            let entry = self
+                .entity_at(index)
+                .and_then(|entity| Some((entity, self.bounds(entity)?)));
+            match entry {
+                Some((entity, bounds)) => tree.insert(entity, bounds.to_aabb3()),
+                None => {
+                    tree.remove(EntityId(Handle::new(index, 0)));
+                }
+            }
+        }
+        spatial
+    }
+
+    /// Entities whose bounds intersect `aabb`, in ascending entity index order.
+    pub fn entities_in_aabb(&self, aabb: &Aabb3) -> Vec<EntityId> {
+        self.spatial_index().tree.query_aabb(aabb)
+    }
+
+    /// Entities whose bounds intersect the ray segment `origin + t * dir` for
+    /// `t` in `[t_min, t_max]`, in ascending entity index order.
+    pub fn entities_on_ray(
+        &self,
+        origin: [f64; 3],
+        dir: [f64; 3],
+        t_min: f64,
+        t_max: f64,
+    ) -> Vec<EntityId> {
+        self.spatial_index()
+            .tree
+            .query_ray(origin, dir, t_min, t_max)
+    }
+
+    /// Entities whose bounds pass `test`, in ascending entity index order. `test` is also
+    /// applied to enclosing node bounds to skip whole subtrees, so it must accept any box
+    /// containing one it accepts (as intersection tests do).
+    pub fn entities_where(&self, test: impl FnMut(&Aabb3) -> bool) -> Vec<EntityId> {
+        self.spatial_index().tree.query_with(test)
+    }
+
+    /// The vector geometry of `entity` if it would be listed by
+    /// [`World::vector_geometries_by_entity`] (visible and with a transform).
+    pub(crate) fn visible_vector_geometry(
+        &self,
+        entity: EntityId,
+    ) -> Option<ComponentVectorGeometry> {
+        let visible = self
+            .get::<Visibility>(entity)
+            .map(|v| v.visible)
+            .unwrap_or(true);
+        if !visible || !self.has::<Transform>(entity) {
+            return None;
+        }
+        self.vector_geometry_component(entity)
     }
 
     pub fn set_transform(&mut self, entity: EntityId, transform: Transform) {
-        self.ensure_capacity(entity.index() as usize);
-        self.transforms[entity.index() as usize] = Some(transform);
+        self.insert(entity, transform);
     }
 
     pub fn set_bounds(&mut self, entity: EntityId, bounds: ComponentBounds) {
-        self.ensure_capacity(entity.index() as usize);
-        self.bounds[entity.index() as usize] = Some(bounds);
+        self.insert(entity, bounds);
     }
 
     pub fn bounds(&self, entity: EntityId) -> Option<ComponentBounds> {
-        self.bounds.get(entity.index() as usize).and_then(|b| *b)
+        self.get(entity).copied()
     }
 
     pub fn set_visibility(&mut self, entity: EntityId, visibility: Visibility) {
-        self.ensure_capacity(entity.index() as usize);
-        self.visibility[entity.index() as usize] = Some(visibility);
+        self.insert(entity, visibility);
     }
 
     pub fn set_time_span(&mut self, entity: EntityId, span: ComponentTimeSpan) {
-        self.ensure_capacity(entity.index() as usize);
-        self.time_spans[entity.index() as usize] = Some(span);
+        self.insert(entity, span);
     }
 
     pub fn time_span(&self, entity: EntityId) -> Option<TimeSpan> {
-        self.time_spans
-            .get(entity.index() as usize)
-            .and_then(|s| *s)
-            .map(|c| c.span)
+        self.get::<ComponentTimeSpan>(entity).map(|c| c.span)
     }
 
     pub fn set_properties(&mut self, entity: EntityId, props: ComponentProperties) {
-        self.ensure_capacity(entity.index() as usize);
-        self.properties[entity.index() as usize] = Some(props);
+        self.insert(entity, props);
     }
 
     pub fn properties(&self, entity: EntityId) -> Option<&ComponentProperties> {
-        self.properties
-            .get(entity.index() as usize)
-            .and_then(|p| p.as_ref())
+        self.get(entity)
     }
 
     pub fn transform(&self, entity: EntityId) -> Option<Transform> {
-        self.transforms
-            .get(entity.index() as usize)
-            .and_then(|t| *t)
+        self.get(entity).copied()
     }
 
     pub fn set_drawable_2d(&mut self, entity: EntityId, drawable: Drawable2D) {
-        self.ensure_capacity(entity.index() as usize);
-        self.drawables_2d[entity.index() as usize] = Some(drawable);
+        self.insert(entity, drawable);
     }
 
     pub fn set_drawable_3d(&mut self, entity: EntityId, drawable: Drawable3D) {
-        self.ensure_capacity(entity.index() as usize);
-        self.drawables_3d[entity.index() as usize] = Some(drawable);
+        self.insert(entity, drawable);
+    }
+
+    pub fn set_orbit(&mut self, entity: EntityId, orbit: ComponentOrbit) {
+        self.insert(entity, orbit);
+    }
+
+    pub fn orbit(&self, entity: EntityId) -> Option<ComponentOrbit> {
+        self.get(entity).copied()
+    }
+
+    /// Moves every orbiting entity to its propagated ECEF position at `time`, with point
+    /// bounds, and returns how many were updated. Entities whose propagation fails (e.g. a
+    /// decayed orbit) keep their previous transform.
+    pub fn update_orbits(&mut self, time: Time) -> usize {
+        let positions: Vec<(EntityId, Vec3)> = self
+            .query::<&ComponentOrbit>()
+            .into_iter()
+            .filter_map(|(entity, orbit)| {
+                let p = orbit.propagator.position_ecef(time).ok()?;
+                Some((entity, Vec3::new(p.x, p.y, p.z)))
+            })
+            .collect();
+        for &(entity, position) in &positions {
+            self.insert(entity, Transform::translate(position));
+            self.insert(entity, ComponentBounds::new(position, position));
+        }
+        positions.len()
     }
 
+    /// Stores a geometry. It lives until the last [`ComponentVectorGeometry`] referencing it
+    /// goes away, so attach it with [`World::set_vector_geometry`].
     pub fn add_vector_geometry(&mut self, geometry: VectorGeometry) -> VectorGeometryId {
-        let id = VectorGeometryId(self.vector_geometries.len() as u32);
-        self.vector_geometries.push(geometry);
-        id
+        self.vector_geometries.sync(&self.components);
+        self.vector_geometries.add(geometry)
+    }
+
+    /// Number of stored geometries.
+    pub fn vector_geometry_count(&self) -> usize {
+        self.vector_geometries.len()
     }
 
     pub fn set_vector_geometry(&mut self, entity: EntityId, component: ComponentVectorGeometry) {
-        self.ensure_capacity(entity.index() as usize);
-        self.vector_geometry[entity.index() as usize] = Some(component);
+        self.insert(entity, component);
     }
 
     pub fn vector_geometry_component(&self, entity: EntityId) -> Option<ComponentVectorGeometry> {
-        self.vector_geometry
-            .get(entity.index() as usize)
-            .and_then(|v| *v)
+        self.get(entity).copied()
     }
 
     pub fn vector_geometry(&self, id: VectorGeometryId) -> Option<&VectorGeometry> {
-        self.vector_geometries.get(id.0 as usize)
+        self.vector_geometries.get(id)
     }
 
     pub fn vector_geometries_by_entity(
         &self,
     ) -> Vec<(EntityId, Transform, ComponentVectorGeometry)> {
-        let mut out = Vec::new();
-        for (idx, comp) in self.vector_geometry.iter().enumerate() {
-            let Some(comp) = comp else { continue };
-            let Some(transform) = self.transforms.get(idx).and_then(|t| *t) else {
-                continue;
-            };
-            let visible = self
-                .visibility
-                .get(idx)
-                .and_then(|v| *v)
-                .map(|v| v.visible)
-                .unwrap_or(true);
-            if !visible {
-                continue;
-            }
-
-            out.push((EntityId(Handle::new(idx as u32, 0)), transform, *comp));
-        }
-        out
+        self.collect_drawables(|_| true)
     }
 
     pub fn drawables_2d(&self) -> Vec<(EntityId, Transform, Drawable2D)> {
-        self.collect_drawables(&self.drawables_2d)
+        self.collect_drawables(|_| true)
     }
 
     pub fn drawables_3d(&self) -> Vec<(EntityId, Transform, Drawable3D)> {
-        self.collect_drawables(&self.drawables_3d)
+        self.collect_drawables(|_| true)
     }
 
     pub fn drawables_2d_at_time(&self, time: Time) -> Vec<(EntityId, Transform, Drawable2D)> {
-        self.collect_drawables_at_time(&self.drawables_2d, time)
+        self.collect_drawables(|entity| self.alive_at(entity, time))
     }
 
     pub fn drawables_3d_at_time(&self, time: Time) -> Vec<(EntityId, Transform, Drawable3D)> {
-        self.collect_drawables_at_time(&self.drawables_3d, time)
-    }
-
-    fn collect_drawables<T: Copy>(&self, drawables: &[Option<T>]) -> Vec<(EntityId, Transform, T)> {
-        let mut out = Vec::new();
-        for (idx, drawable) in drawables.iter().enumerate() {
-            let Some(drawable) = drawable else { continue };
-            let Some(transform) = self.transforms.get(idx).and_then(|t| *t) else {
-                continue;
-            };
-            let visible = self
-                .visibility
-                .get(idx)
-                .and_then(|v| *v)
-                .map(|v| v.visible)
-                .unwrap_or(true);
-            if !visible {
-                continue;
-            }
+        self.collect_drawables(|entity| self.alive_at(entity, time))
+    }
 
-            out.push((EntityId(Handle::new(idx as u32, 0)), transform, *drawable));
-        }
-        out
+    fn alive_at(&self, entity: EntityId, time: Time) -> bool {
+        self.time_span(entity)
+            .is_none_or(|span| span.contains(time))
     }
 
-    fn collect_drawables_at_time<T: Copy>(
+    /// Visible `(entity, transform, T)` triples passing `keep`, in entity index order.
+    fn collect_drawables<T: Copy + Any + Send + Sync>(
         &self,
-        drawables: &[Option<T>],
-        time: Time,
+        keep: impl Fn(EntityId) -> bool,
     ) -> Vec<(EntityId, Transform, T)> {
-        let mut out = Vec::new();
-        for (idx, drawable) in drawables.iter().enumerate() {
-            let Some(drawable) = drawable else { continue };
-            let Some(transform) = self.transforms.get(idx).and_then(|t| *t) else {
-                continue;
-            };
-
-            let visible = self
-                .visibility
-                .get(idx)
-                .and_then(|v| *v)
-                .map(|v| v.visible)
-                .unwrap_or(true);
-            if !visible {
-                continue;
-            }
-
-            if let Some(ComponentTimeSpan { span }) = self.time_spans.get(idx).and_then(|s| *s)
-                && (time.0 < span.start.0 || time.0 > span.end.0)
-            {
-                continue;
-            }
-
-            out.push((EntityId(Handle::new(idx as u32, 0)), transform, *drawable));
-        }
-        out
-    }
-
-    fn ensure_capacity(&mut self, idx: usize) {
-        if self.transforms.len() <= idx {
-            let new_len = idx + 1;
-            self.transforms.resize(new_len, None);
-            self.bounds.resize(new_len, None);
-            self.visibility.resize(new_len, None);
-            self.time_spans.resize(new_len, None);
-            self.properties.resize(new_len, None);
-            self.drawables_2d.resize(new_len, None);
-            self.drawables_3d.resize(new_len, None);
-            self.vector_geometry.resize(new_len, None);
-        }
+        self.query::<(&T, &Transform)>()
+            .into_iter()
+            .filter(|&(entity, _)| {
+                let visible = self
+                    .get::<Visibility>(entity)
+                    .map(|v| v.visible)
+                    .unwrap_or(true);
+                visible && keep(entity)
+            })
+            .map(|(entity, (drawable, transform))| (entity, *transform, *drawable))
+            .collect()
     }
 }
 
+fn geometry_of<T: Any>(component: &T) -> Option<VectorGeometryId> {
+    (component as &dyn Any)
+        .downcast_ref::<ComponentVectorGeometry>()
+        .map(|c| c.id)
+}
+
 #[cfg(test)]
 mod tests {
     use super::World;
-    use crate::components::{ComponentTimeSpan, Drawable2D, Transform, Visibility};
-    use foundation::math::Vec2;
+    use crate::components::{
+        ComponentBounds, ComponentTimeSpan, ComponentVectorGeometry, Drawable2D, Transform,
+        VectorGeometry, VectorGeometryKind, Visibility,
+    };
+    use foundation::bounds::Aabb3;
+    use foundation::math::{Vec2, Vec3};
     use foundation::time::{Time, TimeSpan};
 
     #[test]
@@ -261,10 +555,7 @@ mod tests {
         world.set_drawable_2d(entity, Drawable2D::rect(Vec2::new(1.0, 1.0)));
         world.set_time_span(
             entity,
-            ComponentTimeSpan::new(TimeSpan {
-                start: Time(10.0),
-                end: Time(20.0),
-            }),
+            ComponentTimeSpan::new(TimeSpan::new(Time(10.0), Time(20.0))),
         );
 
         assert!(world.drawables_2d_at_time(Time(5.0)).is_empty());
@@ -273,4 +564,175 @@ mod tests {
         assert_eq!(world.drawables_2d_at_time(Time(20.0)).len(), 1);
         assert!(world.drawables_2d_at_time(Time(25.0)).is_empty());
     }
+
+    #[test]
+    fn despawn_clears_components_and_recycles_index() {
+        let mut world = World::new();
+        let a = world.spawn();
+        let b = world.spawn();
+        world.set_transform(a, Transform::identity());
+        world.set_drawable_2d(a, Drawable2D::rect(Vec2::new(1.0, 1.0)));
+        world.set_bounds(a, ComponentBounds::new(Vec3::ZERO, Vec3::ZERO));
+
+        assert!(world.despawn(a));
+        assert!(!world.despawn(a));
+        assert!(!world.is_alive(a));
+        assert_eq!(world.entity_at(a.index()), None);
+        assert_eq!(world.entity_count(), 1);
+        assert!(world.drawables_2d().is_empty());
+
+        let c = world.spawn();
+        assert_eq!(c.index(), a.index());
+        assert_ne!(c.generation(), a.generation());
+        assert_eq!(world.entity_at(a.index()), Some(c));
+        // The recycled slot starts empty and stale ids can neither read nor write it.
+        assert_eq!(world.transform(c), None);
+        world.set_transform(a, Transform::identity());
+        assert_eq!(world.transform(c), None);
+        world.set_transform(c, Transform::identity());
+        world.set_drawable_2d(c, Drawable2D::rect(Vec2::new(1.0, 1.0)));
+        assert_eq!(world.transform(a), None);
+        assert_eq!(world.bounds(a), None);
+        assert_eq!(world.drawables_2d()[0].0, c);
+        assert!(world.is_alive(b));
+    }
+
+    #[test]
+    fn user_components_query_and_change_ticks() {
+        #[derive(Debug, PartialEq)]
+        struct SensorReading(f64);
+
+        let mut world = World::new();
+        let a = world.spawn();
+        let b = world.spawn();
+        world.set_transform(a, Transform::identity());
+        world.set_transform(b, Transform::identity());
+        world.insert(a, SensorReading(1.5));
+        world.insert(b, SensorReading(2.0));
+        assert_eq!(world.get::<SensorReading>(a), Some(&SensorReading(1.5)));
+        assert_eq!(world.query::<(&Transform, &SensorReading)>().len(), 2);
+
+        let seen = world.change_tick();
+        world.advance_change_tick();
+        world.get_mut::<SensorReading>(b).unwrap().0 += 1.0;
+        let changed = world.changed_since::<SensorReading>(seen);
+        assert_eq!(changed, vec![(b, &SensorReading(3.0))]);
+        assert_eq!(world.ticks::<SensorReading>(b).unwrap().added, seen);
+
+        assert_eq!(world.remove::<SensorReading>(a), Some(SensorReading(1.5)));
+        world.despawn(b);
+        assert!(world.query::<&SensorReading>().is_empty());
+        assert_eq!(world.insert(b, SensorReading(0.0)), None);
+        assert!(!world.has::<SensorReading>(b));
+    }
+
+    #[test]
+    fn geometries_are_freed_with_their_last_entity() {
+        let mut world = World::new();
+        let point = |x: f64| VectorGeometry::Point {
+            position: Vec3::new(x, 0.0, 0.0),
+        };
+        let attach = |world: &mut World, id| {
+            let e = world.spawn();
+            world.set_vector_geometry(
+                e,
+                ComponentVectorGeometry::new(id, VectorGeometryKind::Point),
+            );
+            e
+        };
+
+        // Reloading a feed many times must not grow the geometry storage.
+        for round in 0..10 {
+            let shared = world.add_vector_geometry(point(round as f64));
+            let a = attach(&mut world, shared);
+            let b = attach(&mut world, shared);
+            assert_eq!(world.vector_geometry_count(), 1);
+            world.despawn(a);
+            assert_eq!(world.vector_geometry(shared), Some(&point(round as f64)));
+            world.despawn(b);
+            assert_eq!(world.vector_geometry(shared), None);
+            assert_eq!(world.vector_geometry_count(), 0);
+        }
+
+        let first = world.add_vector_geometry(point(1.0));
+        let second = world.add_vector_geometry(point(2.0));
+        let e = attach(&mut world, first);
+        world.get_mut::<ComponentVectorGeometry>(e).unwrap().id = second;
+        let third = world.add_vector_geometry(point(3.0));
+        assert_eq!(world.vector_geometry(first), None);
+        assert_eq!(third.index(), first.index());
+        assert_ne!(third, first);
+
+        world.set_vector_geometry(
+            e,
+            ComponentVectorGeometry::new(third, VectorGeometryKind::Point),
+        );
+        assert_eq!(world.vector_geometry(second), None);
+        world.remove::<ComponentVectorGeometry>(e);
+        assert_eq!(world.vector_geometry_count(), 0);
+    }
+
+    #[test]
+    fn geometry_refs_survive_mutable_access_to_other_entities() {
+        let point = VectorGeometry::Point {
+            position: Vec3::new(1.0, 0.0, 0.0),
+        };
+        let geometry = |id| ComponentVectorGeometry::new(id, VectorGeometryKind::Point);
+
+        // A shared geometry outlives the first of its two entities.
+        let mut world = World::new();
+        let shared = world.add_vector_geometry(point.clone());
+        let (a, b, c) = (world.spawn(), world.spawn(), world.spawn());
+        world.set_vector_geometry(a, geometry(shared));
+        world.set_vector_geometry(b, geometry(shared));
+        assert!(world.get_mut::<ComponentVectorGeometry>(c).is_none());
+        world.despawn(a);
+        assert_eq!(world.vector_geometry(shared), Some(&point));
+        world.despawn(b);
+        assert_eq!(world.vector_geometry_count(), 0);
+
+        // A geometry attached right after such an access is freed with its entity.
+        let mut world = World::new();
+        let g = world.add_vector_geometry(point);
+        let (a, c) = (world.spawn(), world.spawn());
+        assert!(world.get_mut::<ComponentVectorGeometry>(c).is_none());
+        world.set_vector_geometry(a, geometry(g));
+        world.despawn(a);
+        assert_eq!(world.vector_geometry(g), None);
+        assert_eq!(world.vector_geometry_count(), 0);
+    }
+
+    #[test]
+    fn spatial_queries_follow_bounds_edits_and_despawn() {
+        let mut world = World::new();
+        let cube =
+            |x: f64| ComponentBounds::new(Vec3::new(x, 0.0, 0.0), Vec3::new(x + 1.0, 1.0, 1.0));
+        let entities: Vec<_> = (0..100)
+            .map(|i| {
+                let e = world.spawn();
+                world.set_bounds(e, cube(i as f64 * 10.0));
+                e
+            })
+            .collect();
+        let near_origin = Aabb3::new([-1.0, -1.0, -1.0], [15.0, 2.0, 2.0]);
+        assert_eq!(
+            world.entities_in_aabb(&near_origin),
+            vec![entities[0], entities[1]]
+        );
+
+        world.set_bounds(entities[50], cube(5.0));
+        *world.get_mut::<ComponentBounds>(entities[1]).unwrap() = cube(500.0);
+        world.despawn(entities[0]);
+        assert_eq!(world.entities_in_aabb(&near_origin), vec![entities[50]]);
+
+        let reused = world.spawn();
+        assert_eq!(reused.index(), entities[0].index());
+        assert_eq!(world.entities_in_aabb(&near_origin), vec![entities[50]]);
+        world.set_bounds(reused, cube(0.0));
+        world.remove::<ComponentBounds>(entities[50]);
+        assert_eq!(
+            world.entities_on_ray([-5.0, 0.5, 0.5], [1.0, 0.0, 0.0], 0.0, 20.0),
+            vec![reused]
+        );
+    }
 }
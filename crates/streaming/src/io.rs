@@ -1 +1,116 @@
-pub struct Io;
+use std::collections::BTreeMap;
+use std::future::Future;
+
+use runtime::budget::FrameBudget;
+use runtime::event_bus::EventBus;
+use runtime::executor::{Executor, TaskHandle};
+use runtime::frame::Frame;
+
+use crate::request::Request;
+
+/// Outcome of loading one request: the raw payload or an error message.
+pub type FetchResult = Result<Vec<u8>, String>;
+
+/// In-flight loads for streaming requests.
+///
+/// The caller supplies the load future (an HTTP fetch on the web, a file read on native);
+/// `Io` runs it on a `runtime::Executor` so loads only make progress inside the frame
+/// budget, and hands finished loads back in request order.
+#[derive(Default)]
+pub struct Io {
+    executor: Executor,
+    in_flight: BTreeMap<Request, TaskHandle<FetchResult>>,
+}
+
+impl Io {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    pub fn is_loading(&self, req: Request) -> bool {
+        self.in_flight.contains_key(&req)
+    }
+
+    /// Starts loading `req`. Returns `false` (and drops `load`) if it is already loading.
+    pub fn start(
+        &mut self,
+        req: Request,
+        load: impl Future<Output = FetchResult> + 'static,
+    ) -> bool {
+        if self.in_flight.contains_key(&req) {
+            return false;
+        }
+        let handle = self.executor.spawn("streaming.load", load);
+        self.in_flight.insert(req, handle);
+        true
+    }
+
+    /// Abandons a load; its future is dropped at the next frame.
+    pub fn cancel(&mut self, req: Request) -> bool {
+        match self.in_flight.remove(&req) {
+            Some(handle) => {
+                handle.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Polls loads within `budget` and returns those that finished.
+    pub fn run_frame(
+        &mut self,
+        frame: Frame,
+        bus: &mut EventBus,
+        budget: &mut FrameBudget,
+    ) -> Vec<(Request, FetchResult)> {
+        self.executor.run_frame(frame, bus, budget);
+        let mut done = Vec::new();
+        self.in_flight
+            .retain(|req, handle| match handle.try_take() {
+                Some(result) => {
+                    done.push((*req, result));
+                    false
+                }
+                None => true,
+            });
+        done
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Io;
+    use crate::request::Request;
+    use runtime::budget::FrameBudget;
+    use runtime::event_bus::EventBus;
+    use runtime::executor::yield_now;
+    use runtime::frame::Frame;
+
+    #[test]
+    fn loads_complete_within_budget() {
+        let mut io = Io::new();
+        assert!(io.start(Request(2), async { Ok(vec![2]) }));
+        assert!(io.start(Request(1), async {
+            yield_now().await;
+            Err("404".to_string())
+        }));
+        assert!(!io.start(Request(1), async { Ok(Vec::new()) }));
+        io.start(Request(3), async { Ok(vec![3]) });
+        assert!(io.cancel(Request(3)));
+
+        let mut bus = EventBus::new();
+        let frame = Frame::new(0, 1.0);
+        let done = io.run_frame(frame, &mut bus, &mut FrameBudget::new(1));
+        assert_eq!(done, vec![(Request(2), Ok(vec![2]))]);
+
+        let done = io.run_frame(frame.next(), &mut bus, &mut FrameBudget::new(1));
+        assert!(done.is_empty());
+        let done = io.run_frame(frame.next().next(), &mut bus, &mut FrameBudget::new(1));
+        assert_eq!(done, vec![(Request(1), Err("404".to_string()))]);
+        assert_eq!(io.in_flight(), 0);
+    }
+}
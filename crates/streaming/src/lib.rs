@@ -5,10 +5,13 @@ pub mod protocol;
 pub mod queue;
 pub mod request;
 pub mod residency;
+pub mod tile_matrix_set;
 
 pub use cache::*;
+pub use io::*;
 pub use pipeline::*;
 pub use protocol::*;
 pub use queue::*;
 pub use request::*;
 pub use residency::*;
+pub use tile_matrix_set::*;
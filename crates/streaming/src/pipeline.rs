@@ -62,6 +62,16 @@ impl Pipeline {
         false
     }
 
+    /// Change the priority of a pending request, e.g. after the camera moved.
+    ///
+    /// Returns `true` if the request was still pending.
+    pub fn reprioritize(&mut self, req: Request, priority: i32) -> bool {
+        match self.pending.get(&req) {
+            Some(&work_id) => self.queue.reprioritize(work_id, priority),
+            None => false,
+        }
+    }
+
     pub fn pop_next_with_budget(
         &mut self,
         budget: &mut FrameBudget,
@@ -91,6 +101,19 @@ mod tests {
         assert!(p.pop_next_with_budget(&mut budget).is_none());
     }
 
+    #[test]
+    fn pipeline_reprioritize_reorders_pending() {
+        let mut p = Pipeline::new(MemoryBudget::new(1024), 10);
+        p.submit(CacheKey::new("ds", "near"), 0, 1).expect("submit");
+        let far = p.submit(CacheKey::new("ds", "far"), 5, 1).expect("submit");
+        assert!(p.reprioritize(far, -1));
+
+        let mut budget = FrameBudget::new(10);
+        let (req, key) = p.pop_next_with_budget(&mut budget).expect("pop");
+        assert_eq!((req, key.resource_id.as_str()), (far, "far"));
+        assert!(!p.reprioritize(far, 0));
+    }
+
     #[test]
     fn pipeline_pop_returns_key() {
         let mut p = Pipeline::new(MemoryBudget::new(1024), 10);
@@ -102,4 +125,32 @@ mod tests {
         let (_req, key) = p.pop_next_with_budget(&mut budget).expect("pop");
         assert_eq!(key.resource_id, "cities");
     }
+
+    #[test]
+    fn pipeline_pops_within_controller_share() {
+        use runtime::budget_controller::{BudgetController, BudgetControllerConfig};
+
+        let mut p = Pipeline::new(MemoryBudget::new(1024), 32);
+        for i in 0..20 {
+            p.submit(CacheKey::new("ds", format!("t{i}")), 0, 2)
+                .expect("submit");
+        }
+        let mut controller = BudgetController::new(BudgetControllerConfig {
+            initial_units: 16,
+            ..Default::default()
+        });
+        controller.set_share("streaming", 3);
+        controller.set_share("compute", 1);
+
+        let mut budget = controller.budget_for("streaming");
+        let mut popped = 0;
+        while p.pop_next_with_budget(&mut budget).is_some() {
+            popped += 1;
+        }
+        assert_eq!(popped, 6);
+
+        // A slow frame shrinks the next share.
+        controller.record_frame(40.0);
+        assert!(controller.budget_for("streaming").remaining_units() < 12);
+    }
 }
@@ -8,8 +8,11 @@
 //! The protocol is designed to be transport-agnostic (WebSocket, HTTP/2 streams, etc.)
 //! and supports view-driven tile prioritization.
 
+use foundation::bounds::{GeoBounds, wrap_lon_deg};
 use serde::{Deserialize, Serialize};
 
+use crate::tile_matrix_set::{TileMatrixSet, web_mercator_quad};
+
 /// Unique identifier for a streaming session.
 pub type SessionId = String;
 
@@ -17,6 +20,9 @@ pub type SessionId = String;
 pub type ViewId = u64;
 
 /// Tile coordinate in ZXY scheme.
+///
+/// The matrix set gives the coordinate its meaning; the helpers here assume
+/// WebMercatorQuad (XYZ web tiles). See [`TileMatrixSet`] for other grids.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TileCoord {
     pub z: u8,
@@ -34,23 +40,31 @@ impl TileCoord {
         1u64 << (2 * z as u64)
     }
 
-    /// Returns the geographic bounds of this tile in WGS84 (lon_min, lat_min, lon_max, lat_max).
-    pub fn bounds_wgs84(&self) -> (f64, f64, f64, f64) {
-        let n = (1u32 << self.z) as f64;
-        let lon_min = (self.x as f64 / n) * 360.0 - 180.0;
-        let lon_max = ((self.x + 1) as f64 / n) * 360.0 - 180.0;
+    /// The tile one level up that covers this one, or `None` at zoom 0.
+    pub fn parent(&self) -> Option<TileCoord> {
+        (self.z > 0).then(|| TileCoord::new(self.z - 1, self.x / 2, self.y / 2))
+    }
 
-        // Web Mercator Y flip
-        let lat_max = tile_y_to_lat(self.y, self.z);
-        let lat_min = tile_y_to_lat(self.y + 1, self.z);
+    /// The four tiles one level down, row by row from the top-left.
+    pub fn children(&self) -> [TileCoord; 4] {
+        let (z, x, y) = (self.z + 1, self.x * 2, self.y * 2);
+        [
+            TileCoord::new(z, x, y),
+            TileCoord::new(z, x + 1, y),
+            TileCoord::new(z, x, y + 1),
+            TileCoord::new(z, x + 1, y + 1),
+        ]
+    }
 
-        (lon_min, lat_min, lon_max, lat_max)
+    /// Returns the geographic bounds of this tile in WGS84 (lon_min, lat_min, lon_max, lat_max).
+    pub fn bounds_wgs84(&self) -> (f64, f64, f64, f64) {
+        let b = self.geo_bounds();
+        (b.west, b.south, b.east, b.north)
     }
-}
 
-fn tile_y_to_lat(y: u32, z: u8) -> f64 {
-    let n = std::f64::consts::PI - 2.0 * std::f64::consts::PI * (y as f64) / (1u32 << z) as f64;
-    (0.5 * (n.exp() - (-n).exp())).atan().to_degrees()
+    pub fn geo_bounds(&self) -> GeoBounds {
+        web_mercator_quad().tile_geo_bounds(self)
+    }
 }
 
 /// Camera/view state sent by the client to drive tile prioritization.
@@ -105,15 +119,35 @@ impl ViewState {
 
     /// Check if a tile is likely visible from this view state.
     pub fn tile_visible(&self, coord: &TileCoord) -> bool {
-        let (lon_min, lat_min, lon_max, lat_max) = coord.bounds_wgs84();
-
         // Simple visibility: check if tile overlaps a region around the camera.
         // A more accurate implementation would use the actual frustum.
-        let view_radius = self.view_radius_deg();
-        let lon_ok = lon_max >= self.lon - view_radius && lon_min <= self.lon + view_radius;
-        let lat_ok = lat_max >= self.lat - view_radius && lat_min <= self.lat + view_radius;
+        self.view_bounds().intersects(&coord.geo_bounds())
+    }
 
-        lon_ok && lat_ok
+    /// Region within `view_radius_deg` of the camera.
+    ///
+    /// Wraps across the antimeridian, and spans every longitude once it reaches a pole.
+    pub fn view_bounds(&self) -> GeoBounds {
+        let radius = self.view_radius_deg();
+        let south = self.lat - radius;
+        let north = self.lat + radius;
+        if south <= -90.0 || north >= 90.0 {
+            return GeoBounds::new(-180.0, south, 180.0, north);
+        }
+        GeoBounds::new(self.lon - radius, south, self.lon + radius, north)
+    }
+
+    /// WebMercatorQuad tile ranges `(x_min, x_max, y_min, y_max)` covering `view_bounds`
+    /// at zoom `z`.
+    ///
+    /// Returns two ranges when the view crosses the antimeridian.
+    pub fn visible_tile_ranges(&self, z: u8) -> Vec<(u32, u32, u32, u32)> {
+        self.visible_tile_ranges_in(web_mercator_quad(), z)
+    }
+
+    /// Like [`Self::visible_tile_ranges`], for any tile matrix set.
+    pub fn visible_tile_ranges_in(&self, tms: &TileMatrixSet, z: u8) -> Vec<(u32, u32, u32, u32)> {
+        tms.tile_ranges(&self.view_bounds(), z)
     }
 
     /// Estimate the visible radius in degrees based on altitude and FOV.
@@ -132,7 +166,7 @@ impl ViewState {
         let tile_center_lat = (lat_min + lat_max) / 2.0;
 
         // Distance from camera center (in degrees, rough).
-        let dlon = (tile_center_lon - self.lon).abs();
+        let dlon = wrap_lon_deg(tile_center_lon - self.lon).abs();
         let dlat = (tile_center_lat - self.lat).abs();
         let dist = (dlon * dlon + dlat * dlat).sqrt();
 
@@ -318,6 +352,33 @@ mod tests {
         assert!(lat_min < lat_max);
     }
 
+    #[test]
+    fn view_near_antimeridian_sees_tiles_on_both_sides() {
+        let view = ViewState {
+            view_id: 1,
+            lon: 179.0,
+            lat: 0.0,
+            altitude_m: 500_000.0,
+            yaw_deg: 0.0,
+            pitch_deg: 0.0,
+            viewport_width: 1920,
+            viewport_height: 1080,
+            fov_deg: 60.0,
+            max_zoom: 14,
+            layers: vec![],
+        };
+        let z = 4;
+        assert!(view.tile_visible(&TileCoord::new(z, 15, 8)));
+        assert!(view.tile_visible(&TileCoord::new(z, 0, 8)));
+        assert!(!view.tile_visible(&TileCoord::new(z, 8, 8)));
+
+        let ranges = view.visible_tile_ranges(z);
+        assert_eq!(ranges.len(), 2);
+        assert_eq!((ranges[0].0, ranges[0].1), (15, 15));
+        assert_eq!((ranges[1].0, ranges[1].1), (0, 0));
+        assert!(ranges[0].2 <= 8 && ranges[0].3 >= 7);
+    }
+
     #[test]
     fn view_state_zoom_estimate() {
         let view = ViewState {
@@ -40,6 +40,10 @@ impl StreamingQueue {
         self.inner.cancel(id)
     }
 
+    pub fn reprioritize(&mut self, id: WorkId, priority: i32) -> bool {
+        self.inner.reprioritize(id, priority)
+    }
+
     pub fn pop_next_with_budget(&mut self, budget: &mut FrameBudget) -> Option<(WorkId, Request)> {
         let (id, _priority, req) = self.inner.pop_next_with_budget(budget)?;
         Some((id, req))
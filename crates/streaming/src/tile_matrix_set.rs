@@ -0,0 +1,453 @@
+//! Tile matrix sets: how a CRS extent is cut into tiles at each zoom level.
+//!
+//! This follows the OGC TileMatrixSet model restricted to quadtrees: every zoom level
+//! doubles the number of columns and rows of the one above, tile (0, 0) is the top-left
+//! (north-west) corner, and zoom 0 may hold more than one root tile (WorldCRS84Quad has two).
+
+use std::sync::OnceLock;
+
+use foundation::bounds::{GeoBounds, unwrap_lon_deg};
+use foundation::math::{
+    Crs, CrsKind, CrsTransform, Datum, ProjectionDef, Vec3, WEB_MERCATOR_HALF_EXTENT_M,
+};
+
+use crate::protocol::TileCoord;
+
+/// Points sampled along each tile edge when converting bounds between CRSs.
+const EDGE_SAMPLES: usize = 8;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileMatrixSet {
+    id: String,
+    crs: Crs,
+    /// `[min_x, min_y, max_x, max_y]` in the CRS's native units and axis order.
+    extent: [f64; 4],
+    root_cols: u32,
+    root_rows: u32,
+    tile_size: u32,
+    wrap_x: bool,
+    to_wgs84: CrsTransform,
+    from_wgs84: CrsTransform,
+}
+
+impl TileMatrixSet {
+    /// A custom grid over `extent` (`[min_x, min_y, max_x, max_y]`, native CRS units) with
+    /// `root_cols × root_rows` tiles at zoom 0. Tiles are 256 pixels and do not wrap.
+    pub fn new(
+        id: impl Into<String>,
+        crs: Crs,
+        extent: [f64; 4],
+        root_cols: u32,
+        root_rows: u32,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            to_wgs84: CrsTransform::to_wgs84(crs.clone()),
+            from_wgs84: CrsTransform::new(Crs::wgs84(), crs.clone()),
+            crs,
+            extent,
+            root_cols: root_cols.max(1),
+            root_rows: root_rows.max(1),
+            tile_size: 256,
+            wrap_x: false,
+        }
+    }
+
+    /// Spherical Mercator (EPSG:3857) with one root tile: the XYZ scheme of web maps.
+    pub fn web_mercator_quad() -> Self {
+        let crs = Crs::new(
+            3857,
+            "WGS 84 / Pseudo-Mercator",
+            Datum::wgs84(),
+            CrsKind::Projected(ProjectionDef::WebMercator),
+        );
+        let e = WEB_MERCATOR_HALF_EXTENT_M;
+        Self::new("WebMercatorQuad", crs, [-e, -e, e, e], 1, 1).with_wrap_x(true)
+    }
+
+    /// Plate carrée lon/lat (CRS84) with two root tiles, west and east of Greenwich.
+    pub fn world_crs84_quad() -> Self {
+        Self::new(
+            "WorldCRS84Quad",
+            Crs::wgs84(),
+            [-180.0, -90.0, 180.0, 90.0],
+            2,
+            1,
+        )
+        .with_wrap_x(true)
+    }
+
+    /// A custom plate carrée grid over a WGS84 lon/lat extent `[west, south, east, north]`.
+    pub fn geographic(
+        id: impl Into<String>,
+        extent: [f64; 4],
+        root_cols: u32,
+        root_rows: u32,
+    ) -> Self {
+        Self::new(id, Crs::wgs84(), extent, root_cols, root_rows)
+    }
+
+    pub fn with_tile_size(mut self, pixels: u32) -> Self {
+        self.tile_size = pixels;
+        self
+    }
+
+    /// Whether columns wrap around (the extent spans every longitude).
+    pub fn with_wrap_x(mut self, wrap: bool) -> Self {
+        self.wrap_x = wrap;
+        self
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn crs(&self) -> &Crs {
+        &self.crs
+    }
+
+    pub fn extent(&self) -> [f64; 4] {
+        self.extent
+    }
+
+    pub fn tile_size(&self) -> u32 {
+        self.tile_size
+    }
+
+    /// Columns and rows at zoom `z`.
+    pub fn matrix_size(&self, z: u8) -> (u32, u32) {
+        (self.root_cols << z, self.root_rows << z)
+    }
+
+    pub fn tile_count(&self, z: u8) -> u64 {
+        let (cols, rows) = self.matrix_size(z);
+        cols as u64 * rows as u64
+    }
+
+    pub fn contains(&self, coord: &TileCoord) -> bool {
+        let (cols, rows) = self.matrix_size(coord.z);
+        coord.x < cols && coord.y < rows
+    }
+
+    /// Tile width and height in native units at zoom `z`.
+    pub fn tile_span(&self, z: u8) -> (f64, f64) {
+        let (cols, rows) = self.matrix_size(z);
+        let [min_x, min_y, max_x, max_y] = self.extent;
+        ((max_x - min_x) / cols as f64, (max_y - min_y) / rows as f64)
+    }
+
+    /// Native bounds `[min_x, min_y, max_x, max_y]`; row 0 is at `max_y`.
+    pub fn tile_bounds(&self, coord: &TileCoord) -> [f64; 4] {
+        let (w, h) = self.tile_span(coord.z);
+        let min_x = self.extent[0] + coord.x as f64 * w;
+        let max_y = self.extent[3] - coord.y as f64 * h;
+        [min_x, max_y - h, min_x + w, max_y]
+    }
+
+    /// Bounds in another CRS (native axis order), from points sampled along the tile edges.
+    ///
+    /// Use a `CrsRegistry` to look up the target by EPSG code. Returns `None` when no
+    /// sample can be transformed. For lon/lat that may wrap, use [`Self::tile_geo_bounds`].
+    pub fn tile_bounds_in(&self, coord: &TileCoord, target: &Crs) -> Option<[f64; 4]> {
+        let transform = CrsTransform::new(self.crs.clone(), target.clone());
+        let mut out: Option<[f64; 4]> = None;
+        for p in self.edge_samples(coord) {
+            let Some(q) = transform.transform(p) else {
+                continue;
+            };
+            let b = out.get_or_insert([q.x, q.y, q.x, q.y]);
+            b[0] = b[0].min(q.x);
+            b[1] = b[1].min(q.y);
+            b[2] = b[2].max(q.x);
+            b[3] = b[3].max(q.y);
+        }
+        out
+    }
+
+    /// WGS84 bounds of a tile, wrapped across the antimeridian where needed.
+    pub fn tile_geo_bounds(&self, coord: &TileCoord) -> GeoBounds {
+        let mut west = f64::INFINITY;
+        let mut east = f64::NEG_INFINITY;
+        let mut south = f64::INFINITY;
+        let mut north = f64::NEG_INFINITY;
+        let mut prev_lon: Option<f64> = None;
+        let mut touches_pole = false;
+        for p in self.edge_samples(coord) {
+            let Some(q) = self.to_wgs84.transform(p) else {
+                continue;
+            };
+            // Snap away degree/radian round-off so edges on ±180° stay exact.
+            let (lon, lat) = (snap_deg(q.x), snap_deg(q.y));
+            // Follow the ring so a tile straddling ±180° keeps a continuous range.
+            let lon = prev_lon.map_or(lon, |prev| unwrap_lon_deg(prev, lon));
+            prev_lon = Some(lon);
+            west = west.min(lon);
+            east = east.max(lon);
+            south = south.min(lat);
+            north = north.max(lat);
+            touches_pole |= lat.abs() >= 90.0;
+        }
+        if west > east {
+            return GeoBounds::WORLD;
+        }
+        if touches_pole || east - west >= 360.0 {
+            return GeoBounds::new(-180.0, south, 180.0, north);
+        }
+        GeoBounds::new(west, south, east, north)
+    }
+
+    /// Tile containing a native coordinate, or `None` outside the extent.
+    pub fn tile_at(&self, z: u8, x: f64, y: f64) -> Option<TileCoord> {
+        let [min_x, min_y, max_x, max_y] = self.extent;
+        if !(x >= min_x && x <= max_x && y >= min_y && y <= max_y) {
+            return None;
+        }
+        let (col, row) = self.col_row(z, x, y);
+        Some(TileCoord::new(z, col, row))
+    }
+
+    /// Tile containing a WGS84 position, or `None` when it falls outside the set.
+    pub fn tile_for_lon_lat(&self, z: u8, lon: f64, lat: f64) -> Option<TileCoord> {
+        let p = self.from_wgs84.transform(Vec3::new(lon, lat, 0.0))?;
+        self.tile_at(z, p.x, p.y)
+    }
+
+    /// Inclusive tile ranges `(x_min, x_max, y_min, y_max)` covering `bounds` at zoom `z`.
+    ///
+    /// Returns one range per side when `bounds` crosses the antimeridian, and nothing
+    /// when `bounds` misses the extent.
+    pub fn tile_ranges(&self, bounds: &GeoBounds, z: u8) -> Vec<(u32, u32, u32, u32)> {
+        let south = bounds.south.clamp(-90.0, 90.0);
+        let north = bounds.north.clamp(-90.0, 90.0);
+        let mut out = Vec::new();
+        for b in bounds.to_aabb2s() {
+            let (west, east) = (b.min[0], b.max[0]);
+            let mut native: Option<[f64; 4]> = None;
+            for s in 0..=EDGE_SAMPLES {
+                let t = s as f64 / EDGE_SAMPLES as f64;
+                let lon = west + (east - west) * t;
+                let lat = south + (north - south) * t;
+                for [lon, lat] in [[lon, south], [lon, north], [west, lat], [east, lat]] {
+                    let Some(p) = self.from_wgs84.transform(Vec3::new(lon, lat, 0.0)) else {
+                        continue;
+                    };
+                    let n = native.get_or_insert([p.x, p.y, p.x, p.y]);
+                    n[0] = n[0].min(p.x);
+                    n[1] = n[1].min(p.y);
+                    n[2] = n[2].max(p.x);
+                    n[3] = n[3].max(p.y);
+                }
+            }
+            let Some([min_x, min_y, max_x, max_y]) = native else {
+                continue;
+            };
+            let [ext_min_x, ext_min_y, ext_max_x, ext_max_y] = self.extent;
+            if max_x < ext_min_x || min_x > ext_max_x || max_y < ext_min_y || min_y > ext_max_y {
+                continue;
+            }
+            let (x_min, y_min) = self.col_row(z, min_x, max_y);
+            let (x_max, y_max) = self.col_row(z, max_x, min_y);
+            out.push((x_min, x_max, y_min, y_max));
+        }
+        out
+    }
+
+    /// OGC/Bing-style quadkey: one base-4 digit per zoom level below the root.
+    ///
+    /// Sets with several root tiles prefix the root index and a `-`.
+    pub fn quadkey(&self, coord: &TileCoord) -> String {
+        let mut key = String::with_capacity(coord.z as usize + 3);
+        if self.root_cols * self.root_rows > 1 {
+            let root = (coord.y >> coord.z) * self.root_cols + (coord.x >> coord.z);
+            key.push_str(&root.to_string());
+            key.push('-');
+        }
+        for level in (0..coord.z).rev() {
+            let digit = ((coord.x >> level) & 1) | (((coord.y >> level) & 1) << 1);
+            key.push(char::from(b'0' + digit as u8));
+        }
+        key
+    }
+
+    /// Inverse of [`Self::quadkey`], or `None` for a malformed key.
+    pub fn tile_from_quadkey(&self, key: &str) -> Option<TileCoord> {
+        let (root, digits) = if self.root_cols * self.root_rows > 1 {
+            let (root, digits) = key.split_once('-')?;
+            (root.parse::<u32>().ok()?, digits)
+        } else {
+            (0, key)
+        };
+        if root >= self.root_cols * self.root_rows || digits.len() > 31 {
+            return None;
+        }
+        let z = digits.len() as u8;
+        let mut x = (root % self.root_cols) << z;
+        let mut y = (root / self.root_cols) << z;
+        for (i, c) in digits.bytes().enumerate() {
+            let digit = c.checked_sub(b'0').filter(|d| *d < 4)? as u32;
+            let level = z as usize - 1 - i;
+            x |= (digit & 1) << level;
+            y |= (digit >> 1) << level;
+        }
+        Some(TileCoord::new(z, x, y))
+    }
+
+    /// The up to eight tiles around `coord`, wrapping columns when the set wraps.
+    pub fn neighbors(&self, coord: &TileCoord) -> Vec<TileCoord> {
+        let (cols, rows) = self.matrix_size(coord.z);
+        let mut out = Vec::with_capacity(8);
+        for dy in -1i64..=1 {
+            for dx in -1i64..=1 {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+                let y = coord.y as i64 + dy;
+                let mut x = coord.x as i64 + dx;
+                if y < 0 || y >= rows as i64 {
+                    continue;
+                }
+                if self.wrap_x {
+                    x = x.rem_euclid(cols as i64);
+                } else if x < 0 || x >= cols as i64 {
+                    continue;
+                }
+                let n = TileCoord::new(coord.z, x as u32, y as u32);
+                if n != *coord && !out.contains(&n) {
+                    out.push(n);
+                }
+            }
+        }
+        out
+    }
+
+    fn col_row(&self, z: u8, x: f64, y: f64) -> (u32, u32) {
+        let (cols, rows) = self.matrix_size(z);
+        let (w, h) = self.tile_span(z);
+        let col = ((x - self.extent[0]) / w).floor() as i64;
+        let row = ((self.extent[3] - y) / h).floor() as i64;
+        (
+            col.clamp(0, cols as i64 - 1) as u32,
+            row.clamp(0, rows as i64 - 1) as u32,
+        )
+    }
+
+    /// Native points around the tile boundary, corners included.
+    fn edge_samples(&self, coord: &TileCoord) -> Vec<Vec3> {
+        let [min_x, min_y, max_x, max_y] = self.tile_bounds(coord);
+        let corners = [
+            [min_x, max_y],
+            [max_x, max_y],
+            [max_x, min_y],
+            [min_x, min_y],
+        ];
+        let mut out = Vec::with_capacity(4 * EDGE_SAMPLES);
+        for (i, a) in corners.iter().enumerate() {
+            let b = corners[(i + 1) % 4];
+            for s in 0..EDGE_SAMPLES {
+                let t = s as f64 / EDGE_SAMPLES as f64;
+                out.push(Vec3::new(
+                    a[0] + (b[0] - a[0]) * t,
+                    a[1] + (b[1] - a[1]) * t,
+                    0.0,
+                ));
+            }
+        }
+        out
+    }
+}
+
+fn snap_deg(v: f64) -> f64 {
+    (v * 1e9).round() / 1e9
+}
+
+/// Shared WebMercatorQuad instance behind the XYZ helpers on `TileCoord` and `ViewState`.
+pub(crate) fn web_mercator_quad() -> &'static TileMatrixSet {
+    static WEB_MERCATOR_QUAD: OnceLock<TileMatrixSet> = OnceLock::new();
+    WEB_MERCATOR_QUAD.get_or_init(TileMatrixSet::web_mercator_quad)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TileMatrixSet;
+    use crate::protocol::TileCoord;
+    use foundation::bounds::GeoBounds;
+    use foundation::math::{CrsRegistry, WEB_MERCATOR_MAX_LAT_DEG};
+
+    fn assert_close(a: f64, b: f64, eps: f64) {
+        let diff = (a - b).abs();
+        assert!(diff <= eps, "expected {a} ~= {b} (diff {diff})");
+    }
+
+    #[test]
+    fn presets_have_expected_matrices_and_bounds() {
+        let wm = TileMatrixSet::web_mercator_quad();
+        assert_eq!(wm.matrix_size(0), (1, 1));
+        assert_eq!(wm.tile_count(3), 64);
+        let world = wm.tile_geo_bounds(&TileCoord::new(0, 0, 0));
+        assert!(world.is_full_lon());
+        assert_close(world.north, WEB_MERCATOR_MAX_LAT_DEG, 1e-9);
+        let ne = wm.tile_geo_bounds(&TileCoord::new(1, 1, 0));
+        assert_close(ne.west, 0.0, 1e-9);
+        assert_close(ne.east, 180.0, 1e-9);
+        assert_close(ne.south, 0.0, 1e-9);
+
+        let crs84 = TileMatrixSet::world_crs84_quad();
+        assert_eq!(crs84.matrix_size(0), (2, 1));
+        assert_eq!(crs84.matrix_size(2), (8, 4));
+        assert_eq!(
+            crs84.tile_bounds(&TileCoord::new(0, 1, 0)),
+            [0.0, -90.0, 180.0, 90.0]
+        );
+        assert_eq!(
+            crs84.tile_for_lon_lat(1, -100.0, 45.0),
+            Some(TileCoord::new(1, 0, 0))
+        );
+        assert!(!crs84.contains(&TileCoord::new(0, 0, 1)));
+
+        // Bounds in another registered CRS: the WebMercator tile in lon/lat degrees.
+        let wgs84 = CrsRegistry::builtin().get(4326).unwrap();
+        let b = wm.tile_bounds_in(&TileCoord::new(1, 0, 1), &wgs84).unwrap();
+        assert_close(b[0], -180.0, 1e-9);
+        assert_close(b[2], 0.0, 1e-9);
+        assert_close(b[1], -WEB_MERCATOR_MAX_LAT_DEG, 1e-9);
+    }
+
+    #[test]
+    fn quadkeys_round_trip() {
+        let wm = TileMatrixSet::web_mercator_quad();
+        let tile = TileCoord::new(3, 3, 5);
+        assert_eq!(wm.quadkey(&tile), "213");
+        assert_eq!(wm.tile_from_quadkey("213"), Some(tile));
+        assert_eq!(wm.quadkey(&TileCoord::new(0, 0, 0)), "");
+        assert_eq!(wm.tile_from_quadkey("4"), None);
+
+        let crs84 = TileMatrixSet::world_crs84_quad();
+        let east = TileCoord::new(2, 6, 1);
+        assert_eq!(crs84.quadkey(&east), "1-12");
+        assert_eq!(crs84.tile_from_quadkey("1-12"), Some(east));
+        assert_eq!(crs84.tile_from_quadkey("2-0"), None);
+    }
+
+    #[test]
+    fn neighbours_wrap_only_when_the_set_wraps() {
+        let crs84 = TileMatrixSet::world_crs84_quad();
+        let edge = TileCoord::new(1, 0, 0);
+        let n = crs84.neighbors(&edge);
+        assert_eq!(n.len(), 5);
+        assert!(n.contains(&TileCoord::new(1, 3, 0)));
+
+        let custom = TileMatrixSet::geographic("alps", [5.0, 44.0, 17.0, 48.0], 3, 1);
+        assert_eq!(custom.neighbors(&TileCoord::new(0, 0, 0)).len(), 1);
+        assert_eq!(
+            custom.tile_bounds(&TileCoord::new(1, 5, 1)),
+            [15.0, 44.0, 17.0, 46.0]
+        );
+        let ranges = custom.tile_ranges(&GeoBounds::new(9.0, 45.0, 10.5, 47.0), 1);
+        assert_eq!(ranges, vec![(2, 2, 0, 1)]);
+        assert!(
+            custom
+                .tile_ranges(&GeoBounds::new(-50.0, 0.0, -40.0, 10.0), 1)
+                .is_empty()
+        );
+    }
+}
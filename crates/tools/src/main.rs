@@ -4,7 +4,10 @@ use std::fs;
 use std::io::Write;
 use std::path::PathBuf;
 
-use foundation::math::{Ecef, Vec3, ecef_to_geodetic};
+use foundation::bounds::GeoBounds;
+use foundation::math::{
+    CoordinateFormat, Ecef, Equirectangular, ParseMode, Vec3, ecef_to_geodetic, parse_coordinate,
+};
 use layers::vector::VectorLayer;
 use scene::components::VectorGeometryKind;
 use serde::Serialize;
@@ -30,6 +33,7 @@ fn real_main() -> Result<(), String> {
         "manifest" => cmd_manifest(args),
         "unpack" => cmd_unpack(args),
         "surface-tiles" => cmd_surface_tiles(args),
+        "coord" => cmd_coord(args),
         _ => Err(usage()),
     }
 }
@@ -165,6 +169,9 @@ fn cmd_pack(args: Vec<String>) -> Result<(), String> {
 
     let chunk = formats::VectorChunk::from_geojson_str(input_str)
         .map_err(|e| format!("parse geojson: {e}"))?;
+    let chunk = chunk
+        .to_wgs84()
+        .map_err(|e| format!("reproject to WGS 84: {e}"))?;
 
     let file = fs::File::create(&output).map_err(|e| format!("create {output:?}: {e}"))?;
     let mut writer = HashingWriter::new(file);
@@ -316,133 +323,27 @@ fn infer_manifest_kind(chunk: &formats::VectorChunk) -> String {
 }
 
 fn compute_chunk_bounds_q_and_time_us(chunk: &formats::VectorChunk) -> ([i32; 4], [i64; 2]) {
-    // Quantization must match AVc: 1e-6 degrees.
-    fn quantize_deg(v: f64) -> i32 {
-        let q = (v * 1_000_000.0).round();
-        q.clamp(i32::MIN as f64, i32::MAX as f64) as i32
-    }
-
-    fn infer_time_span_micros(props: &serde_json::Map<String, serde_json::Value>) -> (i64, i64) {
-        fn get_num(props: &serde_json::Map<String, serde_json::Value>, k: &str) -> Option<f64> {
-            props.get(k).and_then(|v| match v {
-                serde_json::Value::Number(n) => n.as_f64(),
-                serde_json::Value::String(s) => s.parse::<f64>().ok(),
-                _ => None,
-            })
-        }
-
-        if let Some(t) = get_num(props, "time").or_else(|| get_num(props, "timestamp")) {
-            let us = (t * 1_000_000.0).round();
-            let us = us.clamp(i64::MIN as f64, i64::MAX as f64) as i64;
-            return (us, us);
-        }
-
-        if let (Some(s), Some(e)) = (get_num(props, "start"), get_num(props, "end")) {
-            let s_us = (s * 1_000_000.0).round();
-            let e_us = (e * 1_000_000.0).round();
-            let s_us = s_us.clamp(i64::MIN as f64, i64::MAX as f64) as i64;
-            let e_us = e_us.clamp(i64::MIN as f64, i64::MAX as f64) as i64;
-            return (s_us, e_us);
-        }
-
-        (i64::MIN, i64::MAX)
-    }
-
-    fn update_point_bounds(
-        p: &formats::GeoPoint,
-        min_lon: &mut i32,
-        min_lat: &mut i32,
-        max_lon: &mut i32,
-        max_lat: &mut i32,
-    ) {
-        let lon_q = quantize_deg(p.lon_deg);
-        let lat_q = quantize_deg(p.lat_deg);
-        *min_lon = (*min_lon).min(lon_q);
-        *min_lat = (*min_lat).min(lat_q);
-        *max_lon = (*max_lon).max(lon_q);
-        *max_lat = (*max_lat).max(lat_q);
-    }
-
-    let mut min_lon = i32::MAX;
-    let mut min_lat = i32::MAX;
-    let mut max_lon = i32::MIN;
-    let mut max_lat = i32::MIN;
     let mut min_start_us = i64::MAX;
     let mut max_end_us = i64::MIN;
 
     for f in &chunk.features {
-        let (s, e) = infer_time_span_micros(&f.properties);
+        let (s, e) = formats::time_span_micros(formats::infer_time_span(&f.properties));
         min_start_us = min_start_us.min(s);
         max_end_us = max_end_us.max(e);
-
-        match &f.geometry {
-            formats::VectorGeometry::Point(p) => {
-                update_point_bounds(p, &mut min_lon, &mut min_lat, &mut max_lon, &mut max_lat)
-            }
-            formats::VectorGeometry::MultiPoint(ps) | formats::VectorGeometry::LineString(ps) => {
-                for p in ps {
-                    update_point_bounds(p, &mut min_lon, &mut min_lat, &mut max_lon, &mut max_lat);
-                }
-            }
-            formats::VectorGeometry::MultiLineString(lines) => {
-                for line in lines {
-                    for p in line {
-                        update_point_bounds(
-                            p,
-                            &mut min_lon,
-                            &mut min_lat,
-                            &mut max_lon,
-                            &mut max_lat,
-                        );
-                    }
-                }
-            }
-            formats::VectorGeometry::Polygon(rings) => {
-                for ring in rings {
-                    for p in ring {
-                        update_point_bounds(
-                            p,
-                            &mut min_lon,
-                            &mut min_lat,
-                            &mut max_lon,
-                            &mut max_lat,
-                        );
-                    }
-                }
-            }
-            formats::VectorGeometry::MultiPolygon(polys) => {
-                for poly in polys {
-                    for ring in poly {
-                        for p in ring {
-                            update_point_bounds(
-                                p,
-                                &mut min_lon,
-                                &mut min_lat,
-                                &mut max_lon,
-                                &mut max_lat,
-                            );
-                        }
-                    }
-                }
-            }
-        }
     }
 
-    if min_lon == i32::MAX {
-        min_lon = 0;
-        min_lat = 0;
-        max_lon = 0;
-        max_lat = 0;
-    }
     if min_start_us == i64::MAX {
         min_start_us = i64::MIN;
         max_end_us = i64::MAX;
     }
 
-    (
-        [min_lon, min_lat, max_lon, max_lat],
-        [min_start_us, max_end_us],
-    )
+    // Quantization must match AVc: 1e-6 degrees. Empty chunks get zero bounds.
+    let bounds_q = chunk
+        .geo_bounds()
+        .map(|b| formats::lon_lat_bounds_q(&b))
+        .unwrap_or([0; 4]);
+
+    (bounds_q, [min_start_us, max_end_us])
 }
 
 fn cmd_unpack(args: Vec<String>) -> Result<(), String> {
@@ -529,8 +430,10 @@ fn cmd_surface_tiles(args: Vec<String>) -> Result<(), String> {
 
     let text = fs::read_to_string(&input).map_err(|e| format!("read {input:?}: {e}"))?;
     let chunk = formats::VectorChunk::from_geojson_str(&text)
-        .map_err(|e| format!("decode geojson: {e}"))?;
-    let chunk = unwrap_antimeridian_chunk(&chunk);
+        .map_err(|e| format!("decode geojson: {e}"))?
+        .to_wgs84()
+        .map_err(|e| format!("reproject to WGS 84: {e}"))?;
+    let chunk = chunk.unwrap_antimeridian();
 
     let mut world = scene::World::new();
     scene::prefabs::spawn_wgs84_globe(&mut world);
@@ -570,12 +473,13 @@ fn cmd_surface_tiles(args: Vec<String>) -> Result<(), String> {
         let (lon_b, lat_b) = lon_lat_from_ecef(b);
         let (lon_c, lat_c) = lon_lat_from_ecef(c);
 
-        let mut lat_min = lat_a.min(lat_b.min(lat_c));
-        let mut lat_max = lat_a.max(lat_b.max(lat_c));
-        lat_min = lat_min.clamp(tileset.min_lat, tileset.max_lat);
-        lat_max = lat_max.clamp(tileset.min_lat, tileset.max_lat);
-
-        let lon_ranges = antimeridian_lon_ranges([lon_a, lon_b, lon_c]);
+        // Treat the triangle as a ring so one wrapping a pole reaches it.
+        let Some(bounds) = GeoBounds::from_ring(&[[lon_a, lat_a], [lon_b, lat_b], [lon_c, lat_c]])
+        else {
+            continue;
+        };
+        let lat_min = bounds.south.clamp(tileset.min_lat, tileset.max_lat);
+        let lat_max = bounds.north.clamp(tileset.min_lat, tileset.max_lat);
 
         let tri_view = [
             ecef_vec3_to_viewer_f32(a),
@@ -585,10 +489,10 @@ fn cmd_surface_tiles(args: Vec<String>) -> Result<(), String> {
 
         for z in zoom_min..=zoom_max {
             let n = 2u32.pow(z);
-            let (y_min, y_max) = lat_range_to_tile_range(lat_min, lat_max, n, &tileset);
+            let (y_min, y_max) = lat_range_to_tile_range(lat_min, lat_max, n);
 
-            for (lon_min, lon_max) in &lon_ranges {
-                let (x_min, x_max) = lon_range_to_tile_range(*lon_min, *lon_max, n, &tileset);
+            for lon_box in bounds.to_aabb2s() {
+                let (x_min, x_max) = lon_range_to_tile_range(lon_box.min[0], lon_box.max[0], n);
                 for y in y_min..=y_max {
                     for x in x_min..=x_max {
                         tiles.entry((z, x, y)).or_default().extend(tri_view);
@@ -629,135 +533,92 @@ fn cmd_surface_tiles(args: Vec<String>) -> Result<(), String> {
     Ok(())
 }
 
-fn ecef_vec3_to_viewer_f32(p: Vec3) -> [f32; 3] {
-    [p.x as f32, p.z as f32, (-p.y) as f32]
-}
+fn cmd_coord(args: Vec<String>) -> Result<(), String> {
+    // atlas coord <coordinate...> [--to FORMAT] [--precision N] [--strict]
+    let mut words: Vec<String> = Vec::new();
+    let mut to: Option<String> = None;
+    let mut precision: Option<u8> = None;
+    let mut mode = ParseMode::Lenient;
 
-fn lon_lat_from_ecef(p: Vec3) -> (f64, f64) {
-    let geo = ecef_to_geodetic(Ecef::new(p.x, p.y, p.z));
-    (geo.lon_rad.to_degrees(), geo.lat_rad.to_degrees())
-}
-
-fn antimeridian_lon_ranges(lons: [f64; 3]) -> Vec<(f64, f64)> {
-    let min_lon = lons[0].min(lons[1].min(lons[2]));
-    let max_lon = lons[0].max(lons[1].max(lons[2]));
-    let span = max_lon - min_lon;
-    if span <= 180.0 {
-        return vec![(min_lon, max_lon)];
-    }
-
-    let mut ranges = Vec::new();
-    let neg: Vec<f64> = lons.iter().copied().filter(|lon| *lon < 0.0).collect();
-    let pos: Vec<f64> = lons.iter().copied().filter(|lon| *lon >= 0.0).collect();
-    if !neg.is_empty() {
-        let min_neg = neg.iter().copied().fold(f64::INFINITY, f64::min);
-        ranges.push((min_neg, 180.0));
+    // Anything that is not one of these flags is coordinate text (which may start with `-`).
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--to" => {
+                i += 1;
+                if i >= args.len() {
+                    return Err("--to requires a value".to_string());
+                }
+                to = Some(args[i].clone());
+            }
+            "--precision" => {
+                i += 1;
+                if i >= args.len() {
+                    return Err("--precision requires a value".to_string());
+                }
+                precision = Some(
+                    args[i]
+                        .parse::<u8>()
+                        .map_err(|_| "--precision must be an integer in 0..=255".to_string())?,
+                );
+            }
+            "--strict" => mode = ParseMode::Strict,
+            word => words.push(word.to_string()),
+        }
+        i += 1;
     }
-    if !pos.is_empty() {
-        let max_pos = pos.iter().copied().fold(f64::NEG_INFINITY, f64::max);
-        ranges.push((-180.0, max_pos));
+    if words.is_empty() {
+        return Err(usage());
     }
 
-    if ranges.is_empty() {
-        vec![(min_lon, max_lon)]
-    } else {
-        ranges
+    let geo = parse_coordinate(&words.join(" "), mode).map_err(|e| e.to_string())?;
+    let names: Vec<&str> = match &to {
+        Some(name) => vec![name.as_str()],
+        None => CoordinateFormat::NAMES.to_vec(),
+    };
+    for name in names {
+        let format = CoordinateFormat::from_name(name, precision).ok_or_else(|| {
+            format!(
+                "unknown format: {name} (expected one of {})",
+                CoordinateFormat::NAMES.join(", ")
+            )
+        })?;
+        let text = format.format(geo).unwrap_or_else(|| "-".to_string());
+        if to.is_some() {
+            println!("{text}");
+        } else {
+            println!("{name:>8}: {text}");
+        }
     }
+    Ok(())
 }
 
-fn lon_range_to_tile_range(
-    lon_min: f64,
-    lon_max: f64,
-    n: u32,
-    tileset: &SurfaceTileset,
-) -> (u32, u32) {
-    let span = tileset.max_lon - tileset.min_lon;
-    let t_min = ((lon_min - tileset.min_lon) / span).clamp(0.0, 1.0 - 1e-9);
-    let t_max = ((lon_max - tileset.min_lon) / span).clamp(0.0, 1.0 - 1e-9);
-    let x_min = (t_min * n as f64).floor() as u32;
-    let x_max = (t_max * n as f64).floor() as u32;
-    (x_min.min(n - 1), x_max.min(n - 1))
+fn ecef_vec3_to_viewer_f32(p: Vec3) -> [f32; 3] {
+    [p.x as f32, p.z as f32, (-p.y) as f32]
 }
 
-fn lat_range_to_tile_range(
-    lat_min: f64,
-    lat_max: f64,
-    n: u32,
-    tileset: &SurfaceTileset,
-) -> (u32, u32) {
-    let span = tileset.max_lat - tileset.min_lat;
-    let t_min = ((tileset.max_lat - lat_max) / span).clamp(0.0, 1.0 - 1e-9);
-    let t_max = ((tileset.max_lat - lat_min) / span).clamp(0.0, 1.0 - 1e-9);
-    let y_min = (t_min * n as f64).floor() as u32;
-    let y_max = (t_max * n as f64).floor() as u32;
-    (y_min.min(n - 1), y_max.min(n - 1))
+fn lon_lat_from_ecef(p: Vec3) -> (f64, f64) {
+    let geo = ecef_to_geodetic(Ecef::new(p.x, p.y, p.z));
+    (geo.lon_rad.to_degrees(), geo.lat_rad.to_degrees())
 }
 
-fn unwrap_antimeridian_chunk(chunk: &formats::VectorChunk) -> formats::VectorChunk {
-    use formats::{VectorChunk, VectorFeature, VectorGeometry};
-
-    let features = chunk
-        .features
-        .iter()
-        .map(|feat| {
-            let geometry = match &feat.geometry {
-                VectorGeometry::Polygon(rings) => {
-                    VectorGeometry::Polygon(rings.iter().map(|ring| unwrap_ring(ring)).collect())
-                }
-                VectorGeometry::MultiPolygon(polys) => VectorGeometry::MultiPolygon(
-                    polys
-                        .iter()
-                        .map(|poly| poly.iter().map(|ring| unwrap_ring(ring)).collect())
-                        .collect(),
-                ),
-                VectorGeometry::LineString(points) => {
-                    VectorGeometry::LineString(unwrap_ring(points))
-                }
-                VectorGeometry::MultiLineString(lines) => VectorGeometry::MultiLineString(
-                    lines.iter().map(|line| unwrap_ring(line)).collect(),
-                ),
-                other => other.clone(),
-            };
-
-            VectorFeature {
-                id: feat.id.clone(),
-                properties: feat.properties.clone(),
-                geometry,
-            }
-        })
-        .collect();
-
-    VectorChunk { features }
+/// Tile column range on the global equirectangular grid (`n` x `n` tiles).
+fn lon_range_to_tile_range(lon_min: f64, lon_max: f64, n: u32) -> (u32, u32) {
+    let (u_min, _) = Equirectangular::unit_uv(lon_min, 0.0);
+    let (u_max, _) = Equirectangular::unit_uv(lon_max, 0.0);
+    (unit_to_tile(u_min, n), unit_to_tile(u_max, n))
 }
 
-fn unwrap_ring(points: &[formats::GeoPoint]) -> Vec<formats::GeoPoint> {
-    if points.is_empty() {
-        return Vec::new();
-    }
-
-    let mut out: Vec<formats::GeoPoint> = Vec::with_capacity(points.len());
-    let mut prev_lon = points[0].lon_deg;
-    out.push(formats::GeoPoint::new(prev_lon, points[0].lat_deg));
-
-    for p in points.iter().skip(1) {
-        let mut lon = p.lon_deg;
-        let mut delta = lon - prev_lon;
-        if delta > 180.0 {
-            lon -= 360.0;
-        } else if delta < -180.0 {
-            lon += 360.0;
-        }
-        delta = lon - prev_lon;
-        if delta > 180.0 {
-            lon -= 360.0;
-        } else if delta < -180.0 {
-            lon += 360.0;
-        }
-        prev_lon = lon;
-        out.push(formats::GeoPoint::new(lon, p.lat_deg));
-    }
+/// Tile row range on the global equirectangular grid (row 0 is the north edge).
+fn lat_range_to_tile_range(lat_min: f64, lat_max: f64, n: u32) -> (u32, u32) {
+    let (_, v_min) = Equirectangular::unit_uv(0.0, lat_max);
+    let (_, v_max) = Equirectangular::unit_uv(0.0, lat_min);
+    (unit_to_tile(v_min, n), unit_to_tile(v_max, n))
+}
 
-    out
+fn unit_to_tile(t: f64, n: u32) -> u32 {
+    let i = (t.clamp(0.0, 1.0 - 1e-9) * n as f64).floor() as u32;
+    i.min(n - 1)
 }
 
 fn to_hex(bytes: &[u8]) -> String {
@@ -773,6 +634,6 @@ fn to_hex(bytes: &[u8]) -> String {
 fn usage() -> String {
     let exe = env::args().next().unwrap_or_else(|| "atlas".to_string());
     format!(
-        "Usage:\n  {exe} pack <input.geojson> <output.avc> [--blob-dir DIR] [--print-chunk-entry]\n  {exe} manifest <output_dir> <chunk.avc> [chunk2.avc ...] [--name NAME]\n  {exe} unpack <input.avc> <output.geojson>\n  {exe} surface-tiles <input.geojson> <output_dir> [--zoom-min N] [--zoom-max N]\n\nNotes:\n- Uses lon/lat quantization (1e-6 degrees).\n- Semantic round-trip: unpacked GeoJSON preserves geometry + properties, but JSON ordering may differ.\n- Blob storage is only active when --blob-dir is provided (stores original source bytes by content hash).\n- `manifest` writes a self-contained scene package directory with `scene.manifest.json`.\n"
+        "Usage:\n  {exe} pack <input.geojson> <output.avc> [--blob-dir DIR] [--print-chunk-entry]\n  {exe} manifest <output_dir> <chunk.avc> [chunk2.avc ...] [--name NAME]\n  {exe} unpack <input.avc> <output.geojson>\n  {exe} surface-tiles <input.geojson> <output_dir> [--zoom-min N] [--zoom-max N]\n  {exe} coord <coordinate...> [--to decimal|ddm|dms|utm|mgrs|usng|geohash] [--precision N] [--strict]\n\nNotes:\n- Uses lon/lat quantization (1e-6 degrees).\n- GeoJSON declaring a `crs` (e.g. EPSG:27700) is reprojected to WGS 84 before packing.\n- Semantic round-trip: unpacked GeoJSON preserves geometry + properties, but JSON ordering may differ.\n- Blob storage is only active when --blob-dir is provided (stores original source bytes by content hash).\n- `manifest` writes a self-contained scene package directory with `scene.manifest.json`.\n- `coord` auto-detects the input notation and prints it in every notation unless --to is given.\n"
     )
 }